@@ -182,7 +182,9 @@ export function QuantumBoard({
           </span>
         </div>
         <div className="stat">
-          <span className="stat-value">{mineCount}</span>
+          <span className="stat-value">
+            {grid.mines_remaining}/{mineCount}
+          </span>
           <span className="stat-label">Mines</span>
         </div>
         <div className="stat">
@@ -4,9 +4,12 @@ export type CellState =
   | "contained"
   | "detonated";
 
+export type Mark = "none" | "flag" | "question";
+
 export interface QuantumCellView {
   x: number;
   y: number;
+  mark: Mark;
   state: {
     state: CellState;
     probability?: number;
@@ -21,21 +24,944 @@ export interface GridSnapshot {
   won: boolean;
   seed: bigint;
   containment_charges: number;
+  swap_charges: number;
+  mines_remaining: number;
   entropy: number;
+  /** Bits of uncertainty left: hint entropy plus unresolved links. */
+  quantum_entropy: number;
   cells: QuantumCellView[];
+  decoherence: DecoherenceMeter;
+  /** Cells held still by back-to-back weak measurements. */
+  frozen: FrozenCell[];
+  theme: ThemeHint;
+  /** Points scored, boosted by the combo multiplier. */
+  score: bigint;
+  combo: Combo;
+  /** Isotopes still in Superposition; their hints pulse between moves. */
+  isotopes: { x: number; y: number; stages_left: number }[];
+  /** Consumables held. */
+  inventory: { shields: number };
+  /** Wear on each containment when containments decay. */
+  integrity: { x: number; y: number; integrity: number }[];
+  /** Each cell's look, row by row; the same for every frontend given the seed. */
+  cosmetics: Cosmetic[];
+}
+
+/** Biome, texture variant and decoration flags (1 moss, 2 cracks, 4 crystals). */
+export interface Cosmetic {
+  biome: number;
+  texture: number;
+  decorations: number;
+}
+
+/** Safe reveals in a row and the score multiplier they built. */
+export interface Combo {
+  streak: number;
+  multiplier: number;
+}
+
+/**
+ * How hints map to danger for this board's circuit. Normalise heatmaps with
+ * `(p - safe) / (mine - safe)`: on heavy scrambling 0.5 means "unknown".
+ */
+export interface ThemeHint {
+  /** Hint a certainly safe cell would show. */
+  safe: number;
+  /** Hint a certain mine would show (below `safe` when the circuit flips). */
+  mine: number;
+  min: number;
+  max: number;
+  /** Hint of a cell known only by the board's mine density. */
+  neutral: number;
+}
+
+export interface FrozenCell {
+  x: number;
+  y: number;
+  moves_left: number;
+}
+
+/** Weak-measurement disturbance, in points (0.01% of probability). */
+export interface DecoherenceMeter {
+  /** Per-cell points; empty until the first measurement. */
+  cells: number[];
+  /** Points since the last penalty. */
+  total: number;
+  lifetime: number;
+  penalties: number;
+}
+
+/** A recent Hadamard; amplitude halves with every move of `age`. */
+export interface InterferenceWave {
+  x: number;
+  y: number;
+  age: number;
+}
+
+/** A 16×16 tile of the board, for incremental rendering. */
+export interface ChunkCoord {
+  cx: number;
+  cy: number;
+}
+
+export type FirstClick = "guaranteed_zero" | "guaranteed_safe" | "no_guarantee";
+
+export type ChargePolicy =
+  | { kind: "exact" }
+  | { kind: "spare"; extra: number }
+  | { kind: "regenerating"; every: number };
+
+export type Generator =
+  | { kind: "uniform" }
+  | { kind: "clustered"; veins: number }
+  | { kind: "symmetric"; symmetry: "point" | "left_right" | "top_bottom" };
+
+export type Density =
+  | { kind: "uniform" }
+  | { kind: "gradient"; center: number; edge: number }
+  | { kind: "map"; width: number; height: number; weights: number[] };
+
+/** Which analysis is allowed; only "none" runs are leaderboard-eligible. */
+export type AssistLevel = "none" | "hints" | "full_analysis";
+
+export interface GridConfig {
+  width: number;
+  height: number;
+  mine_count: number;
+  seed: bigint;
+  difficulty: string;
+  first_click?: FirstClick;
+  safe_zone_radius?: number;
+  charge_policy?: ChargePolicy;
+  auto_contain?: boolean;
+  auto_reveal?: "off" | "trusting" | "ground_truth";
+  link_propagation?: { max_depth: number; decay: number };
+  generator?: Generator;
+  density?: Density;
+  /** Decoherence points before weak measurement collapses a random cell. */
+  decoherence_limit?: number | null;
+  measurement?: MeasurementCurve | null;
+  /** Charges for the entanglement-swapping tool (default 1). */
+  swap_charges?: number;
+  oracles?: Oracles;
+  /** Shields the player starts with; each absorbs one clicked mine. */
+  shields?: number;
+  /** Once per game, contain a mine clicked in a forced guess. */
+  mercy?: boolean;
+  /** Multi-stage mines placed by a scenario. */
+  isotopes?: IsotopeCell[];
+  /** Stabilized, turbulent or otherwise modified stretches of board. */
+  zones?: Zone[];
+  /** Scenario goals replacing the purification win. */
+  objectives?: Objective[];
+  /** Defaults to "full_analysis". */
+  assist?: AssistLevel;
+  timed?: TimedRules;
+  /** Combo scoring; off when null. */
+  combo?: ComboRules | null;
+  /** Turn-based action economy; real time when null. */
+  turns?: TurnRules | null;
+  /** Challenge mode: a noise daemon meddles at every new turn. Needs `turns`. */
+  daemon?: DaemonRules | null;
+  /** Outcome modifiers for game variants, consulted in order. */
+  rules?: Rule[];
+  /** Speedrun splits, timed by `apply_action_at`. */
+  splits?: SplitTrigger[];
+}
+
+/** Game parameters from the difficulty tuner, with their simulated win rate. */
+export interface DifficultyParams {
+  difficulty: string;
+  mine_density: number;
+  win_rate: number;
+}
+
+/** Built-in game-variant rule. */
+export type Rule = { kind: "lives"; remaining: number };
+
+/** Time-driven mechanics applied by `advance`; all off by default. */
+export interface TimedRules {
+  /** Decoherence points per second of game time. */
+  ambient_decoherence?: number;
+  /** Links lose half their strength each time this much time passes. */
+  link_half_life_ms?: number | null;
+  /** Integrity (of 1000) each containment loses per second; 0 keeps them. */
+  containment_decay?: number;
+}
+
+/** Action points per action kind; advancing time and ending the turn are free. */
+export interface ActionCost {
+  reveal: number;
+  contain: number;
+  hadamard: number;
+  measure: number;
+  swap: number;
+  teleport: number;
+}
+
+export interface TurnRules {
+  action_points: number;
+  costs?: ActionCost;
+  /** Decoherence points added at each new turn. */
+  decoherence_per_turn?: number;
+}
+
+/** Points the noise daemon spends per turn, and its prices. */
+export interface DaemonRules {
+  budget: number;
+  scramble_cost: number;
+  link_cost: number;
+  shift_cost: number;
+}
+
+/** Both 0 in real-time play. */
+export interface Turns {
+  turn: number;
+  action_points: number;
+}
+
+/** Safe reveals in a row raise the multiplier; a wasted containment resets it. */
+export interface ComboRules {
+  reveals_per_step: number;
+  max_multiplier: number;
+  /** Every this many safe reveals in a row grant a swap charge; 0 never. */
+  charge_every?: number;
+}
+
+/**
+ * Precision tradeoff for `measure_weak_with_precision`: at precision p the
+ * reading is off by up to noise × (1 − p) and the cell drifts by up to
+ * min_drift + (max_drift − min_drift) × p.
+ */
+export interface MeasurementCurve {
+  noise: number;
+  min_drift: number;
+  max_drift: number;
+}
+
+export type Gate = "hadamard" | "not" | { phase_shift: number };
+
+export interface Circuit {
+  gates: Gate[];
+}
+
+/**
+ * Phase-kickback oracles: revealing one runs `circuit` over the unresolved
+ * cells in its row and column. `rate` sprinkles extra ones over safe cells.
+ */
+/** A mine needing `stages` containment charges, one per move. */
+export interface IsotopeCell {
+  x: number;
+  y: number;
+  stages: number;
+}
+
+/** Cells a circuit or zone covers. `rect` is half-open. */
+export type Region =
+  | { kind: "row"; y: number }
+  | { kind: "column"; x: number }
+  | { kind: "cross"; x: number; y: number }
+  | { kind: "rect"; x0: number; y0: number; x1: number; y1: number };
+
+export type ZoneModifier = "no_entanglement" | "double_drift" | "double_hint_noise";
+
+/** Rule changes over a region; overlapping zones add up. */
+export interface Zone {
+  region: Region;
+  modifiers: ZoneModifier[];
+}
+
+export type Objective =
+  | { kind: "resolve_all" }
+  | { kind: "contain_cells"; cells: [number, number][] }
+  | { kind: "entropy_below"; bits: number; moves: number }
+  | {
+      /** Holds until broken; completes with the other objectives. */
+      kind: "decoherence_under";
+      points: number;
+    };
+
+export interface ObjectiveState {
+  objective: Objective;
+  status: "pending" | "complete" | "failed";
+  /** Partial credit in [0, 1]. */
+  progress: number;
+}
+
+export interface Objectives {
+  list: ObjectiveState[];
+  moves: number;
+}
+
+export type SplitTrigger =
+  | { kind: "first_cascade" }
+  | { kind: "entropy_fraction"; fraction: number }
+  | { kind: "all_mines_contained" };
+
+export interface Splits {
+  /** `elapsed_ms` counts from the first action; null until reached. */
+  list: { trigger: SplitTrigger; elapsed_ms: bigint | null }[];
+  start_entropy: number | null;
+}
+
+/** A split against the personal best; negative deltas are ahead. */
+/** A practice run restarted from a replay position. */
+export interface Practice {
+  from_move: number;
+  /** The original game's remaining moves, without `advance` steps. */
+  script: Action[];
+  moves: number;
+  /** First practice move the original did not make; null while following. */
+  diverged_at: number | null;
+}
+
+export interface SplitDelta {
+  elapsed_ms: bigint | null;
+  best_ms: bigint | null;
+  delta_ms: bigint | null;
+}
+
+export interface Oracles {
+  placed?: { x: number; y: number; circuit?: Circuit | null }[];
+  rate?: number;
+  circuit?: Circuit;
+}
+
+/** A cell on one board of a lab. */
+export interface CellRef {
+  board: number;
+  x: number;
+  y: number;
+}
+
+export type GameEvent =
+  | { kind: "revealed"; x: number; y: number; adjacent_mines: number }
+  | { kind: "contained"; x: number; y: number }
+  | { kind: "detonated"; x: number; y: number }
+  | { kind: "won" }
+  | { kind: "auto_contained"; x: number; y: number }
+  | { kind: "auto_revealed"; x: number; y: number }
+  | { kind: "auto_detonated"; x: number; y: number }
+  | {
+      /** Cascades arrive in propagation order; stagger animations by `depth`. */
+      kind: "bell_collapsed";
+      x: number;
+      y: number;
+      depth: number;
+      cause_x: number;
+      cause_y: number;
+    }
+  | { kind: "bell_degraded"; x: number; y: number; probability: number }
+  | { kind: "decohered"; x: number; y: number; mine: boolean }
+  | { kind: "zeno_frozen"; x: number; y: number }
+  | {
+      kind: "entanglement_swapped";
+      x: number;
+      y: number;
+      partner_x: number;
+      partner_y: number;
+    }
+  | {
+      kind: "teleported";
+      x: number;
+      y: number;
+      to_x: number;
+      to_y: number;
+      destroyed_mine: boolean;
+    }
+  | { kind: "shield_absorbed"; x: number; y: number }
+  | { kind: "mercy_granted"; x: number; y: number }
+  | { kind: "containment_breached"; x: number; y: number }
+  | { kind: "objective_completed"; index: number }
+  | { kind: "objective_failed"; index: number }
+  | { kind: "split_reached"; index: number; elapsed_ms: bigint }
+  | { kind: "turn_started"; turn: number; action_points: number }
+  | { kind: "daemon_scrambled"; x: number; y: number }
+  | {
+      kind: "daemon_strengthened";
+      x: number;
+      y: number;
+      partner_x: number;
+      partner_y: number;
+    }
+  /** Where the mine went is not told. */
+  | { kind: "daemon_shifted_mine" }
+  | { kind: "isotope_stabilized"; x: number; y: number; stages_left: number }
+  | { kind: "combo_raised"; multiplier: number }
+  | { kind: "combo_broken" }
+  | { kind: "combo_charge" }
+  | { kind: "oracle_fired"; x: number; y: number }
+  | {
+      /** Set off by `cause`, a cell on another board of the lab. */
+      kind: "link_collapsed";
+      x: number;
+      y: number;
+      cause: CellRef;
+    };
+
+/** Drained events and cues carry a 0–1 `haptic` intensity for `navigator.vibrate`. */
+export type WithHaptic<T> = T & { haptic: number };
+
+/** Semantic audio cue, queued next to the game events. Intensities are 0–1. */
+export type SoundCue =
+  | { kind: "soft_reveal"; intensity: number }
+  | { kind: "big_cascade"; len: number; intensity: number }
+  | { kind: "near_miss_high_probability"; probability: number }
+  | { kind: "detonation"; intensity: number }
+  | { kind: "victory" };
+
+export type ConfigError =
+  | { kind: "invalid_dimensions"; width: number; height: number }
+  | { kind: "too_many_cells"; cells: bigint; max: number }
+  | { kind: "too_many_pairs"; pairs: bigint; max: number }
+  | { kind: "over_memory_budget"; bytes: bigint; max: bigint }
+  | { kind: "too_many_mines"; requested: number; max: number }
+  | { kind: "asymmetric_mine_count"; requested: number }
+  | { kind: "invalid_density" }
+  | { kind: "invalid_measurement_curve" }
+  | { kind: "invalid_oracles" }
+  | { kind: "invalid_isotopes" }
+  | { kind: "invalid_daemon" };
+
+/** One cell of a board pasted in for analysis; cells left out are unknown. */
+export interface ObservedCell {
+  x: number;
+  y: number;
+  seen:
+    | { kind: "revealed"; adjacent_mines: number }
+    | { kind: "contained" }
+    /** A guess: the cell stays unknown. */
+    | { kind: "flagged" };
+}
+
+export type ReconstructError =
+  | { kind: "invalid_config"; error: ConfigError }
+  | { kind: "out_of_bounds"; x: number; y: number }
+  | { kind: "duplicate"; x: number; y: number }
+  | { kind: "contradiction"; x: number; y: number }
+  | { kind: "too_many_mines"; contained: number; mine_count: number };
+
+export type ClockError = { kind: "non_monotonic"; last_ms: bigint; given_ms: bigint };
+
+export interface TimingStats {
+  elapsed_ms: bigint;
+  actions: number;
+  mean_interval_ms: number;
+  longest_pause_ms: bigint;
+  finished: boolean;
+}
+
+export type Action =
+  | { kind: "reveal"; x: number; y: number }
+  | { kind: "contain"; x: number; y: number }
+  | { kind: "hadamard"; x: number; y: number }
+  | { kind: "measure_weak"; x: number; y: number }
+  | { kind: "measure_weak_with_precision"; x: number; y: number; percent: number }
+  | {
+      kind: "swap_entanglement";
+      x: number;
+      y: number;
+      other_x: number;
+      other_y: number;
+    }
+  | { kind: "teleport"; x: number; y: number; to_x: number; to_y: number }
+  | { kind: "advance"; dt_ms: number }
+  | { kind: "end_turn" };
+
+export type ActionResult =
+  | { kind: "outcome"; outcome: unknown }
+  | { kind: "probability"; value: number }
+  | {
+      kind: "relinked";
+      x: number;
+      y: number;
+      partner_x: number;
+      partner_y: number;
+    }
+  | { kind: "advanced"; time_ms: bigint }
+  | { kind: "next_turn"; turn: number; action_points: number }
+  | { kind: "tool_rejected"; reason: string };
+
+export type IllegalAction =
+  | "game_already_over"
+  | "out_of_bounds"
+  | "already_resolved"
+  | "no_charges_remaining"
+  | "assist_disabled"
+  | "unknown_ground_truth"
+  | "not_enough_action_points"
+  | "not_turn_based"
+  | "unsupported"
+  | "same_cell"
+  | "not_generated"
+  | "frozen"
+  | "no_bell_pair"
+  | "invalid_precision"
+  | "refused";
+
+/** How a Bell pair's ends relate: opposite (`anti`) or the same. */
+export type Correlation = "anti" | "positive";
+
+export type Reason =
+  | { kind: "satisfied"; x: number; y: number; adjacent_mines: number }
+  | { kind: "saturated"; x: number; y: number; adjacent_mines: number }
+  | {
+      kind: "subset";
+      x: number;
+      y: number;
+      other_x: number;
+      other_y: number;
+      mines: number;
+    }
+  | {
+      kind: "linked";
+      x: number;
+      y: number;
+      mine: boolean;
+      partner_x: number;
+      partner_y: number;
+      correlation: Correlation;
+    }
+  | {
+      kind: "bell_subset";
+      x: number;
+      y: number;
+      partner_x: number;
+      partner_y: number;
+      number_x: number;
+      number_y: number;
+      mines: number;
+    };
+
+/** One deduction; highlight `premises` and `cells` together. */
+export interface ProofStep {
+  reason: Reason;
+  premises: [number, number][];
+  cells: [number, number][];
+  mine: boolean;
+  /** Earlier steps in the same explanation this one relies on. */
+  uses: number[];
+  text: string;
+}
+
+/** Mine chances from the revealed numbers, solved per frontier component. */
+export interface MineProbabilities {
+  cells: number[];
+  components: number;
+  /** Components estimated by sampling; 0 means the result is exact. */
+  sampled: number;
+}
+
+/** Why a cell must be safe or a mine; the last step proves it. */
+export interface Explanation {
+  x: number;
+  y: number;
+  mine: boolean;
+  steps: ProofStep[];
+}
+
+/** A displayed hint mapped back through the circuit (inspector only). */
+export interface CalibratedEstimate {
+  mean: number;
+  /** Standard deviation; 0.5 when the circuit erases all information. */
+  spread: number;
+}
+
+/** What a move would do, against ground truth (inspector only). */
+export interface MoveEvaluation {
+  detonation_risk: number;
+  /** Bits of quantum entropy removed; negative adds uncertainty. */
+  information_gain: number;
+  /** Cells the move would resolve, including any cascade. */
+  cascade: number;
+}
+
+export type Direction = "up" | "down" | "left" | "right";
+
+export type Tool = "hadamard" | "measure_weak" | "swap_entanglement" | "teleport";
+
+export type CursorCommand =
+  | { kind: "reveal" }
+  | { kind: "contain" }
+  | { kind: "tool"; tool: Tool }
+  | { kind: "cancel" };
+
+/** Keyboard/gamepad cursor; `selection` holds a two-cell tool's first cell. */
+export interface Cursor {
+  x: number;
+  y: number;
+  edge: "clamp" | "wrap";
+  selection: [number, number] | null;
+}
+
+export type Button =
+  | "dpad_up"
+  | "dpad_down"
+  | "dpad_left"
+  | "dpad_right"
+  | "south"
+  | "east"
+  | "west"
+  | "north"
+  | "left_shoulder"
+  | "right_shoulder"
+  | "left_trigger"
+  | "right_trigger"
+  | "start"
+  | "select";
+
+export type ControllerEvent =
+  | { kind: "pressed"; button: Button }
+  | { kind: "released"; button: Button }
+  | { kind: "analog"; button: Button; value: number };
+
+export type Binding =
+  | { kind: "move"; direction: Direction }
+  | { kind: "command"; command: CursorCommand };
+
+/** Controller layout, stored in the player profile. Comes back from wasm as
+ *  a `Map`; plain objects are accepted on the way in. */
+export interface Bindings {
+  buttons: Map<Button, Binding>;
+}
+
+export interface PlayerProfile {
+  bindings: Bindings;
+  session?: SessionManager;
+  rating?: Rating;
+  /** When bindings, session or rating last changed (ms since the epoch). */
+  updated_ms?: bigint;
+  achievements?: string[];
+  games_played?: bigint;
+  games_won?: bigint;
+  /** Fastest wins keyed like `"16x16-40-observer"`. */
+  bests?: Map<string, PersonalBest>;
+}
+
+export interface PersonalBest {
+  elapsed_ms: bigint;
+  achieved_ms: bigint;
+  /** The run's split times, for `split_deltas`. */
+  splits?: (bigint | null)[];
+}
+
+/** Glicko-2 rating for versus and race modes. */
+export interface Rating {
+  rating: number;
+  deviation: number;
+  volatility: number;
+}
+
+/** Adaptive difficulty state; levels change one step after a streak. */
+export interface SessionManager {
+  level: number;
+  streak?: number;
+  games?: number;
+  board?: [number, number] | null;
+}
+
+export interface ReplayStep {
+  t_ms: bigint;
+  action: Action;
+}
+
+/** Engine and rules a replay was recorded under. */
+export interface RulesetStamp {
+  engine: string;
+  ruleset: number;
+  hash: string;
+}
+
+export type Compatibility =
+  | { kind: "compatible" }
+  | { kind: "needs_ruleset"; version: number }
+  | {
+      kind: "incompatible";
+      reason: "unstamped" | "engine_version" | "rules_changed";
+    };
+
+export interface Replay {
+  config: GridConfig;
+  steps: ReplayStep[];
+  /** Missing on replays recorded before stamping. */
+  stamp?: RulesetStamp;
+  /** Optional per-step RNG draws; playback rejects a replay that diverges. */
+  rng_audit?: { stream: string; count: bigint }[][];
+  /** Dropped by `scrub_replay`, as are `notes`. */
+  player?: string;
+  notes?: ReplayNote[];
+  /** Moves flagged by `coach_replay`, in step order. */
+  tags?: CoachingTag[];
+}
+
+export interface CellTiming {
+  actions: number;
+  dwell_ms: bigint;
+  longest_ms: bigint;
+  /** Decision time beyond the game's median. */
+  lost_ms: bigint;
+}
+
+/** Where a timed replay's time went; all arrays are row-major. */
+export interface TimingHeatmap {
+  width: number;
+  height: number;
+  cells: CellTiming[];
+  /** 0..1, the slowest cell at 1. */
+  hesitation: number[];
+  median_ms: bigint;
+  lost_ms: bigint;
+}
+
+export type MoveClass =
+  | "proven"
+  | "forced"
+  | "suboptimal"
+  | "unnecessary_risk"
+  | "blunder";
+
+/** A move to stop at in review; `step` indexes `Replay.steps`. */
+export interface CoachingTag {
+  step: number;
+  class: MoveClass;
+  /** Chance the move was wrong, and the lowest any move had. */
+  risk: number;
+  safest: number;
+}
+
+export interface PostMortem {
+  /** Every reveal and containment, in step order. */
+  moves: CoachingTag[];
+  proven: number;
+  forced: number;
+  suboptimal: number;
+  unnecessary_risk: number;
+  blunders: number;
+}
+
+/** A chat line or annotation on a replay. */
+export interface ReplayNote {
+  t_ms: bigint;
+  author?: string;
+  text: string;
+}
+
+/** Upcoming dailies and recommendations, as signed by the server. */
+export interface Manifest {
+  /** Days since the Unix epoch (UTC), as are the dailies' `day`s. */
+  issued: number;
+  dailies: { day: number; config: GridConfig }[];
+  packs: { id: string; title: string; url: string; sha256: string }[];
+  featured: { title: string; config: GridConfig }[];
+}
+
+/** A `Manifest` as fetched: the signed JSON text and its hex signature. */
+export interface SignedManifest {
+  body: string;
+  signature: string;
+}
+
+export type ManifestError =
+  | { kind: "bad_signature" }
+  | { kind: "malformed"; message: string }
+  | { kind: "duplicate_day"; day: number }
+  | { kind: "invalid_board"; error: ConfigError };
+
+export interface GhostCursor {
+  steps_played: number;
+  last_action: ReplayStep | null;
+  resolved: number;
+  progress: number;
+  game_over: boolean;
+  won: boolean;
+}
+
+export interface SpectatorFrame {
+  t_ms: bigint;
+  action: Action;
+  changed: QuantumCellView[];
+  containment_charges: number;
+  game_over: boolean;
+  won: boolean;
+}
+
+export interface SpectatorReplay {
+  width: number;
+  height: number;
+  mine_count: number;
+  difficulty: string;
+  initial: QuantumCellView[];
+  frames: SpectatorFrame[];
+}
+
+export interface WasmGhostReplay {
+  free?: () => void;
+  cursor_at: (tMs: bigint) => GhostCursor;
+}
+
+export type Unlock =
+  | { kind: "open" }
+  | { kind: "after"; id: string }
+  | { kind: "completed"; count: number };
+
+/** How bots fared on a scenario; sort community levels by `clear_rate`. */
+export interface ScenarioRating {
+  games: number;
+  clear_rate: number;
+  /** Moves per game, standing in for play time. */
+  mean_moves: number;
+  mean_guesses: number;
+}
+
+export interface PackIndex {
+  id: string;
+  title: string;
+  author: string;
+  description: string;
+  /** In play order. */
+  scenarios: {
+    id: string;
+    file: string;
+    sha256: string;
+    unlock: Unlock;
+    rating?: ScenarioRating;
+  }[];
+  assets: { file: string; sha256: string }[];
+}
+
+export interface Scenario {
+  title: string;
+  briefing: string;
+  config: GridConfig;
+}
+
+export type PackError =
+  | { kind: "too_large"; bytes: bigint }
+  | { kind: "archive"; message: string }
+  | { kind: "missing_file"; file: string }
+  | { kind: "hash_mismatch"; file: string }
+  | { kind: "malformed"; file: string; message: string }
+  | { kind: "unsafe_path"; file: string }
+  | { kind: "duplicate_id"; id: string }
+  | { kind: "bad_unlock"; id: string }
+  | { kind: "invalid_board"; id: string; error: ConfigError };
+
+export interface WasmScenarioPack {
+  free?: () => void;
+  index: () => PackIndex;
+  scenario: (position: number) => Scenario | null;
+  /** `completed` holds the ids of the pack's completed scenarios. */
+  is_unlocked: (position: number, completed: string[]) => boolean;
+  asset: (file: string) => Uint8Array | undefined;
+}
+
+export interface BoardMetrics {
+  three_bv: number;
+  longest_bell_chain: number;
+}
+
+export type SearchStatus =
+  | { kind: "pending"; scanned: number; budget: number }
+  | { kind: "found"; seed: bigint }
+  | { kind: "exhausted" };
+
+export type ProgressPhase = "seed_search" | "replaying" | "solving" | "self_play";
+
+export interface WasmSeedSearchJob {
+  free?: () => void;
+  step: (
+    candidates: number,
+    onProgress?: (fraction: number, phase: ProgressPhase) => void,
+  ) => SearchStatus;
+  progress: () => number;
 }
 
 export interface WasmGame {
   free?: () => void;
   reveal_cell: (x: number, y: number) => unknown;
   contain_cell: (x: number, y: number) => unknown;
+  /** Pass an `action_id` to make retries apply the action only once. */
+  apply_action: (action: Action, action_id?: bigint) => ActionResult;
+  /** Times the config's splits; throws a `ClockError` if time goes backwards. */
+  apply_action_at: (action: Action, t_ms: bigint) => ActionResult;
   get_grid_snapshot: () => unknown;
+  get_compact_snapshot: () => Uint8Array;
+  state_hash: () => string;
+  get_interference: () => InterferenceWave[];
+  get_revision: () => bigint;
+  get_dirty_chunks: (since: bigint) => ChunkCoord[];
+  get_viewport: (x0: number, y0: number, x1: number, y1: number) => QuantumCellView[];
   get_probability_cloud: () => unknown;
+  drain_events: () => WithHaptic<GameEvent>[];
+  drain_cues: () => WithHaptic<SoundCue>[];
+  /** Let game time pass for timed modes; call from the frame loop. */
+  advance: (dt_ms: number) => ActionResult;
+  fork: (from?: number) => number;
+  evaluate: (branch: number, action: Action) => ActionResult;
+  /** Requires the quantum inspector. */
+  calibrate: (displayed: number) => CalibratedEstimate;
+  /** Requires the quantum inspector; throws an `IllegalAction` string. */
+  evaluate_move: (action: Action) => MoveEvaluation;
+  /** Needs hints assist; throws an `IllegalAction` string. */
+  explain: (x: number, y: number) => Explanation | null;
+  /** Nothing left to deduce and every move is a 50/50 or worse. */
+  is_forced_guess: () => boolean;
+  /** Needs hints assist; throws an `IllegalAction` string. */
+  mine_probabilities: () => MineProbabilities;
+  cursor_move: (direction: Direction) => Cursor;
+  /** `null` when the command only changed the selection. */
+  cursor_action: (command: CursorCommand) => ActionResult | null;
+  get_cursor: () => Cursor;
+  set_cursor_wrap: (wrap: boolean) => void;
+  /** `null` when the event produced no action. */
+  controller_event: (event: ControllerEvent) => ActionResult | null;
+  set_bindings: (bindings: Bindings) => void;
+  get_bindings: () => Bindings;
+  set_session: (session: SessionManager) => void;
+  get_session: () => SessionManager;
+  /** Counts this game toward the session once it is over. */
+  next_recommended_config: () => GridConfig;
+  /** Full analysis only; throws "assist_disabled" otherwise. */
+  get_unscrambled_probabilities: () => number[];
+  get_branch_snapshot: (branch: number) => GridSnapshot;
+  discard_branch: (branch: number) => boolean;
+  discard_all_branches: () => void;
+  get_timing_stats: () => TimingStats;
   get_seed: () => number;
+  mines_remaining: () => number;
+  /** Set when playing with a lives rule. */
+  lives: () => number | undefined;
+  objectives: () => Objectives;
+  practice: () => Practice | null;
+  splits: () => Splits;
+  split_deltas: (best: (bigint | null)[]) => SplitDelta[];
+  turns: () => Turns;
+  set_mark: (x: number, y: number, mark: Mark) => Mark;
+  measure_weak_with_precision: (x: number, y: number, precision: number) => number;
+  /** Spends a swap charge; returns the newly Bell-linked cells. */
+  swap_entanglement: (
+    x: number,
+    y: number,
+    other_x: number,
+    other_y: number,
+  ) => [[number, number], [number, number]];
+  /** Moves a cell's state (and mine) onto another, spending a Bell link. */
+  teleport: (x: number, y: number, to_x: number, to_y: number) => number;
+  /** Cells holding an oracle that has not fired yet, as `[x, y]`. */
+  get_oracles: () => [number, number][];
+  set_note: (x: number, y: number, note: string | number | null) => void;
+  get_note: (x: number, y: number) => string | number | null;
+  /** Throws when enabling it in a game with assist "none". */
   set_quantum_inspector: (enabled: boolean) => void;
   is_quantum_inspector_enabled: () => boolean;
 }
 
+/** Thrown by the persistence functions. */
+export type StorageError =
+  | { kind: "invalid_key"; key: string }
+  | { kind: "backend"; message: string }
+  | { kind: "corrupt"; key: string; message: string };
+
 export interface WasmModule {
   default: (moduleOrPath?: unknown) => Promise<unknown>;
   init_game: (
@@ -51,4 +977,47 @@ export interface WasmModule {
     seed: bigint,
     difficulty: string,
   ) => WasmGame;
+  init_game_with_config: (config: GridConfig) => WasmGame;
+  /** Board rebuilt for analysis only; throws a `ReconstructError`. */
+  reconstruct_game: (cells: ObservedCell[], config: GridConfig) => WasmGame;
+  /** Practice from a replay position; throws a `ReplayError`. */
+  practice_replay: (replay: Replay, move_index: number) => WasmGame;
+  validate_config: (config: GridConfig) => ConfigError | null;
+  verify_seed_commitment: (
+    commitment: string,
+    seed: bigint,
+    salt: string,
+  ) => boolean;
+  load_ghost: (replay: Replay) => WasmGhostReplay;
+  /** Throws a `PackError`. */
+  load_pack: (bytes: Uint8Array) => WasmScenarioPack;
+  export_spectator_replay: (replay: Replay) => SpectatorReplay;
+  scrub_replay: (replay: Replay) => Replay;
+  timing_heatmap: (replay: Replay) => TimingHeatmap;
+  /** Both throw a `ReplayError`. */
+  post_mortem: (replay: Replay) => PostMortem;
+  coach_replay: (replay: Replay) => Replay;
+  check_replay_compatibility: (replay: Replay) => Compatibility;
+  /** `recent` holds 1 for each recent win and 0 for each loss. */
+  suggest_difficulty: (
+    current: DifficultyParams,
+    recent: Uint8Array,
+    target_win_rate: number,
+  ) => DifficultyParams;
+  load_profile: () => PlayerProfile | null;
+  save_profile: (profile: PlayerProfile) => void;
+  merge_profiles: (local: PlayerProfile, remote: PlayerProfile) => PlayerProfile;
+  store_replay: (name: string, replay: Replay) => void;
+  load_replay: (name: string) => Replay | null;
+  list_replays: () => string[];
+  /** Checks against the build's embedded key; throws a `ManifestError`. */
+  accept_manifest: (signed: SignedManifest) => Manifest;
+  cached_manifest: () => Manifest | null;
+  /** Only in builds with the `tracing` feature; throws after the first call. */
+  init_tracing?: (level: "error" | "warn" | "info" | "debug" | "trace") => void;
+  start_seed_search: (
+    config: GridConfig,
+    predicate: (metrics: BoardMetrics) => boolean,
+    budget: number,
+  ) => WasmSeedSearchJob;
 }
@@ -0,0 +1,62 @@
+use std::fs;
+use std::process::ExitCode;
+
+mod validate;
+
+const USAGE: &str = "usage: qmf-cli validate-pack <pack> [--games <n>] [--words <file>]";
+
+/// Command-line tools for content authors and servers. `validate-pack`
+/// prints a JSON report on a scenario pack and exits non-zero unless the
+/// pack is fit to publish.
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let result = match args.first().map(String::as_str) {
+        Some("validate-pack") => validate_pack(&args[1..]),
+        _ => Err(USAGE.to_string()),
+    };
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Run `validate-pack`, returning whether the pack was accepted.
+fn validate_pack(args: &[String]) -> Result<bool, String> {
+    let mut options = validate::Options::default();
+    let mut path = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--games" => {
+                options.games = args
+                    .next()
+                    .and_then(|games| games.parse().ok())
+                    .ok_or(USAGE)?;
+            }
+            "--words" => {
+                let file = args.next().ok_or(USAGE)?;
+                let words = fs::read_to_string(file)
+                    .map_err(|error| format!("could not read {file}: {error}"))?;
+                options.blocked.extend(
+                    words
+                        .lines()
+                        .map(str::trim)
+                        .filter(|w| !w.is_empty())
+                        .map(String::from),
+                );
+            }
+            _ if path.is_none() => path = Some(arg),
+            _ => return Err(USAGE.to_string()),
+        }
+    }
+    let path = path.ok_or(USAGE)?;
+    let bytes = fs::read(path).map_err(|error| format!("could not read {path}: {error}"))?;
+    let report = validate::validate(&bytes, &options);
+    let json = serde_json::to_string_pretty(&report).map_err(|error| error.to_string())?;
+    println!("{json}");
+    Ok(report.accepted)
+}
@@ -0,0 +1,238 @@
+use serde::Serialize;
+
+use qmf_core::metrics;
+use qmf_core::pack::{Pack, PackError, ScenarioRating};
+use qmf_core::selfplay::Strategy;
+
+/// Words refused in pack metadata unless `--words` adds more. Deliberately
+/// short: servers pass their moderation list.
+const BLOCKED: &[&str] = &[
+    "arse", "asshole", "bastard", "bitch", "bollocks", "cock", "cunt", "dick", "fuck", "fucker",
+    "fucking", "piss", "prick", "pussy", "shit", "slut", "twat", "wank", "wanker", "whore",
+];
+
+#[derive(Debug, Clone)]
+pub struct Options {
+    /// Games per bot per scenario for the rating.
+    pub games: u32,
+    /// Extra words to refuse, on top of [`BLOCKED`].
+    pub blocked: Vec<String>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            games: metrics::RATING_GAMES,
+            blocked: Vec::new(),
+        }
+    }
+}
+
+/// Everything `validate-pack` found, as printed.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    /// Loads cleanly, every scenario is solved, and nothing is flagged.
+    pub accepted: bool,
+    pub id: Option<String>,
+    /// Why the pack did not load, if it did not.
+    pub error: Option<PackError>,
+    pub scenarios: Vec<ScenarioReport>,
+    pub flagged: Vec<Flag>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ScenarioReport {
+    pub id: String,
+    /// The board can be finished without a guess (see
+    /// [`metrics::solves_without_guessing`]).
+    pub solved: bool,
+    /// Moves the reference bot took on the board.
+    pub moves: u32,
+    /// Moves the solver could not prove safe when the bot made them.
+    pub guesses: u32,
+    /// Clicks an expert needs on the board the bot played.
+    pub three_bv: u32,
+    /// How the bot ensemble fared on the scenario's settings from
+    /// successive seeds.
+    pub rating: ScenarioRating,
+}
+
+/// A refused word in the pack's text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Flag {
+    /// Where it appears, e.g. `scenarios[2].briefing`.
+    pub field: String,
+    pub word: String,
+}
+
+pub fn validate(bytes: &[u8], options: &Options) -> Report {
+    let pack = match Pack::read(bytes) {
+        Ok(pack) => pack,
+        Err(error) => {
+            return Report {
+                accepted: false,
+                id: None,
+                error: Some(error),
+                scenarios: Vec::new(),
+                flagged: Vec::new(),
+            }
+        }
+    };
+    let scenarios: Vec<ScenarioReport> = pack
+        .index
+        .scenarios
+        .iter()
+        .zip(&pack.scenarios)
+        .map(|(entry, scenario)| {
+            let run = metrics::run(&scenario.config, Strategy::LowestHint);
+            ScenarioReport {
+                id: entry.id.clone(),
+                solved: metrics::solves_without_guessing(&scenario.config),
+                moves: run.moves,
+                guesses: run.guesses,
+                three_bv: run.three_bv,
+                rating: metrics::rate_config(&scenario.config, options.games),
+            }
+        })
+        .collect();
+    let flagged = flag(&pack, options);
+    Report {
+        accepted: flagged.is_empty() && scenarios.iter().all(|scenario| scenario.solved),
+        id: Some(pack.index.id),
+        error: None,
+        scenarios,
+        flagged,
+    }
+}
+
+/// Every refused word in the pack's ids, titles and text. Matches whole
+/// words after undoing common letter swaps (`4` for `a`, `$` for `s`), so
+/// "Scunthorpe" passes and "sh1t" does not.
+fn flag(pack: &Pack, options: &Options) -> Vec<Flag> {
+    let index = &pack.index;
+    let mut fields = vec![
+        ("id".to_string(), index.id.as_str()),
+        ("title".to_string(), index.title.as_str()),
+        ("author".to_string(), index.author.as_str()),
+        ("description".to_string(), index.description.as_str()),
+    ];
+    for (i, (entry, scenario)) in index.scenarios.iter().zip(&pack.scenarios).enumerate() {
+        fields.push((format!("scenarios[{i}].id"), entry.id.as_str()));
+        fields.push((format!("scenarios[{i}].file"), entry.file.as_str()));
+        fields.push((format!("scenarios[{i}].title"), scenario.title.as_str()));
+        fields.push((
+            format!("scenarios[{i}].briefing"),
+            scenario.briefing.as_str(),
+        ));
+    }
+    for (i, asset) in index.assets.iter().enumerate() {
+        fields.push((format!("assets[{i}].file"), asset.file.as_str()));
+    }
+    let blocked: Vec<String> = BLOCKED
+        .iter()
+        .map(|word| word.to_string())
+        .chain(options.blocked.iter().map(|word| normalize(word)))
+        .collect();
+    let mut flagged = Vec::new();
+    for (field, text) in fields {
+        let normalized = normalize(text);
+        for word in normalized.split(|c: char| !c.is_alphanumeric()) {
+            let hit = blocked
+                .iter()
+                .any(|blocked| word == blocked || word.strip_suffix('s') == Some(blocked.as_str()));
+            if hit {
+                flagged.push(Flag {
+                    field: field.clone(),
+                    word: word.to_string(),
+                });
+            }
+        }
+    }
+    flagged
+}
+
+fn normalize(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '0' => 'o',
+            '1' | '!' => 'i',
+            '3' => 'e',
+            '4' | '@' => 'a',
+            '5' | '$' => 's',
+            '7' => 't',
+            c => c.to_ascii_lowercase(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qmf_core::config::GridConfig;
+    use qmf_core::pack::{PackIndex, Scenario, ScenarioEntry, Unlock};
+
+    fn pack(title: &str, config: GridConfig) -> Vec<u8> {
+        Pack {
+            index: PackIndex {
+                id: "trial".to_string(),
+                title: title.to_string(),
+                scenarios: vec![ScenarioEntry {
+                    id: "only".to_string(),
+                    file: "only.json".to_string(),
+                    sha256: String::new(),
+                    unlock: Unlock::Open,
+                    rating: None,
+                }],
+                ..PackIndex::default()
+            },
+            scenarios: vec![Scenario {
+                title: "Only".to_string(),
+                briefing: String::new(),
+                config,
+            }],
+            ..Pack::default()
+        }
+        .write()
+        .unwrap()
+    }
+
+    #[test]
+    fn reports_solvability_difficulty_and_language() {
+        let options = Options {
+            games: 3,
+            ..Options::default()
+        };
+        let easy = GridConfig::new(6, 6, 1, 3, "observer");
+        let report = validate(&pack("A Scunthorpe Stroll", easy.clone()), &options);
+        assert!(report.accepted, "{report:?}");
+        assert_eq!(report.id.as_deref(), Some("trial"));
+        let only = &report.scenarios[0];
+        assert!(only.solved && only.moves > 0 && only.three_bv > 0);
+        assert!(only.rating.games == 6 && only.rating.clear_rate > 0.0);
+
+        let report = validate(&pack("Sh1t Happens", easy.clone()), &options);
+        assert!(!report.accepted);
+        assert_eq!(
+            report.flagged,
+            [Flag {
+                field: "title".to_string(),
+                word: "shit".to_string()
+            }]
+        );
+        let custom = Options {
+            blocked: vec!["Stroll".to_string()],
+            ..options.clone()
+        };
+        assert!(!validate(&pack("A Scunthorpe Stroll", easy), &custom).accepted);
+
+        // A bot may get lucky on a board that needs guessing; the pack is
+        // still refused.
+        let guessy = GridConfig::new(8, 8, 20, 1, "observer");
+        let report = validate(&pack("Coin Flips", guessy), &options);
+        assert!(!report.accepted && !report.scenarios[0].solved);
+
+        let report = validate(b"not a pack", &options);
+        assert!(!report.accepted);
+        assert!(matches!(report.error, Some(PackError::Archive { .. })));
+    }
+}
@@ -0,0 +1,77 @@
+//! Arbitrary action streams against every board shape and ruleset.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use qmf_core::action::Action;
+use qmf_core::config::{AutoReveal, ChargePolicy, Generator, GridConfig, Symmetry};
+use qmf_core::grid::QuantumGrid;
+use qmf_core::invariants;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((header, moves)) = data.split_first_chunk::<8>() else {
+        return;
+    };
+    let [width, height, mines, difficulty, generator, rules, seed_lo, seed_hi] = *header;
+    let difficulty = ["observer", "researcher", "theorist"][difficulty as usize % 3];
+    let generator = match generator % 5 {
+        0 => Generator::Clustered { veins: 0 },
+        1 => Generator::Symmetric {
+            symmetry: Symmetry::Point,
+        },
+        2 => Generator::Symmetric {
+            symmetry: Symmetry::LeftRight,
+        },
+        _ => Generator::Uniform,
+    };
+    let config = GridConfig::new(
+        u32::from(width % 24) + 1,
+        u32::from(height % 24) + 1,
+        u32::from(mines),
+        u64::from(u16::from_le_bytes([seed_lo, seed_hi])),
+        difficulty,
+    )
+    .with_generator(generator)
+    .with_auto_contain(rules & 1 != 0)
+    .with_auto_reveal(match rules >> 1 & 3 {
+        1 => AutoReveal::Trusting,
+        2 => AutoReveal::GroundTruth,
+        _ => AutoReveal::Off,
+    })
+    .with_charge_policy(if rules & 8 != 0 {
+        ChargePolicy::Regenerating { every: 2 }
+    } else {
+        ChargePolicy::Exact
+    });
+
+    let mut grid = QuantumGrid::from_config(config);
+    for chunk in moves.chunks_exact(3) {
+        let (x, y) = (u32::from(chunk[1]), u32::from(chunk[2]));
+        let action = match chunk[0] % 7 {
+            0 => Action::Reveal { x, y },
+            1 => Action::Contain { x, y },
+            2 => Action::Hadamard { x, y },
+            3 => Action::MeasureWeakWithPrecision {
+                x,
+                y,
+                percent: chunk[0] / 7,
+            },
+            4 => Action::SwapEntanglement {
+                x,
+                y,
+                other_x: y,
+                other_y: x,
+            },
+            5 => Action::Teleport {
+                x,
+                y,
+                to_x: y,
+                to_y: x,
+            },
+            _ => Action::MeasureWeak { x, y },
+        };
+        grid.apply(action);
+        if let Err(violation) = invariants::check(&grid) {
+            panic!("{violation} after {action:?}");
+        }
+    }
+});
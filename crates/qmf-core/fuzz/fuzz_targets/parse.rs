@@ -0,0 +1,51 @@
+//! Hostile bytes into every parser that accepts shared data: saves
+//! (serialized `QuantumGrid`), configs, replays, and compact snapshots.
+//! Parsing must never panic, and a save that passes `invariants::check` must
+//! be playable.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use qmf_core::action::Action;
+use qmf_core::codec;
+use qmf_core::config::GridConfig;
+use qmf_core::grid::QuantumGrid;
+use qmf_core::invariants;
+use qmf_core::replay::{Replay, SpectatorReplay};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(snapshot) = codec::decode_snapshot(data) {
+        assert_eq!(
+            codec::decode_snapshot(&codec::encode_snapshot(&snapshot)).map(|s| s.cells.len()),
+            Ok(snapshot.cells.len())
+        );
+    }
+
+    let Ok(text) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    if let Ok(mut grid) = serde_json::from_str::<QuantumGrid>(text) {
+        if invariants::check(&grid).is_ok() && grid.cells.len() <= 4096 {
+            for (x, y) in [(0, 0), (1, 1), (grid.width / 2, grid.height / 2)] {
+                grid.apply(Action::Hadamard { x, y });
+                grid.apply(Action::Reveal { x, y });
+                grid.apply(Action::Contain { x: y, y: x });
+            }
+            let _ = grid.snapshot();
+        }
+    }
+
+    if let Ok(config) = serde_json::from_str::<GridConfig>(text) {
+        let _ = config.validate();
+    }
+
+    if let Ok(replay) = serde_json::from_str::<Replay>(text) {
+        if replay.config.validate().is_ok() && replay.config.total_cells() <= 4096 {
+            let _ = replay.play();
+        }
+    }
+
+    if let Ok(spectator) = serde_json::from_str::<SpectatorReplay>(text) {
+        let _ = spectator.cells_after(spectator.frames.len());
+    }
+});
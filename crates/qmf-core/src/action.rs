@@ -0,0 +1,215 @@
+use alloc::collections::VecDeque;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::grid::RevealOutcome;
+use crate::prelude::*;
+
+/// A player move, as data. Every state-changing entry point on
+/// [`QuantumGrid`](crate::grid::QuantumGrid) has a variant here so moves can
+/// be validated, recorded, and replayed uniformly.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum Action {
+    Reveal {
+        x: u32,
+        y: u32,
+    },
+    Contain {
+        x: u32,
+        y: u32,
+    },
+    Hadamard {
+        x: u32,
+        y: u32,
+    },
+    MeasureWeak {
+        x: u32,
+        y: u32,
+    },
+    /// Weak measurement at `percent`% precision (0–100).
+    MeasureWeakWithPrecision {
+        x: u32,
+        y: u32,
+        percent: u8,
+    },
+    /// Entanglement swapping between `(x, y)` and `(other_x, other_y)`;
+    /// spends a swap charge.
+    SwapEntanglement {
+        x: u32,
+        y: u32,
+        other_x: u32,
+        other_y: u32,
+    },
+    /// Teleport the state of `(x, y)` onto `(to_x, to_y)`, consuming one
+    /// of the source's Bell links.
+    Teleport {
+        x: u32,
+        y: u32,
+        to_x: u32,
+        to_y: u32,
+    },
+    /// Let `dt_ms` of game time pass (see
+    /// [`QuantumGrid::advance`](crate::grid::QuantumGrid::advance)).
+    Advance {
+        dt_ms: u32,
+    },
+    /// Give up the points left this turn and start the next one (see
+    /// [`GridConfig::turns`](crate::config::GridConfig::turns)).
+    EndTurn,
+}
+
+impl Action {
+    /// The cell the action targets; for two-cell actions, the first one.
+    /// `None` for [`Action::Advance`] and [`Action::EndTurn`], which
+    /// target no cell.
+    pub fn coords(&self) -> Option<(u32, u32)> {
+        match *self {
+            Self::Reveal { x, y }
+            | Self::Contain { x, y }
+            | Self::Hadamard { x, y }
+            | Self::MeasureWeak { x, y }
+            | Self::MeasureWeakWithPrecision { x, y, .. }
+            | Self::SwapEntanglement { x, y, .. }
+            | Self::Teleport { x, y, .. } => Some((x, y)),
+            Self::Advance { .. } | Self::EndTurn => None,
+        }
+    }
+
+    /// The second cell of a two-cell action.
+    pub fn other_coords(&self) -> Option<(u32, u32)> {
+        match *self {
+            Self::SwapEntanglement {
+                other_x, other_y, ..
+            } => Some((other_x, other_y)),
+            Self::Teleport { to_x, to_y, .. } => Some((to_x, to_y)),
+            _ => None,
+        }
+    }
+}
+
+/// What applying an [`Action`] produced.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum ActionResult {
+    /// Result of a reveal or containment.
+    Outcome { outcome: RevealOutcome },
+    /// Probability returned by a tool.
+    Probability { value: f64 },
+    /// An entanglement swap linked these two cells.
+    Relinked {
+        x: u32,
+        y: u32,
+        partner_x: u32,
+        partner_y: u32,
+    },
+    /// Game time moved on to `time_ms`.
+    Advanced { time_ms: u64 },
+    /// Turn `turn` began with `action_points` to spend.
+    NextTurn { turn: u32, action_points: u32 },
+    /// A tool refused to act; the grid is unchanged.
+    ToolRejected { reason: String },
+}
+
+impl ActionResult {
+    /// `true` when the grid was left untouched.
+    pub fn is_rejection(&self) -> bool {
+        match self {
+            Self::Outcome { outcome } => outcome.is_rejection(),
+            Self::Probability { .. }
+            | Self::Relinked { .. }
+            | Self::Advanced { .. }
+            | Self::NextTurn { .. } => false,
+            Self::ToolRejected { .. } => true,
+        }
+    }
+}
+
+/// How many applied action IDs a grid remembers for deduplication.
+pub const REMEMBERED_ACTIONS: usize = 64;
+
+/// Results of the most recent actions applied with an ID, so a retried
+/// request (e.g. after a network timeout) gets the original outcome back
+/// instead of being applied twice.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AppliedActions {
+    recent: VecDeque<(u64, ActionResult)>,
+}
+
+impl AppliedActions {
+    /// The result the action with `action_id` produced, if it is still
+    /// remembered.
+    pub fn get(&self, action_id: u64) -> Option<&ActionResult> {
+        self.recent
+            .iter()
+            .find(|(id, _)| *id == action_id)
+            .map(|(_, result)| result)
+    }
+
+    /// Remember `result` for `action_id`, forgetting the oldest once
+    /// [`REMEMBERED_ACTIONS`] are held.
+    pub fn record(&mut self, action_id: u64, result: ActionResult) {
+        if self.recent.len() == REMEMBERED_ACTIONS {
+            self.recent.pop_front();
+        }
+        self.recent.push_back((action_id, result));
+    }
+}
+
+/// What an [`Action`] would do, worked out against the true mine map —
+/// inspector-level information for a "confirm risky click?" prompt.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MoveEvaluation {
+    /// Chance the move ends the game in a detonation. The engine knows the
+    /// board, so this is `0.0` or `1.0` unless an unplaced board makes the
+    /// first click a gamble.
+    pub detonation_risk: f64,
+    /// Bits of [`quantum_entropy`](crate::grid::QuantumGrid::quantum_entropy)
+    /// the move would remove (negative when it adds uncertainty).
+    pub information_gain: f64,
+    /// Cells the move would resolve, including any cascade.
+    pub cascade: u32,
+}
+
+/// Why an action is not allowed in the current state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum IllegalAction {
+    GameAlreadyOver,
+    OutOfBounds,
+    AlreadyResolved,
+    NoChargesRemaining,
+    /// The game's [`AssistLevel`](crate::config::AssistLevel) does not
+    /// allow this analysis.
+    AssistDisabled,
+    /// The board was reconstructed from what a player could see, so where
+    /// its mines are is unknown.
+    UnknownGroundTruth,
+    /// Turn-based play: the action costs more points than this turn has
+    /// left.
+    NotEnoughActionPoints,
+    /// [`Action::EndTurn`] outside turn-based play.
+    NotTurnBased,
+    /// The kind of board has no such action, like a tool on a
+    /// [`FixedGrid`](crate::fixed::FixedGrid).
+    Unsupported,
+    /// A two-cell tool aimed twice at the same cell.
+    SameCell,
+    /// The mines are not placed until the first reveal.
+    NotGenerated,
+    /// The target is frozen by the quantum Zeno effect.
+    Frozen,
+    /// A teleport from a cell with no unresolved Bell partner to spend.
+    NoBellPair,
+    /// A precision measurement above 100 percent.
+    InvalidPrecision,
+    /// The grid refused the action for a reason the checks above do not
+    /// cover.
+    Refused,
+}
@@ -0,0 +1,316 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::action::Action;
+#[cfg(feature = "solver")]
+use crate::grid::{CellState, QuantumGrid};
+use crate::prelude::*;
+use crate::replay::Replay;
+#[cfg(feature = "solver")]
+use crate::replay::{apply_step, CoachingTag, MoveClass, ReplayError};
+#[cfg(feature = "solver")]
+use crate::solver::{self, SolveBudget};
+
+/// How much riskier than the safest move a guess may be and still count as
+/// [`MoveClass::Suboptimal`] rather than [`MoveClass::UnnecessaryRisk`].
+#[cfg(feature = "solver")]
+pub const RISK_MARGIN: f64 = 0.1;
+
+#[cfg(feature = "solver")]
+const EPSILON: f64 = 1e-9;
+
+/// Time spent deciding on one cell over a game.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CellTiming {
+    /// Actions aimed at the cell.
+    pub actions: u32,
+    /// Decision time before those actions, summed.
+    pub dwell_ms: u64,
+    /// The longest single decision.
+    pub longest_ms: u64,
+    /// Decision time beyond the game's median, summed: the time a faster
+    /// run would have saved here.
+    pub lost_ms: u64,
+}
+
+/// Where the time in a timed replay went, for the post-game review.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TimingHeatmap {
+    pub width: u32,
+    pub height: u32,
+    /// Row-major, one per cell.
+    pub cells: Vec<CellTiming>,
+    /// Row-major [`CellTiming::lost_ms`] scaled so the worst cell is 1.0;
+    /// all zero when no decision was slower than the median.
+    pub hesitation: Vec<f64>,
+    /// Median decision time over the game's aimed actions.
+    pub median_ms: u64,
+    /// Sum of every cell's [`CellTiming::lost_ms`].
+    pub lost_ms: u64,
+}
+
+/// Per-cell decision times from `replay`'s step times. A decision runs
+/// from the previous player action to the next, so the first move and
+/// [`Action::Advance`] steps (time passing, not a choice) start no
+/// decision of their own. Steps outside the board are skipped; the replay
+/// is not verified.
+pub fn timing_heatmap(replay: &Replay) -> TimingHeatmap {
+    let (width, height) = (replay.config.width, replay.config.height);
+    let mut cells = vec![CellTiming::default(); (width as usize) * (height as usize)];
+    let mut decisions = Vec::new();
+    let mut last_ms = None;
+    for step in &replay.steps {
+        if matches!(step.action, Action::Advance { .. }) {
+            continue;
+        }
+        let decision_ms = last_ms.map_or(0, |last| step.t_ms.saturating_sub(last));
+        last_ms = Some(step.t_ms);
+        let Some((x, y)) = step.action.coords() else {
+            continue;
+        };
+        if x >= width || y >= height {
+            continue;
+        }
+        let index = (y as usize) * (width as usize) + x as usize;
+        decisions.push((index, decision_ms));
+    }
+
+    let mut sorted: Vec<u64> = decisions.iter().map(|&(_, ms)| ms).collect();
+    sorted.sort_unstable();
+    let median_ms = sorted.get(sorted.len() / 2).copied().unwrap_or(0);
+    for (index, decision_ms) in decisions {
+        if let Some(cell) = cells.get_mut(index) {
+            cell.actions += 1;
+            cell.dwell_ms += decision_ms;
+            cell.longest_ms = cell.longest_ms.max(decision_ms);
+            cell.lost_ms += decision_ms.saturating_sub(median_ms);
+        }
+    }
+    let worst = cells.iter().map(|cell| cell.lost_ms).max().unwrap_or(0);
+    let hesitation = cells
+        .iter()
+        .map(|cell| match worst {
+            0 => 0.0,
+            worst => cell.lost_ms as f64 / worst as f64,
+        })
+        .collect();
+    TimingHeatmap {
+        width,
+        height,
+        lost_ms: cells.iter().map(|cell| cell.lost_ms).sum(),
+        cells,
+        hesitation,
+        median_ms,
+    }
+}
+
+/// Every reveal and containment in a game, classified from what the
+/// player could see at the time.
+#[cfg(feature = "solver")]
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PostMortem {
+    /// One per reveal or containment, in step order.
+    pub moves: Vec<CoachingTag>,
+    pub proven: u32,
+    pub forced: u32,
+    pub suboptimal: u32,
+    pub unnecessary_risk: u32,
+    pub blunders: u32,
+}
+
+#[cfg(feature = "solver")]
+impl PostMortem {
+    /// The moves worth stopping at in a review: all but the proven ones.
+    pub fn tags(&self) -> Vec<CoachingTag> {
+        self.moves
+            .iter()
+            .filter(|tag| tag.class != MoveClass::Proven)
+            .copied()
+            .collect()
+    }
+}
+
+/// Replay the game, asking the solver before each reveal or containment
+/// how risky it was and how risky the safest move was. The first click is
+/// protected, so it always counts as proven. Tools, time steps and moves
+/// off the board are not judged.
+#[cfg(feature = "solver")]
+pub fn post_mortem(replay: &Replay) -> Result<PostMortem, ReplayError> {
+    let mut grid = replay.seek(0)?;
+    let mut review = PostMortem::default();
+    for (step, replay_step) in replay.steps.iter().enumerate() {
+        if let Some(tag) = classify(&grid, step, replay_step.action) {
+            let count = match tag.class {
+                MoveClass::Proven => &mut review.proven,
+                MoveClass::Forced => &mut review.forced,
+                MoveClass::Suboptimal => &mut review.suboptimal,
+                MoveClass::UnnecessaryRisk => &mut review.unnecessary_risk,
+                MoveClass::Blunder => &mut review.blunders,
+            };
+            *count += 1;
+            review.moves.push(tag);
+        }
+        apply_step(&mut grid, step, replay_step)?;
+    }
+    Ok(review)
+}
+
+/// [`post_mortem`], keeping its [tags](PostMortem::tags) on the replay for
+/// the review screen.
+#[cfg(feature = "solver")]
+pub fn coach(replay: &mut Replay) -> Result<PostMortem, ReplayError> {
+    let review = post_mortem(replay)?;
+    replay.tags = review.tags();
+    Ok(review)
+}
+
+#[cfg(feature = "solver")]
+fn classify(grid: &QuantumGrid, step: usize, action: Action) -> Option<CoachingTag> {
+    let (contain, x, y) = match action {
+        Action::Reveal { x, y } => (false, x, y),
+        Action::Contain { x, y } => (true, x, y),
+        _ => return None,
+    };
+    let tag = |class, risk, safest| CoachingTag {
+        step: step as u32,
+        class,
+        risk,
+        safest,
+    };
+    let index = grid.index_of(x, y)?;
+    if !grid.mines_placed {
+        return Some(tag(MoveClass::Proven, 0.0, 0.0));
+    }
+    let solved = solver::probabilities(grid, SolveBudget::default());
+    let safest = grid
+        .cells
+        .iter()
+        .zip(&solved.cells)
+        .filter(|(cell, _)| matches!(cell.state, CellState::Superposition { .. }))
+        .map(|(_, &p)| p.min(1.0 - p))
+        .fold(1.0, f64::min);
+    let p = *solved.cells.get(index)?;
+    let risk = if contain { 1.0 - p } else { p };
+    let class = if risk <= EPSILON {
+        MoveClass::Proven
+    } else if risk >= 1.0 - EPSILON {
+        MoveClass::Blunder
+    } else if safest <= EPSILON {
+        MoveClass::UnnecessaryRisk
+    } else if risk <= safest + EPSILON {
+        MoveClass::Forced
+    } else if risk <= safest + RISK_MARGIN {
+        MoveClass::Suboptimal
+    } else {
+        MoveClass::UnnecessaryRisk
+    };
+    Some(tag(class, risk, safest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GridConfig;
+    #[cfg(feature = "solver")]
+    use crate::grid::QuantumGrid;
+
+    #[test]
+    fn slow_decisions_light_up_the_heatmap() {
+        let mut replay = Replay::new(GridConfig::new(4, 4, 2, 1, "observer"));
+        for (t_ms, action) in [
+            (1_000, Action::Reveal { x: 0, y: 0 }),
+            (1_200, Action::Reveal { x: 1, y: 0 }),
+            (1_400, Action::Advance { dt_ms: 5_000 }),
+            (5_200, Action::Contain { x: 3, y: 3 }),
+            (5_500, Action::Reveal { x: 1, y: 0 }),
+            (5_700, Action::EndTurn),
+        ] {
+            replay.record(t_ms, action);
+        }
+
+        let heatmap = timing_heatmap(&replay);
+        assert_eq!(heatmap.median_ms, 300);
+        let corner = &heatmap.cells[15];
+        assert_eq!(
+            (corner.actions, corner.dwell_ms, corner.lost_ms),
+            (1, 4_000, 3_700)
+        );
+        let second = &heatmap.cells[1];
+        assert_eq!(
+            (second.actions, second.dwell_ms, second.longest_ms),
+            (2, 500, 300)
+        );
+        assert_eq!(heatmap.cells[0].dwell_ms, 0);
+        assert_eq!(heatmap.hesitation[15], 1.0);
+        assert_eq!(heatmap.hesitation[1], 0.0);
+        assert_eq!(heatmap.lost_ms, 3_700);
+    }
+
+    #[cfg(feature = "solver")]
+    #[test]
+    fn post_mortem_tags_guesses_and_blunders() {
+        // A board where, after the opening, one cell is proven safe, one
+        // proven a mine, and some are neither.
+        let (config, safe, mine) = (1..)
+            .find_map(|seed| {
+                let config = GridConfig::new(9, 9, 14, seed, "observer");
+                let mut grid = QuantumGrid::from_config(config.clone());
+                grid.reveal_cell(4, 4);
+                let solved = solver::probabilities(&grid, SolveBudget::default());
+                let open = |index: usize| {
+                    matches!(grid.cells[index].state, CellState::Superposition { .. })
+                };
+                let find = |p: f64| (0..81).find(|&i| open(i) && solved.cells[i] == p);
+                let uncertain = (0..81).any(|i| open(i) && solved.cells[i] > 0.3);
+                (uncertain && !grid.game_over).then_some((config, find(0.0)?, find(1.0)?))
+            })
+            .unwrap();
+        let at = |index: usize| ((index % 9) as u32, (index / 9) as u32);
+        let mut replay = Replay::new(config);
+        replay.record(0, Action::Reveal { x: 4, y: 4 });
+        let mut live = replay.play().unwrap();
+        let (sx, sy) = at(safe);
+        let (mx, my) = at(mine);
+        // Containing a proven safe cell goes against the proof.
+        replay.record(100, Action::Contain { x: sx, y: sy });
+        live.apply(Action::Contain { x: sx, y: sy });
+        // A risky guess while the mine was still there to contain.
+        let cells = solver::probabilities(&live, SolveBudget::default()).cells;
+        let risky = (0..81)
+            .find(|&i| {
+                matches!(live.cells[i].state, CellState::Superposition { .. })
+                    && cells[i] > 0.3
+                    && cells[i] < 1.0
+            })
+            .unwrap();
+        let (rx, ry) = at(risky);
+        replay.record(200, Action::Contain { x: mx, y: my });
+        replay.record(300, Action::Reveal { x: rx, y: ry });
+
+        let mut coached = replay.clone();
+        let review = coach(&mut coached).unwrap();
+        let classes: Vec<MoveClass> = review.moves.iter().map(|tag| tag.class).collect();
+        assert_eq!(
+            classes[..3],
+            [MoveClass::Proven, MoveClass::Blunder, MoveClass::Proven]
+        );
+        assert_eq!(classes[3], MoveClass::UnnecessaryRisk);
+        assert_eq!(review.blunders, 1);
+        assert_eq!(
+            coached.tags.iter().map(|tag| tag.step).collect::<Vec<_>>(),
+            [1, 3]
+        );
+
+        // A forged step far off the board is refused, not judged.
+        let mut forged = Replay::new(GridConfig::new(9, 9, 14, 1, "observer"));
+        forged.record(0, Action::Reveal { x: 4, y: 4 });
+        forged.record(100, Action::Reveal { x: u32::MAX, y: 9 });
+        assert!(matches!(
+            post_mortem(&forged),
+            Err(ReplayError::Illegal { step: 1, .. })
+        ));
+    }
+}
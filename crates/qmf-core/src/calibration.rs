@@ -0,0 +1,235 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::config::GridConfig;
+use crate::generator::neighbors;
+use crate::grid::{CellState, QuantumGrid};
+use crate::prelude::*;
+
+/// Buckets in a [`HintAudit`] calibration curve.
+pub const BINS: usize = 10;
+
+/// One bucket of a calibration curve: the unresolved cells whose displayed
+/// hint fell in `[lower, upper)`, and how many of them were mines.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CalibrationBin {
+    pub lower: f64,
+    pub upper: f64,
+    pub cells: u32,
+    pub mines: u32,
+    pub mean_hint: f64,
+}
+
+impl CalibrationBin {
+    /// Share of the bucket's cells that were mines; `None` when empty.
+    pub fn mine_rate(&self) -> Option<f64> {
+        (self.cells > 0).then(|| f64::from(self.mines) / f64::from(self.cells))
+    }
+}
+
+/// How well displayed hints track where the mines really are, for tests
+/// and balancing. Hints blend the density of a cell's *neighbourhood* (the
+/// cell's own mine is not counted), so they are scored against both the
+/// cell and its neighbours.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct HintAudit {
+    pub difficulty: String,
+    /// Unresolved cells sampled.
+    pub cells: u32,
+    pub mines: u32,
+    /// Pearson correlation of the displayed hint with being a mine;
+    /// negative under an inverting circuit.
+    pub correlation: f64,
+    /// Chance a random mine reads more dangerous than a random safe cell,
+    /// reading hints the way the circuit points (see
+    /// [`ThemeHint::is_inverted`]). 0.5 is no information, 1.0 a perfect
+    /// ranking.
+    pub auc: f64,
+    /// The same, for cells with at least one mine next to them against
+    /// cells with none.
+    pub neighborhood_auc: f64,
+    /// Mean squared error of the displayed hint taken at face value as a
+    /// mine probability.
+    pub brier: f64,
+    /// Calibration curve over the displayed hint, [`BINS`] buckets wide.
+    pub bins: Vec<CalibrationBin>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Sample {
+    hint: f64,
+    /// Hint oriented so that higher reads as more dangerous.
+    danger: f64,
+    mine: bool,
+    mine_nearby: bool,
+}
+
+/// Audit the hints on the unresolved cells of `grids`, pooled. Boards
+/// without mines yet, or rebuilt by
+/// [`QuantumGrid::reconstruct`](crate::grid::QuantumGrid::reconstruct),
+/// have no ground truth and are skipped.
+pub fn audit<'a>(grids: impl IntoIterator<Item = &'a QuantumGrid>) -> HintAudit {
+    let mut difficulty = String::new();
+    let mut samples = Vec::new();
+    for grid in grids {
+        if !grid.mines_placed || grid.reconstructed {
+            continue;
+        }
+        if difficulty.is_empty() {
+            difficulty.clone_from(&grid.config.difficulty);
+        }
+        let density = f64::from(grid.mine_count) / grid.cells.len().max(1) as f64;
+        let inverted = grid.circuit.theme_hint(density).is_inverted();
+        for (index, cell) in grid.cells.iter().enumerate() {
+            let CellState::Superposition { probability } = cell.state else {
+                continue;
+            };
+            samples.push(Sample {
+                hint: probability,
+                danger: if inverted { -probability } else { probability },
+                mine: grid.is_mine(index),
+                mine_nearby: neighbors(grid.width, grid.height, index)
+                    .any(|neighbor| grid.is_mine(neighbor)),
+            });
+        }
+    }
+    summarize(difficulty, samples)
+}
+
+/// Audit `boards` games of `config`, seeded from `config.seed` upwards,
+/// each opened with a reveal in the middle of the board.
+pub fn audit_difficulty(config: &GridConfig, boards: u32) -> HintAudit {
+    let grids: Vec<QuantumGrid> = (0..u64::from(boards))
+        .map(|offset| {
+            let mut grid = QuantumGrid::from_config(GridConfig {
+                seed: config.seed.wrapping_add(offset),
+                ..config.clone()
+            });
+            grid.reveal_cell(config.width / 2, config.height / 2);
+            grid
+        })
+        .collect();
+    HintAudit {
+        difficulty: config.difficulty.clone(),
+        ..audit(&grids)
+    }
+}
+
+fn summarize(difficulty: String, mut samples: Vec<Sample>) -> HintAudit {
+    let n = (samples.len() as f64).max(1.0);
+    let truth = |sample: &Sample| f64::from(u8::from(sample.mine));
+    let mines = samples.iter().filter(|sample| sample.mine).count();
+
+    let mut bins: Vec<CalibrationBin> = (0..BINS)
+        .map(|i| CalibrationBin {
+            lower: i as f64 / BINS as f64,
+            upper: (i + 1) as f64 / BINS as f64,
+            cells: 0,
+            mines: 0,
+            mean_hint: 0.0,
+        })
+        .collect();
+    for sample in &samples {
+        let Some(bin) = bins.get_mut(((sample.hint * BINS as f64) as usize).min(BINS - 1)) else {
+            continue;
+        };
+        bin.cells += 1;
+        bin.mines += u32::from(sample.mine);
+        bin.mean_hint += sample.hint;
+    }
+    for bin in &mut bins {
+        if bin.cells > 0 {
+            bin.mean_hint /= f64::from(bin.cells);
+        }
+    }
+
+    let brier = samples
+        .iter()
+        .map(|sample| (sample.hint - truth(sample)).powi(2))
+        .sum::<f64>()
+        / n;
+
+    let correlation = {
+        let mean_x = samples.iter().map(|sample| sample.hint).sum::<f64>() / n;
+        let mean_y = mines as f64 / n;
+        let (mut cov, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+        for sample in &samples {
+            let dx = sample.hint - mean_x;
+            let dy = truth(sample) - mean_y;
+            cov += dx * dy;
+            var_x += dx * dx;
+            var_y += dy * dy;
+        }
+        if var_x > 0.0 && var_y > 0.0 {
+            cov / (var_x * var_y).sqrt()
+        } else {
+            0.0
+        }
+    };
+
+    samples.sort_by(|a, b| a.danger.total_cmp(&b.danger));
+    HintAudit {
+        difficulty,
+        cells: samples.len() as u32,
+        mines: mines as u32,
+        correlation,
+        auc: auc(&samples, |sample| sample.mine),
+        neighborhood_auc: auc(&samples, |sample| sample.mine_nearby),
+        brier,
+        bins,
+    }
+}
+
+/// Mann–Whitney AUC of `positive` over `samples` sorted by danger, ties
+/// sharing their average rank. 0.5 when either class is empty.
+fn auc(samples: &[Sample], positive: impl Fn(&Sample) -> bool) -> f64 {
+    let mut positive_ranks = 0.0;
+    let mut positives = 0_usize;
+    let mut start = 0;
+    for group in samples.chunk_by(|a, b| a.danger == b.danger) {
+        let end = start + group.len();
+        let rank = (start + end + 1) as f64 / 2.0;
+        let tied = group.iter().filter(|s| positive(s)).count();
+        positive_ranks += rank * tied as f64;
+        positives += tied;
+        start = end;
+    }
+    let negatives = samples.len() - positives;
+    if positives == 0 || negatives == 0 {
+        return 0.5;
+    }
+    let (positives, negatives) = (positives as f64, negatives as f64);
+    (positive_ranks - positives * (positives + 1.0) / 2.0) / (positives * negatives)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tuner::DIFFICULTIES;
+
+    #[test]
+    fn hints_stay_informative_at_every_difficulty() {
+        for difficulty in DIFFICULTIES {
+            let config = GridConfig::new(16, 16, 40, 1, difficulty);
+            let report = audit_difficulty(&config, 24);
+            assert_eq!(report.difficulty, difficulty);
+            assert!(report.cells > 0 && report.mines > 0);
+            assert_eq!(
+                report.bins.iter().map(|bin| bin.cells).sum::<u32>(),
+                report.cells
+            );
+            assert!(
+                report.neighborhood_auc > 0.9,
+                "{difficulty}: AUC {}",
+                report.neighborhood_auc
+            );
+            // Hints describe the neighbourhood, never the mine itself.
+            assert!((0.3..0.7).contains(&report.auc), "{difficulty}: {report:?}");
+        }
+        // No ground truth before the first click.
+        let fresh = QuantumGrid::from_config(GridConfig::new(8, 8, 10, 1, "observer"));
+        assert_eq!(audit([&fresh]).cells, 0);
+    }
+}
@@ -0,0 +1,86 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+/// Side length, in cells, of a dirty-tracking chunk.
+pub const CHUNK_SIZE: u32 = 16;
+
+/// A `CHUNK_SIZE × CHUNK_SIZE` tile of the board; the last row and column
+/// of chunks may be partial.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ChunkCoord {
+    pub cx: u32,
+    pub cy: u32,
+}
+
+/// Per-chunk revision stamps, bumped by every cell mutation.
+///
+/// A renderer remembers [`Self::revision`] after uploading tiles and later
+/// asks [`Self::changed_since`] for the chunks it needs to re-upload.
+/// Not saved: a loaded grid starts at revision 0 with nothing dirty, and the
+/// renderer is expected to upload it whole.
+#[derive(Debug, Clone, Default)]
+pub struct ChunkTracker {
+    revision: u64,
+    columns: u32,
+    stamps: Vec<u64>,
+}
+
+impl ChunkTracker {
+    /// Revision of the most recent mutation; 0 if nothing has changed yet.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Chunks mutated after `revision`, in row-major order.
+    pub fn changed_since(&self, revision: u64) -> Vec<ChunkCoord> {
+        self.stamps
+            .iter()
+            .enumerate()
+            .filter(|&(_, &stamp)| stamp > revision)
+            .map(|(index, _)| ChunkCoord {
+                cx: index as u32 % self.columns,
+                cy: index as u32 / self.columns,
+            })
+            .collect()
+    }
+
+    /// Record a mutation of cell `(x, y)` on a `width × height` board.
+    pub(crate) fn touch(&mut self, width: u32, height: u32, x: u32, y: u32) {
+        if self.stamps.is_empty() {
+            self.columns = width.div_ceil(CHUNK_SIZE);
+            let rows = height.div_ceil(CHUNK_SIZE);
+            self.stamps = vec![0; self.columns as usize * rows as usize];
+        }
+        self.revision += 1;
+        let index = (y / CHUNK_SIZE) as usize * self.columns as usize + (x / CHUNK_SIZE) as usize;
+        if let Some(stamp) = self.stamps.get_mut(index) {
+            *stamp = self.revision;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_only_chunks_touched_after_a_revision() {
+        let mut tracker = ChunkTracker::default();
+        assert!(tracker.changed_since(0).is_empty());
+
+        tracker.touch(40, 20, 0, 0);
+        let seen = tracker.revision();
+        tracker.touch(40, 20, 39, 19);
+        tracker.touch(40, 20, 17, 3);
+
+        assert_eq!(tracker.changed_since(0).len(), 3);
+        assert_eq!(
+            tracker.changed_since(seen),
+            vec![ChunkCoord { cx: 1, cy: 0 }, ChunkCoord { cx: 2, cy: 1 }]
+        );
+        assert!(tracker.changed_since(tracker.revision()).is_empty());
+    }
+}
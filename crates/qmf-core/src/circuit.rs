@@ -1,14 +1,131 @@
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
+use crate::generator::cell_index;
+use crate::prelude::*;
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Gate {
     Hadamard,
     Not,
     PhaseShift(f64),
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+/// Half-width of the uniform noise added to a placed board's hints before
+/// scrambling, so identical neighbourhoods don't look identical.
+pub const HINT_NOISE: f64 = 0.03;
+
+/// What a displayed hint says about the probability that went into the
+/// circuit; see [`Circuit::calibrate`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CalibratedEstimate {
+    /// Best estimate of the unscrambled probability.
+    pub mean: f64,
+    /// Standard deviation around `mean`: the hint noise, or 0.5 when the
+    /// circuit erases all information.
+    pub spread: f64,
+}
+
+/// A set of cells on a `width × height` board that a circuit can run over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum Region {
+    Row {
+        y: u32,
+    },
+    Column {
+        x: u32,
+    },
+    /// Row and column through `(x, y)`, without `(x, y)` itself.
+    Cross {
+        x: u32,
+        y: u32,
+    },
+    /// Half-open rectangle `[x0, x1) × [y0, y1)`, clipped to the board.
+    Rect {
+        x0: u32,
+        y0: u32,
+        x1: u32,
+        y1: u32,
+    },
+}
+
+impl Region {
+    /// Cell indices covered, row-major and without duplicates.
+    pub fn cells(&self, width: u32, height: u32) -> Vec<usize> {
+        let index = |x: u32, y: u32| cell_index(width, height, x, y);
+        match *self {
+            Self::Row { y } => (0..width).filter_map(|x| index(x, y)).collect(),
+            Self::Column { x } => (0..height).filter_map(|y| index(x, y)).collect(),
+            Self::Cross { x, y } if x < width && y < height => {
+                let mut cells: Vec<usize> = (0..height)
+                    .filter(|&row| row != y)
+                    .filter_map(|row| index(x, row))
+                    .chain(
+                        (0..width)
+                            .filter(|&col| col != x)
+                            .filter_map(|col| index(col, y)),
+                    )
+                    .collect();
+                cells.sort_unstable();
+                cells
+            }
+            Self::Rect { x0, y0, x1, y1 } => (y0..y1.min(height))
+                .flat_map(|y| (x0..x1.min(width)).map(move |x| (x, y)))
+                .filter_map(|(x, y)| index(x, y))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// How a circuit's hints map to danger, so frontends can normalise their
+/// heatmaps per difficulty: after heavy scrambling a hint of 0.5 means
+/// "unknown", not "medium risk".
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ThemeHint {
+    /// Hint a certainly safe cell would show.
+    pub safe: f64,
+    /// Hint a certain mine would show.
+    pub mine: f64,
+    /// Lowest and highest hints the circuit can produce.
+    pub min: f64,
+    pub max: f64,
+    /// Hint of a cell known only by the board's mine density.
+    pub neutral: f64,
+}
+
+impl Default for ThemeHint {
+    /// The identity circuit's hint on an even board.
+    fn default() -> Self {
+        Circuit::default().theme_hint(0.5)
+    }
+}
+
+impl ThemeHint {
+    /// `true` when the circuit flips hints, so low means dangerous.
+    pub fn is_inverted(&self) -> bool {
+        self.mine < self.safe
+    }
+
+    /// Map a displayed hint onto danger in `[0, 1]` (0 safe, 1 mine). A
+    /// circuit that erases all information maps everything to 0.5.
+    pub fn danger(&self, hint: f64) -> f64 {
+        let span = self.mine - self.safe;
+        if span.abs() < 1e-9 {
+            return 0.5;
+        }
+        ((hint - self.safe) / span).clamp(0.0, 1.0)
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Circuit {
     pub gates: Vec<Gate>,
 }
@@ -41,6 +158,57 @@ impl Circuit {
         })
     }
 
+    /// Calibration for [`ThemeHint`] on a board with mine `density`. Every
+    /// gate is affine in the probability, so the extremes come from the
+    /// certain inputs.
+    pub fn theme_hint(&self, density: f64) -> ThemeHint {
+        let safe = self.apply_probability(0.0);
+        let mine = self.apply_probability(1.0);
+        ThemeHint {
+            safe,
+            mine,
+            min: safe.min(mine),
+            max: safe.max(mine),
+            neutral: self.apply_probability(density),
+        }
+    }
+
+    /// Map a displayed hint back to the probability that produced it, so
+    /// assists and solvers need not redo the circuit math. The gates are
+    /// affine, so the chain is inverted exactly; the spread is that of the
+    /// [`HINT_NOISE`] added before scrambling.
+    pub fn calibrate(&self, displayed: f64) -> CalibratedEstimate {
+        let offset = self.apply_probability(0.0);
+        let slope = self.apply_probability(1.0) - offset;
+        if slope.abs() < 1e-9 {
+            return CalibratedEstimate {
+                mean: 0.5,
+                spread: 0.5,
+            };
+        }
+        CalibratedEstimate {
+            mean: ((displayed - offset) / slope).clamp(0.0, 1.0),
+            spread: HINT_NOISE / 3f64.sqrt(),
+        }
+    }
+
+    /// Run the circuit over every cell of `region`. `probability` reads a
+    /// cell's current value, or `None` to leave the cell out (e.g. it is
+    /// already resolved). Returns the new value of each cell it read.
+    pub fn apply_region(
+        &self,
+        width: u32,
+        height: u32,
+        region: Region,
+        mut probability: impl FnMut(usize) -> Option<f64>,
+    ) -> Vec<(usize, f64)> {
+        region
+            .cells(width, height)
+            .into_iter()
+            .filter_map(|index| Some((index, self.apply_probability(probability(index)?))))
+            .collect()
+    }
+
     /// Construct a difficulty-appropriate gate pipeline.
     ///
     /// - `"observer"`:   mild distortion — probabilities stay close to truth
@@ -48,15 +216,15 @@ impl Circuit {
     /// - `"theorist"`:   heavy scrambling — hints are unreliable
     pub fn for_difficulty(label: &str) -> Self {
         match label {
-            "observer" => Self::default().with_gate(Gate::PhaseShift(std::f64::consts::FRAC_PI_6)),
+            "observer" => Self::default().with_gate(Gate::PhaseShift(core::f64::consts::FRAC_PI_6)),
             "theorist" => Self::default()
                 .with_gate(Gate::Hadamard)
-                .with_gate(Gate::PhaseShift(std::f64::consts::FRAC_PI_3))
+                .with_gate(Gate::PhaseShift(core::f64::consts::FRAC_PI_3))
                 .with_gate(Gate::Hadamard),
             // "researcher" or any other label
             _ => Self::default()
                 .with_gate(Gate::Hadamard)
-                .with_gate(Gate::PhaseShift(std::f64::consts::FRAC_PI_4)),
+                .with_gate(Gate::PhaseShift(core::f64::consts::FRAC_PI_4)),
         }
     }
 }
@@ -65,6 +233,41 @@ impl Circuit {
 mod tests {
     use super::*;
 
+    #[test]
+    fn theme_hints_normalise_each_difficulty() {
+        let observer = Circuit::for_difficulty("observer").theme_hint(0.15);
+        assert!(!observer.is_inverted());
+        assert!(observer.min > 0.0 && observer.max < 1.0);
+        let theorist = Circuit::for_difficulty("theorist").theme_hint(0.2);
+        assert!(theorist.max - theorist.min < observer.max - observer.min);
+        for hint in [observer, theorist] {
+            assert!((hint.danger(hint.safe)).abs() < 1e-9);
+            assert!((hint.danger(hint.mine) - 1.0).abs() < 1e-9);
+        }
+
+        let flipped = Circuit::default().with_gate(Gate::Not).theme_hint(0.2);
+        assert!(flipped.is_inverted());
+        assert!((flipped.danger(0.1) - 0.9).abs() < 1e-9);
+        let erased = Circuit::default()
+            .with_gate(Gate::PhaseShift(std::f64::consts::FRAC_PI_2))
+            .theme_hint(0.2);
+        assert_eq!(erased.danger(0.9), 0.5);
+    }
+
+    #[test]
+    fn calibration_inverts_every_difficulty() {
+        for label in ["observer", "researcher", "theorist"] {
+            let circuit = Circuit::for_difficulty(label);
+            for p in [0.05, 0.3, 0.8] {
+                let estimate = circuit.calibrate(circuit.apply_probability(p));
+                assert!((estimate.mean - p).abs() < 1e-9, "{label}: {p}");
+                assert!(estimate.spread > 0.0 && estimate.spread < HINT_NOISE);
+            }
+        }
+        let erased = Circuit::default().with_gate(Gate::PhaseShift(std::f64::consts::FRAC_PI_2));
+        assert_eq!(erased.calibrate(0.5).spread, 0.5);
+    }
+
     #[test]
     fn hadamard_compresses_toward_half() {
         let c = Circuit::default().with_gate(Gate::Hadamard);
@@ -93,6 +296,32 @@ mod tests {
         assert!((c.apply_probability(0.3) - 0.7).abs() < 1e-10);
     }
 
+    #[test]
+    fn regions_clip_and_skip_their_centre() {
+        assert_eq!(Region::Row { y: 1 }.cells(3, 2), vec![3, 4, 5]);
+        assert_eq!(Region::Column { x: 3 }.cells(3, 2), Vec::<usize>::new());
+        assert_eq!(Region::Cross { x: 1, y: 1 }.cells(3, 3), vec![1, 3, 5, 7]);
+        let rect = Region::Rect {
+            x0: 1,
+            y0: 1,
+            x1: 9,
+            y1: 9,
+        };
+        assert_eq!(rect.cells(3, 3), vec![4, 5, 7, 8]);
+    }
+
+    #[test]
+    fn apply_region_only_touches_readable_cells() {
+        let c = Circuit::default().with_gate(Gate::Not);
+        let probabilities = [0.1, 0.2, 0.3, 0.4];
+        let out = c.apply_region(2, 2, Region::Column { x: 1 }, |i| {
+            (i != 3).then_some(probabilities[i])
+        });
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0].0, 1);
+        assert!((out[0].1 - 0.8).abs() < 1e-10);
+    }
+
     #[test]
     fn difficulty_pipelines_differ() {
         let obs = Circuit::for_difficulty("observer").apply_probability(0.15);
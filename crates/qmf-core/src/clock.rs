@@ -0,0 +1,135 @@
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A timestamp went backwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum ClockError {
+    NonMonotonic { last_ms: u64, given_ms: u64 },
+}
+
+impl fmt::Display for ClockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonMonotonic { last_ms, given_ms } => {
+                write!(
+                    f,
+                    "timestamp {given_ms}ms is before the last action at {last_ms}ms"
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for ClockError {}
+
+/// Timestamps of accepted actions, supplied by an authoritative caller
+/// (e.g. a server) in milliseconds on any monotonic timeline.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GameClock {
+    pub first_action_ms: Option<u64>,
+    pub last_action_ms: Option<u64>,
+    /// Set by the action that won or lost the game.
+    pub finished_ms: Option<u64>,
+    pub actions: u32,
+    pub longest_pause_ms: u64,
+}
+
+impl GameClock {
+    /// Reject `t_ms` if it is earlier than the last recorded action.
+    pub fn check(&self, t_ms: u64) -> Result<(), ClockError> {
+        match self.last_action_ms {
+            Some(last_ms) if t_ms < last_ms => Err(ClockError::NonMonotonic {
+                last_ms,
+                given_ms: t_ms,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Record an accepted action. Callers must [`check`](Self::check) first.
+    pub fn record(&mut self, t_ms: u64, finished: bool) {
+        if let Some(last_ms) = self.last_action_ms {
+            self.longest_pause_ms = self.longest_pause_ms.max(t_ms.saturating_sub(last_ms));
+        }
+        self.first_action_ms.get_or_insert(t_ms);
+        self.last_action_ms = Some(t_ms);
+        self.actions = self.actions.saturating_add(1);
+        if finished && self.finished_ms.is_none() {
+            self.finished_ms = Some(t_ms);
+        }
+    }
+
+    pub fn stats(&self) -> TimingStats {
+        let elapsed_ms = match (
+            self.first_action_ms,
+            self.finished_ms.or(self.last_action_ms),
+        ) {
+            (Some(first), Some(end)) => end.saturating_sub(first),
+            _ => 0,
+        };
+        let mean_interval_ms = if self.actions > 1 {
+            elapsed_ms as f64 / (self.actions - 1) as f64
+        } else {
+            0.0
+        };
+        TimingStats {
+            elapsed_ms,
+            actions: self.actions,
+            mean_interval_ms,
+            longest_pause_ms: self.longest_pause_ms,
+            finished: self.finished_ms.is_some(),
+        }
+    }
+}
+
+/// Derived timing figures for leaderboard verification.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TimingStats {
+    /// First accepted action to the finishing action (or the latest action
+    /// if the game is still running).
+    pub elapsed_ms: u64,
+    pub actions: u32,
+    pub mean_interval_ms: f64,
+    pub longest_pause_ms: u64,
+    pub finished: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_time_travel() {
+        let mut clock = GameClock::default();
+        assert!(clock.check(100).is_ok());
+        clock.record(100, false);
+        assert!(clock.check(100).is_ok());
+        assert_eq!(
+            clock.check(99),
+            Err(ClockError::NonMonotonic {
+                last_ms: 100,
+                given_ms: 99
+            })
+        );
+    }
+
+    #[test]
+    fn stats_measure_first_to_finish() {
+        let mut clock = GameClock::default();
+        clock.record(1_000, false);
+        clock.record(1_500, false);
+        clock.record(3_000, true);
+        let stats = clock.stats();
+        assert_eq!(stats.elapsed_ms, 2_000);
+        assert_eq!(stats.actions, 3);
+        assert!((stats.mean_interval_ms - 1_000.0).abs() < 1e-10);
+        assert_eq!(stats.longest_pause_ms, 1_500);
+        assert!(stats.finished);
+    }
+}
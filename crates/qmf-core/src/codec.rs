@@ -0,0 +1,507 @@
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::circuit::ThemeHint;
+use crate::combo::Combo;
+use crate::cosmetics;
+use crate::decoherence::DecoherenceMeter;
+use crate::grid::{CellState, GridSnapshot, Mark, QuantumCell};
+use crate::integrity::CellIntegrity;
+use crate::inventory::ToolInventory;
+use crate::isotope::IsotopeView;
+use crate::prelude::*;
+use crate::zeno::FrozenCell;
+
+const MAGIC: &[u8; 4] = b"QMS\x02";
+const SCALE: f64 = u16::MAX as f64;
+
+/// Why [`decode_snapshot`] rejected its input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum DecodeError {
+    BadMagic,
+    Truncated,
+    /// A field held a value no encoder produces.
+    Corrupt,
+    /// The header describes an implausibly large board.
+    TooLarge,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BadMagic => write!(f, "not a compact snapshot"),
+            Self::Truncated => write!(f, "compact snapshot is truncated"),
+            Self::Corrupt => write!(f, "compact snapshot is corrupt"),
+            Self::TooLarge => write!(f, "compact snapshot board is too large"),
+        }
+    }
+}
+
+impl core::error::Error for DecodeError {}
+
+/// Largest board [`decode_snapshot`] will allocate for.
+pub const MAX_DECODED_CELLS: usize = 4_000_000;
+
+/// Compact binary encoding of a [`GridSnapshot`] for the wire.
+///
+/// JSON spends ~60 bytes per cell; here cell states and marks are
+/// run-length encoded and Superposition probabilities are quantised to 16
+/// bits and delta-encoded, so large, mostly uniform boards shrink by one to
+/// two orders of magnitude. Probabilities come back within 1/131070 of the
+/// original; everything else round-trips exactly.
+///
+/// Layout (integers are LEB128 varints unless noted):
+///
+/// ```text
+/// "QMS" 0x02 | width | height | charges | swap charges | mines_remaining
+/// | seed: u64 LE
+/// | flags: u8 (bit 0 game_over, bit 1 won) | entropy: f64 LE
+/// | quantum entropy: f64 LE
+/// | theme: (safe, mine, min, max, neutral): f64 LE each
+/// | state runs: (code: u8, run)*   code 0 superposition, 1 contained,
+///                                   2 detonated, 10 + n revealed with n
+/// | mark runs: (mark: u8, run)*    0 none, 1 flag, 2 question
+/// | probability deltas: zigzag(q[i] - q[i-1])* over Superposition cells
+/// | decoherence: total | lifetime | penalties | n | (index gap, points){n}
+/// | frozen: n | (x, y, moves_left){n}
+/// | score | combo streak | combo multiplier
+/// | isotopes: n | (x, y, stages_left){n} | shields
+/// | integrity: n | (x, y, integrity){n}
+/// ```
+///
+/// Cosmetics are not stored: decoding derives them from the seed.
+pub fn encode_snapshot(snapshot: &GridSnapshot) -> Vec<u8> {
+    let mut out = Vec::with_capacity(64 + snapshot.cells.len() / 4);
+    out.extend_from_slice(MAGIC);
+    for value in [
+        snapshot.width,
+        snapshot.height,
+        snapshot.containment_charges,
+        snapshot.swap_charges,
+        snapshot.mines_remaining,
+    ] {
+        write_varint(&mut out, value as u64);
+    }
+    out.extend_from_slice(&snapshot.seed.to_le_bytes());
+    out.push(u8::from(snapshot.game_over) | (u8::from(snapshot.won) << 1));
+    out.extend_from_slice(&snapshot.entropy.to_le_bytes());
+    out.extend_from_slice(&snapshot.quantum_entropy.to_le_bytes());
+    let theme = &snapshot.theme;
+    for value in [theme.safe, theme.mine, theme.min, theme.max, theme.neutral] {
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+
+    write_runs(
+        &mut out,
+        snapshot.cells.iter().map(|c| state_code(&c.state)),
+    );
+    write_runs(&mut out, snapshot.cells.iter().map(|c| mark_code(c.mark)));
+
+    let mut previous = 0_i64;
+    for cell in &snapshot.cells {
+        if let CellState::Superposition { probability } = cell.state {
+            let q = (probability.clamp(0.0, 1.0) * SCALE).round() as i64;
+            write_varint(&mut out, zigzag(q - previous));
+            previous = q;
+        }
+    }
+
+    let meter = &snapshot.decoherence;
+    for value in [meter.total, meter.lifetime, meter.penalties] {
+        write_varint(&mut out, value as u64);
+    }
+    let disturbed: Vec<_> = meter
+        .cells
+        .iter()
+        .enumerate()
+        .filter(|&(_, &points)| points > 0)
+        .collect();
+    write_varint(&mut out, disturbed.len() as u64);
+    let mut next = 0;
+    for (index, &points) in disturbed {
+        write_varint(&mut out, (index - next) as u64);
+        write_varint(&mut out, points as u64);
+        next = index + 1;
+    }
+
+    write_varint(&mut out, snapshot.frozen.len() as u64);
+    for cell in &snapshot.frozen {
+        for value in [cell.x, cell.y, cell.moves_left] {
+            write_varint(&mut out, value as u64);
+        }
+    }
+    write_varint(&mut out, snapshot.score);
+    write_varint(&mut out, u64::from(snapshot.combo.streak));
+    write_varint(&mut out, u64::from(snapshot.combo.multiplier));
+    write_varint(&mut out, snapshot.isotopes.len() as u64);
+    for isotope in &snapshot.isotopes {
+        for value in [isotope.x, isotope.y, isotope.stages_left] {
+            write_varint(&mut out, value as u64);
+        }
+    }
+    write_varint(&mut out, u64::from(snapshot.inventory.shields));
+    write_varint(&mut out, snapshot.integrity.len() as u64);
+    for cell in &snapshot.integrity {
+        for value in [cell.x, cell.y, cell.integrity] {
+            write_varint(&mut out, value as u64);
+        }
+    }
+    out
+}
+
+pub fn decode_snapshot(bytes: &[u8]) -> Result<GridSnapshot, DecodeError> {
+    let mut input = Reader { bytes, pos: 0 };
+    if input.take(4)? != MAGIC {
+        return Err(DecodeError::BadMagic);
+    }
+    let width = input.varint_u32()?;
+    let height = input.varint_u32()?;
+    let containment_charges = input.varint_u32()?;
+    let swap_charges = input.varint_u32()?;
+    let mines_remaining = input.varint_u32()?;
+    let seed = u64::from_le_bytes(input.array()?);
+    let flags = input.byte()?;
+    let entropy = f64::from_le_bytes(input.array()?);
+    let quantum_entropy = f64::from_le_bytes(input.array()?);
+    let total = (width as usize)
+        .checked_mul(height as usize)
+        .filter(|&total| total <= MAX_DECODED_CELLS)
+        .ok_or(DecodeError::TooLarge)?;
+    let mut float = || input.array().map(f64::from_le_bytes);
+    let theme = ThemeHint {
+        safe: float()?,
+        mine: float()?,
+        min: float()?,
+        max: float()?,
+        neutral: float()?,
+    };
+
+    let states = input.runs(total)?;
+    let marks = input.runs(total)?;
+    let mut previous = 0_i64;
+    let mut cells = Vec::with_capacity(total);
+    for (index, (&state, &mark)) in states.iter().zip(&marks).enumerate() {
+        let state = match state {
+            0 => {
+                previous = previous
+                    .checked_add(unzigzag(input.varint()?))
+                    .filter(|q| (0..=u16::MAX as i64).contains(q))
+                    .ok_or(DecodeError::Corrupt)?;
+                CellState::Superposition {
+                    probability: previous as f64 / SCALE,
+                }
+            }
+            1 => CellState::Contained,
+            2 => CellState::Detonated,
+            code @ 10..=18 => CellState::Revealed {
+                adjacent_mines: code - 10,
+            },
+            _ => return Err(DecodeError::Corrupt),
+        };
+        let mark = match mark {
+            0 => Mark::None,
+            1 => Mark::Flag,
+            2 => Mark::Question,
+            _ => return Err(DecodeError::Corrupt),
+        };
+        cells.push(QuantumCell {
+            x: (index % width as usize) as u32,
+            y: (index / width as usize) as u32,
+            state,
+            mark,
+        });
+    }
+    let decoherence = input.decoherence(total)?;
+    let frozen = input.frozen(width, height)?;
+    let score = input.varint()?;
+    let combo = Combo {
+        streak: input.varint_u32()?,
+        multiplier: input.varint_u32()?,
+    };
+    let isotopes = input.isotopes(width, height)?;
+    let inventory = ToolInventory {
+        shields: input.varint_u32()?,
+    };
+    let integrity = input.integrity(width, height)?;
+    if input.pos != bytes.len() {
+        return Err(DecodeError::Corrupt);
+    }
+
+    Ok(GridSnapshot {
+        width,
+        height,
+        game_over: flags & 1 != 0,
+        won: flags & 2 != 0,
+        seed,
+        containment_charges,
+        swap_charges,
+        mines_remaining,
+        entropy,
+        quantum_entropy,
+        cells,
+        decoherence,
+        frozen,
+        theme,
+        score,
+        combo,
+        isotopes,
+        inventory,
+        integrity,
+        cosmetics: cosmetics::field(seed, width, height),
+    })
+}
+
+fn state_code(state: &CellState) -> u8 {
+    match state {
+        CellState::Superposition { .. } => 0,
+        CellState::Contained => 1,
+        CellState::Detonated => 2,
+        CellState::Revealed { adjacent_mines } => 10 + (*adjacent_mines).min(8),
+    }
+}
+
+fn mark_code(mark: Mark) -> u8 {
+    match mark {
+        Mark::None => 0,
+        Mark::Flag => 1,
+        Mark::Question => 2,
+    }
+}
+
+fn write_runs(out: &mut Vec<u8>, codes: impl Iterator<Item = u8>) {
+    let mut current: Option<(u8, u64)> = None;
+    for code in codes {
+        current = match current {
+            Some((c, run)) if c == code => Some((c, run + 1)),
+            Some((c, run)) => {
+                out.push(c);
+                write_varint(out, run);
+                Some((code, 1))
+            }
+            None => Some((code, 1)),
+        };
+    }
+    if let Some((c, run)) = current {
+        out.push(c);
+        write_varint(out, run);
+    }
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+fn unzigzag(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], DecodeError> {
+        let end = self.pos.checked_add(n).ok_or(DecodeError::Truncated)?;
+        let slice = self
+            .bytes
+            .get(self.pos..end)
+            .ok_or(DecodeError::Truncated)?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], DecodeError> {
+        self.take(N)?.try_into().map_err(|_| DecodeError::Truncated)
+    }
+
+    fn byte(&mut self) -> Result<u8, DecodeError> {
+        let [byte] = self.array()?;
+        Ok(byte)
+    }
+
+    fn varint(&mut self) -> Result<u64, DecodeError> {
+        let mut value = 0_u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(DecodeError::Corrupt)
+    }
+
+    fn varint_u32(&mut self) -> Result<u32, DecodeError> {
+        u32::try_from(self.varint()?).map_err(|_| DecodeError::Corrupt)
+    }
+
+    fn decoherence(&mut self, total: usize) -> Result<DecoherenceMeter, DecodeError> {
+        let mut meter = DecoherenceMeter {
+            total: self.varint_u32()?,
+            lifetime: self.varint_u32()?,
+            penalties: self.varint_u32()?,
+            cells: Vec::new(),
+        };
+        let disturbed = self.varint()?;
+        if disturbed > 0 {
+            meter.cells = vec![0; total];
+        }
+        let mut next = 0_usize;
+        for _ in 0..disturbed {
+            let index = usize::try_from(self.varint()?)
+                .ok()
+                .and_then(|gap| next.checked_add(gap))
+                .filter(|&index| index < total)
+                .ok_or(DecodeError::Corrupt)?;
+            let disturbance = self.varint_u32()?;
+            *meter.cells.get_mut(index).ok_or(DecodeError::Corrupt)? = disturbance;
+            next = index + 1;
+        }
+        Ok(meter)
+    }
+
+    fn frozen(&mut self, width: u32, height: u32) -> Result<Vec<FrozenCell>, DecodeError> {
+        let count = self.varint()?;
+        let mut frozen = Vec::new();
+        for _ in 0..count {
+            let cell = FrozenCell {
+                x: self.varint_u32()?,
+                y: self.varint_u32()?,
+                moves_left: self.varint_u32()?,
+            };
+            if cell.x >= width || cell.y >= height {
+                return Err(DecodeError::Corrupt);
+            }
+            frozen.push(cell);
+        }
+        Ok(frozen)
+    }
+
+    fn isotopes(&mut self, width: u32, height: u32) -> Result<Vec<IsotopeView>, DecodeError> {
+        let count = self.varint()?;
+        let mut isotopes = Vec::new();
+        for _ in 0..count {
+            let isotope = IsotopeView {
+                x: self.varint_u32()?,
+                y: self.varint_u32()?,
+                stages_left: self.varint_u32()?,
+            };
+            if isotope.x >= width || isotope.y >= height {
+                return Err(DecodeError::Corrupt);
+            }
+            isotopes.push(isotope);
+        }
+        Ok(isotopes)
+    }
+
+    fn integrity(&mut self, width: u32, height: u32) -> Result<Vec<CellIntegrity>, DecodeError> {
+        let count = self.varint()?;
+        let mut cells = Vec::new();
+        for _ in 0..count {
+            let cell = CellIntegrity {
+                x: self.varint_u32()?,
+                y: self.varint_u32()?,
+                integrity: self.varint_u32()?,
+            };
+            if cell.x >= width || cell.y >= height {
+                return Err(DecodeError::Corrupt);
+            }
+            cells.push(cell);
+        }
+        Ok(cells)
+    }
+
+    /// Expand `(code, run)` pairs covering exactly `total` cells.
+    fn runs(&mut self, total: usize) -> Result<Vec<u8>, DecodeError> {
+        let mut codes = Vec::with_capacity(total);
+        while codes.len() < total {
+            let code = self.byte()?;
+            let run = usize::try_from(self.varint()?).map_err(|_| DecodeError::Corrupt)?;
+            if run == 0 || run > total - codes.len() {
+                return Err(DecodeError::Corrupt);
+            }
+            codes.resize(codes.len() + run, code);
+        }
+        Ok(codes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::QuantumGrid;
+
+    fn played(width: u32, height: u32, mines: u32) -> GridSnapshot {
+        let mut grid = QuantumGrid::new(width, height, mines, 7, "researcher");
+        grid.reveal_cell(width / 2, height / 2);
+        grid.set_mark(0, 0, Mark::Flag).ok();
+        for _ in 0..crate::zeno::STREAK {
+            grid.measure_weak(width - 1, height - 1).ok();
+        }
+        grid.snapshot()
+    }
+
+    #[test]
+    fn round_trips_within_quantisation() {
+        let snapshot = played(16, 16, 40);
+        let decoded = decode_snapshot(&encode_snapshot(&snapshot)).unwrap();
+        assert_eq!(decoded.width, snapshot.width);
+        assert_eq!(decoded.seed, snapshot.seed);
+        assert_eq!(decoded.mines_remaining, snapshot.mines_remaining);
+        assert_eq!(decoded.swap_charges, snapshot.swap_charges);
+        assert_eq!(decoded.quantum_entropy, snapshot.quantum_entropy);
+        assert_eq!(decoded.theme, snapshot.theme);
+        assert_eq!(decoded.decoherence, snapshot.decoherence);
+        assert_eq!(decoded.frozen, snapshot.frozen);
+        for (a, b) in decoded.cells.iter().zip(&snapshot.cells) {
+            assert_eq!((a.x, a.y, a.mark), (b.x, b.y, b.mark));
+            match (&a.state, &b.state) {
+                (
+                    CellState::Superposition { probability: p },
+                    CellState::Superposition { probability: q },
+                ) => assert!((p - q).abs() <= 1.0 / 131_070.0 + 1e-12),
+                (a, b) => assert_eq!(a, b),
+            }
+        }
+    }
+
+    #[test]
+    fn large_board_is_much_smaller_than_json() {
+        let snapshot = played(300, 300, 9_000);
+        let json = serde_json::to_vec(&snapshot).unwrap().len();
+        let compact = encode_snapshot(&snapshot).len();
+        // ~6 MB of JSON vs. a few hundred KB.
+        assert!(compact * 20 < json, "compact {compact} B vs json {json} B");
+    }
+
+    #[test]
+    fn rejects_damaged_input() {
+        let bytes = encode_snapshot(&played(8, 8, 10));
+        assert_eq!(decode_snapshot(b"nope").err(), Some(DecodeError::BadMagic));
+        assert_eq!(
+            decode_snapshot(&bytes[..bytes.len() - 1]).err(),
+            Some(DecodeError::Truncated)
+        );
+        let mut extra = bytes.clone();
+        extra.push(0);
+        assert_eq!(decode_snapshot(&extra).err(), Some(DecodeError::Corrupt));
+        let mut huge = MAGIC.to_vec();
+        write_varint(&mut huge, u32::MAX as u64);
+        write_varint(&mut huge, u32::MAX as u64);
+        huge.extend_from_slice(&[0; 32]);
+        assert_eq!(decode_snapshot(&huge).err(), Some(DecodeError::TooLarge));
+    }
+}
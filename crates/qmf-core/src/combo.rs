@@ -0,0 +1,85 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::config::ComboRules;
+
+/// Score for each cell a move resolves, before the multiplier.
+pub const POINTS_PER_CELL: u64 = 10;
+
+/// The running combo: safe reveals in a row and the multiplier they built.
+/// Without [`GridConfig::combo`](crate::config::GridConfig::combo) the
+/// multiplier stays at 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Combo {
+    pub streak: u32,
+    pub multiplier: u32,
+}
+
+impl Default for Combo {
+    fn default() -> Self {
+        Self {
+            streak: 0,
+            multiplier: 1,
+        }
+    }
+}
+
+impl Combo {
+    /// Score for `cells` resolved at the current multiplier.
+    pub fn points(&self, cells: u32) -> u64 {
+        u64::from(cells) * POINTS_PER_CELL * u64::from(self.multiplier)
+    }
+
+    /// Count one more safe reveal. Returns whether it earned a free tool
+    /// charge.
+    pub(crate) fn extend(&mut self, rules: &ComboRules) -> bool {
+        self.streak = self.streak.saturating_add(1);
+        let steps = self.streak / rules.reveals_per_step.max(1);
+        self.multiplier = steps.saturating_add(1).min(rules.max_multiplier.max(1));
+        rules.charge_every > 0 && self.streak.is_multiple_of(rules.charge_every)
+    }
+
+    /// Break the combo. Returns whether there was one to break.
+    pub(crate) fn reset(&mut self) -> bool {
+        let broken = self.streak > 0;
+        *self = Self::default();
+        broken
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiplier_climbs_in_steps_up_to_the_cap() {
+        let rules = ComboRules {
+            reveals_per_step: 2,
+            max_multiplier: 3,
+            charge_every: 5,
+        };
+        let mut combo = Combo::default();
+        let earned: Vec<(u32, bool)> = (0..6)
+            .map(|_| {
+                let charge = combo.extend(&rules);
+                (combo.multiplier, charge)
+            })
+            .collect();
+        assert_eq!(
+            earned,
+            [
+                (1, false),
+                (2, false),
+                (2, false),
+                (3, false),
+                (3, true),
+                (3, false)
+            ]
+        );
+        assert_eq!(combo.points(2), 60);
+        assert!(combo.reset());
+        assert_eq!(combo, Combo::default());
+        assert!(!combo.reset());
+    }
+}
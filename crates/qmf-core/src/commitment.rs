@@ -0,0 +1,207 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+/// Domain separator so a seed commitment can't be confused with any other
+/// SHA-256 digest.
+const DOMAIN: &[u8] = b"qmf-seed-commitment-v1";
+
+/// Commit-reveal scheme for competitive seeds.
+///
+/// Before the game the server publishes `SHA-256(DOMAIN || seed || salt)`;
+/// once it ends it reveals `seed` and `salt`, and anyone can check the board
+/// was not regenerated mid-game. The salt stops players brute-forcing the
+/// 64-bit seed from the published digest, so it must be random and secret
+/// until the reveal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "String", try_from = "String"))]
+pub struct SeedCommitment {
+    digest: [u8; 32],
+}
+
+impl SeedCommitment {
+    pub fn commit(seed: u64, salt: &[u8]) -> Self {
+        let mut preimage = Vec::with_capacity(DOMAIN.len() + 8 + salt.len());
+        preimage.extend_from_slice(DOMAIN);
+        preimage.extend_from_slice(&seed.to_le_bytes());
+        preimage.extend_from_slice(salt);
+        Self {
+            digest: sha256(&preimage),
+        }
+    }
+
+    /// Check a revealed `seed` and `salt` against this commitment.
+    pub fn verify(&self, seed: u64, salt: &[u8]) -> bool {
+        Self::commit(seed, salt) == *self
+    }
+
+    pub fn to_hex(&self) -> String {
+        to_hex(&self.digest)
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, &'static str> {
+        let bytes = from_hex(hex)?;
+        let digest = bytes
+            .try_into()
+            .map_err(|_| "commitment must be 32 bytes")?;
+        Ok(Self { digest })
+    }
+}
+
+impl From<SeedCommitment> for String {
+    fn from(value: SeedCommitment) -> Self {
+        value.to_hex()
+    }
+}
+
+impl TryFrom<String> for SeedCommitment {
+    type Error = &'static str;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::from_hex(&value)
+    }
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+pub fn from_hex(hex: &str) -> Result<Vec<u8>, &'static str> {
+    if !hex.len().is_multiple_of(2) {
+        return Err("hex string has odd length");
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            hex.get(i..i + 2)
+                .and_then(|pair| u8::from_str_radix(pair, 16).ok())
+                .ok_or("invalid hex digit")
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// SHA-256 (FIPS 180-4) — hand-rolled like `SplitMix64`, to keep qmf-core free
+// of runtime dependencies.
+// ---------------------------------------------------------------------------
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// SHA-256 digest of `data`.
+// The schedule and round loops only index the 64-entry `w` and `K` with
+// counters below 64, so the bounds are fixed.
+#[allow(clippy::indexing_slicing)]
+pub fn sha256(data: &[u8]) -> [u8; 32] {
+    let mut h: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+
+    let bit_len = (data.len() as u64).wrapping_mul(8);
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&bit_len.to_be_bytes());
+
+    for block in message.as_chunks::<64>().0 {
+        let mut w = [0u32; 64];
+        for (i, word) in block.as_chunks::<4>().0.iter().enumerate() {
+            w[i] = u32::from_be_bytes(*word);
+        }
+        for i in 16..64 {
+            let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+            let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+            w[i] = w[i - 16]
+                .wrapping_add(s0)
+                .wrapping_add(w[i - 7])
+                .wrapping_add(s1);
+        }
+
+        let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut hh] = h;
+        for i in 0..64 {
+            let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+            let ch = (e & f) ^ (!e & g);
+            let t1 = hh
+                .wrapping_add(s1)
+                .wrapping_add(ch)
+                .wrapping_add(K[i])
+                .wrapping_add(w[i]);
+            let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+            let maj = (a & b) ^ (a & c) ^ (b & c);
+            let t2 = s0.wrapping_add(maj);
+            hh = g;
+            g = f;
+            f = e;
+            e = d.wrapping_add(t1);
+            d = c;
+            c = b;
+            b = a;
+            a = t1.wrapping_add(t2);
+        }
+        for (state, value) in h.iter_mut().zip([a, b, c, d, e, f, g, hh]) {
+            *state = state.wrapping_add(value);
+        }
+    }
+
+    let mut digest = [0u8; 32];
+    for (chunk, word) in digest.as_chunks_mut::<4>().0.iter_mut().zip(h) {
+        chunk.copy_from_slice(&word.to_be_bytes());
+    }
+    digest
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sha256_known_vectors() {
+        assert_eq!(
+            to_hex(&sha256(b"")),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            to_hex(&sha256(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        // Two-block message.
+        assert_eq!(
+            to_hex(&sha256(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+            )),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+    }
+
+    #[test]
+    fn commitment_verifies_only_the_original_preimage() {
+        let salt = b"server-secret-salt";
+        let commitment = SeedCommitment::commit(42, salt);
+        assert!(commitment.verify(42, salt));
+        assert!(!commitment.verify(43, salt));
+        assert!(!commitment.verify(42, b"other-salt"));
+    }
+
+    #[test]
+    fn commitment_hex_round_trip() {
+        let commitment = SeedCommitment::commit(7, &[1, 2, 3]);
+        let hex = commitment.to_hex();
+        assert_eq!(hex.len(), 64);
+        assert_eq!(SeedCommitment::from_hex(&hex), Ok(commitment));
+        assert!(SeedCommitment::from_hex("abc").is_err());
+        assert!(SeedCommitment::from_hex(&"zz".repeat(32)).is_err());
+    }
+}
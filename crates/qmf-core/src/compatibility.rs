@@ -0,0 +1,210 @@
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::circuit::{Circuit, HINT_NOISE};
+use crate::commitment::{sha256, to_hex};
+use crate::config::GridConfig;
+use crate::grid::{entanglement_profile, QuantumGrid};
+use crate::prelude::*;
+use crate::replay::Replay;
+use crate::zeno;
+
+/// Engine version stamped into new replays and saves.
+pub const ENGINE_VERSION: &str = env!("CARGO_PKG_VERSION");
+/// Bumped whenever a rule change alters how a recorded game plays out.
+pub const RULESET_VERSION: u32 = 1;
+
+/// Which engine and rules a replay or save was recorded under.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RulesetStamp {
+    pub engine: String,
+    pub ruleset: u32,
+    /// [`ruleset_hash`] of the recorded config.
+    pub hash: String,
+}
+
+impl RulesetStamp {
+    /// The stamp this engine puts on a game played with `config`.
+    pub fn current(config: &GridConfig) -> Self {
+        Self {
+            engine: ENGINE_VERSION.to_string(),
+            ruleset: RULESET_VERSION,
+            hash: ruleset_hash(config),
+        }
+    }
+}
+
+/// Hex SHA-256 over the engine-side rules a game with `config` depends on:
+/// its difficulty's circuit and entanglement, the mine generator, hint noise
+/// and the Zeno thresholds. Settings carried in the config itself are
+/// replayed from the config and need no hashing.
+pub fn ruleset_hash(config: &GridConfig) -> String {
+    let (step, strength, bell) = entanglement_profile(&config.difficulty);
+    let circuit = Circuit::for_difficulty(&config.difficulty);
+    let rules = format!(
+        "ruleset {RULESET_VERSION}|circuit {:?}|entanglement {step} {strength:?} {bell}\
+         |generator {:?}|noise {HINT_NOISE:?}|zeno {} {}",
+        circuit.gates,
+        config.generator,
+        zeno::STREAK,
+        zeno::FROZEN_MOVES,
+    );
+    to_hex(&sha256(rules.as_bytes()))
+}
+
+/// Why a recording cannot be played back faithfully.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Incompatibility {
+    /// Recorded before stamps existed; its rules are unknown.
+    Unstamped,
+    /// Recorded by an engine this one is not semver-compatible with.
+    EngineVersion,
+    /// Same ruleset version, but the rules it hashed have changed.
+    RulesChanged,
+}
+
+impl fmt::Display for Incompatibility {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unstamped => write!(f, "recording has no ruleset stamp"),
+            Self::EngineVersion => write!(f, "recorded by an incompatible engine version"),
+            Self::RulesChanged => write!(f, "the rules have changed since recording"),
+        }
+    }
+}
+
+/// Result of [`check`]: whether a recording can be trusted on this engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum Compatibility {
+    Compatible,
+    /// Recorded under another ruleset version; play it back on an engine
+    /// that implements `version`.
+    NeedsRuleset {
+        version: u32,
+    },
+    Incompatible {
+        reason: Incompatibility,
+    },
+}
+
+/// Check a replay's stamp against this engine. Leaderboards should only
+/// accept [`Compatibility::Compatible`] replays.
+pub fn check(replay: &Replay) -> Compatibility {
+    check_stamp(replay.stamp.as_ref(), &replay.config)
+}
+
+/// [`check`] for a saved game.
+pub fn check_save(grid: &QuantumGrid) -> Compatibility {
+    check_stamp(grid.stamp.as_ref(), &grid.config)
+}
+
+fn check_stamp(stamp: Option<&RulesetStamp>, config: &GridConfig) -> Compatibility {
+    let Some(stamp) = stamp else {
+        return Compatibility::Incompatible {
+            reason: Incompatibility::Unstamped,
+        };
+    };
+    if stamp.ruleset != RULESET_VERSION {
+        return Compatibility::NeedsRuleset {
+            version: stamp.ruleset,
+        };
+    }
+    if !semver_compatible(&stamp.engine, ENGINE_VERSION) {
+        return Compatibility::Incompatible {
+            reason: Incompatibility::EngineVersion,
+        };
+    }
+    if stamp.hash != ruleset_hash(config) {
+        return Compatibility::Incompatible {
+            reason: Incompatibility::RulesChanged,
+        };
+    }
+    Compatibility::Compatible
+}
+
+/// Same major version, and for `0.x` also the same minor.
+fn semver_compatible(recorded: &str, current: &str) -> bool {
+    let parse = |version: &str| -> Option<(u64, u64)> {
+        let mut parts = version.split('.');
+        Some((parts.next()?.parse().ok()?, parts.next()?.parse().ok()?))
+    };
+    match (parse(recorded), parse(current)) {
+        (Some((major, minor)), Some((current_major, current_minor))) => {
+            major == current_major && (major != 0 || minor == current_minor)
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action::Action;
+    use crate::replay::ReplayError;
+
+    #[test]
+    fn new_recordings_are_compatible() {
+        let config = GridConfig::new(8, 8, 10, 42, "theorist");
+        let mut replay = Replay::new(config.clone());
+        replay.record(0, Action::Reveal { x: 0, y: 0 });
+        assert_eq!(check(&replay), Compatibility::Compatible);
+        assert_eq!(
+            check_save(&QuantumGrid::from_config(config)),
+            Compatibility::Compatible
+        );
+        assert_ne!(
+            ruleset_hash(&GridConfig::new(8, 8, 10, 42, "observer")),
+            replay.stamp.as_ref().unwrap().hash
+        );
+    }
+
+    #[test]
+    fn changed_rules_are_refused() {
+        let mut replay = Replay::new(GridConfig::new(8, 8, 10, 42, "observer"));
+        replay.record(0, Action::Reveal { x: 0, y: 0 });
+
+        let mut older = replay.clone();
+        older.stamp.as_mut().unwrap().ruleset = 0;
+        assert_eq!(check(&older), Compatibility::NeedsRuleset { version: 0 });
+
+        let mut changed = replay.clone();
+        changed.stamp.as_mut().unwrap().hash = "00".into();
+        let refused = Compatibility::Incompatible {
+            reason: Incompatibility::RulesChanged,
+        };
+        assert_eq!(check(&changed), refused);
+        assert_eq!(
+            changed.play().unwrap_err(),
+            ReplayError::Incompatible {
+                compatibility: refused
+            }
+        );
+
+        let mut legacy = replay;
+        legacy.stamp = None;
+        assert_eq!(
+            check(&legacy),
+            Compatibility::Incompatible {
+                reason: Incompatibility::Unstamped
+            }
+        );
+        // Unstamped replays still play; only leaderboards need the gate.
+        assert!(legacy.play().is_ok());
+    }
+
+    #[test]
+    fn semver_rules() {
+        assert!(semver_compatible("1.2.0", "1.9.3"));
+        assert!(!semver_compatible("1.2.0", "2.0.0"));
+        assert!(semver_compatible("0.1.0", "0.1.7"));
+        assert!(!semver_compatible("0.1.0", "0.2.0"));
+        assert!(!semver_compatible("garbage", "0.1.0"));
+    }
+}
@@ -0,0 +1,1316 @@
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::action::Action;
+use crate::circuit::{Circuit, Gate};
+#[cfg(doc)]
+use crate::decoherence::DecoherenceMeter;
+use crate::entanglement::EntanglementPair;
+use crate::generator::cell_index;
+use crate::grid::{entanglement_profile, QuantumCell, QuantumGrid};
+use crate::objective::Objective;
+use crate::prelude::*;
+use crate::rules::Rule;
+use crate::splits::SplitTrigger;
+use crate::zone::Zone;
+
+/// Difficulty labels, easiest first. Each fixes a circuit and an
+/// entanglement density.
+pub const DIFFICULTIES: [&str; 3] = ["observer", "researcher", "theorist"];
+
+/// What the first interaction is guaranteed to land on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum FirstClick {
+    /// The clicked cell and every cell within `safe_zone_radius` are mine-free,
+    /// so the first click always opens a cascade (classic behaviour).
+    #[default]
+    GuaranteedZero,
+    /// Only the clicked cell itself is mine-free.
+    GuaranteedSafe,
+    /// No guarantee — the first click can detonate.
+    NoGuarantee,
+}
+
+/// How many containment charges the player gets, and whether they come back.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum ChargePolicy {
+    /// Exactly one charge per mine — a wasted charge is gone for good.
+    #[default]
+    Exact,
+    /// One charge per mine plus `extra` spares.
+    Spare { extra: u32 },
+    /// One charge per mine, and one more after every `every` safe reveals
+    /// (never more than the number of mines still uncontained).
+    Regenerating { every: u32 },
+}
+
+impl ChargePolicy {
+    pub fn initial_charges(&self, mine_count: u32) -> u32 {
+        match self {
+            Self::Spare { extra } => mine_count.saturating_add(*extra),
+            Self::Exact | Self::Regenerating { .. } => mine_count,
+        }
+    }
+}
+
+/// Mine-placement strategy; see [`BoardGenerator`](crate::generator::BoardGenerator).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum Generator {
+    /// Every eligible cell equally likely (classic).
+    #[default]
+    Uniform,
+    /// Mines form branching veins. `veins: 0` picks one per eight mines.
+    Clustered { veins: u32 },
+    /// The mine layout is its own mirror image, for split-screen versus.
+    Symmetric { symmetry: Symmetry },
+}
+
+/// Which transformation a [`Generator::Symmetric`] layout is invariant under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Symmetry {
+    /// 180° rotation about the board centre.
+    Point,
+    /// Mirrored across the vertical centre line.
+    LeftRight,
+    /// Mirrored across the horizontal centre line.
+    TopBottom,
+}
+
+impl Symmetry {
+    /// Row-major index of the cell that mirrors `index`; `None` when
+    /// `index` is off the board.
+    pub fn image(&self, width: u32, height: u32, index: usize) -> Option<usize> {
+        let index = u32::try_from(index).ok()?;
+        let (x, y) = (index.checked_rem(width)?, index.checked_div(width)?);
+        let mirror = |v: u32, size: u32| size.checked_sub(v)?.checked_sub(1);
+        let (ix, iy) = match self {
+            Self::Point => (mirror(x, width)?, mirror(y, height)?),
+            Self::LeftRight => (mirror(x, width)?, y),
+            Self::TopBottom => (x, mirror(y, height)?),
+        };
+        cell_index(width, height, ix, iy)
+    }
+
+    /// Cells that are their own image (the centre cell, line, or nothing).
+    pub fn fixed_cells(&self, width: u32, height: u32) -> u32 {
+        match self {
+            Self::Point => u32::from(width % 2 == 1 && height % 2 == 1),
+            Self::LeftRight => (width % 2) * height,
+            Self::TopBottom => (height % 2) * width,
+        }
+    }
+}
+
+/// Where mines are likely to be. Weights are relative: a cell of weight 2 is
+/// twice as likely to hold a mine as a cell of weight 1, and weight 0 cells
+/// only get mines when nothing else is left.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum Density {
+    #[default]
+    Uniform,
+    /// Linear ramp from the board centre to its border.
+    Gradient { center: f64, edge: f64 },
+    /// A low-resolution weight grid (row-major) stretched over the board.
+    Map {
+        width: u32,
+        height: u32,
+        weights: Vec<f64>,
+    },
+}
+
+impl Density {
+    /// Per-cell weights normalised to a mean of 1, or `None` when uniform
+    /// (an invalid density is treated as uniform).
+    pub fn weights(&self, width: u32, height: u32) -> Option<Vec<f64>> {
+        if !self.is_valid() {
+            return None;
+        }
+        let raw: Vec<f64> = match self {
+            Self::Uniform => return None,
+            Self::Gradient { center, edge } => {
+                let (cx, cy) = ((width as f64 - 1.0) / 2.0, (height as f64 - 1.0) / 2.0);
+                (0..height)
+                    .flat_map(|y| (0..width).map(move |x| (x, y)))
+                    .map(|(x, y)| {
+                        // Normalised Chebyshev distance: 0 at the centre, 1 on the border.
+                        let dx = if cx > 0.0 {
+                            (x as f64 - cx).abs() / cx
+                        } else {
+                            0.0
+                        };
+                        let dy = if cy > 0.0 {
+                            (y as f64 - cy).abs() / cy
+                        } else {
+                            0.0
+                        };
+                        center + (edge - center) * dx.max(dy)
+                    })
+                    .collect()
+            }
+            Self::Map {
+                width: map_width,
+                height: map_height,
+                weights,
+            } => (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .map(|(x, y)| {
+                    let mx = (x as u64 * *map_width as u64 / width as u64) as usize;
+                    let my = (y as u64 * *map_height as u64 / height as u64) as usize;
+                    weights
+                        .get(my * *map_width as usize + mx)
+                        .copied()
+                        .unwrap_or_default()
+                })
+                .collect(),
+        };
+        let mean = raw.iter().sum::<f64>() / raw.len().max(1) as f64;
+        if mean <= 0.0 {
+            return None;
+        }
+        Some(raw.into_iter().map(|w| w / mean).collect())
+    }
+
+    fn is_valid(&self) -> bool {
+        let ok = |w: f64| w.is_finite() && w >= 0.0;
+        match self {
+            Self::Uniform => true,
+            Self::Gradient { center, edge } => {
+                ok(*center) && ok(*edge) && (*center > 0.0 || *edge > 0.0)
+            }
+            Self::Map {
+                width,
+                height,
+                weights,
+            } => {
+                *width > 0
+                    && *height > 0
+                    && weights.len() == (*width as usize) * (*height as usize)
+                    && weights.iter().all(|&w| ok(w))
+                    && weights.iter().any(|&w| w > 0.0)
+            }
+        }
+    }
+}
+
+/// Which analysis the player may call on. Every level includes the ones
+/// below it. The level is part of the config, so replays record it and a
+/// verified score shows whether the run was assisted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum AssistLevel {
+    /// No analysis: the only run eligible for leaderboards.
+    None,
+    /// Probability hints, e.g. the quantum inspector overlay.
+    Hints,
+    /// Also the risk meter ([`QuantumGrid::evaluate_move`]), what-if
+    /// branches, and unscrambled probabilities. The default, so configs
+    /// that predate the setting are not mistaken for unassisted runs.
+    #[default]
+    FullAnalysis,
+}
+
+impl AssistLevel {
+    /// Whether an API needing `required` may be called at this level.
+    pub fn allows(self, required: AssistLevel) -> bool {
+        self >= required
+    }
+
+    /// Whether runs at this level can be ranked.
+    pub fn is_ranked(self) -> bool {
+        self == Self::None
+    }
+}
+
+/// End-game assist that opens the rest of the board once every mine is
+/// accounted for.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum AutoReveal {
+    #[default]
+    Off,
+    /// Contained cells plus cells the player has flagged (`Mark::Flag`) are
+    /// taken at face value: when they add up to `mine_count`, every other
+    /// cell is opened — and a wrong flag means a detonation.
+    Trusting,
+    /// Only fires once every mine is actually contained, so it can never
+    /// open a mine.
+    GroundTruth,
+}
+
+/// How far an observation ripples along chains of Probabilistic links.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LinkPropagation {
+    /// Maximum hops from the observed cell. `1` adjusts direct partners only.
+    pub max_depth: u32,
+    /// Strength multiplier applied per additional hop (geometric decay).
+    pub decay: f64,
+}
+
+impl Default for LinkPropagation {
+    fn default() -> Self {
+        Self {
+            max_depth: 1,
+            decay: 0.5,
+        }
+    }
+}
+
+/// Precision/disturbance tradeoff for
+/// [`QuantumGrid::measure_weak_with_precision`]: at precision `p` the
+/// reading is off by up to `noise × (1 − p)`, and the cell drifts by up to
+/// `min_drift + (max_drift − min_drift) × p`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MeasurementCurve {
+    pub noise: f64,
+    pub min_drift: f64,
+    pub max_drift: f64,
+}
+
+impl MeasurementCurve {
+    /// Harder difficulties blur cheap readings more and punish sharp ones
+    /// harder.
+    pub fn for_difficulty(label: &str) -> Self {
+        let (noise, min_drift, max_drift) = match label {
+            "observer" => (0.10, 0.01, 0.04),
+            "theorist" => (0.20, 0.03, 0.08),
+            _ => (0.15, 0.02, 0.06),
+        };
+        Self {
+            noise,
+            min_drift,
+            max_drift,
+        }
+    }
+
+    /// `(reading noise, drift)` half-widths at `precision` in `[0, 1]`.
+    pub fn at(&self, precision: f64) -> (f64, f64) {
+        let precision = precision.clamp(0.0, 1.0);
+        (
+            self.noise * (1.0 - precision),
+            self.min_drift + (self.max_drift - self.min_drift) * precision,
+        )
+    }
+
+    fn is_valid(&self) -> bool {
+        let unit = |v: f64| (0.0..=1.0).contains(&v);
+        unit(self.noise) && unit(self.min_drift) && unit(self.max_drift)
+    }
+}
+
+/// An oracle placed by hand, e.g. by a puzzle.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OracleCell {
+    pub x: u32,
+    pub y: u32,
+    /// Overrides [`Oracles::circuit`] for this cell.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub circuit: Option<Circuit>,
+}
+
+/// An unstable isotope placed by a scenario: a mine that takes `stages`
+/// containment charges, one per move, before it is Contained (see
+/// [`crate::isotope`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IsotopeCell {
+    pub x: u32,
+    pub y: u32,
+    pub stages: u32,
+}
+
+/// Phase-kickback oracle cells: revealing one runs a circuit over every
+/// unresolved cell in its row and column. Oracles never hold a mine.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Oracles {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub placed: Vec<OracleCell>,
+    /// Chance that any other safe cell becomes an oracle when mines are
+    /// placed. Meant to stay rare; `0` generates none.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rate: f64,
+    /// Kickback for generated oracles and placed ones without their own.
+    #[cfg_attr(feature = "serde", serde(default = "default_oracle_circuit"))]
+    pub circuit: Circuit,
+}
+
+fn default_oracle_circuit() -> Circuit {
+    Circuit::default().with_gate(Gate::Not)
+}
+
+impl Default for Oracles {
+    fn default() -> Self {
+        Self {
+            placed: Vec::new(),
+            rate: 0.0,
+            circuit: default_oracle_circuit(),
+        }
+    }
+}
+
+impl Oracles {
+    fn is_valid(&self, width: u32, height: u32) -> bool {
+        (0.0..=1.0).contains(&self.rate)
+            && self
+                .placed
+                .iter()
+                .all(|cell| cell.x < width && cell.y < height)
+    }
+}
+
+/// Mechanics driven by game time rather than by moves, applied by
+/// [`QuantumGrid::advance`](crate::grid::QuantumGrid::advance). The default
+/// turns them all off.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TimedRules {
+    /// Decoherence points added to the meter per second of game time.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ambient_decoherence: u32,
+    /// Every entanglement link loses half its strength each time this much
+    /// game time passes. `None` keeps links at full strength.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub link_half_life_ms: Option<u32>,
+    /// Integrity (out of [`FULL_INTEGRITY`](crate::integrity::FULL_INTEGRITY))
+    /// each Contained cell loses per second of game time; a containment
+    /// worn to zero breaches. `0` keeps containments permanent.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub containment_decay: u32,
+}
+
+/// Combo scoring (see [`crate::combo`]): safe reveals in a row raise the
+/// score multiplier, and a wasted containment resets it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ComboRules {
+    /// Safe reveals in a row per step up of the multiplier.
+    pub reveals_per_step: u32,
+    pub max_multiplier: u32,
+    /// Every this many safe reveals in a row grant a free tool charge (a
+    /// swap charge). `0` never does.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub charge_every: u32,
+}
+
+impl Default for ComboRules {
+    fn default() -> Self {
+        Self {
+            reveals_per_step: 3,
+            max_multiplier: 5,
+            charge_every: 10,
+        }
+    }
+}
+
+/// Action points each kind of [`Action`](crate::action::Action) costs in
+/// turn-based play. Letting time pass is free.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ActionCost {
+    pub reveal: u32,
+    pub contain: u32,
+    pub hadamard: u32,
+    /// Either kind of weak measurement.
+    pub measure: u32,
+    pub swap: u32,
+    pub teleport: u32,
+}
+
+impl Default for ActionCost {
+    fn default() -> Self {
+        Self {
+            reveal: 1,
+            contain: 2,
+            hadamard: 3,
+            measure: 1,
+            swap: 3,
+            teleport: 3,
+        }
+    }
+}
+
+impl ActionCost {
+    pub fn of(&self, action: &Action) -> u32 {
+        match action {
+            Action::Reveal { .. } => self.reveal,
+            Action::Contain { .. } => self.contain,
+            Action::Hadamard { .. } => self.hadamard,
+            Action::MeasureWeak { .. } | Action::MeasureWeakWithPrecision { .. } => self.measure,
+            Action::SwapEntanglement { .. } => self.swap,
+            Action::Teleport { .. } => self.teleport,
+            Action::Advance { .. } | Action::EndTurn => 0,
+        }
+    }
+}
+
+/// Turn-based play (see [`crate::turns`]): every turn grants a budget of
+/// action points, and each new turn lets the board decohere a little.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TurnRules {
+    pub action_points: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub costs: ActionCost,
+    /// Decoherence points added to the meter at each new turn.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub decoherence_per_turn: u32,
+}
+
+impl Default for TurnRules {
+    fn default() -> Self {
+        Self {
+            action_points: 3,
+            costs: ActionCost::default(),
+            decoherence_per_turn: 100,
+        }
+    }
+}
+
+/// The noise daemon's allowance (see [`crate::daemon`]): points it may
+/// spend at the start of every turn, and what each kind of meddling costs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DaemonRules {
+    pub budget: u32,
+    /// Nudge one cell's hint.
+    pub scramble_cost: u32,
+    /// Strengthen one entanglement link.
+    pub link_cost: u32,
+    /// Move a mine no revealed number can see.
+    pub shift_cost: u32,
+}
+
+impl Default for DaemonRules {
+    fn default() -> Self {
+        Self {
+            budget: 3,
+            scramble_cost: 1,
+            link_cost: 2,
+            shift_cost: 3,
+        }
+    }
+}
+
+impl DaemonRules {
+    /// Every move costs something, so a turn's budget runs out.
+    pub fn is_valid(&self) -> bool {
+        self.scramble_cost > 0 && self.link_cost > 0 && self.shift_cost > 0
+    }
+}
+
+/// Caps on what a [`GridConfig`] may ask for, so a hostile game code cannot
+/// run a browser tab or server out of memory. [`GridConfig::validate`]
+/// applies the defaults; embedders with more or less room to spare check
+/// against their own with [`GridConfig::validate_within`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Limits {
+    pub max_cells: u32,
+    /// Entanglement pairs dealt at the start.
+    pub max_pairs: u32,
+    /// Bytes, as [`GridConfig::estimated_memory`] counts them.
+    pub max_memory: u64,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_cells: 4_000_000,
+            max_pairs: 1_000_000,
+            max_memory: 512 << 20,
+        }
+    }
+}
+
+/// Why a [`GridConfig`] cannot be built as requested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum ConfigError {
+    /// An empty board, or one with more cells than a `u32` can count.
+    InvalidDimensions { width: u32, height: u32 },
+    /// More cells than [`Limits::max_cells`].
+    TooManyCells { cells: u64, max: u32 },
+    /// More entanglement pairs than [`Limits::max_pairs`].
+    TooManyPairs { pairs: u64, max: u32 },
+    /// An [`GridConfig::estimated_memory`] over [`Limits::max_memory`].
+    OverMemoryBudget { bytes: u64, max: u64 },
+    /// More mines were requested than the board can hold once the
+    /// first-click safe zone is reserved.
+    TooManyMines { requested: u32, max: u32 },
+    /// An odd mine count on a symmetric board with no self-mirrored cells
+    /// to hold the odd mine.
+    AsymmetricMineCount { requested: u32 },
+    /// A density map with the wrong size, or negative, non-finite, or
+    /// all-zero weights.
+    InvalidDensity,
+    /// A measurement curve with values outside `[0, 1]`.
+    InvalidMeasurementCurve,
+    /// An oracle placed off the board, or a rate outside `[0, 1]`.
+    InvalidOracles,
+    /// An isotope off the board, on an oracle or another isotope, with no
+    /// stages, or more isotopes than mines.
+    InvalidIsotopes,
+    /// A daemon move that costs nothing, which it could repeat forever.
+    InvalidDaemon,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidDimensions { width, height } => {
+                write!(f, "a {width}×{height} board cannot be built")
+            }
+            Self::TooManyCells { cells, max } => {
+                write!(f, "board too large: {cells} cells, at most {max}")
+            }
+            Self::TooManyPairs { pairs, max } => {
+                write!(f, "too many entanglement pairs: {pairs}, at most {max}")
+            }
+            Self::OverMemoryBudget { bytes, max } => {
+                write!(f, "board needs about {bytes} bytes, budget is {max}")
+            }
+            Self::TooManyMines { requested, max } => {
+                write!(
+                    f,
+                    "too many mines: requested {requested}, board holds at most {max}"
+                )
+            }
+            Self::AsymmetricMineCount { requested } => {
+                write!(
+                    f,
+                    "{requested} mines cannot be laid out symmetrically on this board"
+                )
+            }
+            Self::InvalidDensity => write!(f, "invalid mine density map"),
+            Self::InvalidMeasurementCurve => write!(f, "invalid measurement curve"),
+            Self::InvalidOracles => write!(f, "invalid oracle cells"),
+            Self::InvalidIsotopes => write!(f, "invalid isotope cells"),
+            Self::InvalidDaemon => write!(f, "daemon moves must cost at least one point"),
+        }
+    }
+}
+
+impl core::error::Error for ConfigError {}
+
+/// Everything needed to build a [`QuantumGrid`](crate::grid::QuantumGrid).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GridConfig {
+    pub width: u32,
+    pub height: u32,
+    pub mine_count: u32,
+    pub seed: u64,
+    pub difficulty: String,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub first_click: FirstClick,
+    /// Chebyshev radius of the mine-free zone for [`FirstClick::GuaranteedZero`].
+    /// `1` is the classic 3×3 zone.
+    #[cfg_attr(feature = "serde", serde(default = "default_safe_zone_radius"))]
+    pub safe_zone_radius: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub charge_policy: ChargePolicy,
+    /// End-game assist: once every remaining Superposition cell must be a
+    /// mine, contain them all (free of charge) and finish the game.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub auto_contain: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub auto_reveal: AutoReveal,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub link_propagation: LinkPropagation,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub generator: Generator,
+    /// Non-uniform mine density, honoured by every generator and by the
+    /// probability hints.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub density: Density,
+    /// Decoherence points (see [`DecoherenceMeter`]) after which weak
+    /// measurement collapses a random unresolved cell. `None` keeps the
+    /// meter purely informational.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub decoherence_limit: Option<u32>,
+    /// Overrides [`MeasurementCurve::for_difficulty`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub measurement: Option<MeasurementCurve>,
+    /// Charges for the rare entanglement-swapping tool.
+    #[cfg_attr(feature = "serde", serde(default = "default_swap_charges"))]
+    pub swap_charges: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub oracles: Oracles,
+    /// Shields the player starts with (see
+    /// [`ToolInventory`](crate::inventory::ToolInventory)).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub shields: u32,
+    /// Mercy rule: once per game, a mine clicked in a
+    /// [forced guess](crate::grid::QuantumGrid::is_forced_guess) is
+    /// contained instead of detonating. Telling a forced guess apart takes
+    /// the `solver` feature; without it the rule never fires.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub mercy: bool,
+    /// Multi-stage mines. Each counts toward `mine_count` and is always a
+    /// mine, unless the first click's safe zone covers it, in which case it
+    /// is dropped and the mine goes elsewhere.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub isotopes: Vec<IsotopeCell>,
+    /// Stabilized, turbulent or otherwise modified stretches of board.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub zones: Vec<Zone>,
+    /// Scenario goals replacing the purification win; empty plays the
+    /// classic game.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub objectives: Vec<Objective>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub assist: AssistLevel,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub timed: TimedRules,
+    /// Combo scoring. `None` keeps the score multiplier at 1.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub combo: Option<ComboRules>,
+    /// Turn-based action economy. `None` plays in real time.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub turns: Option<TurnRules>,
+    /// Challenge mode: a noise daemon meddles with the board at the start
+    /// of every turn. Needs `turns`.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub daemon: Option<DaemonRules>,
+    /// Outcome modifiers for game variants, consulted in order.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rules: Vec<Rule>,
+    /// Speedrun splits timed by the `*_at` moves, in order.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub splits: Vec<SplitTrigger>,
+}
+
+fn default_safe_zone_radius() -> u32 {
+    1
+}
+
+fn default_swap_charges() -> u32 {
+    1
+}
+
+impl GridConfig {
+    pub fn new(width: u32, height: u32, mine_count: u32, seed: u64, difficulty: &str) -> Self {
+        Self {
+            width,
+            height,
+            mine_count,
+            seed,
+            difficulty: difficulty.to_string(),
+            first_click: FirstClick::default(),
+            safe_zone_radius: default_safe_zone_radius(),
+            charge_policy: ChargePolicy::default(),
+            auto_contain: false,
+            auto_reveal: AutoReveal::default(),
+            link_propagation: LinkPropagation::default(),
+            generator: Generator::default(),
+            density: Density::default(),
+            decoherence_limit: None,
+            measurement: None,
+            swap_charges: default_swap_charges(),
+            oracles: Oracles::default(),
+            shields: 0,
+            mercy: false,
+            isotopes: Vec::new(),
+            zones: Vec::new(),
+            objectives: Vec::new(),
+            assist: AssistLevel::default(),
+            timed: TimedRules::default(),
+            combo: None,
+            turns: None,
+            daemon: None,
+            rules: Vec::new(),
+            splits: Vec::new(),
+        }
+    }
+
+    pub fn with_first_click(mut self, first_click: FirstClick) -> Self {
+        self.first_click = first_click;
+        self
+    }
+
+    pub fn with_safe_zone_radius(mut self, radius: u32) -> Self {
+        self.safe_zone_radius = radius;
+        self
+    }
+
+    pub fn with_charge_policy(mut self, charge_policy: ChargePolicy) -> Self {
+        self.charge_policy = charge_policy;
+        self
+    }
+
+    pub fn with_auto_contain(mut self, enabled: bool) -> Self {
+        self.auto_contain = enabled;
+        self
+    }
+
+    pub fn with_auto_reveal(mut self, auto_reveal: AutoReveal) -> Self {
+        self.auto_reveal = auto_reveal;
+        self
+    }
+
+    pub fn with_link_propagation(mut self, link_propagation: LinkPropagation) -> Self {
+        self.link_propagation = link_propagation;
+        self
+    }
+
+    pub fn with_generator(mut self, generator: Generator) -> Self {
+        self.generator = generator;
+        self
+    }
+
+    pub fn with_density(mut self, density: Density) -> Self {
+        self.density = density;
+        self
+    }
+
+    pub fn with_decoherence_limit(mut self, limit: u32) -> Self {
+        self.decoherence_limit = Some(limit);
+        self
+    }
+
+    pub fn with_measurement_curve(mut self, curve: MeasurementCurve) -> Self {
+        self.measurement = Some(curve);
+        self
+    }
+
+    pub fn with_swap_charges(mut self, charges: u32) -> Self {
+        self.swap_charges = charges;
+        self
+    }
+
+    pub fn with_oracles(mut self, oracles: Oracles) -> Self {
+        self.oracles = oracles;
+        self
+    }
+
+    pub fn with_assist(mut self, assist: AssistLevel) -> Self {
+        self.assist = assist;
+        self
+    }
+
+    pub fn with_timed(mut self, timed: TimedRules) -> Self {
+        self.timed = timed;
+        self
+    }
+
+    pub fn with_combo(mut self, combo: ComboRules) -> Self {
+        self.combo = Some(combo);
+        self
+    }
+
+    pub fn with_turns(mut self, turns: TurnRules) -> Self {
+        self.turns = Some(turns);
+        self
+    }
+
+    pub fn with_daemon(mut self, daemon: DaemonRules) -> Self {
+        self.daemon = Some(daemon);
+        self
+    }
+
+    pub fn with_shields(mut self, shields: u32) -> Self {
+        self.shields = shields;
+        self
+    }
+
+    pub fn with_mercy(mut self, mercy: bool) -> Self {
+        self.mercy = mercy;
+        self
+    }
+
+    pub fn with_isotope(mut self, isotope: IsotopeCell) -> Self {
+        self.isotopes.push(isotope);
+        self
+    }
+
+    pub fn with_zone(mut self, zone: Zone) -> Self {
+        self.zones.push(zone);
+        self
+    }
+
+    pub fn with_objective(mut self, objective: Objective) -> Self {
+        self.objectives.push(objective);
+        self
+    }
+
+    pub fn with_split(mut self, trigger: SplitTrigger) -> Self {
+        self.splits.push(trigger);
+        self
+    }
+
+    /// Add `rule` after the rules already in play.
+    pub fn with_rule(mut self, rule: Rule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// The configured curve, or the difficulty's default.
+    pub fn measurement_curve(&self) -> MeasurementCurve {
+        self.measurement
+            .unwrap_or_else(|| MeasurementCurve::for_difficulty(&self.difficulty))
+    }
+
+    /// Entanglement pairs dealt at the start: one per `step` cells of the
+    /// difficulty's profile, at most.
+    pub fn pair_count(&self) -> u64 {
+        let (step, _, _) = entanglement_profile(&self.difficulty);
+        (u64::from(self.width) * u64::from(self.height)).div_ceil(step as u64)
+    }
+
+    /// Rough heap footprint in bytes of a grid built from this config, for
+    /// budgeting before building it: the per-cell state and the starting
+    /// entanglement. Branches, undo history and replays come on top.
+    pub fn estimated_memory(&self) -> u64 {
+        use core::mem::size_of;
+        // Cell, mine map, decoherence, the hint prior while building, and
+        // the zone map when there are zones.
+        let per_cell = size_of::<QuantumCell>()
+            + size_of::<bool>()
+            + size_of::<u32>()
+            + size_of::<f64>()
+            + usize::from(!self.zones.is_empty());
+        let cells = u64::from(self.width) * u64::from(self.height);
+        (size_of::<QuantumGrid>() as u64)
+            .saturating_add(cells.saturating_mul(per_cell as u64))
+            .saturating_add(
+                self.pair_count()
+                    .saturating_mul(size_of::<EntanglementPair>() as u64),
+            )
+    }
+
+    /// Check the board's size against `limits`, leaving the rest of the
+    /// config alone; see [`Self::validate_within`].
+    pub fn check_limits(&self, limits: &Limits) -> Result<(), ConfigError> {
+        let cells = u64::from(self.width) * u64::from(self.height);
+        if cells > u64::from(limits.max_cells) {
+            return Err(ConfigError::TooManyCells {
+                cells,
+                max: limits.max_cells,
+            });
+        }
+        let pairs = self.pair_count();
+        if pairs > u64::from(limits.max_pairs) {
+            return Err(ConfigError::TooManyPairs {
+                pairs,
+                max: limits.max_pairs,
+            });
+        }
+        let bytes = self.estimated_memory();
+        if bytes > limits.max_memory {
+            return Err(ConfigError::OverMemoryBudget {
+                bytes,
+                max: limits.max_memory,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn total_cells(&self) -> u32 {
+        self.width.saturating_mul(self.height)
+    }
+
+    /// Upper bound on the number of cells the first-click policy may exclude
+    /// from mine placement (the zone is clipped at the board edges, and
+    /// doubled by its mirror image on symmetric boards).
+    pub fn safe_zone_cells(&self) -> u32 {
+        let zone = self.first_click_zone_cells();
+        match self.generator {
+            Generator::Symmetric { .. } => zone.saturating_mul(2).min(self.total_cells()),
+            _ => zone,
+        }
+    }
+
+    fn first_click_zone_cells(&self) -> u32 {
+        match self.first_click {
+            FirstClick::GuaranteedZero => {
+                let side = self.safe_zone_radius.saturating_mul(2).saturating_add(1);
+                side.min(self.width).saturating_mul(side.min(self.height))
+            }
+            FirstClick::GuaranteedSafe => 1.min(self.total_cells()),
+            FirstClick::NoGuarantee => 0,
+        }
+    }
+
+    /// Largest mine count that can always be placed under this policy.
+    pub fn max_mines(&self) -> u32 {
+        self.total_cells()
+            .saturating_sub(self.safe_zone_cells())
+            .saturating_sub(self.oracle_cells())
+    }
+
+    /// Cells kept mine-free by placed oracles; like the safe zone, a
+    /// symmetric layout blocks each one's mirror image as well.
+    fn oracle_cells(&self) -> u32 {
+        let placed = self.oracles.placed.len() as u32;
+        match self.generator {
+            Generator::Symmetric { .. } => placed.saturating_mul(2),
+            _ => placed,
+        }
+    }
+
+    /// Whether `cell` may join the isotopes `before` it.
+    fn isotope_fits(&self, cell: &IsotopeCell, before: &[IsotopeCell]) -> bool {
+        let at = (cell.x, cell.y);
+        cell.stages > 0
+            && cell.x < self.width
+            && cell.y < self.height
+            && !before.iter().any(|other| (other.x, other.y) == at)
+            && !self
+                .oracles
+                .placed
+                .iter()
+                .any(|oracle| (oracle.x, oracle.y) == at)
+    }
+
+    fn isotopes_are_valid(&self) -> bool {
+        self.isotopes.len() as u32 <= self.mine_count
+            && self.isotopes.iter().enumerate().all(|(i, cell)| {
+                self.isotope_fits(cell, self.isotopes.get(..i).unwrap_or_default())
+            })
+    }
+
+    /// Check the configuration without modifying it, within the default
+    /// [`Limits`].
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        self.validate_within(&Limits::default())
+    }
+
+    /// [`Self::validate`] against custom [`Limits`].
+    pub fn validate_within(&self, limits: &Limits) -> Result<(), ConfigError> {
+        if self.width == 0 || self.height == 0 || self.width.checked_mul(self.height).is_none() {
+            return Err(ConfigError::InvalidDimensions {
+                width: self.width,
+                height: self.height,
+            });
+        }
+        self.check_limits(limits)?;
+        let max = self.max_mines();
+        if self.mine_count > max {
+            return Err(ConfigError::TooManyMines {
+                requested: self.mine_count,
+                max,
+            });
+        }
+        if !self.density.is_valid() {
+            return Err(ConfigError::InvalidDensity);
+        }
+        if self.measurement.is_some_and(|curve| !curve.is_valid()) {
+            return Err(ConfigError::InvalidMeasurementCurve);
+        }
+        if !self.oracles.is_valid(self.width, self.height) {
+            return Err(ConfigError::InvalidOracles);
+        }
+        if !self.isotopes_are_valid() {
+            return Err(ConfigError::InvalidIsotopes);
+        }
+        if self.daemon.is_some_and(|rules| !rules.is_valid()) {
+            return Err(ConfigError::InvalidDaemon);
+        }
+        if let Generator::Symmetric { symmetry } = self.generator {
+            if self.mine_count % 2 == 1 && symmetry.fixed_cells(self.width, self.height) == 0 {
+                return Err(ConfigError::AsymmetricMineCount {
+                    requested: self.mine_count,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Clamp any out-of-range values, reporting what had to change.
+    /// The returned error (if any) describes the original request.
+    pub fn clamped(mut self) -> (Self, Option<ConfigError>) {
+        let report = self.validate().err();
+        self.fit_dimensions();
+        let (width, height) = (self.width, self.height);
+        self.oracles
+            .placed
+            .retain(|cell| cell.x < width && cell.y < height);
+        if !(0.0..=1.0).contains(&self.oracles.rate) {
+            self.oracles.rate = 0.0;
+        }
+        self.mine_count = self.mine_count.min(self.max_mines());
+        let mut kept = Vec::new();
+        for cell in core::mem::take(&mut self.isotopes) {
+            if kept.len() < self.mine_count as usize && self.isotope_fits(&cell, &kept) {
+                kept.push(cell);
+            }
+        }
+        self.isotopes = kept;
+        if !self.density.is_valid() {
+            self.density = Density::Uniform;
+        }
+        if self.measurement.is_some_and(|curve| !curve.is_valid()) {
+            self.measurement = None;
+        }
+        if let Some(rules) = &mut self.daemon {
+            for cost in [
+                &mut rules.scramble_cost,
+                &mut rules.link_cost,
+                &mut rules.shift_cost,
+            ] {
+                *cost = (*cost).max(1);
+            }
+        }
+        if let Generator::Symmetric { symmetry } = self.generator {
+            if symmetry.fixed_cells(self.width, self.height) == 0 {
+                self.mine_count -= self.mine_count % 2;
+            }
+        }
+        (self, report)
+    }
+
+    /// At least one cell each way, and few enough rows that every index
+    /// fits a `u32`.
+    pub(crate) fn fit_dimensions(&mut self) {
+        self.width = self.width.max(1);
+        self.height = self.height.clamp(1, u32::MAX / self.width);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_policy_reserves_three_by_three() {
+        let config = GridConfig::new(8, 8, 10, 0, "observer");
+        assert_eq!(config.first_click, FirstClick::GuaranteedZero);
+        assert_eq!(config.safe_zone_cells(), 9);
+        assert_eq!(config.max_mines(), 55);
+    }
+
+    #[test]
+    fn safe_zone_scales_with_policy_and_radius() {
+        let base = GridConfig::new(8, 8, 10, 0, "observer");
+        assert_eq!(base.clone().with_safe_zone_radius(2).safe_zone_cells(), 25);
+        assert_eq!(base.clone().with_safe_zone_radius(0).safe_zone_cells(), 1);
+        let safe = base.clone().with_first_click(FirstClick::GuaranteedSafe);
+        assert_eq!(safe.max_mines(), 63);
+        let none = base.with_first_click(FirstClick::NoGuarantee);
+        assert_eq!(none.max_mines(), 64);
+    }
+
+    #[test]
+    fn too_many_mines_is_reported() {
+        let config = GridConfig::new(8, 8, 60, 0, "observer");
+        let expected = ConfigError::TooManyMines {
+            requested: 60,
+            max: 55,
+        };
+        assert_eq!(config.validate(), Err(expected));
+        let (clamped, report) = config.clamped();
+        assert_eq!(clamped.mine_count, 55);
+        assert_eq!(report, Some(expected));
+
+        let ok = GridConfig::new(8, 8, 55, 0, "observer");
+        assert_eq!(ok.validate(), Ok(()));
+        assert_eq!(ok.clamped().1, None);
+    }
+
+    #[test]
+    fn unbuildable_dimensions_are_reported_and_clamped() {
+        for (width, height) in [(0, 8), (8, 0), (70_000, 70_000)] {
+            let config = GridConfig::new(width, height, 0, 0, "observer");
+            let (clamped, report) = config.clamped();
+            assert_eq!(
+                report,
+                Some(ConfigError::InvalidDimensions { width, height })
+            );
+            assert!(clamped.width > 0 && clamped.height > 0);
+            assert!(clamped.width.checked_mul(clamped.height).is_some());
+        }
+    }
+
+    #[test]
+    fn limits_cap_cells_pairs_and_memory() {
+        let config = GridConfig::new(16, 16, 40, 0, "theorist");
+        assert_eq!(config.pair_count(), 52);
+        let bytes = config.estimated_memory();
+        assert!(bytes > 256 * std::mem::size_of::<QuantumCell>() as u64);
+        assert_eq!(config.validate(), Ok(()));
+
+        let within = |limits| config.validate_within(&limits);
+        let defaults = Limits::default();
+        assert_eq!(
+            within(Limits {
+                max_cells: 100,
+                ..defaults
+            }),
+            Err(ConfigError::TooManyCells {
+                cells: 256,
+                max: 100
+            })
+        );
+        assert_eq!(
+            within(Limits {
+                max_pairs: 10,
+                ..defaults
+            }),
+            Err(ConfigError::TooManyPairs { pairs: 52, max: 10 })
+        );
+        assert_eq!(
+            within(Limits {
+                max_memory: bytes - 1,
+                ..defaults
+            }),
+            Err(ConfigError::OverMemoryBudget {
+                bytes,
+                max: bytes - 1
+            })
+        );
+
+        // A hostile code is refused before anything is allocated.
+        let hostile = GridConfig::new(60_000, 60_000, 10, 0, "observer");
+        assert!(matches!(
+            hostile.validate(),
+            Err(ConfigError::TooManyCells { .. })
+        ));
+        assert!(matches!(
+            crate::replay::Replay::new(hostile).play(),
+            Err(crate::replay::ReplayError::TooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn symmetric_boards_need_a_place_for_an_odd_mine() {
+        let point = Generator::Symmetric {
+            symmetry: Symmetry::Point,
+        };
+        let even = GridConfig::new(8, 8, 11, 0, "observer").with_generator(point);
+        assert_eq!(
+            even.validate(),
+            Err(ConfigError::AsymmetricMineCount { requested: 11 })
+        );
+        assert_eq!(even.clamped().0.mine_count, 10);
+        // A 9×9 board has a centre cell; mirrored safe zones count twice.
+        let odd = GridConfig::new(9, 9, 11, 0, "observer").with_generator(point);
+        assert_eq!(odd.validate(), Ok(()));
+        assert_eq!(odd.max_mines(), 81 - 18);
+    }
+
+    #[test]
+    fn symmetry_images_and_fixed_cells() {
+        assert_eq!(Symmetry::Point.image(4, 3, 0), Some(11));
+        assert_eq!(Symmetry::LeftRight.image(4, 3, 1), Some(2));
+        assert_eq!(Symmetry::TopBottom.image(4, 3, 1), Some(9));
+        assert_eq!(Symmetry::TopBottom.image(4, 3, 12), None);
+        assert_eq!(Symmetry::Point.fixed_cells(5, 5), 1);
+        assert_eq!(Symmetry::LeftRight.fixed_cells(5, 4), 4);
+        assert_eq!(Symmetry::TopBottom.fixed_cells(5, 4), 0);
+    }
+
+    #[test]
+    fn density_weights_are_normalised() {
+        let gradient = Density::Gradient {
+            center: 0.0,
+            edge: 1.0,
+        };
+        let weights = gradient.weights(5, 5).unwrap();
+        assert_eq!(weights[12], 0.0);
+        assert!(weights[0] > weights[6]);
+        let mean = weights.iter().sum::<f64>() / 25.0;
+        assert!((mean - 1.0).abs() < 1e-9);
+
+        let map = Density::Map {
+            width: 2,
+            height: 1,
+            weights: vec![3.0, 1.0],
+        };
+        let weights = map.weights(4, 2).unwrap();
+        assert_eq!(weights, vec![1.5, 1.5, 0.5, 0.5, 1.5, 1.5, 0.5, 0.5]);
+        assert_eq!(Density::Uniform.weights(4, 2), None);
+    }
+
+    #[test]
+    fn invalid_density_is_rejected() {
+        let base = GridConfig::new(8, 8, 10, 0, "observer");
+        for density in [
+            Density::Map {
+                width: 2,
+                height: 2,
+                weights: vec![1.0; 3],
+            },
+            Density::Map {
+                width: 1,
+                height: 1,
+                weights: vec![0.0],
+            },
+            Density::Gradient {
+                center: -1.0,
+                edge: 1.0,
+            },
+        ] {
+            let config = base.clone().with_density(density);
+            assert_eq!(config.validate(), Err(ConfigError::InvalidDensity));
+        }
+    }
+
+    #[test]
+    fn assist_levels_nest_and_only_none_is_ranked() {
+        assert!(AssistLevel::FullAnalysis.allows(AssistLevel::Hints));
+        assert!(!AssistLevel::Hints.allows(AssistLevel::FullAnalysis));
+        assert!(AssistLevel::None.is_ranked());
+        assert!(!AssistLevel::Hints.is_ranked());
+        // Configs saved before the setting existed count as assisted.
+        let legacy: GridConfig = serde_json::from_str(
+            r#"{"width":8,"height":8,"mine_count":10,"seed":1,"difficulty":"observer"}"#,
+        )
+        .unwrap();
+        assert_eq!(legacy.assist, AssistLevel::FullAnalysis);
+    }
+
+    #[test]
+    fn oracles_reserve_cells_and_stay_on_the_board() {
+        let oracle = |x, y| OracleCell {
+            x,
+            y,
+            circuit: None,
+        };
+        let base = GridConfig::new(8, 8, 10, 0, "observer");
+        let placed = base.clone().with_oracles(Oracles {
+            placed: vec![oracle(1, 1), oracle(8, 0)],
+            ..Oracles::default()
+        });
+        assert_eq!(placed.max_mines(), 53);
+        assert_eq!(placed.validate(), Err(ConfigError::InvalidOracles));
+        let (clamped, report) = placed.clamped();
+        assert_eq!(report, Some(ConfigError::InvalidOracles));
+        assert_eq!(clamped.oracles.placed, vec![oracle(1, 1)]);
+        // A mirrored layout cannot put a mine opposite an oracle either.
+        let mirrored = clamped.with_generator(Generator::Symmetric {
+            symmetry: Symmetry::Point,
+        });
+        assert_eq!(mirrored.max_mines(), 64 - 18 - 2);
+
+        let rate = base.with_oracles(Oracles {
+            rate: 1.5,
+            ..Oracles::default()
+        });
+        assert_eq!(rate.validate(), Err(ConfigError::InvalidOracles));
+    }
+
+    #[test]
+    fn safe_zone_is_clipped_to_board() {
+        let config = GridConfig::new(2, 2, 1, 0, "observer").with_safe_zone_radius(5);
+        assert_eq!(config.safe_zone_cells(), 4);
+        assert_eq!(config.max_mines(), 0);
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+use crate::rng::SplitMix64;
+
+/// Distinct biomes a board can be painted with.
+pub const BIOMES: u8 = 4;
+/// Texture variants within a biome.
+pub const TEXTURES: u8 = 8;
+/// Side, in cells, of the square patches that share a biome.
+pub const BIOME_PATCH: u32 = 4;
+
+/// Decoration flags, drawn independently, each on about one cell in eight.
+pub const DECOR_MOSS: u8 = 1;
+pub const DECOR_CRACKS: u8 = 2;
+pub const DECOR_CRYSTALS: u8 = 4;
+
+/// Keeps the cosmetic hash clear of the streams gameplay draws from the
+/// same seed.
+const SALT: u64 = 0x636f_736d_6574_6963;
+
+/// How one cell looks. Derived from the seed and the cell's position alone,
+/// so every frontend paints the same board and nothing here touches the
+/// grid RNG, gameplay, or replays.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Cosmetic {
+    pub biome: u8,
+    pub texture: u8,
+    /// `DECOR_*` flags.
+    pub decorations: u8,
+}
+
+/// A stateless hash of `seed` and a pair of coordinates.
+fn hash(seed: u64, a: u32, b: u32) -> u64 {
+    SplitMix64::new(seed ^ SALT ^ (u64::from(a) << 32 | u64::from(b))).next_u64()
+}
+
+/// The look of the cell at `(x, y)` on a board dealt from `seed`.
+pub fn cosmetic(seed: u64, x: u32, y: u32) -> Cosmetic {
+    let patch = hash(seed.rotate_left(17), x / BIOME_PATCH, y / BIOME_PATCH);
+    let cell = hash(seed, x, y);
+    let decorations = [DECOR_MOSS, DECOR_CRACKS, DECOR_CRYSTALS]
+        .into_iter()
+        .enumerate()
+        .filter(|&(i, _)| (cell >> (8 + 3 * i)) & 7 == 0)
+        .fold(0, |flags, (_, flag)| flags | flag);
+    Cosmetic {
+        biome: (patch % u64::from(BIOMES)) as u8,
+        texture: (cell % u64::from(TEXTURES)) as u8,
+        decorations,
+    }
+}
+
+/// Every cell's look, row by row.
+pub fn field(seed: u64, width: u32, height: u32) -> Vec<Cosmetic> {
+    (0..height)
+        .flat_map(|y| (0..width).map(move |x| cosmetic(seed, x, y)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn biomes_come_in_patches_and_follow_the_seed() {
+        let board = field(42, 8, 8);
+        assert_eq!(board, field(42, 8, 8));
+        assert_ne!(board, field(43, 8, 8));
+        assert_eq!(board[9].biome, board[0].biome);
+        assert!(board.iter().any(|cell| cell.decorations != 0));
+        assert!(board.iter().all(|cell| cell.texture < TEXTURES));
+    }
+}
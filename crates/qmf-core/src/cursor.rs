@@ -0,0 +1,199 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::action::Action;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+/// What the cursor does at the edge of the board.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum EdgeRule {
+    /// Stop at the edge.
+    #[default]
+    Clamp,
+    /// Reappear on the opposite edge, same row or column.
+    Wrap,
+}
+
+/// Tools the cursor can fire. Two-cell tools take the selection as their
+/// first cell and the cursor as their second.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Tool {
+    Hadamard,
+    MeasureWeak,
+    SwapEntanglement,
+    Teleport,
+}
+
+impl Tool {
+    pub fn needs_selection(self) -> bool {
+        matches!(self, Self::SwapEntanglement | Self::Teleport)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum CursorCommand {
+    Reveal,
+    Contain,
+    Tool {
+        tool: Tool,
+    },
+    /// Drop the selection, if any.
+    Cancel,
+}
+
+/// Keyboard/gamepad navigation shared by every frontend, so they all move
+/// and act identically. The cursor only produces [`Action`]s; applying and
+/// recording them is up to the caller, so cursor play replays like any
+/// other.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Cursor {
+    pub x: u32,
+    pub y: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub edge: EdgeRule,
+    /// First cell of a two-cell tool awaiting its second.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub selection: Option<(u32, u32)>,
+}
+
+impl Cursor {
+    pub fn new(edge: EdgeRule) -> Self {
+        Self {
+            edge,
+            ..Self::default()
+        }
+    }
+
+    /// Step one cell on a `width × height` board.
+    pub fn step(&mut self, direction: Direction, width: u32, height: u32) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        let (x, y) = (self.x.min(width - 1), self.y.min(height - 1));
+        let wrap = self.edge == EdgeRule::Wrap;
+        let back = |v: u32, size: u32| match v {
+            0 if wrap => size - 1,
+            0 => 0,
+            v => v - 1,
+        };
+        let forward = |v: u32, size: u32| match v + 1 {
+            next if next < size => next,
+            _ if wrap => 0,
+            _ => v,
+        };
+        (self.x, self.y) = match direction {
+            Direction::Up => (x, back(y, height)),
+            Direction::Down => (x, forward(y, height)),
+            Direction::Left => (back(x, width), y),
+            Direction::Right => (forward(x, width), y),
+        };
+    }
+
+    /// The action `command` stands for at the cursor, if any. A two-cell
+    /// tool with nothing selected selects the cursor cell instead;
+    /// selecting the same cell twice cancels.
+    pub fn command(&mut self, command: CursorCommand) -> Option<Action> {
+        let (x, y) = (self.x, self.y);
+        match command {
+            CursorCommand::Reveal => Some(Action::Reveal { x, y }),
+            CursorCommand::Contain => Some(Action::Contain { x, y }),
+            CursorCommand::Cancel => {
+                self.selection = None;
+                None
+            }
+            CursorCommand::Tool { tool } if !tool.needs_selection() => Some(match tool {
+                Tool::Hadamard => Action::Hadamard { x, y },
+                _ => Action::MeasureWeak { x, y },
+            }),
+            CursorCommand::Tool { tool } => {
+                let Some((sx, sy)) = self.selection.take() else {
+                    self.selection = Some((x, y));
+                    return None;
+                };
+                if (sx, sy) == (x, y) {
+                    return None;
+                }
+                Some(match tool {
+                    Tool::Teleport => Action::Teleport {
+                        x: sx,
+                        y: sy,
+                        to_x: x,
+                        to_y: y,
+                    },
+                    _ => Action::SwapEntanglement {
+                        x: sx,
+                        y: sy,
+                        other_x: x,
+                        other_y: y,
+                    },
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn clamps_or_wraps_at_the_edges() {
+        let mut cursor = Cursor::default();
+        cursor.step(Direction::Up, 3, 2);
+        cursor.step(Direction::Left, 3, 2);
+        assert_eq!((cursor.x, cursor.y), (0, 0));
+
+        let mut cursor = Cursor::new(EdgeRule::Wrap);
+        cursor.step(Direction::Up, 3, 2);
+        cursor.step(Direction::Left, 3, 2);
+        assert_eq!((cursor.x, cursor.y), (2, 1));
+        cursor.step(Direction::Right, 3, 2);
+        cursor.step(Direction::Down, 3, 2);
+        assert_eq!((cursor.x, cursor.y), (0, 0));
+    }
+
+    #[test]
+    fn two_cell_tools_select_then_fire() {
+        let mut cursor = Cursor::default();
+        assert_eq!(
+            cursor.command(CursorCommand::Reveal),
+            Some(Action::Reveal { x: 0, y: 0 })
+        );
+        let teleport = CursorCommand::Tool {
+            tool: Tool::Teleport,
+        };
+        assert_eq!(cursor.command(teleport), None);
+        assert_eq!(cursor.selection, Some((0, 0)));
+        cursor.step(Direction::Right, 4, 4);
+        assert_eq!(
+            cursor.command(teleport),
+            Some(Action::Teleport {
+                x: 0,
+                y: 0,
+                to_x: 1,
+                to_y: 0,
+            })
+        );
+        assert_eq!(cursor.selection, None);
+
+        cursor.command(teleport);
+        assert_eq!(cursor.command(CursorCommand::Cancel), None);
+        assert_eq!(cursor.selection, None);
+    }
+}
@@ -0,0 +1,222 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::config::DaemonRules;
+use crate::generator::neighbors;
+use crate::grid::{CellState, Mark, QuantumGrid};
+use crate::prelude::*;
+use crate::rng::SplitMix64;
+
+/// Keeps the daemon's draws apart from the grid RNG dealt from the same
+/// seed.
+const SALT: u64 = 0x6e6f_6973_6564_6d6e;
+
+/// Largest nudge a scramble gives a hint, either way.
+pub const SCRAMBLE: f64 = 0.15;
+/// Strength a link gains from the daemon, up to full strength.
+pub const LINK_BOOST: f64 = 0.25;
+
+/// One piece of meddling by the noise daemon.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum DaemonMove {
+    /// Nudge the hint at `(x, y)` by `shift`.
+    Scramble { x: u32, y: u32, shift: f64 },
+    /// Strengthen the link between `(x, y)` and its partner by
+    /// [`LINK_BOOST`].
+    Strengthen {
+        x: u32,
+        y: u32,
+        partner_x: u32,
+        partner_y: u32,
+    },
+    /// Move the mine at `(x, y)` to `(to_x, to_y)`. Both cells are out of
+    /// sight of every revealed number, so no deduction the player made is
+    /// broken.
+    ShiftMine {
+        x: u32,
+        y: u32,
+        to_x: u32,
+        to_y: u32,
+    },
+}
+
+/// The challenge-mode adversary (see
+/// [`GridConfig::daemon`](crate::config::GridConfig::daemon)). It plays
+/// like a [`Strategy`](crate::selfplay::Strategy), but on the board
+/// itself, and draws from its own generator seeded from the game's seed,
+/// so replays meddle the same way.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct NoiseDaemon {
+    pub rng: SplitMix64,
+}
+
+impl Default for NoiseDaemon {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+impl NoiseDaemon {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: SplitMix64::new(seed ^ SALT),
+        }
+    }
+
+    /// The daemon's next move on `grid` with `budget` points left, and its
+    /// cost; `None` once nothing it can afford is possible. Moves that cost
+    /// nothing are never chosen, so a turn always ends.
+    pub fn choose(
+        grid: &QuantumGrid,
+        rules: &DaemonRules,
+        budget: u32,
+        rng: &mut SplitMix64,
+    ) -> Option<(DaemonMove, u32)> {
+        let coords = |index: usize| (index as u32 % grid.width, index as u32 / grid.width);
+        let open = |index: usize| grid.is_unresolved(index) && !grid.zeno.is_frozen(index);
+
+        let mut options: Vec<(u32, Vec<DaemonMove>)> = Vec::new();
+        if (1..=budget).contains(&rules.scramble_cost) {
+            let cells: Vec<usize> = (0..grid.cells.len()).filter(|&i| open(i)).collect();
+            if let Some(&cell) = rng.pick(&cells) {
+                let (x, y) = coords(cell);
+                let shift = (rng.next_f64() * 2.0 - 1.0) * SCRAMBLE;
+                options.push((
+                    rules.scramble_cost,
+                    vec![DaemonMove::Scramble { x, y, shift }],
+                ));
+            }
+        }
+        if (1..=budget).contains(&rules.link_cost) {
+            let links: Vec<DaemonMove> = grid
+                .entanglement
+                .pairs
+                .iter()
+                .filter(|pair| pair.strength < 1.0 && open(pair.left) && open(pair.right))
+                .map(|pair| {
+                    let ((x, y), (partner_x, partner_y)) = (coords(pair.left), coords(pair.right));
+                    DaemonMove::Strengthen {
+                        x,
+                        y,
+                        partner_x,
+                        partner_y,
+                    }
+                })
+                .collect();
+            if !links.is_empty() {
+                options.push((rules.link_cost, links));
+            }
+        }
+        if (1..=budget).contains(&rules.shift_cost) {
+            let hidden = |index: usize| {
+                open(index)
+                    && grid
+                        .cells
+                        .get(index)
+                        .is_some_and(|cell| cell.mark == Mark::None)
+                    && !grid.isotopes.contains_key(&index)
+                    && !grid.triggers.contains_key(&index)
+                    && grid.entanglement.partners_of(index).is_empty()
+                    && neighbors(grid.width, grid.height, index).all(|n| {
+                        !matches!(
+                            grid.cells.get(n).map(|cell| &cell.state),
+                            Some(CellState::Revealed { .. })
+                        )
+                    })
+            };
+            let (mines, safe): (Vec<usize>, Vec<usize>) = (0..grid.cells.len())
+                .filter(|&i| hidden(i))
+                .partition(|&i| grid.is_mine(i));
+            if !mines.is_empty() && !safe.is_empty() {
+                if let (Some(&mine), Some(&to)) = (rng.pick(&mines), rng.pick(&safe)) {
+                    let ((x, y), (to_x, to_y)) = (coords(mine), coords(to));
+                    options.push((
+                        rules.shift_cost,
+                        vec![DaemonMove::ShiftMine { x, y, to_x, to_y }],
+                    ));
+                }
+            }
+        }
+
+        let (cost, moves) = rng.pick(&options)?;
+        Some((*rng.pick(moves)?, *cost))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action::Action;
+    use crate::config::{ConfigError, GridConfig, TurnRules};
+    use crate::events::GameEvent;
+    use crate::replay::{Replay, ReplayError};
+
+    #[test]
+    fn the_daemon_meddles_fairly_and_replays_the_same() {
+        let config = GridConfig::new(12, 12, 20, 42, "researcher")
+            .with_turns(TurnRules::default())
+            .with_daemon(DaemonRules::default());
+        let play = || {
+            let mut grid = QuantumGrid::from_config(config.clone());
+            grid.apply(Action::Reveal { x: 6, y: 6 });
+            for _ in 0..8 {
+                grid.apply(Action::EndTurn);
+            }
+            grid
+        };
+        let (mut grid, again) = (play(), play());
+        assert_eq!(grid.mine_map, again.mine_map);
+        assert_eq!(grid.get_probability_cloud(), again.get_probability_cloud());
+
+        let events = grid.drain_events();
+        assert!(events.contains(&GameEvent::DaemonShiftedMine));
+        assert!(events
+            .iter()
+            .any(|event| matches!(event, GameEvent::DaemonScrambled { .. })));
+        // Every revealed number still holds.
+        assert_eq!(grid.mine_map.iter().filter(|&&mine| mine).count(), 20);
+        crate::invariants::check(&grid).unwrap();
+        for (i, cell) in grid.cells.iter().enumerate() {
+            if let CellState::Revealed { adjacent_mines } = cell.state {
+                let mines = neighbors(12, 12, i).filter(|&n| grid.mine_map[n]).count();
+                assert_eq!(usize::from(adjacent_mines), mines);
+            }
+        }
+    }
+
+    #[test]
+    fn free_moves_are_refused_and_never_stall_a_turn() {
+        let rules = DaemonRules {
+            budget: 0,
+            scramble_cost: 0,
+            ..DaemonRules::default()
+        };
+        let config = GridConfig::new(8, 8, 6, 7, "researcher")
+            .with_turns(TurnRules::default())
+            .with_daemon(rules);
+        assert_eq!(config.validate(), Err(ConfigError::InvalidDaemon));
+        let (clamped, _) = config.clone().clamped();
+        assert!(clamped.validate().is_ok());
+
+        let mut replay = Replay::new(config.clone());
+        replay.record(0, Action::EndTurn);
+        assert!(matches!(
+            replay.play(),
+            Err(ReplayError::InvalidConfig { .. })
+        ));
+
+        // Built directly, the grid still ends the turn.
+        let mut grid = QuantumGrid::from_config(config);
+        grid.apply(Action::Reveal { x: 4, y: 4 });
+        for _ in 0..3 {
+            grid.apply(Action::EndTurn);
+        }
+        assert!(!grid
+            .drain_events()
+            .iter()
+            .any(|event| matches!(event, GameEvent::DaemonScrambled { .. })));
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+/// Points per unit of probability: one point is a 0.01% shift.
+pub const POINTS_PER_UNIT: f64 = 10_000.0;
+
+/// Accumulated measurement disturbance, in points of probability shifted.
+///
+/// Every weak measurement adds the size of the drift it caused, both to the
+/// measured cell and to the board-wide `total`. With
+/// [`GridConfig::decoherence_limit`](crate::config::GridConfig::decoherence_limit)
+/// set, `total` overflowing the limit costs a penalty and carries the excess.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DecoherenceMeter {
+    /// Per-cell disturbance; empty until the first measurement.
+    pub cells: Vec<u32>,
+    /// Disturbance since the last penalty.
+    pub total: u32,
+    /// All disturbance this game, penalties notwithstanding — what scoring
+    /// charges for.
+    pub lifetime: u32,
+    pub penalties: u32,
+}
+
+impl DecoherenceMeter {
+    /// Disturbance accumulated on one cell.
+    pub fn cell(&self, index: usize) -> u32 {
+        self.cells.get(index).copied().unwrap_or(0)
+    }
+
+    /// Record a probability shift of `amount` on cell `index` of a
+    /// `total_cells` board.
+    pub(crate) fn disturb(&mut self, index: usize, amount: f64, total_cells: usize) {
+        let points = (amount.abs() * POINTS_PER_UNIT).round() as u32;
+        if points == 0 {
+            return;
+        }
+        if self.cells.len() != total_cells {
+            self.cells.resize(total_cells, 0);
+        }
+        if let Some(cell) = self.cells.get_mut(index) {
+            *cell = cell.saturating_add(points);
+        }
+        self.total = self.total.saturating_add(points);
+        self.lifetime = self.lifetime.saturating_add(points);
+    }
+
+    /// Record disturbance from the environment rather than a measurement,
+    /// so no cell is charged.
+    pub(crate) fn ambient(&mut self, points: u32) {
+        self.total = self.total.saturating_add(points);
+        self.lifetime = self.lifetime.saturating_add(points);
+    }
+
+    /// Take one penalty if `total` has reached `limit`, keeping the excess.
+    pub(crate) fn overflowed(&mut self, limit: u32) -> bool {
+        if limit == 0 || self.total < limit {
+            return false;
+        }
+        self.total -= limit;
+        self.penalties = self.penalties.saturating_add(1);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accumulates_per_cell_and_overflows_with_carry() {
+        let mut meter = DecoherenceMeter::default();
+        meter.disturb(3, -0.03, 10);
+        meter.disturb(3, 0.02, 10);
+        meter.disturb(5, 0.0, 10);
+        assert_eq!(meter.cell(3), 500);
+        assert_eq!(meter.cell(5), 0);
+        assert_eq!(meter.cell(99), 0);
+
+        assert!(!meter.overflowed(600));
+        assert!(meter.overflowed(400));
+        assert_eq!(
+            (meter.total, meter.lifetime, meter.penalties),
+            (100, 500, 1)
+        );
+    }
+}
@@ -0,0 +1,180 @@
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::grid::{CellState, Mark, QuantumGrid};
+use crate::prelude::*;
+
+/// A cell as it stands on the newer board.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CellChange {
+    pub index: usize,
+    pub state: CellState,
+    pub mark: Mark,
+}
+
+/// What changed between two states of the same board: the cells that
+/// differ, the charge deltas, and the end-of-game flags. Compact enough to
+/// send over the wire, and readable as a test assertion.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GridDiff {
+    pub cells: Vec<CellChange>,
+    /// Cells whose ground truth flipped, e.g. through a teleport.
+    pub mines: Vec<usize>,
+    pub containment_charges: i64,
+    pub swap_charges: i64,
+    /// Set when the flag changed.
+    pub game_over: Option<bool>,
+    pub won: Option<bool>,
+}
+
+/// A diff that does not fit the board it was applied to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum DiffError {
+    /// The diff touches a cell past the end of the board.
+    OutOfBounds { index: usize },
+    /// The board has fewer charges than the diff takes away.
+    NotEnoughCharges,
+}
+
+impl fmt::Display for DiffError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::OutOfBounds { index } => write!(f, "cell {index} is not on this board"),
+            Self::NotEnoughCharges => write!(f, "the diff spends charges the board lacks"),
+        }
+    }
+}
+
+impl core::error::Error for DiffError {}
+
+impl GridDiff {
+    /// The changes that take `a` to `b`. Both should be the same board;
+    /// cells past the end of the smaller one are ignored.
+    pub fn between(a: &QuantumGrid, b: &QuantumGrid) -> Self {
+        let cells = a
+            .cells
+            .iter()
+            .zip(&b.cells)
+            .enumerate()
+            .filter(|(_, (old, new))| old.state != new.state || old.mark != new.mark)
+            .map(|(index, (_, new))| CellChange {
+                index,
+                state: new.state.clone(),
+                mark: new.mark,
+            })
+            .collect();
+        let mines = a
+            .mine_map
+            .iter()
+            .zip(&b.mine_map)
+            .enumerate()
+            .filter(|(_, (old, new))| old != new)
+            .map(|(index, _)| index)
+            .collect();
+        let flag = |old: bool, new: bool| (old != new).then_some(new);
+        Self {
+            cells,
+            mines,
+            containment_charges: i64::from(b.containment_charges)
+                - i64::from(a.containment_charges),
+            swap_charges: i64::from(b.swap_charges) - i64::from(a.swap_charges),
+            game_over: flag(a.game_over, b.game_over),
+            won: flag(a.won, b.won),
+        }
+    }
+
+    /// No change at all.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// Bring `grid` forward by this diff. Checked up front, so a diff that
+    /// does not fit leaves `grid` untouched.
+    pub fn apply(&self, grid: &mut QuantumGrid) -> Result<(), DiffError> {
+        let len = grid.cells.len();
+        if let Some(index) = self
+            .cells
+            .iter()
+            .map(|change| change.index)
+            .chain(self.mines.iter().copied())
+            .find(|&index| index >= len)
+        {
+            return Err(DiffError::OutOfBounds { index });
+        }
+        let shift = |charges: u32, delta: i64| {
+            u32::try_from(i64::from(charges) + delta).map_err(|_| DiffError::NotEnoughCharges)
+        };
+        let containment_charges = shift(grid.containment_charges, self.containment_charges)?;
+        let swap_charges = shift(grid.swap_charges, self.swap_charges)?;
+
+        for change in &self.cells {
+            let Some(cell) = grid.cells.get_mut(change.index) else {
+                continue;
+            };
+            cell.state = change.state.clone();
+            cell.mark = change.mark;
+            let (x, y) = (cell.x, cell.y);
+            grid.chunks.touch(grid.width, grid.height, x, y);
+        }
+        if grid.mine_map.len() == len {
+            for &index in &self.mines {
+                if let Some(mine) = grid.mine_map.get_mut(index) {
+                    *mine = !*mine;
+                }
+            }
+        }
+        grid.containment_charges = containment_charges;
+        grid.swap_charges = swap_charges;
+        if let Some(game_over) = self.game_over {
+            grid.game_over = game_over;
+        }
+        if let Some(won) = self.won {
+            grid.won = won;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GridConfig;
+
+    #[test]
+    fn a_diff_carries_one_board_to_the_other() {
+        let before = QuantumGrid::from_config(GridConfig::new(8, 8, 10, 42, "observer"));
+        assert!(GridDiff::between(&before, &before).is_empty());
+
+        let mut after = before.clone();
+        after.reveal_cell(4, 4);
+        let mine = (0..64).find(|&i| after.mine_map[i]).unwrap();
+        after.contain_cell(mine as u32 % 8, mine as u32 / 8);
+
+        let diff = GridDiff::between(&before, &after);
+        assert_eq!(diff.containment_charges, -1);
+        // Mines are placed on the first click.
+        assert_eq!(diff.mines.len(), 10);
+        assert!(diff
+            .cells
+            .iter()
+            .any(|change| change.index == mine && change.state == CellState::Contained));
+
+        let mut synced = before.clone();
+        diff.apply(&mut synced).unwrap();
+        assert!(GridDiff::between(&synced, &after).is_empty());
+
+        let small = QuantumGrid::from_config(GridConfig::new(4, 4, 3, 42, "observer"));
+        let mut untouched = small.clone();
+        assert!(matches!(
+            diff.apply(&mut untouched),
+            Err(DiffError::OutOfBounds { .. })
+        ));
+        assert!(GridDiff::between(&small, &untouched).is_empty());
+    }
+}
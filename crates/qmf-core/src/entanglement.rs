@@ -1,8 +1,16 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::fmt;
+
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
+use crate::lab::BoardId;
+use crate::prelude::*;
+
 /// The type of quantum link between two entangled cells.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum LinkType {
     /// Bayesian probability adjustment — weak correlation.
     Probabilistic,
@@ -11,26 +19,149 @@ pub enum LinkType {
     BellState,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// How the two halves of a BellState pair relate once one is observed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Correlation {
+    /// Opposite states: one mine, one safe.
+    #[default]
+    Anti,
+    /// Same state: both mines or both safe.
+    Positive,
+}
+
+impl Correlation {
+    /// The partner's predicted mine status given the observed cell's.
+    pub fn predict(self, observed_mine: bool) -> bool {
+        match self {
+            Self::Anti => !observed_mine,
+            Self::Positive => observed_mine,
+        }
+    }
+
+    /// The correlation left between the outer cells when two links are
+    /// joined end to end: two flips cancel out.
+    pub fn compose(self, other: Self) -> Self {
+        if self == other {
+            Self::Positive
+        } else {
+            Self::Anti
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct EntanglementPair {
     pub left: usize,
     pub right: usize,
     pub strength: f64,
     pub link_type: LinkType,
+    /// Only meaningful for `BellState` links.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub correlation: Correlation,
+}
+
+/// A cell on a particular board of a [`Lab`](crate::lab::Lab).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CellRef {
+    pub board: BoardId,
+    pub x: u32,
+    pub y: u32,
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+/// An entanglement pair whose ends may sit on different boards of a lab,
+/// so a collapse on one board resolves or shifts a cell on another.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CrossLink {
+    pub left: CellRef,
+    pub right: CellRef,
+    pub strength: f64,
+    pub link_type: LinkType,
+    /// Only meaningful for `BellState` links.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub correlation: Correlation,
+}
+
+impl CrossLink {
+    /// The other end of the link, if `cell` is one of its ends.
+    pub fn partner_of(&self, cell: CellRef) -> Option<CellRef> {
+        if self.left == cell {
+            Some(self.right)
+        } else if self.right == cell {
+            Some(self.left)
+        } else {
+            None
+        }
+    }
+
+    /// The link as seen from the board holding `index`, for the
+    /// single-board collapse rules.
+    pub(crate) fn local(&self, index: usize) -> EntanglementPair {
+        EntanglementPair {
+            left: index,
+            right: index,
+            strength: self.strength.clamp(0.0, 1.0),
+            link_type: self.link_type,
+            correlation: self.correlation,
+        }
+    }
+}
+
+/// Why the entanglement graph refused a change.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum EntanglementError {
+    /// Both ends of the change are the same cell.
+    SameCell,
+    /// The cell has no BellState partner to give up.
+    NoBellPartner { cell: usize },
+    /// The two cells are Bell partners of each other, or share their
+    /// partner, so swapping would link a cell to itself.
+    AlreadyLinked,
+}
+
+impl fmt::Display for EntanglementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SameCell => write!(f, "cannot entangle a cell with itself"),
+            Self::NoBellPartner { cell } => write!(f, "cell {cell} has no Bell partner"),
+            Self::AlreadyLinked => write!(f, "cells already share a Bell link"),
+        }
+    }
+}
+
+impl core::error::Error for EntanglementError {}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Entanglement {
     pub pairs: Vec<EntanglementPair>,
 }
 
 impl Entanglement {
     pub fn add_pair(&mut self, left: usize, right: usize, strength: f64, link_type: LinkType) {
+        self.add_correlated_pair(left, right, strength, link_type, Correlation::Anti);
+    }
+
+    pub fn add_correlated_pair(
+        &mut self,
+        left: usize,
+        right: usize,
+        strength: f64,
+        link_type: LinkType,
+        correlation: Correlation,
+    ) {
         self.pairs.push(EntanglementPair {
             left,
             right,
             strength: strength.clamp(0.0, 1.0),
             link_type,
+            correlation,
         });
     }
 
@@ -63,11 +194,92 @@ impl Entanglement {
             .collect()
     }
 
+    /// The **first** BellState partner of a cell.
+    pub fn bell_partner_of(&self, index: usize) -> Option<(&EntanglementPair, usize)> {
+        self.partners_of(index)
+            .into_iter()
+            .find(|(pair, _)| pair.link_type == LinkType::BellState)
+    }
+
+    /// Remove the first link between `a` and `b`, in either orientation.
+    pub fn remove_pair(&mut self, a: usize, b: usize) -> Option<EntanglementPair> {
+        let position = self
+            .pairs
+            .iter()
+            .position(|p| (p.left, p.right) == (a, b) || (p.left, p.right) == (b, a))?;
+        Some(self.pairs.remove(position))
+    }
+
+    /// Entanglement swapping: with `a`'s Bell partner `p` and `b`'s Bell
+    /// partner `q`, consume the links `a–p` and `b–q` and link `p–q`
+    /// instead. The new link is as reliable as both old ones together and
+    /// carries their composed correlation. Returns the new pair; on error
+    /// the graph is unchanged.
+    pub fn swap(&mut self, a: usize, b: usize) -> Result<EntanglementPair, EntanglementError> {
+        if a == b {
+            return Err(EntanglementError::SameCell);
+        }
+        let (first, p) = self
+            .bell_partner_of(a)
+            .map(|(pair, p)| (pair.clone(), p))
+            .ok_or(EntanglementError::NoBellPartner { cell: a })?;
+        let (second, q) = self
+            .bell_partner_of(b)
+            .map(|(pair, q)| (pair.clone(), q))
+            .ok_or(EntanglementError::NoBellPartner { cell: b })?;
+        if p == b || p == q {
+            return Err(EntanglementError::AlreadyLinked);
+        }
+        self.remove_pair(a, p);
+        self.remove_pair(b, q);
+        let pair = EntanglementPair {
+            left: p,
+            right: q,
+            strength: (first.strength * second.strength).clamp(0.0, 1.0),
+            link_type: LinkType::BellState,
+            correlation: first.correlation.compose(second.correlation),
+        };
+        self.pairs.push(pair.clone());
+        Ok(pair)
+    }
+
+    /// Size (in cells) of the largest group joined by BellState links, so a
+    /// lone Bell pair is 2 and a three-link chain is 4. `0` with no Bell links.
+    pub fn longest_bell_chain(&self) -> usize {
+        let mut adjacency: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for pair in self
+            .pairs
+            .iter()
+            .filter(|p| p.link_type == LinkType::BellState)
+        {
+            adjacency.entry(pair.left).or_default().push(pair.right);
+            adjacency.entry(pair.right).or_default().push(pair.left);
+        }
+        let mut seen = BTreeSet::new();
+        let mut longest = 0;
+        for &start in adjacency.keys() {
+            if !seen.insert(start) {
+                continue;
+            }
+            let (mut stack, mut size) = (vec![start], 0);
+            while let Some(cell) = stack.pop() {
+                size += 1;
+                for &next in adjacency.get(&cell).into_iter().flatten() {
+                    if seen.insert(next) {
+                        stack.push(next);
+                    }
+                }
+            }
+            longest = longest.max(size);
+        }
+        longest
+    }
+
     /// Compute the partner's new probability after observing a cell.
     ///
-    /// - **`BellState`**: Perfect anti-correlation. If a mine was observed the
-    ///   partner is safe (`0.0`); if safe was observed the partner is a mine
-    ///   (`1.0`).
+    /// - **`BellState`**: Perfect correlation. With `Correlation::Anti`, an
+    ///   observed mine makes the partner safe (`0.0`) and an observed safe
+    ///   cell makes it a mine (`1.0`); `Correlation::Positive` is the reverse.
     /// - **`Probabilistic`**: Bayesian blend weighted by `strength`.
     pub fn collapse_partner_probability(
         &self,
@@ -77,11 +289,10 @@ impl Entanglement {
     ) -> f64 {
         match pair.link_type {
             LinkType::BellState => {
-                // Perfect anti-correlation: partner is the opposite.
-                if observed_mine {
-                    0.0 // partner is definitely safe
-                } else {
+                if pair.correlation.predict(observed_mine) {
                     1.0 // partner is definitely a mine
+                } else {
+                    0.0 // partner is definitely safe
                 }
             }
             LinkType::Probabilistic => {
@@ -97,3 +308,48 @@ impl Entanglement {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn swapping_relinks_the_partners() {
+        let mut graph = Entanglement::default();
+        graph.add_correlated_pair(0, 1, 1.0, LinkType::BellState, Correlation::Anti);
+        graph.add_correlated_pair(2, 3, 0.5, LinkType::BellState, Correlation::Anti);
+        graph.add_pair(0, 4, 0.3, LinkType::Probabilistic);
+
+        let pair = graph.swap(0, 3).unwrap();
+        assert_eq!((pair.left, pair.right), (1, 2));
+        assert_eq!(pair.strength, 0.5);
+        assert_eq!(pair.correlation, Correlation::Positive);
+        assert!(graph.bell_partner_of(0).is_none());
+        assert!(graph.bell_partner_of(3).is_none());
+        // Probabilistic links are left alone.
+        assert_eq!(graph.partner_of(0).map(|(_, p)| p), Some(4));
+        assert_eq!(graph.pairs.len(), 2);
+    }
+
+    #[test]
+    fn invalid_swaps_leave_the_graph_alone() {
+        let mut graph = Entanglement::default();
+        graph.add_pair(0, 1, 1.0, LinkType::BellState);
+        graph.add_pair(2, 1, 1.0, LinkType::BellState);
+
+        assert_eq!(graph.swap(0, 0).unwrap_err(), EntanglementError::SameCell);
+        assert_eq!(
+            graph.swap(0, 5).unwrap_err(),
+            EntanglementError::NoBellPartner { cell: 5 }
+        );
+        assert_eq!(
+            graph.swap(0, 1).unwrap_err(),
+            EntanglementError::AlreadyLinked
+        );
+        assert_eq!(
+            graph.swap(0, 2).unwrap_err(),
+            EntanglementError::AlreadyLinked
+        );
+        assert_eq!(graph.pairs.len(), 2);
+    }
+}
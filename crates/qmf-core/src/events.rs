@@ -0,0 +1,151 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::entanglement::CellRef;
+use crate::prelude::*;
+
+/// Everything the engine did to the board, in order: cell outcomes as well
+/// as side effects it applied on its own (e.g. assists to animate). Queued
+/// on the grid until drained, by a frontend or any other [`EventSink`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum GameEvent {
+    /// A cell collapsed safe, whatever caused it.
+    Revealed { x: u32, y: u32, adjacent_mines: u8 },
+    /// A mine was locked down, by the player or an assist.
+    Contained { x: u32, y: u32 },
+    /// A mine went off, whatever caused it.
+    Detonated { x: u32, y: u32 },
+    /// The board was cleared.
+    Won,
+    /// The auto-contain assist locked down a mine without spending a charge.
+    AutoContained { x: u32, y: u32 },
+    /// The auto-reveal assist opened a safe cell.
+    AutoRevealed { x: u32, y: u32 },
+    /// The trusting auto-reveal assist opened a mine the player had not
+    /// flagged — game over.
+    AutoDetonated { x: u32, y: u32 },
+    /// A BellState link held and force-collapsed this cell. Cascades are
+    /// reported in the order the engine resolved them, so renderers can
+    /// stagger the animation along the real propagation path: `depth` counts
+    /// links from the observed cell (its direct partner is `1`) and
+    /// `(cause_x, cause_y)` is the cell whose collapse triggered this one.
+    BellCollapsed {
+        x: u32,
+        y: u32,
+        depth: u32,
+        cause_x: u32,
+        cause_y: u32,
+    },
+    /// A BellState link with strength < 1.0 failed its reliability roll and
+    /// only shifted this cell's probability.
+    BellDegraded { x: u32, y: u32, probability: f64 },
+    /// The decoherence meter overflowed its limit and collapsed this cell
+    /// to its true state; `mine` means it detonated.
+    Decohered { x: u32, y: u32, mine: bool },
+    /// Back-to-back weak measurements froze this cell (Zeno effect).
+    ZenoFrozen { x: u32, y: u32 },
+    /// An entanglement swap consumed two Bell links and joined the cells
+    /// they left behind with a new one.
+    EntanglementSwapped {
+        x: u32,
+        y: u32,
+        partner_x: u32,
+        partner_y: u32,
+    },
+    /// A teleport moved the state of `(x, y)` onto `(to_x, to_y)`, leaving
+    /// the source revealed; `destroyed_mine` means a mine under the
+    /// destination was overwritten.
+    Teleported {
+        x: u32,
+        y: u32,
+        to_x: u32,
+        to_y: u32,
+        destroyed_mine: bool,
+    },
+    /// The combo multiplier went up after a safe reveal.
+    ComboRaised { multiplier: u32 },
+    /// A wasted containment reset the combo.
+    ComboBroken,
+    /// The combo earned a free swap charge.
+    ComboCharge,
+    /// A worn-out containment at `(x, y)` breached: the cell is back in
+    /// Superposition.
+    ContainmentBreached { x: u32, y: u32 },
+    /// A shield absorbed the mine the player clicked at `(x, y)`.
+    ShieldAbsorbed { x: u32, y: u32 },
+    /// The mercy rule contained the mine the player clicked at `(x, y)` in
+    /// a forced guess.
+    MercyGranted { x: u32, y: u32 },
+    /// A containment charge went into an isotope that needs `stages_left`
+    /// more.
+    IsotopeStabilized { x: u32, y: u32, stages_left: u32 },
+    /// An oracle cell was revealed and kicked its circuit back onto its
+    /// row and column.
+    OracleFired { x: u32, y: u32 },
+    /// A cross-board link in a [`Lab`](crate::lab::Lab) held and
+    /// force-collapsed this cell after `cause`, on another board, was
+    /// observed. Any cascade it starts here follows as
+    /// [`GameEvent::BellCollapsed`].
+    LinkCollapsed { x: u32, y: u32, cause: CellRef },
+    /// Scenario objective `index` (in
+    /// [`GridConfig::objectives`](crate::config::GridConfig::objectives))
+    /// was met.
+    ObjectiveCompleted { index: u32 },
+    /// Scenario objective `index` can no longer be met; the game is over.
+    ObjectiveFailed { index: u32 },
+    /// Speedrun split `index` (see
+    /// [`GridConfig::splits`](crate::config::GridConfig::splits)) was
+    /// reached `elapsed_ms` after the first action.
+    SplitReached { index: u32, elapsed_ms: u64 },
+    /// Turn-based play: turn `turn` began with `action_points` to spend.
+    TurnStarted { turn: u32, action_points: u32 },
+    /// The noise daemon nudged the hint at `(x, y)`.
+    DaemonScrambled { x: u32, y: u32 },
+    /// The noise daemon strengthened a link.
+    DaemonStrengthened {
+        x: u32,
+        y: u32,
+        partner_x: u32,
+        partner_y: u32,
+    },
+    /// The noise daemon moved a mine somewhere no revealed number can see.
+    /// Where is not told.
+    DaemonShiftedMine,
+}
+
+impl GameEvent {
+    /// Whether this reports a cell outcome or the win, rather than
+    /// something the engine did on its own.
+    pub fn is_outcome(&self) -> bool {
+        matches!(
+            self,
+            Self::Revealed { .. } | Self::Contained { .. } | Self::Detonated { .. } | Self::Won
+        )
+    }
+}
+
+/// Subscriber to the engine's [`GameEvent`] stream, so achievements, stats
+/// and frontend queues all consume the same events instead of each hooking
+/// the reveal and containment paths. `Vec<GameEvent>` collects, closures
+/// `FnMut(&GameEvent)` are sinks, and `()` discards everything.
+pub trait EventSink {
+    fn on_event(&mut self, event: &GameEvent);
+}
+
+impl EventSink for () {
+    fn on_event(&mut self, _event: &GameEvent) {}
+}
+
+impl EventSink for Vec<GameEvent> {
+    fn on_event(&mut self, event: &GameEvent) {
+        self.push(event.clone());
+    }
+}
+
+impl<F: FnMut(&GameEvent)> EventSink for F {
+    fn on_event(&mut self, event: &GameEvent) {
+        self(event)
+    }
+}
@@ -0,0 +1,562 @@
+use crate::action::{Action, ActionResult, IllegalAction};
+use crate::circuit::Circuit;
+use crate::config::ConfigError;
+use crate::cosmetics;
+use crate::events::GameEvent;
+use crate::generator::neighbors;
+#[cfg(doc)]
+use crate::grid::QuantumGrid;
+use crate::grid::{
+    binary_entropy, hint_noise, CellState, GridSnapshot, Mark, QuantumCell, RevealOutcome,
+};
+use crate::ops::GridOps;
+use crate::prelude::*;
+use crate::rng::SplitMix64;
+
+/// A `W × H` board held entirely in arrays, for targets with little or no
+/// heap such as the badge build. It plays the classic game — hints,
+/// reveals, containment and marks — but has none of the circuits,
+/// entanglement, tools or timers of a [`QuantumGrid`].
+#[derive(Debug, Clone)]
+pub struct FixedGrid<const W: usize, const H: usize> {
+    states: [[CellState; W]; H],
+    marks: [[Mark; W]; H],
+    mines: [[bool; W]; H],
+    /// Resolved cells already handed out by `drain_events`.
+    reported: [[bool; W]; H],
+    won_reported: bool,
+    mine_count: u32,
+    containment_charges: u32,
+    seed: u64,
+    rng: SplitMix64,
+    mines_placed: bool,
+    game_over: bool,
+    won: bool,
+}
+
+impl<const W: usize, const H: usize> FixedGrid<W, H> {
+    /// A board with `mine_count` mines, placed on the first move so that
+    /// it always opens onto a zero. One containment charge per mine.
+    pub fn new(mine_count: u32, seed: u64) -> Result<Self, ConfigError> {
+        let width = u32::try_from(W).unwrap_or(u32::MAX);
+        let height = u32::try_from(H).unwrap_or(u32::MAX);
+        if width == 0 || height == 0 || width.checked_mul(height).is_none() {
+            return Err(ConfigError::InvalidDimensions { width, height });
+        }
+        let max = Self::max_mines();
+        if mine_count > max {
+            return Err(ConfigError::TooManyMines {
+                requested: mine_count,
+                max,
+            });
+        }
+        let baseline = f64::from(mine_count) / f64::from(width * height);
+        let states = core::array::from_fn(|y| {
+            core::array::from_fn(|x| CellState::Superposition {
+                probability: hint(baseline + hint_noise(seed, y * W + x, 0)),
+            })
+        });
+        Ok(Self {
+            states,
+            marks: [[Mark::None; W]; H],
+            mines: [[false; W]; H],
+            reported: [[false; W]; H],
+            won_reported: false,
+            mine_count,
+            containment_charges: mine_count,
+            seed,
+            rng: SplitMix64::new(seed),
+            mines_placed: false,
+            game_over: false,
+            won: false,
+        })
+    }
+
+    /// Most mines a board this size takes: every cell outside the 3×3
+    /// the first move keeps clear.
+    pub fn max_mines() -> u32 {
+        let zone = W.min(3) * H.min(3);
+        u32::try_from(W.saturating_mul(H) - zone).unwrap_or(u32::MAX)
+    }
+
+    fn index_of(x: u32, y: u32) -> Option<usize> {
+        let (x, y) = (x as usize, y as usize);
+        (x < W && y < H).then_some(y * W + x)
+    }
+
+    fn coords_of(index: usize) -> (usize, usize) {
+        (index % W, index / W)
+    }
+
+    /// Cell `index` of one of the per-cell arrays.
+    fn at<T>(cells: &[[T; W]; H], index: usize) -> Option<&T> {
+        let (x, y) = Self::coords_of(index);
+        cells.get(y)?.get(x)
+    }
+
+    fn at_mut<T>(cells: &mut [[T; W]; H], index: usize) -> Option<&mut T> {
+        let (x, y) = Self::coords_of(index);
+        cells.get_mut(y)?.get_mut(x)
+    }
+
+    fn state(&self, index: usize) -> Option<&CellState> {
+        Self::at(&self.states, index)
+    }
+
+    fn set_state(&mut self, index: usize, state: CellState) {
+        if let Some(slot) = Self::at_mut(&mut self.states, index) {
+            *slot = state;
+        }
+    }
+
+    fn is_mine(&self, index: usize) -> bool {
+        Self::at(&self.mines, index).is_some_and(|&mine| mine)
+    }
+
+    fn neighbours(index: usize) -> impl Iterator<Item = usize> {
+        neighbors(W as u32, H as u32, index)
+    }
+
+    fn adjacent_mines(&self, index: usize) -> u8 {
+        Self::neighbours(index).filter(|&i| self.is_mine(i)).count() as u8
+    }
+
+    /// Scatter the mines clear of the 3×3 around `safe`, then give every
+    /// unresolved cell a hint from its neighbourhood, like a
+    /// [`QuantumGrid`] without a circuit.
+    fn place_mines(&mut self, safe: usize) {
+        let (sx, sy) = Self::coords_of(safe);
+        let mut placed = 0;
+        while placed < self.mine_count {
+            let index = self.rng.next_usize(W * H);
+            let (x, y) = Self::coords_of(index);
+            if x.abs_diff(sx) <= 1 && y.abs_diff(sy) <= 1 {
+                continue;
+            }
+            let Some(mine) = Self::at_mut(&mut self.mines, index).filter(|mine| !**mine) else {
+                continue;
+            };
+            *mine = true;
+            placed += 1;
+        }
+        self.mines_placed = true;
+
+        let baseline = f64::from(self.mine_count) / (W * H) as f64;
+        for index in 0..W * H {
+            if !matches!(self.state(index), Some(CellState::Superposition { .. })) {
+                continue;
+            }
+            let around = Self::neighbours(index).count();
+            let local = if around > 0 {
+                f64::from(self.adjacent_mines(index)) / around as f64
+            } else {
+                baseline
+            };
+            let probability = hint(local * 0.6 + baseline * 0.4 + hint_noise(self.seed, index, 1));
+            self.set_state(index, CellState::Superposition { probability });
+        }
+    }
+
+    fn reveal_safe(&mut self, index: usize) -> RevealOutcome {
+        let adjacent_mines = self.adjacent_mines(index);
+        self.set_state(index, CellState::Revealed { adjacent_mines });
+        if adjacent_mines == 0 {
+            self.flood_fill();
+        }
+        self.check_win();
+        let (x, y) = Self::coords_of(index);
+        match self.cell(x, y) {
+            Some(cell) => RevealOutcome::Revealed { cell },
+            None => RevealOutcome::OutOfBounds,
+        }
+    }
+
+    /// Open everything next to a revealed zero, sweeping the board until a
+    /// pass opens nothing. Slower than the stack a [`QuantumGrid`] uses,
+    /// but it needs no heap.
+    fn flood_fill(&mut self) {
+        let mut opened = true;
+        while opened {
+            opened = false;
+            for index in 0..W * H {
+                if self.state(index) != Some(&CellState::Revealed { adjacent_mines: 0 }) {
+                    continue;
+                }
+                for neighbour in Self::neighbours(index) {
+                    if matches!(self.state(neighbour), Some(CellState::Superposition { .. }))
+                        && !self.is_mine(neighbour)
+                    {
+                        let adjacent_mines = self.adjacent_mines(neighbour);
+                        self.set_state(neighbour, CellState::Revealed { adjacent_mines });
+                        opened = true;
+                    }
+                }
+            }
+        }
+    }
+
+    fn check_win(&mut self) {
+        self.won = !self.game_over
+            && self
+                .states
+                .iter()
+                .flatten()
+                .all(|state| !matches!(state, CellState::Superposition { .. }));
+    }
+
+    fn cell(&self, x: usize, y: usize) -> Option<QuantumCell> {
+        Some(QuantumCell {
+            x: x as u32,
+            y: y as u32,
+            state: self.states.get(y)?.get(x)?.clone(),
+            mark: *self.marks.get(y)?.get(x)?,
+        })
+    }
+}
+
+/// A displayed hint, kept off the certainties.
+fn hint(raw: f64) -> f64 {
+    raw.clamp(0.01, 0.99)
+}
+
+impl<const W: usize, const H: usize> GridOps for FixedGrid<W, H> {
+    fn width(&self) -> u32 {
+        W as u32
+    }
+
+    fn height(&self) -> u32 {
+        H as u32
+    }
+
+    fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    fn mine_count(&self) -> u32 {
+        self.mine_count
+    }
+
+    fn mines_placed(&self) -> bool {
+        self.mines_placed
+    }
+
+    fn containment_charges(&self) -> u32 {
+        self.containment_charges
+    }
+
+    fn cell_at(&self, x: u32, y: u32) -> Option<QuantumCell> {
+        let (x, y) = Self::coords_of(Self::index_of(x, y)?);
+        self.cell(x, y)
+    }
+
+    fn is_game_over(&self) -> bool {
+        self.game_over
+    }
+
+    fn is_won(&self) -> bool {
+        self.won
+    }
+
+    fn mines_remaining(&self) -> u32 {
+        let contained = self
+            .states
+            .iter()
+            .flatten()
+            .filter(|state| matches!(state, CellState::Contained))
+            .count() as u32;
+        self.mine_count.saturating_sub(contained)
+    }
+
+    fn reveal_cell(&mut self, x: u32, y: u32) -> RevealOutcome {
+        if self.game_over || self.won {
+            return RevealOutcome::GameAlreadyOver;
+        }
+        let Some(index) = Self::index_of(x, y) else {
+            return RevealOutcome::OutOfBounds;
+        };
+        if !matches!(self.state(index), Some(CellState::Superposition { .. })) {
+            return RevealOutcome::AlreadyResolved;
+        }
+        if !self.mines_placed {
+            self.place_mines(index);
+        }
+        if self.is_mine(index) {
+            self.set_state(index, CellState::Detonated);
+            self.game_over = true;
+            return RevealOutcome::MineDetonated { x, y };
+        }
+        self.reveal_safe(index)
+    }
+
+    fn contain_cell(&mut self, x: u32, y: u32) -> RevealOutcome {
+        if self.game_over || self.won {
+            return RevealOutcome::GameAlreadyOver;
+        }
+        if self.containment_charges == 0 {
+            return RevealOutcome::NoChargesRemaining;
+        }
+        let Some(index) = Self::index_of(x, y) else {
+            return RevealOutcome::OutOfBounds;
+        };
+        if !matches!(self.state(index), Some(CellState::Superposition { .. })) {
+            return RevealOutcome::AlreadyResolved;
+        }
+        if !self.mines_placed {
+            self.place_mines(index);
+        }
+        self.containment_charges -= 1;
+        if self.is_mine(index) {
+            self.set_state(index, CellState::Contained);
+            self.check_win();
+            return RevealOutcome::ContainmentSuccess { x, y };
+        }
+        match self.reveal_safe(index) {
+            RevealOutcome::Revealed { cell } => RevealOutcome::ContainmentFailed { cell },
+            other => other,
+        }
+    }
+
+    fn set_mark(&mut self, x: u32, y: u32, mark: Mark) -> Result<Mark, &'static str> {
+        let index = Self::index_of(x, y).ok_or("coordinates out of bounds")?;
+        if !matches!(self.state(index), Some(CellState::Superposition { .. })) {
+            return Err("cell is already resolved");
+        }
+        let slot = Self::at_mut(&mut self.marks, index).ok_or("coordinates out of bounds")?;
+        Ok(core::mem::replace(slot, mark))
+    }
+
+    fn validate_action(&self, action: &Action) -> Result<(), IllegalAction> {
+        let (x, y) = match *action {
+            Action::Reveal { x, y } | Action::Contain { x, y } => (x, y),
+            _ => return Err(IllegalAction::Unsupported),
+        };
+        if self.game_over || self.won {
+            return Err(IllegalAction::GameAlreadyOver);
+        }
+        if matches!(action, Action::Contain { .. }) && self.containment_charges == 0 {
+            return Err(IllegalAction::NoChargesRemaining);
+        }
+        let index = Self::index_of(x, y).ok_or(IllegalAction::OutOfBounds)?;
+        if !matches!(self.state(index), Some(CellState::Superposition { .. })) {
+            return Err(IllegalAction::AlreadyResolved);
+        }
+        Ok(())
+    }
+
+    fn apply(&mut self, action: Action) -> ActionResult {
+        let outcome = match action {
+            Action::Reveal { x, y } => self.reveal_cell(x, y),
+            Action::Contain { x, y } => self.contain_cell(x, y),
+            Action::Hadamard { .. }
+            | Action::MeasureWeak { .. }
+            | Action::MeasureWeakWithPrecision { .. }
+            | Action::SwapEntanglement { .. }
+            | Action::Teleport { .. }
+            | Action::Advance { .. }
+            | Action::EndTurn => {
+                return ActionResult::ToolRejected {
+                    reason: "a fixed grid has no tools, clock or turns".to_string(),
+                }
+            }
+        };
+        ActionResult::Outcome { outcome }
+    }
+
+    /// The board in the engine's snapshot shape. There is no circuit,
+    /// score, combo or tool state, so those read as on a fresh
+    /// [`QuantumGrid`].
+    fn snapshot(&self) -> GridSnapshot {
+        let hints = self
+            .states
+            .iter()
+            .flatten()
+            .filter_map(|state| match state {
+                CellState::Superposition { probability } => Some(*probability),
+                _ => None,
+            });
+        let unresolved = hints.clone().count();
+        let (width, height) = (W as u32, H as u32);
+        GridSnapshot {
+            width,
+            height,
+            game_over: self.game_over,
+            won: self.won,
+            seed: self.seed,
+            containment_charges: self.containment_charges,
+            swap_charges: 0,
+            mines_remaining: self.mines_remaining(),
+            entropy: unresolved as f64 / (W * H) as f64,
+            quantum_entropy: hints.map(binary_entropy).sum(),
+            cells: (0..H)
+                .flat_map(|y| (0..W).map(move |x| (x, y)))
+                .filter_map(|(x, y)| self.cell(x, y))
+                .collect(),
+            decoherence: Default::default(),
+            frozen: Vec::new(),
+            theme: Circuit::default().theme_hint(f64::from(self.mine_count) / (W * H) as f64),
+            score: 0,
+            combo: Default::default(),
+            isotopes: Vec::new(),
+            inventory: Default::default(),
+            integrity: Vec::new(),
+            cosmetics: cosmetics::field(self.seed, width, height),
+        }
+    }
+
+    /// Cells resolved since the last call, in row order rather than the
+    /// order they were played, then [`GameEvent::Won`] once the board is
+    /// cleared. Worked out from the board, so nothing is queued between
+    /// calls.
+    fn drain_events(&mut self) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+        for index in 0..W * H {
+            let (x, y) = Self::coords_of(index);
+            if Self::at(&self.reported, index) != Some(&false) {
+                continue;
+            }
+            let (x32, y32) = (x as u32, y as u32);
+            let event = match self.state(index) {
+                None | Some(CellState::Superposition { .. }) => continue,
+                Some(&CellState::Revealed { adjacent_mines }) => GameEvent::Revealed {
+                    x: x32,
+                    y: y32,
+                    adjacent_mines,
+                },
+                Some(CellState::Contained) => GameEvent::Contained { x: x32, y: y32 },
+                Some(CellState::Detonated) => GameEvent::Detonated { x: x32, y: y32 },
+            };
+            if let Some(reported) = Self::at_mut(&mut self.reported, index) {
+                *reported = true;
+            }
+            events.push(event);
+        }
+        if self.won && !self.won_reported {
+            self.won_reported = true;
+            events.push(GameEvent::Won);
+        }
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GridConfig;
+    use crate::grid::QuantumGrid;
+    use crate::replay::Replay;
+    use crate::rng::SplitMix64;
+    use crate::selfplay::Strategy;
+    use crate::solver::{self, SolveBudget};
+
+    /// Clear any board through [`GridOps`] alone, given where its mines are.
+    fn clear(grid: &mut impl GridOps, is_mine: impl Fn(u32, u32) -> bool) {
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                let action = if is_mine(x, y) {
+                    Action::Contain { x, y }
+                } else {
+                    Action::Reveal { x, y }
+                };
+                grid.apply(action);
+            }
+        }
+    }
+
+    #[test]
+    fn a_fixed_grid_plays_through_the_shared_interface() {
+        let mut fixed = FixedGrid::<9, 9>::new(10, 42).unwrap();
+        let first = fixed.apply(Action::Reveal { x: 4, y: 4 });
+        assert!(matches!(
+            first,
+            ActionResult::Outcome {
+                outcome: RevealOutcome::Revealed {
+                    cell: QuantumCell {
+                        state: CellState::Revealed { adjacent_mines: 0 },
+                        ..
+                    }
+                }
+            }
+        ));
+        assert!(matches!(
+            fixed.cell_at(0, 0).unwrap().state,
+            CellState::Revealed { .. } | CellState::Superposition { .. }
+        ));
+        assert!(fixed.apply(Action::Hadamard { x: 0, y: 0 }).is_rejection());
+        assert_eq!(fixed.reveal_cell(9, 0), RevealOutcome::OutOfBounds);
+
+        let mines = fixed.mines;
+        clear(&mut fixed, |x, y| mines[y as usize][x as usize]);
+        assert!(fixed.is_won() && !fixed.is_game_over());
+        assert_eq!(fixed.mines_remaining(), 0);
+        assert_eq!(fixed.containment_charges(), 0);
+
+        // The same driver clears a full engine board.
+        let mut grid = QuantumGrid::from_config(GridConfig::new(9, 9, 10, 42, "observer"));
+        GridOps::reveal_cell(&mut grid, 4, 4);
+        let mines = grid.mine_map.clone();
+        clear(&mut grid, |x, y| mines[(y * 9 + x) as usize]);
+        assert!(grid.is_won());
+    }
+
+    #[test]
+    fn bots_solver_and_replays_drive_a_fixed_grid() {
+        let config = GridConfig::new(9, 9, 10, 7, "observer");
+        let mut fixed = FixedGrid::<9, 9>::new(10, 7).unwrap();
+        let mut replay = Replay::new(config);
+        let mut rng = SplitMix64::new(7);
+        let mut events = Vec::new();
+        while !fixed.is_game_over() && !fixed.is_won() {
+            let Some(action) = Strategy::LowestHint.choose(&fixed, &mut rng) else {
+                break;
+            };
+            assert_eq!(fixed.validate_action(&action), Ok(()));
+            fixed.apply(action);
+            replay.record(0, action);
+            events.extend(fixed.drain_events());
+            let solved = solver::probabilities(&fixed, SolveBudget::default());
+            assert_eq!(solved.cells.len(), 81);
+        }
+        assert!(fixed.is_game_over() || fixed.is_won());
+        assert!(fixed.drain_events().is_empty());
+        let resolved = fixed
+            .states()
+            .iter()
+            .filter(|state| !matches!(state, CellState::Superposition { .. }))
+            .count();
+        let won = usize::from(fixed.is_won());
+        assert_eq!(events.len(), resolved + won);
+        assert_eq!(
+            fixed.validate_action(&Action::Hadamard { x: 0, y: 0 }),
+            Err(IllegalAction::Unsupported)
+        );
+
+        let mut again = FixedGrid::<9, 9>::new(10, 7).unwrap();
+        replay.play_on(&mut again).unwrap();
+        assert_eq!(again.snapshot(), fixed.snapshot());
+    }
+
+    #[test]
+    fn impossible_fixed_grids_are_refused() {
+        assert_eq!(
+            FixedGrid::<0, 4>::new(0, 1).err(),
+            Some(ConfigError::InvalidDimensions {
+                width: 0,
+                height: 4
+            })
+        );
+        assert_eq!(FixedGrid::<4, 4>::max_mines(), 7);
+        assert_eq!(
+            FixedGrid::<4, 4>::new(8, 1).err(),
+            Some(ConfigError::TooManyMines {
+                requested: 8,
+                max: 7
+            })
+        );
+
+        // A full board still places every mine on the first move.
+        let mut full = FixedGrid::<4, 4>::new(7, 1).unwrap();
+        full.reveal_cell(1, 1);
+        assert_eq!(full.mines.iter().flatten().filter(|&&mine| mine).count(), 7);
+        assert!(full.contain_cell(3, 3) == RevealOutcome::ContainmentSuccess { x: 3, y: 3 });
+    }
+}
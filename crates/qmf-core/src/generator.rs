@@ -0,0 +1,721 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(doc)]
+use crate::config::Density;
+use crate::config::{Generator, GridConfig, Symmetry};
+use crate::grid::QuantumGrid;
+use crate::prelude::*;
+use crate::progress::{Phase, ProgressSink};
+use crate::rng::SplitMix64;
+
+/// The board a [`BoardGenerator`] has to fill.
+#[derive(Debug, Clone, Copy)]
+pub struct Placement<'a> {
+    pub width: u32,
+    pub height: u32,
+    pub mine_count: u32,
+    /// Cells that must stay mine-free (the first-click safe zone), row-major.
+    pub excluded: &'a [bool],
+    /// Relative per-cell mine likelihood ([`Density::weights`]); `None` is
+    /// uniform.
+    pub weights: Option<&'a [f64]>,
+}
+
+impl Placement<'_> {
+    pub fn total_cells(&self) -> usize {
+        (self.width * self.height) as usize
+    }
+
+    /// Cells off the board are excluded.
+    pub fn is_excluded(&self, index: usize) -> bool {
+        self.excluded.get(index).copied().unwrap_or(true)
+    }
+
+    /// Cells off the board weigh nothing.
+    pub fn weight(&self, index: usize) -> f64 {
+        self.weights.map_or(1.0, |weights| {
+            weights.get(index).copied().unwrap_or_default()
+        })
+    }
+
+    /// Order `candidates` by a weighted random shuffle (Efraimidis–Spirakis):
+    /// heavier cells tend to come first, zero-weight cells always last.
+    /// With uniform weights this is a plain Fisher-Yates shuffle.
+    pub fn weighted_shuffle(&self, candidates: &mut [usize], rng: &mut SplitMix64) {
+        if self.weights.is_none() {
+            let n = candidates.len();
+            for i in 0..n {
+                let j = i + rng.next_usize(n - i);
+                candidates.swap(i, j);
+            }
+            return;
+        }
+        let mut keyed: Vec<(f64, usize)> = candidates
+            .iter()
+            .map(|&i| (rng.next_f64().powf(1.0 / self.weight(i)), i))
+            .collect();
+        keyed.sort_by(|a, b| b.0.total_cmp(&a.0));
+        for (slot, (_, i)) in candidates.iter_mut().zip(keyed) {
+            *slot = i;
+        }
+    }
+
+    /// Pick one of `candidates` with probability proportional to its weight
+    /// (uniformly if they all weigh nothing); `None` if there are none.
+    pub fn weighted_pick(&self, candidates: &[usize], rng: &mut SplitMix64) -> Option<usize> {
+        let total: f64 = candidates.iter().map(|&i| self.weight(i)).sum();
+        if self.weights.is_none() || total <= 0.0 {
+            return rng.pick(candidates).copied();
+        }
+        let mut target = rng.next_f64() * total;
+        for &i in candidates {
+            target -= self.weight(i);
+            if target < 0.0 {
+                return Some(i);
+            }
+        }
+        candidates.last().copied()
+    }
+
+    /// Row-major indices of the in-bounds neighbours of `index`.
+    pub fn neighbors(&self, index: usize) -> impl Iterator<Item = usize> {
+        neighbors(self.width, self.height, index)
+    }
+}
+
+/// Set `map[index]`, ignoring an index off the map.
+fn set(map: &mut [bool], index: usize, value: bool) {
+    if let Some(slot) = map.get_mut(index) {
+        *slot = value;
+    }
+}
+
+/// Row-major index of `(x, y)` on a `width × height` board; `None` off it.
+pub(crate) fn cell_index(width: u32, height: u32, x: u32, y: u32) -> Option<usize> {
+    (x < width && y < height).then(|| y as usize * width as usize + x as usize)
+}
+
+pub(crate) fn neighbors(width: u32, height: u32, index: usize) -> impl Iterator<Item = usize> {
+    // With no columns, `y` lands off the board and nothing is yielded.
+    let index = index as u32;
+    let (x, y) = index
+        .checked_rem(width)
+        .zip(index.checked_div(width))
+        .unwrap_or((0, height));
+    (-1_i32..=1)
+        .flat_map(|dy| (-1_i32..=1).map(move |dx| (dx, dy)))
+        .filter(|&(dx, dy)| dx != 0 || dy != 0)
+        .filter_map(move |(dx, dy)| {
+            cell_index(
+                width,
+                height,
+                x.checked_add_signed(dx)?,
+                y.checked_add_signed(dy)?,
+            )
+        })
+}
+
+/// The cells within `radius` of `(x, y)` (a square, centre included)
+/// clipped to a `width × height` board, row-major, as `(x, y, index)`.
+/// Empty when `(x, y)` is off the board.
+pub(crate) fn square(
+    width: u32,
+    height: u32,
+    x: u32,
+    y: u32,
+    radius: u32,
+) -> impl Iterator<Item = (u32, u32, usize)> {
+    let span = |centre: u32, size: u32| {
+        let end = if centre < size {
+            centre.saturating_add(radius).saturating_add(1).min(size)
+        } else {
+            0
+        };
+        centre.saturating_sub(radius)..end
+    };
+    let columns = span(x, width);
+    span(y, height).flat_map(move |ny| {
+        columns
+            .clone()
+            .filter_map(move |nx| Some((nx, ny, cell_index(width, height, nx, ny)?)))
+    })
+}
+
+/// A mine-placement strategy.
+///
+/// Implementations return a row-major mine map holding exactly
+/// `mine_count` mines (or every eligible cell, if there are fewer), never on
+/// an excluded cell, and must draw all randomness from `rng` so boards stay
+/// reproducible from their seed.
+pub trait BoardGenerator {
+    fn generate(&self, placement: &Placement, rng: &mut SplitMix64) -> Vec<bool>;
+}
+
+impl BoardGenerator for Generator {
+    fn generate(&self, placement: &Placement, rng: &mut SplitMix64) -> Vec<bool> {
+        match *self {
+            Self::Uniform => UniformGenerator.generate(placement, rng),
+            Self::Clustered { veins } => ClusteredGenerator { veins }.generate(placement, rng),
+            Self::Symmetric { symmetry } => {
+                SymmetricGenerator { symmetry }.generate(placement, rng)
+            }
+        }
+    }
+}
+
+/// Every eligible cell is equally likely, scaled by the density weights
+/// (partial Fisher-Yates shuffle when uniform).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct UniformGenerator;
+
+impl BoardGenerator for UniformGenerator {
+    fn generate(&self, placement: &Placement, rng: &mut SplitMix64) -> Vec<bool> {
+        let total = placement.total_cells();
+        let mut candidates: Vec<usize> =
+            (0..total).filter(|&i| !placement.is_excluded(i)).collect();
+        let to_place = (placement.mine_count as usize).min(candidates.len());
+
+        if placement.weights.is_some() {
+            placement.weighted_shuffle(&mut candidates, rng);
+        } else {
+            // Partial shuffle: only the first `to_place` slots matter.
+            let n = candidates.len();
+            for i in 0..to_place {
+                let j = i + rng.next_usize(n - i);
+                candidates.swap(i, j);
+            }
+        }
+        let mut mine_map = vec![false; total];
+        for &index in candidates.iter().take(to_place) {
+            set(&mut mine_map, index, true);
+        }
+        mine_map
+    }
+}
+
+/// Mines grow in branching veins from a few random starting cells, leaving
+/// dense seams and wide open fields instead of an even scatter.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ClusteredGenerator {
+    /// Number of veins; `0` picks one vein per eight mines.
+    pub veins: u32,
+}
+
+impl BoardGenerator for ClusteredGenerator {
+    fn generate(&self, placement: &Placement, rng: &mut SplitMix64) -> Vec<bool> {
+        let total = placement.total_cells();
+        let mut mine_map = vec![false; total];
+        let eligible = placement.excluded.iter().filter(|&&e| !e).count();
+        let target = (placement.mine_count as usize).min(eligible);
+        let veins = match self.veins {
+            0 => (target / 8).max(1),
+            veins => veins as usize,
+        };
+        let vein_len = target.div_ceil(veins.max(1));
+        let free = |map: &[bool], i: usize| map.get(i) == Some(&false) && !placement.is_excluded(i);
+
+        let mut placed = 0;
+        while placed < target {
+            // Start a vein on a random free cell.
+            let mut starts: Vec<usize> = (0..total)
+                .filter(|&i| free(&mine_map, i) && placement.weight(i) > 0.0)
+                .collect();
+            if starts.is_empty() {
+                starts = (0..total).filter(|&i| free(&mine_map, i)).collect();
+            }
+            let Some(start) = placement.weighted_pick(&starts, rng) else {
+                break;
+            };
+            set(&mut mine_map, start, true);
+            placed += 1;
+
+            // Grow it from random cells already in the vein, so it branches.
+            let mut vein = vec![start];
+            while vein.len() < vein_len && placed < target {
+                let frontier: Vec<usize> = vein
+                    .iter()
+                    .flat_map(|&i| placement.neighbors(i))
+                    .filter(|&i| free(&mine_map, i) && placement.weight(i) > 0.0)
+                    .collect();
+                if frontier.is_empty() {
+                    break;
+                }
+                let Some(next) = placement.weighted_pick(&frontier, rng) else {
+                    break;
+                };
+                set(&mut mine_map, next, true);
+                vein.push(next);
+                placed += 1;
+            }
+        }
+        mine_map
+    }
+}
+
+/// Mines are placed in mirrored pairs so the layout is invariant under
+/// `symmetry`; self-mirrored cells take single mines to make up odd counts.
+///
+/// The safe zone is excluded together with its mirror image. If the zone
+/// swallows every self-mirrored cell and the count is odd, the last mine
+/// goes on a random free cell — the count is kept, the symmetry is not.
+#[derive(Debug, Clone, Copy)]
+pub struct SymmetricGenerator {
+    pub symmetry: Symmetry,
+}
+
+impl BoardGenerator for SymmetricGenerator {
+    fn generate(&self, placement: &Placement, rng: &mut SplitMix64) -> Vec<bool> {
+        let total = placement.total_cells();
+        let orbit = |i: usize| {
+            let image = self.symmetry.image(placement.width, placement.height, i)?;
+            Some((i, image))
+        };
+        let blocked = |(a, b): (usize, usize)| placement.is_excluded(a) || placement.is_excluded(b);
+
+        // Each orbit is a cell and its image (the same cell when fixed).
+        let mut orbits: Vec<(usize, usize)> = (0..total)
+            .filter_map(orbit)
+            .filter(|&(a, b)| a <= b && !blocked((a, b)))
+            .collect();
+        let capacity: usize = orbits
+            .iter()
+            .map(|&(a, b)| if a == b { 1 } else { 2 })
+            .sum();
+        let mut remaining = (placement.mine_count as usize).min(capacity);
+
+        let mut order: Vec<usize> = orbits.iter().map(|&(a, _)| a).collect();
+        placement.weighted_shuffle(&mut order, rng);
+        orbits = order.into_iter().filter_map(orbit).collect();
+        let mut mine_map = vec![false; total];
+        let mut leftover_pair = None;
+        let mut last_fixed = None;
+        for &(a, b) in &orbits {
+            let size = if a == b { 1 } else { 2 };
+            if size <= remaining {
+                set(&mut mine_map, a, true);
+                set(&mut mine_map, b, true);
+                remaining -= size;
+                if size == 1 {
+                    last_fixed = Some(a);
+                }
+            } else if size == 2 {
+                leftover_pair.get_or_insert((a, b));
+            }
+        }
+
+        if remaining == 1 {
+            // Every fixed cell is taken: trade one for a spare pair, or give
+            // up on symmetry for the last mine.
+            if let (Some(fixed), Some((a, b))) = (last_fixed, leftover_pair) {
+                set(&mut mine_map, fixed, false);
+                set(&mut mine_map, a, true);
+                set(&mut mine_map, b, true);
+            } else {
+                let free: Vec<usize> = (0..total)
+                    .filter(|&i| mine_map.get(i) == Some(&false) && !placement.is_excluded(i))
+                    .collect();
+                if let Some(index) = placement.weighted_pick(&free, rng) {
+                    set(&mut mine_map, index, true);
+                }
+            }
+        }
+        mine_map
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Seed search
+// ---------------------------------------------------------------------------
+
+/// Bechtel's Board Benchmark Value: the minimum number of clicks needed to
+/// clear a placed board (one per opening, plus one per safe cell that no
+/// opening uncovers). `0` before mines are placed.
+pub fn three_bv(grid: &QuantumGrid) -> u32 {
+    if !grid.mines_placed {
+        return 0;
+    }
+    let (width, height) = (grid.width, grid.height);
+    let is_zero =
+        |i: usize| !grid.is_mine(i) && neighbors(width, height, i).all(|n| !grid.is_mine(n));
+
+    let mut covered = vec![false; grid.mine_map.len()];
+    let mut clicks = 0;
+    for start in 0..grid.mine_map.len() {
+        if covered.get(start) != Some(&false) || !is_zero(start) {
+            continue;
+        }
+        // Flood one opening: zeros spread, numbers on its edge are uncovered.
+        clicks += 1;
+        set(&mut covered, start, true);
+        let mut stack = vec![start];
+        while let Some(i) = stack.pop() {
+            for n in neighbors(width, height, i) {
+                if covered.get(n) == Some(&false) && !grid.is_mine(n) {
+                    set(&mut covered, n, true);
+                    if is_zero(n) {
+                        stack.push(n);
+                    }
+                }
+            }
+        }
+    }
+    let stragglers = grid
+        .mine_map
+        .iter()
+        .zip(&covered)
+        .filter(|&(&mine, &covered)| !mine && !covered)
+        .count() as u32;
+    clicks + stragglers
+}
+
+/// Scan up to `budget` seeds, starting at `config.seed`, for a board that
+/// satisfies `predicate`, and return the first match.
+///
+/// Candidate boards are generated as if the first click lands on the centre
+/// cell (see [`QuantumGrid::pregenerate`]), so publish that click alongside
+/// the seed. With the `rayon` feature the scan runs in parallel and still
+/// returns the same (lowest) seed.
+pub fn find_seed<F>(config: &GridConfig, predicate: F, budget: u32) -> Option<u64>
+where
+    F: Fn(&QuantumGrid) -> bool + Sync,
+{
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+        (0..budget)
+            .into_par_iter()
+            .find_map_first(|offset| candidate(config, offset, &predicate))
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        match SeedSearch::new(config.clone(), predicate, budget).step(budget) {
+            SearchStatus::Found { seed } => Some(seed),
+            _ => None,
+        }
+    }
+}
+
+/// The seed at `offset` if its pregenerated board satisfies `predicate`.
+fn candidate<F>(config: &GridConfig, offset: u32, predicate: &F) -> Option<u64>
+where
+    F: Fn(&QuantumGrid) -> bool,
+{
+    let seed = config.seed.wrapping_add(offset as u64);
+    let mut grid = QuantumGrid::from_config(GridConfig {
+        seed,
+        ..config.clone()
+    });
+    grid.pregenerate(grid.width / 2, grid.height / 2).ok()?;
+    predicate(&grid).then_some(seed)
+}
+
+/// Where a [`SeedSearch`] stands after a [`SeedSearch::step`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum SearchStatus {
+    /// More candidates remain; call `step` again.
+    Pending {
+        scanned: u32,
+        budget: u32,
+    },
+    Found {
+        seed: u64,
+    },
+    /// The whole budget was scanned without a match.
+    Exhausted,
+}
+
+/// [`find_seed`] as a resumable, sequential scan, for callers that must
+/// not block (the wasm layer yields to the event loop between steps).
+/// Cancelling is just dropping it.
+#[derive(Debug, Clone)]
+pub struct SeedSearch<F> {
+    config: GridConfig,
+    predicate: F,
+    budget: u32,
+    scanned: u32,
+    found: Option<u64>,
+}
+
+impl<F: Fn(&QuantumGrid) -> bool> SeedSearch<F> {
+    pub fn new(config: GridConfig, predicate: F, budget: u32) -> Self {
+        Self {
+            config,
+            predicate,
+            budget,
+            scanned: 0,
+            found: None,
+        }
+    }
+
+    /// Scan up to `candidates` more seeds. Finds the same seed as
+    /// [`find_seed`] however the budget is split into steps.
+    pub fn step(&mut self, candidates: u32) -> SearchStatus {
+        self.step_with(candidates, &mut ())
+    }
+
+    /// [`Self::step`], reporting [`Self::progress`] after every candidate.
+    pub fn step_with(&mut self, candidates: u32, sink: &mut impl ProgressSink) -> SearchStatus {
+        let end = self.scanned.saturating_add(candidates).min(self.budget);
+        while self.found.is_none() && self.scanned < end {
+            self.found = candidate(&self.config, self.scanned, &self.predicate);
+            self.scanned += 1;
+            sink.on_progress(self.progress(), Phase::SeedSearch);
+        }
+        self.status()
+    }
+
+    pub fn status(&self) -> SearchStatus {
+        match self.found {
+            Some(seed) => SearchStatus::Found { seed },
+            None if self.scanned >= self.budget => SearchStatus::Exhausted,
+            None => SearchStatus::Pending {
+                scanned: self.scanned,
+                budget: self.budget,
+            },
+        }
+    }
+
+    /// Fraction of the budget scanned so far; `1.0` once finished.
+    pub fn progress(&self) -> f64 {
+        if self.found.is_some() || self.budget == 0 {
+            1.0
+        } else {
+            self.scanned as f64 / self.budget as f64
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn placement(excluded: &[bool]) -> Placement<'_> {
+        Placement {
+            width: 16,
+            height: 16,
+            mine_count: 40,
+            excluded,
+            weights: None,
+        }
+    }
+
+    /// Mean number of mine neighbours per mine.
+    fn clumping(placement: &Placement, mine_map: &[bool]) -> f64 {
+        let mines: Vec<usize> = (0..mine_map.len()).filter(|&i| mine_map[i]).collect();
+        let touching: usize = mines
+            .iter()
+            .map(|&i| placement.neighbors(i).filter(|&n| mine_map[n]).count())
+            .sum();
+        touching as f64 / mines.len() as f64
+    }
+
+    #[test]
+    fn board_geometry_stays_on_the_board() {
+        let corner: Vec<usize> = square(4, 3, 3, 2, 1).map(|(.., i)| i).collect();
+        assert_eq!(corner, [6, 7, 10, 11]);
+        assert_eq!(square(4, 3, 1, 1, 5).count(), 12);
+        assert_eq!(square(4, 3, 4, 0, 1).count(), 0);
+        assert_eq!(square(4, 3, u32::MAX, u32::MAX, 1).count(), 0);
+        assert_eq!(neighbors(4, 3, 0).collect::<Vec<_>>(), [1, 4, 5]);
+        assert_eq!(neighbors(0, 3, 0).count(), 0);
+        assert_eq!(cell_index(4, 3, 3, 2), Some(11));
+        assert_eq!(cell_index(4, 3, 4, 0), None);
+    }
+
+    #[test]
+    fn generators_place_exactly_and_respect_exclusions() {
+        let mut excluded = vec![false; 256];
+        excluded[..48].iter_mut().for_each(|e| *e = true);
+        let placement = placement(&excluded);
+        for generator in [Generator::Uniform, Generator::Clustered { veins: 0 }] {
+            for seed in 0..20 {
+                let map = generator.generate(&placement, &mut SplitMix64::new(seed));
+                assert_eq!(map.iter().filter(|&&m| m).count(), 40, "{generator:?}");
+                assert!(map[..48].iter().all(|&m| !m), "{generator:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn symmetric_layouts_mirror_themselves() {
+        for symmetry in [Symmetry::Point, Symmetry::LeftRight, Symmetry::TopBottom] {
+            for (width, mines) in [(16, 40), (15, 41)] {
+                let excluded = vec![false; (width * 16) as usize];
+                let placement = Placement {
+                    width,
+                    mine_count: mines,
+                    ..placement(&excluded)
+                };
+                if mines % 2 == 1 && symmetry.fixed_cells(width, 16) == 0 {
+                    continue; // rejected by GridConfig::validate
+                }
+                let generator = SymmetricGenerator { symmetry };
+                let map = generator.generate(&placement, &mut SplitMix64::new(9));
+                assert_eq!(map.iter().filter(|&&m| m).count(), mines as usize);
+                for i in 0..map.len() {
+                    let image = symmetry.image(width, 16, i).unwrap();
+                    assert_eq!(map[i], map[image], "{symmetry:?}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn symmetric_exclusion_is_mirrored() {
+        let mut excluded = vec![false; 256];
+        excluded[0] = true;
+        excluded[17] = true;
+        let symmetry = Symmetry::Point;
+        for seed in 0..20 {
+            let map = SymmetricGenerator { symmetry }
+                .generate(&placement(&excluded), &mut SplitMix64::new(seed));
+            assert_eq!(map.iter().filter(|&&m| m).count(), 40);
+            for i in [0, 17] {
+                assert!(!map[i] && !map[symmetry.image(16, 16, i).unwrap()]);
+            }
+        }
+    }
+
+    #[test]
+    fn odd_count_trades_a_fixed_cell_for_a_pair() {
+        // 3×1 board, left-right mirror: one pair (0, 2) and fixed cell 1.
+        let excluded = vec![false; 3];
+        let placement = Placement {
+            width: 3,
+            height: 1,
+            mine_count: 2,
+            excluded: &excluded,
+            weights: None,
+        };
+        for seed in 0..10 {
+            let map = SymmetricGenerator {
+                symmetry: Symmetry::LeftRight,
+            }
+            .generate(&placement, &mut SplitMix64::new(seed));
+            assert_eq!(map, vec![true, false, true]);
+        }
+    }
+
+    #[test]
+    fn clustered_mines_clump_together() {
+        let excluded = vec![false; 256];
+        let placement = placement(&excluded);
+        let (mut uniform, mut clustered) = (0.0, 0.0);
+        for seed in 0..20 {
+            let mut rng = SplitMix64::new(seed);
+            uniform += clumping(
+                &placement,
+                &Generator::Uniform.generate(&placement, &mut rng),
+            );
+            let mut rng = SplitMix64::new(seed);
+            let veins = Generator::Clustered { veins: 3 };
+            clustered += clumping(&placement, &veins.generate(&placement, &mut rng));
+        }
+        assert!(clustered > uniform * 2.0, "{clustered} vs {uniform}");
+    }
+
+    #[test]
+    fn density_weights_steer_every_generator() {
+        // All the weight on the left half of the board.
+        let weights: Vec<f64> = (0..256)
+            .map(|i| if i % 16 < 8 { 2.0 } else { 0.0 })
+            .collect();
+        let excluded = vec![false; 256];
+        let placement = Placement {
+            weights: Some(&weights),
+            ..placement(&excluded)
+        };
+        for generator in [Generator::Uniform, Generator::Clustered { veins: 4 }] {
+            let map = generator.generate(&placement, &mut SplitMix64::new(5));
+            assert_eq!(map.iter().filter(|&&m| m).count(), 40);
+            assert!(
+                (0..256).all(|i| !map[i] || i % 16 < 8),
+                "{generator:?} put a mine on a zero-weight cell"
+            );
+        }
+        // Top-bottom mirroring keeps columns, so it can honour the halves too.
+        let symmetric = Generator::Symmetric {
+            symmetry: Symmetry::TopBottom,
+        };
+        let map = symmetric.generate(&placement, &mut SplitMix64::new(5));
+        assert!((0..256).all(|i| !map[i] || i % 16 < 8));
+    }
+
+    #[test]
+    fn three_bv_counts_openings_and_stragglers() {
+        // 5×1 strip, mine in the middle: "0 1 * 1 0" — two openings, each
+        // uncovering its neighbouring number.
+        let mut grid = QuantumGrid::new(5, 1, 1, 0, "observer");
+        grid.mine_map = vec![false, false, true, false, false].into();
+        grid.mines_placed = true;
+        assert_eq!(three_bv(&grid), 2);
+        // "1 * 1 * 1": no openings, every safe cell is its own click.
+        grid.mine_map = vec![false, true, false, true, false].into();
+        assert_eq!(three_bv(&grid), 3);
+    }
+
+    #[test]
+    fn find_seed_returns_the_first_match() {
+        let config = GridConfig::new(9, 9, 10, 100, "observer");
+        let hard = |grid: &QuantumGrid| three_bv(grid) >= 25;
+        let seed = find_seed(&config, hard, 500).expect("a hard board within budget");
+        let mut grid = QuantumGrid::from_config(GridConfig {
+            seed,
+            ..config.clone()
+        });
+        grid.pregenerate(4, 4).unwrap();
+        assert!(three_bv(&grid) >= 25);
+        // No earlier seed matched.
+        assert!(find_seed(&config, hard, (seed - 100) as u32).is_none());
+        assert_eq!(find_seed(&config, |_| false, 20), None);
+    }
+
+    #[test]
+    fn clustered_handles_a_crowded_board() {
+        let mut excluded = vec![false; 256];
+        excluded[100] = true;
+        let placement = Placement {
+            mine_count: 255,
+            ..placement(&excluded)
+        };
+        let map = ClusteredGenerator { veins: 1 }.generate(&placement, &mut SplitMix64::new(3));
+        assert_eq!(map.iter().filter(|&&m| m).count(), 255);
+        assert!(!map[100]);
+    }
+
+    #[test]
+    fn stepped_search_agrees_with_find_seed() {
+        let config = GridConfig::new(9, 9, 10, 100, "observer");
+        let hard = |grid: &QuantumGrid| three_bv(grid) >= 25;
+        let expected = find_seed(&config, hard, 500).unwrap();
+
+        let mut search = SeedSearch::new(config.clone(), hard, 500);
+        let mut steps = 0;
+        let status = loop {
+            steps += 1;
+            match search.step(7) {
+                SearchStatus::Pending { scanned, budget } => {
+                    assert_eq!((scanned, budget), (steps * 7, 500));
+                    assert!(search.progress() < 1.0);
+                }
+                done => break done,
+            }
+        };
+        assert_eq!(status, SearchStatus::Found { seed: expected });
+        assert_eq!(search.step(100), status);
+
+        let mut none = SeedSearch::new(config, |_: &QuantumGrid| false, 10);
+        assert_eq!(
+            none.step(4),
+            SearchStatus::Pending {
+                scanned: 4,
+                budget: 10
+            }
+        );
+        assert_eq!(none.step(100), SearchStatus::Exhausted);
+        assert_eq!(none.progress(), 1.0);
+    }
+}
@@ -1,15 +1,58 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-use crate::circuit::Circuit;
-use crate::entanglement::{Entanglement, LinkType};
-use crate::rng::SplitMix64;
+use crate::action::{Action, ActionResult, AppliedActions, IllegalAction, MoveEvaluation};
+use crate::chunks::ChunkTracker;
+use crate::circuit::{Circuit, Gate, Region, ThemeHint, HINT_NOISE};
+use crate::clock::{ClockError, GameClock, TimingStats};
+use crate::combo::Combo;
+use crate::commitment::sha256;
+use crate::compatibility::RulesetStamp;
+use crate::config::{
+    AssistLevel, AutoReveal, ChargePolicy, ConfigError, FirstClick, GridConfig, LinkPropagation,
+    TimedRules, TurnRules,
+};
+use crate::cosmetics::{self, Cosmetic};
+use crate::daemon::{DaemonMove, NoiseDaemon, LINK_BOOST};
+use crate::decoherence::DecoherenceMeter;
+use crate::entanglement::{
+    CellRef, Correlation, CrossLink, Entanglement, EntanglementError, EntanglementPair, LinkType,
+};
+use crate::events::{EventSink, GameEvent};
+use crate::generator::{cell_index, neighbors, square, BoardGenerator, Placement};
+use crate::integrity::{CellIntegrity, Integrity, BREACH_HINT, FULL_INTEGRITY};
+use crate::interference::InterferenceField;
+use crate::inventory::ToolInventory;
+use crate::isotope::{Isotope, IsotopeView};
+use crate::notes::CellNote;
+use crate::objective::{Objective, Objectives};
+use crate::practice::Practice;
+use crate::prelude::*;
+use crate::reconstruct::{ObservedCell, ReconstructError, Seen};
+use crate::replay::{Replay, ReplayError, ReplayStep};
+use crate::rng::{keyed_f64, SplitMix64};
+use crate::rules::{Rule, RuleHooks, RuleSet};
+use crate::shared::Shared;
+#[cfg(feature = "solver")]
+use crate::solver::{self, Explanation, MineProbabilities, SolveBudget};
+use crate::sound::{SoundCue, NEAR_MISS};
+use crate::splits::{SplitTrigger, Splits};
+use crate::telemetry;
+use crate::timeline::Timeline;
+use crate::trigger::CellTrigger;
+use crate::turns::Turns;
+use crate::zeno::{FrozenCell, ZenoTracker};
+use crate::zone::ZoneMap;
 
 // ---------------------------------------------------------------------------
 // Cell state
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-#[serde(tag = "state", rename_all = "snake_case")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "state", rename_all = "snake_case"))]
 pub enum CellState {
     /// Unobserved — player sees a probability hint.
     Superposition { probability: f64 },
@@ -21,18 +64,35 @@ pub enum CellState {
     Detonated,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+/// Player annotation on a cell. Purely cosmetic — never affects game state.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Mark {
+    #[default]
+    None,
+    /// "I think this is a mine" — unlike containment, costs nothing.
+    Flag,
+    /// "Not sure yet."
+    Question,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct QuantumCell {
     pub x: u32,
     pub y: u32,
     pub state: CellState,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub mark: Mark,
 }
 
 // ---------------------------------------------------------------------------
 // Grid snapshot (serialised to JS)
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct GridSnapshot {
     pub width: u32,
     pub height: u32,
@@ -40,23 +100,58 @@ pub struct GridSnapshot {
     pub won: bool,
     pub seed: u64,
     pub containment_charges: u32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub swap_charges: u32,
+    pub mines_remaining: u32,
     pub entropy: f64,
+    /// See [`QuantumGrid::quantum_entropy`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub quantum_entropy: f64,
     pub cells: Vec<QuantumCell>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub decoherence: DecoherenceMeter,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub frozen: Vec<FrozenCell>,
+    /// How this board's hints map to danger; see [`QuantumGrid::theme_hint`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub theme: ThemeHint,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub score: u64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub combo: Combo,
+    /// Isotopes still in Superposition.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub isotopes: Vec<IsotopeView>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub inventory: ToolInventory,
+    /// Wear on each containment, when containments decay.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub integrity: Vec<CellIntegrity>,
+    /// Each cell's look, row by row; see [`cosmetics`](crate::cosmetics).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub cosmetics: Vec<Cosmetic>,
 }
 
 // ---------------------------------------------------------------------------
 // Reveal / contain outcomes
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-#[serde(tag = "kind", rename_all = "snake_case")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
 pub enum RevealOutcome {
     /// Safe cell uncovered.
     Revealed { cell: QuantumCell },
+    /// A shield absorbed a direct click on a mine, which is now Contained.
+    ShieldAbsorbed { x: u32, y: u32 },
+    /// The mercy rule contained a mine clicked in a forced guess.
+    MercyGranted { x: u32, y: u32 },
     /// Mine detonated by direct click — game over.
     MineDetonated { x: u32, y: u32 },
     /// Correct containment — mine locked down.
     ContainmentSuccess { x: u32, y: u32 },
+    /// A charge went into an isotope that needs `stages_left` more.
+    ContainmentProgress { x: u32, y: u32, stages_left: u32 },
     /// Wrong containment — cell was safe, charge wasted. Cell gets revealed.
     ContainmentFailed { cell: QuantumCell },
     /// Cell was already resolved (not in Superposition).
@@ -67,16 +162,34 @@ pub enum RevealOutcome {
     GameAlreadyOver,
     /// No containment charges remaining.
     NoChargesRemaining,
+    /// A rule in play refused the reveal (see [`RuleHooks::on_before_reveal`]).
+    Refused,
     /// One or more entangled partners were force-collapsed by Bell State
     /// propagation. The `cells` vector contains their resolved states.
     EntangledCollapse { cells: Vec<QuantumCell> },
 }
 
+impl RevealOutcome {
+    /// `true` for outcomes that left the grid untouched because the action
+    /// was not allowed.
+    pub fn is_rejection(&self) -> bool {
+        matches!(
+            self,
+            Self::AlreadyResolved
+                | Self::OutOfBounds
+                | Self::GameAlreadyOver
+                | Self::NoChargesRemaining
+                | Self::Refused
+        )
+    }
+}
+
 // ---------------------------------------------------------------------------
 // QuantumGrid — the core game state
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct QuantumGrid {
     pub width: u32,
     pub height: u32,
@@ -85,64 +198,241 @@ pub struct QuantumGrid {
     pub won: bool,
     pub seed: u64,
     pub containment_charges: u32,
-    pub cells: Vec<QuantumCell>,
+    /// Charges left for [`Self::swap_entanglement`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub swap_charges: u32,
+    pub cells: Shared<Vec<QuantumCell>>,
     pub circuit: Circuit,
-    pub entanglement: Entanglement,
+    pub entanglement: Shared<Entanglement>,
+    pub config: Shared<GridConfig>,
+    /// Engine and rules the game was started under; `None` in saves from
+    /// before stamping (see [`crate::compatibility::check_save`]).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub stamp: Option<RulesetStamp>,
+    /// Server-authoritative timestamps of accepted `*_at` actions.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub clock: GameClock,
+    /// Analysis notes keyed by cell index. Saved with the grid, deliberately
+    /// left out of [`GridSnapshot`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub notes: BTreeMap<usize, CellNote>,
 
     // Private-ish fields (pub for serde, not exposed to wasm)
     pub rng: SplitMix64,
-    pub mine_map: Vec<bool>,
+    pub mine_map: Shared<Vec<bool>>,
     pub mines_placed: bool,
+    /// Events not yet drained by the frontend. Transient — not saved.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub events: Vec<GameEvent>,
+    /// Audio cues not yet drained by the frontend. Transient — not saved.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub cues: Vec<SoundCue>,
+    /// Safe reveals counted toward the next [`ChargePolicy::Regenerating`] charge.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reveals_toward_charge: u32,
+    /// Disturbance caused by weak measurements.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub decoherence: DecoherenceMeter,
+    /// Waves left by recent Hadamards.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub interference: InterferenceField,
+    /// Measurement streaks and the cells they froze.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub zeno: ZenoTracker,
+    /// One-shot effects keyed by cell index, fired when the cell is revealed.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub triggers: BTreeMap<usize, CellTrigger>,
+    /// Multi-stage mines from [`GridConfig::isotopes`], until contained.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub isotopes: BTreeMap<usize, Isotope>,
+    /// Consumables held, e.g. shields.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub inventory: ToolInventory,
+    /// Wear on containments under [`TimedRules::containment_decay`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub integrity: Integrity,
+    /// Outcome modifiers from [`GridConfig::rules`], with their state.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rules: RuleSet,
+    /// Game time let pass by [`Self::advance`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub time_ms: u64,
+    /// Recently applied action IDs and their results (see
+    /// [`Self::apply_with_id`]).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub applied: AppliedActions,
+    /// Points scored, boosted by the combo multiplier.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub score: u64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub combo: Combo,
+    /// Which chunks changed when, for incremental rendering. Transient.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub chunks: ChunkTracker,
+    /// Whether [`GridConfig::mercy`] has been spent this game.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub mercy_used: bool,
+    /// Built by [`Self::reconstruct`]: the mine map only holds the mines
+    /// seen contained, so moves and ground-truth analysis are refused.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub reconstructed: bool,
+    /// Bumped whenever the mine map changes after placement; keys the
+    /// noise of the hints recalculated for it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub recalc_epoch: u64,
+    /// Modifiers from [`GridConfig::zones`], per cell.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub zones: ZoneMap,
+    /// The scenario's [`GridConfig::objectives`] and their progress.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub objectives: Objectives,
+    /// Turn and action points under [`GridConfig::turns`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub turns: Turns,
+    /// The adversary under [`GridConfig::daemon`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub daemon: NoiseDaemon,
+    /// The run's [`GridConfig::splits`] and their times.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub splits: Splits,
+    /// Set on a practice run (see [`Self::from_replay_at`]).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub practice: Option<Practice>,
 }
 
 impl QuantumGrid {
     /// Create a new grid. Mine placement is deferred to first interaction
     /// so the first click is guaranteed safe.
     pub fn new(width: u32, height: u32, mine_count: u32, seed: u64, difficulty: &str) -> Self {
-        let total = (width * height) as usize;
-        let mine_count = mine_count.min(width * height - 9); // must leave room for safe zone
-        let baseline = (mine_count as f64 / total.max(1) as f64).clamp(0.0, 1.0);
+        Self::from_config(GridConfig::new(width, height, mine_count, seed, difficulty))
+    }
+
+    /// Create a grid from a [`GridConfig`], rejecting configurations that
+    /// cannot be honoured exactly (e.g. too many mines for the safe zone).
+    pub fn try_from_config(config: GridConfig) -> Result<Self, ConfigError> {
+        config.validate()?;
+        Ok(Self::from_config(config))
+    }
+
+    /// Practice mode: the game from `replay` as it stood after its first
+    /// `move_index` steps, RNG included, ready to play on. The clock starts
+    /// afresh, and [`Self::practice`] tracks where the new run departs
+    /// from the original. Use [`ReplaySeeker::practice_at`] to restart
+    /// the same replay repeatedly.
+    ///
+    /// [`ReplaySeeker::practice_at`]: crate::replay::ReplaySeeker::practice_at
+    pub fn from_replay_at(replay: &Replay, move_index: usize) -> Result<Self, ReplayError> {
+        let grid = replay.seek(move_index)?;
+        Ok(grid.into_practice(&replay.steps, move_index))
+    }
+
+    /// Turn a grid seeked to `move_index` of `steps` into a practice run.
+    pub(crate) fn into_practice(mut self, steps: &[ReplayStep], move_index: usize) -> Self {
+        self.clock = GameClock::default();
+        self.events.clear();
+        self.cues.clear();
+        self.practice = Some(Practice::new(steps, move_index));
+        self
+    }
+
+    /// Create a grid from a full [`GridConfig`]. Mine placement is deferred to
+    /// the first interaction, which is protected according to `first_click`.
+    ///
+    /// An excessive `mine_count` is clamped; use [`Self::try_from_config`] or
+    /// [`GridConfig::clamped`] to find out when that happens.
+    pub fn from_config(mut config: GridConfig) -> Self {
+        config.fit_dimensions();
+        let GridConfig {
+            width,
+            height,
+            seed,
+            ..
+        } = config;
+        let difficulty = config.difficulty.as_str();
+        let total = config.total_cells() as usize;
+        let mine_count = config.mine_count.min(config.max_mines()); // must leave room for safe zone
+        let baselines = baselines(&config, mine_count);
         let circuit = Circuit::for_difficulty(difficulty);
+        let zones = ZoneMap::new(&config.zones, width, height);
+        let objectives = Objectives::new(&config.objectives);
+        let splits = Splits::new(&config.splits);
+        let turns = Turns::new(config.turns.as_ref());
 
         // Generate per-cell probability hints using RNG + circuit scrambling
         let mut rng = SplitMix64::new(seed);
         let cells = (0..height)
             .flat_map(|y| (0..width).map(move |x| (x, y)))
-            .map(|(x, y)| {
+            .enumerate()
+            .map(|(index, (x, y))| {
                 // Add ±5% noise to baseline, then run through circuit
-                let noise = rng.next_f64() * 0.10 - 0.05;
+                let noise = (rng.next_f64() * 0.10 - 0.05) * zones.noise_scale(index);
+                let baseline = baselines.get(index).copied().unwrap_or_default();
                 let raw = (baseline + noise).clamp(0.0, 1.0);
                 let probability = circuit.apply_probability(raw);
                 QuantumCell {
                     x,
                     y,
                     state: CellState::Superposition { probability },
+                    mark: Mark::None,
                 }
             })
             .collect::<Vec<_>>();
 
         // Difficulty-scaled entanglement
-        let (step, strength, use_bell) = match difficulty {
-            "observer" => (11_usize, 0.2, false),
-            "theorist" => (5, 0.5, true), // BellState pairs at highest difficulty
-            _ => (7, 0.35, false),        // "researcher" default
-        };
+        let (step, strength, use_bell) = entanglement_profile(difficulty);
         let mut entanglement = Entanglement::default();
         let mut pair_index = 0_usize;
         for left in (0..total).step_by(step) {
             let right = left + (step / 2).max(1);
-            if right < total {
-                // At "theorist", every other pair is a hard BellState link
-                let link_type = if use_bell && pair_index % 2 == 0 {
+            if right < total && !zones.isolated(left) && !zones.isolated(right) {
+                // At "theorist", every other pair is a hard BellState link,
+                // and every other Bell link is positively correlated.
+                let link_type = if use_bell && pair_index.is_multiple_of(2) {
                     LinkType::BellState
                 } else {
                     LinkType::Probabilistic
                 };
-                entanglement.add_pair(left, right, strength, link_type);
+                let correlation = if link_type == LinkType::BellState && pair_index % 4 == 2 {
+                    Correlation::Positive
+                } else {
+                    Correlation::Anti
+                };
+                entanglement.add_correlated_pair(left, right, strength, link_type, correlation);
                 pair_index += 1;
             }
         }
 
+        let triggers = config
+            .oracles
+            .placed
+            .iter()
+            .filter_map(|cell| {
+                let index = cell_index(width, height, cell.x, cell.y)?;
+                let circuit = cell
+                    .circuit
+                    .clone()
+                    .unwrap_or_else(|| config.oracles.circuit.clone());
+                Some((index, CellTrigger::Oracle { circuit }))
+            })
+            .collect();
+        let isotopes: BTreeMap<usize, Isotope> = config
+            .isotopes
+            .iter()
+            .filter_map(|cell| {
+                let index = cell_index(width, height, cell.x, cell.y)?;
+                Some((index, Isotope::new(cell.stages)))
+            })
+            .collect();
+        // Every stage past the first takes a charge of its own.
+        let isotope_charges: u32 = isotopes.values().map(|i| i.stages_left - 1).sum();
+        let rules = RuleSet::new(config.rules.clone());
+        let inventory = ToolInventory {
+            shields: config.shields,
+        };
+
         Self {
             width,
             height,
@@ -150,13 +440,46 @@ impl QuantumGrid {
             game_over: false,
             won: false,
             seed,
-            containment_charges: mine_count,
-            cells,
+            containment_charges: config
+                .charge_policy
+                .initial_charges(mine_count)
+                .saturating_add(isotope_charges),
+            swap_charges: config.swap_charges,
+            cells: cells.into(),
             circuit,
-            entanglement,
+            entanglement: entanglement.into(),
+            stamp: Some(RulesetStamp::current(&config)),
+            config: config.into(),
+            clock: GameClock::default(),
+            notes: BTreeMap::new(),
             rng,
-            mine_map: vec![false; total],
+            mine_map: vec![false; total].into(),
             mines_placed: false,
+            events: Vec::new(),
+            cues: Vec::new(),
+            reveals_toward_charge: 0,
+            decoherence: DecoherenceMeter::default(),
+            interference: InterferenceField::default(),
+            zeno: ZenoTracker::default(),
+            triggers,
+            isotopes,
+            inventory,
+            integrity: Integrity::default(),
+            rules,
+            time_ms: 0,
+            applied: AppliedActions::default(),
+            score: 0,
+            combo: Combo::default(),
+            chunks: ChunkTracker::default(),
+            mercy_used: false,
+            reconstructed: false,
+            recalc_epoch: 0,
+            zones,
+            objectives,
+            turns,
+            daemon: NoiseDaemon::new(seed),
+            splits,
+            practice: None,
         }
     }
 
@@ -164,32 +487,165 @@ impl QuantumGrid {
     // Public actions
     // -----------------------------------------------------------------------
 
+    /// Rebuild a board from what a player could see of it, e.g. pasted
+    /// from a screenshot of a half-finished game, for analysis. There is no
+    /// seed to replay: cells not in `partial_cells` stay in Superposition
+    /// at the density of the mines not yet seen, without entanglement or
+    /// special cells, and the result is [`Self::reconstructed`]. Marks and
+    /// notes can still be edited.
+    pub fn reconstruct(
+        partial_cells: &[ObservedCell],
+        config: GridConfig,
+    ) -> Result<Self, ReconstructError> {
+        // The first click is long past, so its safe zone reserves nothing.
+        let config = GridConfig {
+            first_click: FirstClick::NoGuarantee,
+            ..config
+        };
+        let mut grid = Self::try_from_config(config)
+            .map_err(|error| ReconstructError::InvalidConfig { error })?;
+        grid.entanglement = Shared::default();
+        grid.triggers.clear();
+        grid.isotopes.clear();
+        grid.mines_placed = true;
+        grid.reconstructed = true;
+
+        let mut seen = vec![false; grid.cells.len()];
+        for &ObservedCell { x, y, seen: what } in partial_cells {
+            let index = grid
+                .index_of(x, y)
+                .ok_or(ReconstructError::OutOfBounds { x, y })?;
+            let (Some(seen), Some(cell)) = (seen.get_mut(index), grid.cells.get_mut(index)) else {
+                return Err(ReconstructError::OutOfBounds { x, y });
+            };
+            if core::mem::replace(seen, true) {
+                return Err(ReconstructError::Duplicate { x, y });
+            }
+            match what {
+                Seen::Revealed { adjacent_mines } => {
+                    cell.state = CellState::Revealed { adjacent_mines };
+                }
+                Seen::Contained => {
+                    cell.state = CellState::Contained;
+                    grid.set_mine(index, true);
+                }
+                Seen::Flagged => cell.mark = Mark::Flag,
+            }
+        }
+
+        let contained = grid.mine_map.iter().filter(|&&mine| mine).count() as u32;
+        if contained > grid.mine_count {
+            return Err(ReconstructError::TooManyMines {
+                contained,
+                mine_count: grid.mine_count,
+            });
+        }
+        for (index, cell) in grid.cells.iter().enumerate() {
+            let CellState::Revealed { adjacent_mines } = cell.state else {
+                continue;
+            };
+            let (mut certain, mut possible) = (0, 0);
+            for neighbor in neighbors(grid.width, grid.height, index) {
+                match grid.cells.get(neighbor).map(|cell| &cell.state) {
+                    Some(CellState::Contained | CellState::Detonated) => certain += 1,
+                    Some(CellState::Superposition { .. }) => possible += 1,
+                    Some(CellState::Revealed { .. }) | None => {}
+                }
+            }
+            if !(certain..=certain + possible).contains(&adjacent_mines) {
+                return Err(ReconstructError::Contradiction {
+                    x: cell.x,
+                    y: cell.y,
+                });
+            }
+        }
+
+        let unknown = grid
+            .cells
+            .iter()
+            .filter(|cell| matches!(cell.state, CellState::Superposition { .. }))
+            .count();
+        let probability =
+            (f64::from(grid.mine_count - contained) / unknown.max(1) as f64).clamp(0.0, 1.0);
+        for cell in &mut grid.cells {
+            if let CellState::Superposition { .. } = cell.state {
+                cell.state = CellState::Superposition { probability };
+            }
+        }
+        grid.events.clear();
+        Ok(grid)
+    }
+
     /// Left-click: reveal a cell.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self)))]
     pub fn reveal_cell(&mut self, x: u32, y: u32) -> RevealOutcome {
         if self.game_over || self.won {
             return RevealOutcome::GameAlreadyOver;
         }
+        if self.reconstructed {
+            return RevealOutcome::Refused;
+        }
         let Some(index) = self.index_of(x, y) else {
             return RevealOutcome::OutOfBounds;
         };
-        if !matches!(self.cells[index].state, CellState::Superposition { .. }) {
+        let Some(probability) = self.hint_at(index) else {
             return RevealOutcome::AlreadyResolved;
+        };
+        if !self.rules.on_before_reveal(x, y, probability) {
+            return RevealOutcome::Refused;
         }
+        let resolved = self.resolved_cells();
+        self.advance_move(None);
 
         // Deferred mine placement — first interaction is always safe
         if !self.mines_placed {
             self.place_mines(index);
         }
 
-        if self.mine_map[index] {
+        let mercy =
+            self.is_mine(index) && self.config.mercy && !self.mercy_used && self.is_forced_guess();
+        let absorbed = !mercy
+            && self.is_mine(index)
+            && (self.inventory.on_mine_clicked(x, y) || self.rules.on_mine_clicked(x, y));
+        let outcome = if mercy {
+            self.mercy_used = true;
+            self.set_state(index, CellState::Contained);
+            self.publish(GameEvent::MercyGranted { x, y });
+            self.propagate_entanglement(index, true);
+            self.check_win();
+            RevealOutcome::MercyGranted { x, y }
+        } else if absorbed {
+            self.set_state(index, CellState::Contained);
+            self.publish(GameEvent::ShieldAbsorbed { x, y });
+            self.propagate_entanglement(index, true);
+            self.check_win();
+            RevealOutcome::ShieldAbsorbed { x, y }
+        } else if self.is_mine(index) {
             // BOOM
-            self.cells[index].state = CellState::Detonated;
-            self.game_over = true;
+            self.detonate(index);
             self.propagate_entanglement(index, true);
+            self.check_win();
             RevealOutcome::MineDetonated { x, y }
         } else {
-            self.reveal_safe(index)
+            let outcome = self.reveal_safe(index);
+            self.score_safe_reveal(resolved);
+            self.regenerate_charge();
+            self.apply_assists();
+            outcome
+        };
+        self.cue_move(resolved, Some(probability));
+        outcome
+    }
+
+    /// Place mines now, as if the first interaction were at `(x, y)`, so the
+    /// board can be analysed before anyone plays it (e.g. seed search).
+    pub fn pregenerate(&mut self, x: u32, y: u32) -> Result<(), &'static str> {
+        if self.mines_placed {
+            return Err("mines are already placed");
         }
+        let index = self.index_of(x, y).ok_or("coordinates out of bounds")?;
+        self.place_mines(index);
+        Ok(())
     }
 
     /// Right-click / contain: mark a cell as a mine.
@@ -197,13 +653,16 @@ impl QuantumGrid {
         if self.game_over || self.won {
             return RevealOutcome::GameAlreadyOver;
         }
+        if self.reconstructed {
+            return RevealOutcome::Refused;
+        }
         if self.containment_charges == 0 {
             return RevealOutcome::NoChargesRemaining;
         }
         let Some(index) = self.index_of(x, y) else {
             return RevealOutcome::OutOfBounds;
         };
-        if !matches!(self.cells[index].state, CellState::Superposition { .. }) {
+        if !self.is_unresolved(index) {
             return RevealOutcome::AlreadyResolved;
         }
 
@@ -211,34 +670,104 @@ impl QuantumGrid {
             self.place_mines(index);
         }
 
+        let resolved = self.resolved_cells();
+        self.advance_move(None);
         self.containment_charges -= 1;
 
-        if self.mine_map[index] {
+        if let Some(isotope) = self.isotopes.get_mut(&index) {
+            if isotope.stages_left > 1 {
+                isotope.stages_left -= 1;
+                let stages_left = isotope.stages_left;
+                self.chunks.touch(self.width, self.height, x, y);
+                self.publish(GameEvent::IsotopeStabilized { x, y, stages_left });
+                self.cue_move(resolved, None);
+                return RevealOutcome::ContainmentProgress { x, y, stages_left };
+            }
+        }
+
+        self.rules.on_contain(x, y, self.is_mine(index));
+        let outcome = if self.is_mine(index) {
             // Correct containment
-            self.cells[index].state = CellState::Contained;
+            self.set_state(index, CellState::Contained);
             self.propagate_entanglement(index, true);
-            self.won = self.is_win_condition_met();
+            self.score += self
+                .combo
+                .points(self.resolved_cells().saturating_sub(resolved));
+            self.check_win();
             RevealOutcome::ContainmentSuccess { x, y }
         } else {
             // Wrong — cell was safe. Reveal it (charge is lost).
+            if self.combo.reset() {
+                self.publish(GameEvent::ComboBroken);
+            }
             let outcome = self.reveal_safe(index);
             match outcome {
                 RevealOutcome::Revealed { cell } => RevealOutcome::ContainmentFailed { cell },
                 other => other,
             }
-        }
+        };
+        self.apply_assists();
+        self.cue_move(resolved, None);
+        outcome
+    }
+
+    /// [`Self::reveal_cell`] stamped with an authoritative time `t_ms`.
+    /// Timestamps must never go backwards; rejected actions are not recorded.
+    pub fn reveal_cell_at(
+        &mut self,
+        x: u32,
+        y: u32,
+        t_ms: u64,
+    ) -> Result<RevealOutcome, ClockError> {
+        self.clock.check(t_ms)?;
+        let outcome = self.reveal_cell(x, y);
+        self.stamp(&outcome, t_ms);
+        Ok(outcome)
+    }
+
+    /// [`Self::contain_cell`] stamped with an authoritative time `t_ms`.
+    pub fn contain_cell_at(
+        &mut self,
+        x: u32,
+        y: u32,
+        t_ms: u64,
+    ) -> Result<RevealOutcome, ClockError> {
+        self.clock.check(t_ms)?;
+        let outcome = self.contain_cell(x, y);
+        self.stamp(&outcome, t_ms);
+        Ok(outcome)
+    }
+
+    pub fn timing_stats(&self) -> TimingStats {
+        self.clock.stats()
     }
 
     /// **Hadamard Tool** — Apply destructive interference to a Superposition
     /// cell, flipping its probability (high → low, low → high).
     ///
     /// Game Mechanic: lets the player "rewrite" a dangerous cell before clicking.
+    ///
+    /// Hadamards in quick succession interfere: where this cell's
+    /// neighbourhood overlaps a recent one's, probabilities ripple up or down
+    /// (see [`InterferenceField`]).
     pub fn apply_hadamard(&mut self, x: u32, y: u32) -> Result<f64, &'static str> {
         let index = self.index_of(x, y).ok_or("coordinates out of bounds")?;
-        match self.cells[index].state {
-            CellState::Superposition { probability } => {
+        match self.hint_at(index) {
+            Some(probability) => {
                 let new_p = (1.0 - probability).clamp(0.0, 1.0);
-                self.cells[index].state = CellState::Superposition { probability: new_p };
+                self.set_state(index, CellState::Superposition { probability: new_p });
+                for (ripple, shift) in self.interference.ripples(self.width, self.height, x, y) {
+                    if self.zeno.is_frozen(ripple) {
+                        continue;
+                    }
+                    if let Some(probability) = self.hint_at(ripple) {
+                        let probability = (probability + shift).clamp(0.01, 0.99);
+                        self.set_state(ripple, CellState::Superposition { probability });
+                    }
+                }
+                self.advance_move(None);
+                self.interference.emit(x, y);
+                self.check_win();
                 Ok(new_p)
             }
             _ => Err("cell is already resolved"),
@@ -248,364 +777,2036 @@ impl QuantumGrid {
     /// **Observer Effect (Heisenbug)** — Weak measurement. Returns the current
     /// probability but introduces drift (±4% noise) to the stored state,
     /// simulating that "looking changes the system."
+    ///
+    /// The drift is charged to the [`DecoherenceMeter`]; past the configured
+    /// limit, a random unresolved cell collapses (see [`GameEvent::Decohered`]).
     pub fn measure_weak(&mut self, x: u32, y: u32) -> Result<f64, &'static str> {
         let index = self.index_of(x, y).ok_or("coordinates out of bounds")?;
-        match self.cells[index].state {
-            CellState::Superposition { probability } => {
+        match self.hint_at(index) {
+            Some(probability) => {
+                self.advance_move(Some(index));
                 let observed = probability;
                 // Introduce observer drift
                 let drift = self.rng.next_f64() * 0.08 - 0.04;
-                let perturbed = (probability + drift).clamp(0.01, 0.99);
-                self.cells[index].state = CellState::Superposition {
-                    probability: perturbed,
-                };
+                self.drift(index, probability, drift);
+                self.check_win();
                 Ok(observed)
             }
             _ => Err("cell is already resolved"),
         }
     }
 
-    pub fn get_probability_cloud(&self) -> Vec<f64> {
-        self.cells
+    /// Weak measurement with a chosen `precision` in `[0, 1]`: a sharper
+    /// reading costs a larger drift (and so more decoherence), along the
+    /// config's [`MeasurementCurve`](crate::config::MeasurementCurve).
+    /// Returns the possibly noisy reading.
+    pub fn measure_weak_with_precision(
+        &mut self,
+        x: u32,
+        y: u32,
+        precision: f64,
+    ) -> Result<f64, &'static str> {
+        if !(0.0..=1.0).contains(&precision) {
+            return Err("precision must be between 0 and 1");
+        }
+        let index = self.index_of(x, y).ok_or("coordinates out of bounds")?;
+        match self.hint_at(index) {
+            Some(probability) => {
+                self.advance_move(Some(index));
+                let (noise, drift) = self.config.measurement_curve().at(precision);
+                let reading =
+                    (probability + (self.rng.next_f64() * 2.0 - 1.0) * noise).clamp(0.0, 1.0);
+                let drift = (self.rng.next_f64() * 2.0 - 1.0) * drift;
+                self.drift(index, probability, drift);
+                self.check_win();
+                Ok(reading)
+            }
+            _ => Err("cell is already resolved"),
+        }
+    }
+
+    /// **Entanglement Swapping** — spend a swap charge to hand two Bell
+    /// links over: with `(x, y)` Bell-linked to `p` and `(other_x, other_y)`
+    /// to `q`, both links are consumed and `p` and `q` become Bell partners
+    /// (see [`Entanglement::swap`]). All four cells must be unresolved.
+    /// Once mines are placed the new link takes the correlation `p` and `q`
+    /// actually have, as a Bell measurement of the consumed ends would
+    /// report it, so every Bell link on the board holds. Returns the
+    /// coordinates of the newly linked `p` and `q`.
+    pub fn swap_entanglement(
+        &mut self,
+        x: u32,
+        y: u32,
+        other_x: u32,
+        other_y: u32,
+    ) -> Result<[(u32, u32); 2], &'static str> {
+        let a = self.index_of(x, y).ok_or("coordinates out of bounds")?;
+        let b = self
+            .index_of(other_x, other_y)
+            .ok_or("coordinates out of bounds")?;
+        if self.swap_charges == 0 {
+            return Err("no swap charges remaining");
+        }
+        let unresolved = |index: usize| self.is_unresolved(index);
+        if !unresolved(a) || !unresolved(b) {
+            return Err("cell is already resolved");
+        }
+        let partners = [a, b].map(|cell| self.entanglement.bell_partner_of(cell).map(|(_, p)| p));
+        if partners.into_iter().flatten().any(|p| !unresolved(p)) {
+            return Err("Bell partner is already resolved");
+        }
+        let pair = self.entanglement.swap(a, b).map_err(|error| match error {
+            EntanglementError::SameCell => "cannot swap a cell with itself",
+            EntanglementError::NoBellPartner { .. } => "both cells need a Bell partner",
+            EntanglementError::AlreadyLinked => "cells already share a Bell link",
+        })?;
+        if self.mines_placed {
+            let ends = (self.mine_map.get(pair.left), self.mine_map.get(pair.right));
+            if let ((Some(&left), Some(&right)), Some(link)) =
+                (ends, self.entanglement.pairs.last_mut())
+            {
+                link.correlation = if left == right {
+                    Correlation::Positive
+                } else {
+                    Correlation::Anti
+                };
+            }
+        }
+        self.swap_charges -= 1;
+        self.advance_move(None);
+        let (left, right) = (self.coords_of(pair.left), self.coords_of(pair.right));
+        self.publish(GameEvent::EntanglementSwapped {
+            x: left.0,
+            y: left.1,
+            partner_x: right.0,
+            partner_y: right.1,
+        });
+        self.check_win();
+        Ok([left, right])
+    }
+
+    /// **Quantum Teleportation** — move the state of `(x, y)` onto
+    /// `(to_x, to_y)`, spending one of the source's Bell links as the
+    /// shared pair.
+    ///
+    /// The destination takes over the source's probability hint *and* its
+    /// true mine status; whatever it held before is overwritten, so a mine
+    /// under it is destroyed and `mine_count` drops by one. The source is
+    /// left empty and revealed (without cascading): it no longer counts
+    /// toward any neighbour's number, and revealed neighbours of both cells
+    /// are recounted. Needs a generated board, since a teleport moves mines.
+    /// Returns the destination's new probability.
+    pub fn teleport(&mut self, x: u32, y: u32, to_x: u32, to_y: u32) -> Result<f64, &'static str> {
+        let src = self.index_of(x, y).ok_or("coordinates out of bounds")?;
+        let dst = self
+            .index_of(to_x, to_y)
+            .ok_or("coordinates out of bounds")?;
+        let partner = self
+            .teleport_partner(src, dst)
+            .map_err(|error| match error {
+                IllegalAction::SameCell => "cannot teleport a cell onto itself",
+                IllegalAction::AlreadyResolved => "cell is already resolved",
+                IllegalAction::NotGenerated => "board not generated yet",
+                IllegalAction::Frozen => "destination is frozen",
+                _ => "teleporting needs a Bell pair on the source",
+            })?;
+        let Some(probability) = self.hint_at(src) else {
+            return Err("cell is already resolved");
+        };
+        self.entanglement.remove_pair(src, partner);
+
+        let destroyed_mine = self.is_mine(dst);
+        self.set_mine(dst, self.is_mine(src));
+        self.set_mine(src, false);
+        self.mine_count -= u32::from(destroyed_mine);
+        self.set_state(dst, CellState::Superposition { probability });
+        let adjacent_mines = self.adjacent_mines(x, y);
+        self.set_state(src, CellState::Revealed { adjacent_mines });
+        self.ground_truth_changed(&[src, dst]);
+
+        self.advance_move(None);
+        self.publish(GameEvent::Teleported {
+            x,
+            y,
+            to_x,
+            to_y,
+            destroyed_mine,
+        });
+        self.check_win();
+        Ok(probability)
+    }
+
+    /// Whether `src` may teleport onto `dst`, and if so the Bell partner
+    /// the teleport spends.
+    fn teleport_partner(&self, src: usize, dst: usize) -> Result<usize, IllegalAction> {
+        let unresolved = |index: usize| self.is_unresolved(index);
+        if src == dst {
+            return Err(IllegalAction::SameCell);
+        }
+        if !unresolved(src) || !unresolved(dst) {
+            return Err(IllegalAction::AlreadyResolved);
+        }
+        if !self.mines_placed {
+            return Err(IllegalAction::NotGenerated);
+        }
+        if self.zeno.is_frozen(dst) {
+            return Err(IllegalAction::Frozen);
+        }
+        self.entanglement
+            .partners_of(src)
+            .into_iter()
+            .find(|&(pair, partner)| pair.link_type == LinkType::BellState && unresolved(partner))
+            .map(|(_, partner)| partner)
+            .ok_or(IllegalAction::NoBellPair)
+    }
+
+    /// Cells still holding an unfired oracle.
+    pub fn oracles(&self) -> Vec<(u32, u32)> {
+        self.triggers
             .iter()
-            .map(|cell| match cell.state {
-                CellState::Superposition { probability } => probability,
-                CellState::Contained | CellState::Detonated => 1.0,
-                CellState::Revealed { .. } => 0.0,
+            .filter(|&(&index, trigger)| {
+                index < self.cells.len() && matches!(trigger, CellTrigger::Oracle { .. })
             })
+            .map(|(&index, _)| self.coords_of(index))
             .collect()
     }
 
-    /// Fraction of cells still in Superposition: 1.0 = fully uncertain, 0.0 = fully resolved.
-    pub fn entropy(&self) -> f64 {
-        let total = self.cells.len() as f64;
-        if total == 0.0 {
-            return 0.0;
-        }
-        let unresolved = self
-            .cells
+    /// Cells currently held by the Zeno effect.
+    pub fn frozen_cells(&self) -> Vec<FrozenCell> {
+        self.zeno
+            .frozen
             .iter()
-            .filter(|c| matches!(c.state, CellState::Superposition { .. }))
-            .count() as f64;
-        unresolved / total
+            .filter(|&(&index, _)| index < self.cells.len())
+            .map(|(&index, &moves_left)| {
+                let (x, y) = self.coords_of(index);
+                FrozenCell { x, y, moves_left }
+            })
+            .collect()
     }
 
-    pub fn snapshot(&self) -> GridSnapshot {
-        GridSnapshot {
-            width: self.width,
-            height: self.height,
-            game_over: self.game_over,
-            won: self.won,
-            seed: self.seed,
-            containment_charges: self.containment_charges,
-            entropy: self.entropy(),
-            cells: self.cells.clone(),
+    /// Annotate a Superposition cell, returning the previous mark so callers
+    /// can undo. Has no effect on charges, probabilities, or the win check,
+    /// and is allowed after the game ends.
+    pub fn set_mark(&mut self, x: u32, y: u32, mark: Mark) -> Result<Mark, &'static str> {
+        let index = self.index_of(x, y).ok_or("coordinates out of bounds")?;
+        if !self.is_unresolved(index) {
+            return Err("cell is already resolved");
         }
+        let (x, y) = self.coords_of(index);
+        self.chunks.touch(self.width, self.height, x, y);
+        let cell = self
+            .cells
+            .get_mut(index)
+            .ok_or("coordinates out of bounds")?;
+        Ok(core::mem::replace(&mut cell.mark, mark))
     }
 
-    // -----------------------------------------------------------------------
-    // Private helpers
-    // -----------------------------------------------------------------------
-
-    fn index_of(&self, x: u32, y: u32) -> Option<usize> {
-        if x >= self.width || y >= self.height {
-            None
-        } else {
-            Some((y * self.width + x) as usize)
+    /// Attach (or with `None`, clear) an analysis note on any cell, returning
+    /// the note it replaces.
+    pub fn set_note(
+        &mut self,
+        x: u32,
+        y: u32,
+        note: Option<CellNote>,
+    ) -> Result<Option<CellNote>, &'static str> {
+        let index = self.index_of(x, y).ok_or("coordinates out of bounds")?;
+        match note {
+            Some(note) => {
+                note.validate()?;
+                Ok(self.notes.insert(index, note))
+            }
+            None => Ok(self.notes.remove(&index)),
         }
     }
 
-    fn coords_of(&self, index: usize) -> (u32, u32) {
-        let x = index as u32 % self.width;
-        let y = index as u32 / self.width;
-        (x, y)
+    pub fn note(&self, x: u32, y: u32) -> Option<&CellNote> {
+        self.index_of(x, y).and_then(|index| self.notes.get(&index))
     }
 
-    /// Fisher-Yates mine placement, excluding `safe_index` and its 8 neighbors.
-    fn place_mines(&mut self, safe_index: usize) {
-        let total = self.cells.len();
-        let (sx, sy) = self.coords_of(safe_index);
-
-        // Build exclusion set (safe zone = clicked cell + neighbors)
-        let mut excluded = Vec::with_capacity(9);
-        for dy in -1_i32..=1 {
-            for dx in -1_i32..=1 {
-                let nx = sx as i32 + dx;
-                let ny = sy as i32 + dy;
-                if nx >= 0 && nx < self.width as i32 && ny >= 0 && ny < self.height as i32 {
-                    excluded.push((ny as u32 * self.width + nx as u32) as usize);
+    /// **Physics step** — let `dt_ms` of game time pass. The single entry
+    /// point for every time-driven mechanic in [`TimedRules`]: ambient
+    /// decoherence (which can collapse a cell, as measurement does) and
+    /// entanglement decay. Frontends drive time explicitly, and replays
+    /// record these calls as [`Action::Advance`], so timed games reproduce
+    /// exactly. Does nothing once the game has ended.
+    ///
+    /// Effects are computed from whole milliseconds on the running total,
+    /// so many small steps equal one large one.
+    pub fn advance(&mut self, dt_ms: u32) {
+        if self.game_over || self.won || self.reconstructed {
+            return;
+        }
+        let before = self.time_ms;
+        self.time_ms = before.saturating_add(u64::from(dt_ms));
+        let TimedRules {
+            ambient_decoherence,
+            link_half_life_ms,
+            containment_decay,
+        } = self.config.timed;
+
+        if let Some(half_life) = link_half_life_ms.filter(|&h| h > 0) {
+            let half_life = u64::from(half_life);
+            let halvings = self.time_ms / half_life - before / half_life;
+            for _ in 0..halvings.min(64) {
+                for pair in &mut self.entanglement.pairs {
+                    pair.strength *= 0.5;
                 }
             }
         }
 
-        // Collect eligible indices
-        let mut candidates: Vec<usize> = (0..total).filter(|i| !excluded.contains(i)).collect();
-
-        // Shuffle (Fisher-Yates) and pick first mine_count
-        let n = candidates.len();
-        let to_place = (self.mine_count as usize).min(n);
-        for i in 0..to_place {
-            let j = i + self.rng.next_usize(n - i);
-            candidates.swap(i, j);
-        }
-        for &idx in &candidates[..to_place] {
-            self.mine_map[idx] = true;
+        let rate = u64::from(ambient_decoherence);
+        let points = (self.time_ms * rate / 1000).saturating_sub(before * rate / 1000);
+        if points > 0 {
+            self.decoherence
+                .ambient(u32::try_from(points).unwrap_or(u32::MAX));
+            self.collapse_if_decohered();
         }
 
-        self.mines_placed = true;
+        let rate = u64::from(containment_decay);
+        let loss = (self.time_ms * rate / 1000).saturating_sub(before * rate / 1000);
+        if loss > 0 {
+            for index in self
+                .integrity
+                .decay(u32::try_from(loss).unwrap_or(u32::MAX))
+            {
+                self.breach(index);
+            }
+        }
+    }
 
-        // Recalculate probability hints: neighbor-aware hinting
-        self.recalculate_probabilities();
+    /// Take all queued [`GameEvent`]s, oldest first.
+    pub fn drain_events(&mut self) -> Vec<GameEvent> {
+        core::mem::take(&mut self.events)
     }
 
-    /// Recalculate displayed probabilities for all Superposition cells
-    /// based on the actual mine map + circuit scrambling. This gives
-    /// heterogeneous hints without revealing exact positions.
-    fn recalculate_probabilities(&mut self) {
-        let total = self.cells.len();
-        for i in 0..total {
-            if !matches!(self.cells[i].state, CellState::Superposition { .. }) {
-                continue;
+    /// Hand every queued [`GameEvent`] to each of `sinks` in turn, oldest
+    /// first, and clear the queue.
+    pub fn dispatch_events(&mut self, sinks: &mut [&mut dyn EventSink]) {
+        for event in self.drain_events() {
+            for sink in sinks.iter_mut() {
+                sink.on_event(&event);
             }
-            let (x, y) = self.coords_of(i);
-            // Count how many neighbors are mines (ground truth)
-            let neighbor_mines = self.adjacent_mines(x, y);
-            let max_neighbors = self.neighbor_count(x, y);
+        }
+    }
 
-            // Blend: baseline weight + neighbor density
-            let baseline = self.mine_count as f64 / total as f64;
-            let local_density = if max_neighbors > 0 {
-                neighbor_mines as f64 / max_neighbors as f64
-            } else {
-                baseline
-            };
+    /// Take all queued [`SoundCue`]s, oldest first.
+    pub fn drain_cues(&mut self) -> Vec<SoundCue> {
+        core::mem::take(&mut self.cues)
+    }
 
-            // 60% local signal, 40% global baseline, then circuit-scramble
-            let blended = local_density * 0.6 + baseline * 0.4;
-            // Add per-cell noise so identical neighbor counts don't look identical
-            let noise = self.rng.next_f64() * 0.06 - 0.03;
-            let raw = (blended + noise).clamp(0.01, 0.99);
-            let scrambled = self.circuit.apply_probability(raw);
+    /// The cell at `(x, y)`, or `None` off the board. Prefer this to
+    /// indexing `cells` with coordinates from outside the engine.
+    pub fn cell(&self, x: u32, y: u32) -> Option<&QuantumCell> {
+        self.cells.get(self.index_of(x, y)?)
+    }
 
-            self.cells[i].state = CellState::Superposition {
-                probability: scrambled,
-            };
-        }
+    /// Mines not yet accounted for: `mine_count` minus every `Contained`
+    /// cell and every mine a rule such as [`Rule::Lives`] let detonate.
+    pub fn mines_remaining(&self) -> u32 {
+        let resolved = self
+            .cells
+            .iter()
+            .filter(|c| matches!(c.state, CellState::Contained | CellState::Detonated))
+            .count() as u32;
+        self.mine_count.saturating_sub(resolved)
     }
 
-    /// Reveal a cell known to be safe. Computes adjacent count, does flood fill
-    /// if zero, and checks win condition.
-    fn reveal_safe(&mut self, index: usize) -> RevealOutcome {
-        let (x, y) = self.coords_of(index);
-        let adj = self.adjacent_mines(x, y);
-        self.cells[index].state = CellState::Revealed {
-            adjacent_mines: adj,
+    /// Apply any [`Action`]. A [reconstructed](Self::reconstruct) board
+    /// refuses them all.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self)))]
+    pub fn apply(&mut self, action: Action) -> ActionResult {
+        if self.reconstructed {
+            return ActionResult::ToolRejected {
+                reason: "the board was reconstructed and its mines are unknown".to_string(),
+            };
+        }
+        let result = match self.config.turns {
+            Some(rules) => self.apply_turn(action, rules),
+            None => self.apply_now(action),
         };
-        self.propagate_entanglement(index, false);
-
-        if adj == 0 {
-            self.flood_fill(x, y);
+        if let Some(practice) = self.practice.as_mut().filter(|_| !result.is_rejection()) {
+            practice.follow(action);
         }
+        result
+    }
 
-        self.won = self.is_win_condition_met();
-        RevealOutcome::Revealed {
-            cell: self.cells[index].clone(),
+    /// Turn-based [`Self::apply`]: charge accepted actions to the turn's
+    /// budget, and start the next turn once it is spent or given up.
+    fn apply_turn(&mut self, action: Action, rules: TurnRules) -> ActionResult {
+        if action == Action::EndTurn {
+            if self.game_over || self.won {
+                return ActionResult::ToolRejected {
+                    reason: "the game is over".to_string(),
+                };
+            }
+            self.next_turn(&rules);
+            return ActionResult::NextTurn {
+                turn: self.turns.turn,
+                action_points: self.turns.action_points,
+            };
         }
+        let cost = rules.costs.of(&action);
+        if cost > self.turns.action_points {
+            return ActionResult::ToolRejected {
+                reason: format!(
+                    "costs {cost} action points, {} left this turn",
+                    self.turns.action_points
+                ),
+            };
+        }
+        let result = self.apply_now(action);
+        if !result.is_rejection()
+            && self.turns.spend(cost)
+            && cost > 0
+            && self.turns.action_points == 0
+            && !self.game_over
+            && !self.won
+        {
+            self.next_turn(&rules);
+        }
+        result
     }
 
-    /// Stack-based flood fill for zero-adjacent safe cells.
-    fn flood_fill(&mut self, start_x: u32, start_y: u32) {
-        let mut stack = vec![(start_x, start_y)];
-
-        while let Some((cx, cy)) = stack.pop() {
-            for ny in cy.saturating_sub(1)..=(cy + 1).min(self.height - 1) {
-                for nx in cx.saturating_sub(1)..=(cx + 1).min(self.width - 1) {
-                    if nx == cx && ny == cy {
-                        continue;
-                    }
-                    let Some(idx) = self.index_of(nx, ny) else {
-                        continue;
-                    };
-                    // Only process cells still in superposition and not mines
-                    if !matches!(self.cells[idx].state, CellState::Superposition { .. }) {
-                        continue;
-                    }
-                    if self.mine_map[idx] {
-                        continue;
-                    }
-
-                    let adj = self.adjacent_mines(nx, ny);
-                    self.cells[idx].state = CellState::Revealed {
-                        adjacent_mines: adj,
-                    };
+    /// Start the next turn: a full budget, and the turn's decoherence.
+    fn next_turn(&mut self, rules: &TurnRules) {
+        self.turns.next(rules);
+        self.publish(GameEvent::TurnStarted {
+            turn: self.turns.turn,
+            action_points: self.turns.action_points,
+        });
+        if rules.decoherence_per_turn > 0 {
+            self.decoherence.ambient(rules.decoherence_per_turn);
+            self.collapse_if_decohered();
+        }
+        self.daemon_turn();
+    }
 
-                    if adj == 0 {
-                        stack.push((nx, ny));
-                    }
-                }
-            }
+    /// Let the [`NoiseDaemon`] spend its budget for the turn. It waits for
+    /// the mines, and stops with the game.
+    fn daemon_turn(&mut self) {
+        let Some(rules) = self.config.daemon else {
+            return;
+        };
+        let mut budget = rules.budget;
+        let mut rng = self.daemon.rng.clone();
+        while budget > 0 && self.mines_placed && !self.game_over && !self.won {
+            let Some((act, cost)) = NoiseDaemon::choose(self, &rules, budget, &mut rng) else {
+                break;
+            };
+            budget = budget.saturating_sub(cost.max(1));
+            self.meddle(act);
         }
+        self.daemon.rng = rng;
     }
 
-    /// Count adjacent mines using the ground-truth mine_map.
-    fn adjacent_mines(&self, x: u32, y: u32) -> u8 {
-        let mut count = 0u8;
-        for ny in y.saturating_sub(1)..=(y + 1).min(self.height.saturating_sub(1)) {
-            for nx in x.saturating_sub(1)..=(x + 1).min(self.width.saturating_sub(1)) {
-                if nx == x && ny == y {
-                    continue;
+    fn meddle(&mut self, act: DaemonMove) {
+        telemetry::debug!(?act, "daemon move");
+        match act {
+            DaemonMove::Scramble { x, y, shift } => {
+                let Some(index) = self.index_of(x, y) else {
+                    return;
+                };
+                if let Some(probability) = self.hint_at(index) {
+                    let probability = (probability + shift).clamp(0.01, 0.99);
+                    self.set_state(index, CellState::Superposition { probability });
                 }
-                if let Some(idx) = self.index_of(nx, ny) {
-                    if self.mine_map[idx] {
-                        count = count.saturating_add(1);
+                self.publish(GameEvent::DaemonScrambled { x, y });
+            }
+            DaemonMove::Strengthen {
+                x,
+                y,
+                partner_x,
+                partner_y,
+            } => {
+                let (Some(left), Some(right)) =
+                    (self.index_of(x, y), self.index_of(partner_x, partner_y))
+                else {
+                    return;
+                };
+                for pair in &mut self.entanglement.pairs {
+                    if (pair.left, pair.right) == (left, right) {
+                        pair.strength = (pair.strength + LINK_BOOST).min(1.0);
                     }
                 }
+                self.publish(GameEvent::DaemonStrengthened {
+                    x,
+                    y,
+                    partner_x,
+                    partner_y,
+                });
+            }
+            DaemonMove::ShiftMine { x, y, to_x, to_y } => {
+                let (Some(from), Some(to)) = (self.index_of(x, y), self.index_of(to_x, to_y))
+                else {
+                    return;
+                };
+                self.set_mine(from, false);
+                self.set_mine(to, true);
+                self.ground_truth_changed(&[from, to]);
+                self.publish(GameEvent::DaemonShiftedMine);
             }
         }
-        count
     }
 
-    /// Number of valid neighbor cells for (x, y).
-    fn neighbor_count(&self, x: u32, y: u32) -> u8 {
-        let mut count = 0u8;
-        for ny in y.saturating_sub(1)..=(y + 1).min(self.height.saturating_sub(1)) {
-            for nx in x.saturating_sub(1)..=(x + 1).min(self.width.saturating_sub(1)) {
-                if nx == x && ny == y {
-                    continue;
+    fn apply_now(&mut self, action: Action) -> ActionResult {
+        let tool = |result: Result<f64, &'static str>| match result {
+            Ok(value) => ActionResult::Probability { value },
+            Err(reason) => ActionResult::ToolRejected {
+                reason: reason.to_string(),
+            },
+        };
+        match action {
+            Action::Reveal { x, y } => ActionResult::Outcome {
+                outcome: self.reveal_cell(x, y),
+            },
+            Action::Contain { x, y } => ActionResult::Outcome {
+                outcome: self.contain_cell(x, y),
+            },
+            Action::Hadamard { x, y } => tool(self.apply_hadamard(x, y)),
+            Action::MeasureWeak { x, y } => tool(self.measure_weak(x, y)),
+            Action::MeasureWeakWithPrecision { x, y, percent } => {
+                tool(self.measure_weak_with_precision(x, y, f64::from(percent) / 100.0))
+            }
+            Action::SwapEntanglement {
+                x,
+                y,
+                other_x,
+                other_y,
+            } => match self.swap_entanglement(x, y, other_x, other_y) {
+                Ok([(x, y), (partner_x, partner_y)]) => ActionResult::Relinked {
+                    x,
+                    y,
+                    partner_x,
+                    partner_y,
+                },
+                Err(reason) => ActionResult::ToolRejected {
+                    reason: reason.to_string(),
+                },
+            },
+            Action::Teleport { x, y, to_x, to_y } => tool(self.teleport(x, y, to_x, to_y)),
+            Action::Advance { dt_ms } => {
+                self.advance(dt_ms);
+                ActionResult::Advanced {
+                    time_ms: self.time_ms,
                 }
-                count += 1;
             }
+            Action::EndTurn => ActionResult::ToolRejected {
+                reason: "not playing in turns".to_string(),
+            },
         }
-        count
     }
 
-    /// Propagate entanglement: after resolving a cell, handle its partners.
-    ///
-    /// - **BellState** links trigger `propagate_collapse` — the partner is
-    ///   force-collapsed (revealed if safe, contained if mine) and the
-    ///   cascade continues recursively through any further Bell partners.
-    /// - **Probabilistic** links just shift the displayed probability.
-    fn propagate_entanglement(&mut self, index: usize, was_mine: bool) {
-        // Collect partner info first to avoid borrow issues.
-        let partners: Vec<(usize, LinkType, f64)> = self
-            .entanglement
+    /// [`Self::apply`], deduplicated by `action_id`: an ID already applied
+    /// returns its original result without touching the grid, so a client
+    /// may safely retry a move. Rejected actions are not remembered.
+    pub fn apply_with_id(&mut self, action: Action, action_id: Option<u64>) -> ActionResult {
+        if let Some(result) = action_id.and_then(|id| self.applied.get(id)) {
+            return result.clone();
+        }
+        let result = self.apply(action);
+        self.remember(action_id, &result);
+        result
+    }
+
+    pub(crate) fn remember(&mut self, action_id: Option<u64>, result: &ActionResult) {
+        if let Some(id) = action_id.filter(|_| !result.is_rejection()) {
+            self.applied.record(id, result.clone());
+        }
+    }
+
+    /// [`Self::apply`] stamped with an authoritative time `t_ms`.
+    pub fn apply_at(&mut self, action: Action, t_ms: u64) -> Result<ActionResult, ClockError> {
+        self.clock.check(t_ms)?;
+        let resolved = self.resolved_cells();
+        if !self.splits.is_empty() && self.splits.start_entropy.is_none() {
+            self.splits.start_entropy = Some(self.quantum_entropy());
+        }
+        let result = self.apply(action);
+        // Time passing is not a player action, so it stays off the clock.
+        if !result.is_rejection() && !matches!(action, Action::Advance { .. }) {
+            self.clock.record(t_ms, self.game_over || self.won);
+            self.check_splits(resolved, t_ms);
+        }
+        Ok(result)
+    }
+
+    /// Fork the current state into a [`Timeline`] for "what if I click
+    /// here?" exploration. The live grid is never touched by the timeline;
+    /// pending events are not carried over.
+    ///
+    /// Cheap to call: the cells, config, entanglement and mine map are
+    /// [`Shared`] with the live grid rather than copied. Only counters and
+    /// flat per-cell tallies such as the decoherence meter are cloned.
+    pub fn branch(&self) -> Timeline {
+        let mut root = self.clone();
+        root.events.clear();
+        root.cues.clear();
+        Timeline::new(root)
+    }
+
+    /// Check whether `action` would be accepted, without applying it.
+    /// Unlike the tool methods themselves, this also refuses tools once the
+    /// game has ended.
+    pub fn validate_action(&self, action: &Action) -> Result<(), IllegalAction> {
+        if self.reconstructed {
+            return Err(IllegalAction::UnknownGroundTruth);
+        }
+        if self.game_over || self.won {
+            return Err(IllegalAction::GameAlreadyOver);
+        }
+        if let Some(rules) = self.config.turns {
+            if rules.costs.of(action) > self.turns.action_points {
+                return Err(IllegalAction::NotEnoughActionPoints);
+            }
+        } else if *action == Action::EndTurn {
+            return Err(IllegalAction::NotTurnBased);
+        }
+        let charges = match action {
+            Action::Contain { .. } => self.containment_charges,
+            Action::SwapEntanglement { .. } => self.swap_charges,
+            _ => 1,
+        };
+        if charges == 0 {
+            return Err(IllegalAction::NoChargesRemaining);
+        }
+        for (x, y) in action.coords().into_iter().chain(action.other_coords()) {
+            let index = self.index_of(x, y).ok_or(IllegalAction::OutOfBounds)?;
+            if !self.is_unresolved(index) {
+                return Err(IllegalAction::AlreadyResolved);
+            }
+        }
+        match *action {
+            Action::MeasureWeakWithPrecision { percent, .. } if percent > 100 => {
+                return Err(IllegalAction::InvalidPrecision);
+            }
+            Action::Teleport { x, y, to_x, to_y } => {
+                let (src, dst) = (self.index_of(x, y), self.index_of(to_x, to_y));
+                let (Some(src), Some(dst)) = (src, dst) else {
+                    return Err(IllegalAction::OutOfBounds);
+                };
+                self.teleport_partner(src, dst)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Work out what `action` would do without touching the grid, by
+    /// playing it on a copy. Sees ground truth, so it needs
+    /// [`AssistLevel::FullAnalysis`].
+    pub fn evaluate_move(&self, action: Action) -> Result<MoveEvaluation, IllegalAction> {
+        if !self.config.assist.allows(AssistLevel::FullAnalysis) {
+            return Err(IllegalAction::AssistDisabled);
+        }
+        self.validate_action(&action)?;
+        let mut sim = self.clone();
+        sim.apply(action);
+        let detonated = sim.game_over && !self.game_over;
+        let detonation_risk = match action {
+            Action::Reveal { .. } if !self.mines_placed => match self.config.first_click {
+                FirstClick::NoGuarantee => {
+                    f64::from(self.mine_count) / self.cells.len().max(1) as f64
+                }
+                FirstClick::GuaranteedSafe | FirstClick::GuaranteedZero => 0.0,
+            },
+            _ => f64::from(u8::from(detonated)),
+        };
+        Ok(MoveEvaluation {
+            detonation_risk,
+            information_gain: self.quantum_entropy() - sim.quantum_entropy(),
+            cascade: sim.resolved_cells().saturating_sub(self.resolved_cells()),
+        })
+    }
+
+    /// Hints as they would read without circuit scrambling or noise: the
+    /// blend of neighbouring mine density and the prior that the displayed
+    /// hints are built from. Resolved cells read as in
+    /// [`Self::get_probability_cloud`]. Needs [`AssistLevel::FullAnalysis`].
+    pub fn unscrambled_probabilities(&self) -> Result<Vec<f64>, IllegalAction> {
+        if !self.config.assist.allows(AssistLevel::FullAnalysis) {
+            return Err(IllegalAction::AssistDisabled);
+        }
+        if self.reconstructed {
+            return Err(IllegalAction::UnknownGroundTruth);
+        }
+        let baselines = baselines(&self.config, self.mine_count);
+        Ok(self
+            .cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| match cell.state {
+                CellState::Superposition { .. } => {
+                    let baseline = baselines.get(i).copied().unwrap_or_default();
+                    if self.mines_placed {
+                        self.local_blend(i, baseline)
+                    } else {
+                        baseline
+                    }
+                }
+                CellState::Contained | CellState::Detonated => 1.0,
+                CellState::Revealed { .. } => 0.0,
+            })
+            .collect())
+    }
+
+    /// Why the unresolved cell at `(x, y)` must be safe or a mine, as a
+    /// chain of deductions from the revealed numbers (see
+    /// [`solver::explain`]); `None` when they do not decide it. Works from
+    /// what the player can see, so it only needs [`AssistLevel::Hints`].
+    #[cfg(feature = "solver")]
+    pub fn explain(&self, x: u32, y: u32) -> Result<Option<Explanation>, IllegalAction> {
+        if !self.config.assist.allows(AssistLevel::Hints) {
+            return Err(IllegalAction::AssistDisabled);
+        }
+        let index = self.index_of(x, y).ok_or(IllegalAction::OutOfBounds)?;
+        Ok(solver::explain(self, index))
+    }
+
+    /// Whether the player is down to a coin flip or worse with nothing left
+    /// to deduce (see [`solver::is_forced_guess`]), e.g. an unwinnable 50/50
+    /// at the end of a game.
+    #[cfg(feature = "solver")]
+    pub fn is_forced_guess(&self) -> bool {
+        solver::is_forced_guess(self)
+    }
+
+    /// Without the solver a forced guess cannot be told from a careless
+    /// one, so the mercy rule never fires.
+    #[cfg(not(feature = "solver"))]
+    fn is_forced_guess(&self) -> bool {
+        false
+    }
+
+    /// Each cell's chance of being a mine given the revealed numbers and
+    /// the mine count, solved within `budget` (see [`solver::probabilities`]).
+    /// Needs [`AssistLevel::Hints`].
+    #[cfg(feature = "solver")]
+    pub fn mine_probabilities(
+        &self,
+        budget: SolveBudget,
+    ) -> Result<MineProbabilities, IllegalAction> {
+        if !self.config.assist.allows(AssistLevel::Hints) {
+            return Err(IllegalAction::AssistDisabled);
+        }
+        Ok(solver::probabilities(self, budget))
+    }
+
+    pub fn get_probability_cloud(&self) -> Vec<f64> {
+        self.cells
+            .iter()
+            .map(|cell| match cell.state {
+                CellState::Superposition { probability } => probability,
+                CellState::Contained | CellState::Detonated => 1.0,
+                CellState::Revealed { .. } => 0.0,
+            })
+            .collect()
+    }
+
+    /// Cells the player has cleared: revealed or contained (detonations excluded).
+    pub fn resolved_cells(&self) -> u32 {
+        self.cells
+            .iter()
+            .filter(|c| matches!(c.state, CellState::Revealed { .. } | CellState::Contained))
+            .count() as u32
+    }
+
+    /// Fraction of cells still in Superposition: 1.0 = fully uncertain, 0.0 = fully resolved.
+    pub fn entropy(&self) -> f64 {
+        let total = self.cells.len() as f64;
+        if total == 0.0 {
+            return 0.0;
+        }
+        let unresolved = self
+            .cells
+            .iter()
+            .filter(|c| matches!(c.state, CellState::Superposition { .. }))
+            .count() as f64;
+        unresolved / total
+    }
+
+    /// Uncertainty left on the board, in bits: the Shannon entropy of every
+    /// Superposition cell's hint, plus one bit per BellState pair (and
+    /// `strength` bits per Probabilistic pair) whose ends are both still
+    /// unresolved. Unlike [`Self::entropy`], a board of 0.95s scores far
+    /// lower than a board of 0.5s.
+    pub fn quantum_entropy(&self) -> f64 {
+        let superposed = |index: usize| match self.cells.get(index).map(|c| &c.state) {
+            Some(CellState::Superposition { probability }) => Some(*probability),
+            _ => None,
+        };
+        let cells: f64 = (0..self.cells.len())
+            .filter_map(superposed)
+            .map(binary_entropy)
+            .sum();
+        let links: f64 = self
+            .entanglement
+            .pairs
+            .iter()
+            .filter(|pair| superposed(pair.left).is_some() && superposed(pair.right).is_some())
+            .map(|pair| match pair.link_type {
+                LinkType::BellState => 1.0,
+                LinkType::Probabilistic => pair.strength,
+            })
+            .sum();
+        cells + links
+    }
+
+    /// Cells in the half-open rectangle `[x0, x1) × [y0, y1)`, row by row,
+    /// clipped to the board. Lets a renderer pull just the visible window of
+    /// a huge board instead of cloning every cell through [`Self::snapshot`].
+    pub fn cells_in_rect(
+        &self,
+        x0: u32,
+        y0: u32,
+        x1: u32,
+        y1: u32,
+    ) -> impl Iterator<Item = &QuantumCell> + '_ {
+        let (x0, x1) = (x0.min(self.width), x1.min(self.width));
+        let (y0, y1) = (y0.min(self.height), y1.min(self.height));
+        let width = self.width as usize;
+        (y0..y1).flat_map(move |y| {
+            let row = y as usize * width;
+            let span = if x0 < x1 {
+                row + x0 as usize..row + x1 as usize
+            } else {
+                row..row
+            };
+            self.cells.get(span).unwrap_or_default().iter()
+        })
+    }
+
+    /// SHA-256 over everything that decides how play continues: cells,
+    /// marks, mine map, charges, flags, RNG state, the decoherence meter
+    /// (per cell and over the game), live interference waves, Zeno streaks
+    /// and freezes, isotopes, held tools, containment integrity, the
+    /// recalculation epoch, whether mercy was used, objective progress, the
+    /// turn and its action points, rule state such as lives left, the noise
+    /// daemon's generator and allowance, speedrun splits, the score and the
+    /// combo. Every list is length-prefixed, so no two states share an
+    /// encoding. Two grids with equal hashes respond identically to any
+    /// action, so peers can compare hashes every few moves to catch a
+    /// desync early. The clock (beyond split times), notes, and chunk
+    /// revisions are left out. Probabilities are hashed bit-exactly, so
+    /// JSON saves only keep their hash with serde_json's `float_roundtrip`
+    /// feature.
+    pub fn state_hash(&self) -> [u8; 32] {
+        let mut bytes = Vec::with_capacity(64 + self.cells.len() * 10 + self.mine_map.len());
+        bytes.extend_from_slice(b"qmf-state-v2");
+        for value in [
+            self.width,
+            self.height,
+            self.mine_count,
+            self.containment_charges,
+            self.swap_charges,
+            self.reveals_toward_charge,
+        ] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes.extend_from_slice(&self.rng.state().to_le_bytes());
+        bytes.extend_from_slice(&self.time_ms.to_le_bytes());
+        bytes.extend([
+            u8::from(self.game_over),
+            u8::from(self.won),
+            u8::from(self.mines_placed),
+            u8::from(self.mercy_used),
+        ]);
+        bytes.extend_from_slice(&self.recalc_epoch.to_le_bytes());
+        bytes.extend_from_slice(&self.score.to_le_bytes());
+        for value in [self.combo.streak, self.combo.multiplier] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        push_len(&mut bytes, self.cells.len());
+        for cell in &self.cells {
+            match cell.state {
+                CellState::Superposition { probability } => {
+                    bytes.push(0);
+                    bytes.extend_from_slice(&probability.to_bits().to_le_bytes());
+                }
+                CellState::Revealed { adjacent_mines } => bytes.extend([1, adjacent_mines]),
+                CellState::Contained => bytes.push(2),
+                CellState::Detonated => bytes.push(3),
+            }
+            bytes.push(cell.mark as u8);
+        }
+        push_len(&mut bytes, self.mine_map.len());
+        bytes.extend(self.mine_map.iter().map(|&mine| u8::from(mine)));
+        for value in [
+            self.decoherence.total,
+            self.decoherence.lifetime,
+            self.decoherence.penalties,
+        ] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        // Only disturbed cells, so a meter not yet sized to the board hashes
+        // like one full of zeros.
+        let disturbed: Vec<(usize, u32)> = self
+            .decoherence
+            .cells
+            .iter()
+            .copied()
+            .enumerate()
+            .filter(|&(_, points)| points > 0)
+            .collect();
+        push_len(&mut bytes, disturbed.len());
+        for (index, points) in disturbed {
+            bytes.extend_from_slice(&(index as u64).to_le_bytes());
+            bytes.extend_from_slice(&points.to_le_bytes());
+        }
+        bytes.extend_from_slice(&self.zeno.streak.to_le_bytes());
+        bytes.extend_from_slice(
+            &self
+                .zeno
+                .streak_cell
+                .map_or(u64::MAX, |i| i as u64)
+                .to_le_bytes(),
+        );
+        push_len(&mut bytes, self.zeno.frozen.len());
+        for (&index, &left) in &self.zeno.frozen {
+            bytes.extend_from_slice(&(index as u64).to_le_bytes());
+            bytes.extend_from_slice(&left.to_le_bytes());
+        }
+        push_len(&mut bytes, self.interference.waves.len());
+        for wave in &self.interference.waves {
+            for value in [wave.x, wave.y, wave.age] {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+        }
+        push_len(&mut bytes, self.triggers.len());
+        for (&index, trigger) in &self.triggers {
+            bytes.extend_from_slice(&(index as u64).to_le_bytes());
+            let CellTrigger::Oracle { circuit } = trigger;
+            push_len(&mut bytes, circuit.gates.len());
+            for gate in &circuit.gates {
+                match gate {
+                    Gate::Hadamard => bytes.push(0),
+                    Gate::Not => bytes.push(1),
+                    Gate::PhaseShift(theta) => {
+                        bytes.push(2);
+                        bytes.extend_from_slice(&theta.to_bits().to_le_bytes());
+                    }
+                }
+            }
+        }
+        push_len(&mut bytes, self.isotopes.len());
+        for (&index, isotope) in &self.isotopes {
+            bytes.extend_from_slice(&(index as u64).to_le_bytes());
+            bytes.extend_from_slice(&isotope.stages_left.to_le_bytes());
+            bytes.extend_from_slice(&isotope.pulse.to_bits().to_le_bytes());
+        }
+        bytes.extend_from_slice(&self.inventory.shields.to_le_bytes());
+        bytes.extend_from_slice(&self.integrity.refund_credit.to_le_bytes());
+        push_len(&mut bytes, self.integrity.cells.len());
+        for (&index, &integrity) in &self.integrity.cells {
+            bytes.extend_from_slice(&(index as u64).to_le_bytes());
+            bytes.extend_from_slice(&integrity.to_le_bytes());
+        }
+        for value in [self.turns.turn, self.turns.action_points] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        push_len(&mut bytes, self.rules.rules.len());
+        for rule in &self.rules.rules {
+            match *rule {
+                Rule::Lives { remaining } => {
+                    bytes.push(0);
+                    bytes.extend_from_slice(&remaining.to_le_bytes());
+                }
+            }
+        }
+        bytes.extend_from_slice(&self.daemon.rng.state().to_le_bytes());
+        match self.config.daemon {
+            Some(rules) => {
+                bytes.push(1);
+                for value in [
+                    rules.budget,
+                    rules.scramble_cost,
+                    rules.link_cost,
+                    rules.shift_cost,
+                ] {
+                    bytes.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+            None => bytes.push(0),
+        }
+        push_len(&mut bytes, self.splits.list.len());
+        for split in &self.splits.list {
+            match split.trigger {
+                SplitTrigger::FirstCascade => bytes.push(0),
+                SplitTrigger::EntropyFraction { fraction } => {
+                    bytes.push(1);
+                    bytes.extend_from_slice(&fraction.to_bits().to_le_bytes());
+                }
+                SplitTrigger::AllMinesContained => bytes.push(2),
+            }
+            bytes.extend_from_slice(&split.elapsed_ms.unwrap_or(u64::MAX).to_le_bytes());
+        }
+        let start_entropy = self.splits.start_entropy.map_or(u64::MAX, f64::to_bits);
+        bytes.extend_from_slice(&start_entropy.to_le_bytes());
+        bytes.extend_from_slice(&self.objectives.moves.to_le_bytes());
+        push_len(&mut bytes, self.objectives.list.len());
+        for state in &self.objectives.list {
+            match &state.objective {
+                Objective::ResolveAll => bytes.push(0),
+                Objective::ContainCells { cells } => {
+                    bytes.push(1);
+                    push_len(&mut bytes, cells.len());
+                    for &(x, y) in cells {
+                        bytes.extend_from_slice(&x.to_le_bytes());
+                        bytes.extend_from_slice(&y.to_le_bytes());
+                    }
+                }
+                Objective::EntropyBelow { bits, moves } => {
+                    bytes.push(2);
+                    bytes.extend_from_slice(&bits.to_bits().to_le_bytes());
+                    bytes.extend_from_slice(&moves.to_le_bytes());
+                }
+                Objective::DecoherenceUnder { points } => {
+                    bytes.push(3);
+                    bytes.extend_from_slice(&points.to_le_bytes());
+                }
+            }
+            bytes.push(state.status as u8);
+            bytes.extend_from_slice(&state.progress.to_bits().to_le_bytes());
+        }
+        push_len(&mut bytes, self.entanglement.pairs.len());
+        for pair in &self.entanglement.pairs {
+            bytes.extend_from_slice(&(pair.left as u64).to_le_bytes());
+            bytes.extend_from_slice(&(pair.right as u64).to_le_bytes());
+            bytes.extend_from_slice(&pair.strength.to_bits().to_le_bytes());
+            bytes.extend([pair.link_type as u8, pair.correlation as u8]);
+        }
+        sha256(&bytes)
+    }
+
+    pub fn snapshot(&self) -> GridSnapshot {
+        GridSnapshot {
+            width: self.width,
+            height: self.height,
+            game_over: self.game_over,
+            won: self.won,
+            seed: self.seed,
+            containment_charges: self.containment_charges,
+            swap_charges: self.swap_charges,
+            mines_remaining: self.mines_remaining(),
+            entropy: self.entropy(),
+            quantum_entropy: self.quantum_entropy(),
+            cells: self.cells.to_vec(),
+            decoherence: self.decoherence.clone(),
+            frozen: self.frozen_cells(),
+            theme: self.theme_hint(),
+            score: self.score,
+            combo: self.combo,
+            isotopes: self
+                .isotopes
+                .iter()
+                .map(|(&index, isotope)| {
+                    let (x, y) = self.coords_of(index);
+                    IsotopeView {
+                        x,
+                        y,
+                        stages_left: isotope.stages_left,
+                    }
+                })
+                .collect(),
+            inventory: self.inventory,
+            integrity: self
+                .integrity
+                .cells
+                .iter()
+                .map(|(&index, &integrity)| {
+                    let (x, y) = self.coords_of(index);
+                    CellIntegrity { x, y, integrity }
+                })
+                .collect(),
+            cosmetics: cosmetics::field(self.seed, self.width, self.height),
+        }
+    }
+
+    /// Calibration of this board's circuit, for normalising heatmaps.
+    pub fn theme_hint(&self) -> ThemeHint {
+        let density = f64::from(self.mine_count) / self.cells.len().max(1) as f64;
+        self.circuit.theme_hint(density)
+    }
+
+    // -----------------------------------------------------------------------
+    // Private helpers
+    // -----------------------------------------------------------------------
+
+    pub(crate) fn index_of(&self, x: u32, y: u32) -> Option<usize> {
+        cell_index(self.width, self.height, x, y)
+    }
+
+    /// Whether cell `index` hides a mine; cells off the board do not.
+    pub(crate) fn is_mine(&self, index: usize) -> bool {
+        self.mine_map.get(index).copied().unwrap_or(false)
+    }
+
+    fn set_mine(&mut self, index: usize, mine: bool) {
+        if let Some(slot) = self.mine_map.get_mut(index) {
+            *slot = mine;
+        }
+    }
+
+    /// Cell `index`'s hint while it is in Superposition.
+    pub(crate) fn hint_at(&self, index: usize) -> Option<f64> {
+        match self.cells.get(index)?.state {
+            CellState::Superposition { probability } => Some(probability),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn is_unresolved(&self, index: usize) -> bool {
+        self.hint_at(index).is_some()
+    }
+
+    /// Every cell state change goes through here so chunk tracking sees it,
+    /// and so revealing a cell fires its trigger however it was revealed.
+    fn set_state(&mut self, index: usize, state: CellState) {
+        let (x, y) = self.coords_of(index);
+        self.chunks.touch(self.width, self.height, x, y);
+        if !matches!(state, CellState::Superposition { .. }) {
+            self.zeno.release(index);
+            self.isotopes.remove(&index);
+        }
+        if matches!(state, CellState::Contained) && self.config.timed.containment_decay > 0 {
+            self.integrity.cells.insert(index, FULL_INTEGRITY);
+        } else {
+            self.integrity.cells.remove(&index);
+        }
+        let revealed = matches!(state, CellState::Revealed { .. });
+        match state {
+            CellState::Revealed { adjacent_mines } => self.publish(GameEvent::Revealed {
+                x,
+                y,
+                adjacent_mines,
+            }),
+            CellState::Contained => self.publish(GameEvent::Contained { x, y }),
+            CellState::Detonated => self.publish(GameEvent::Detonated { x, y }),
+            CellState::Superposition { .. } => {}
+        }
+        if let Some(cell) = self.cells.get_mut(index) {
+            cell.state = state;
+        }
+        if revealed {
+            if let Some(trigger) = self.triggers.remove(&index) {
+                self.fire(index, trigger);
+            }
+        }
+    }
+
+    /// The one place events enter the stream.
+    fn publish(&mut self, event: GameEvent) {
+        self.events.on_event(&event);
+    }
+
+    /// Set off the mine at `index`; the game ends unless a rule spares it.
+    fn detonate(&mut self, index: usize) {
+        self.set_state(index, CellState::Detonated);
+        let (x, y) = self.coords_of(index);
+        if !self.rules.on_detonation(x, y) {
+            self.game_over = true;
+        }
+    }
+
+    /// A worn-out containment gives way: the mine is back in Superposition,
+    /// looking dangerous, and part of its charge comes back.
+    fn breach(&mut self, index: usize) {
+        self.set_state(
+            index,
+            CellState::Superposition {
+                probability: BREACH_HINT,
+            },
+        );
+        let (x, y) = self.coords_of(index);
+        self.publish(GameEvent::ContainmentBreached { x, y });
+        if self.integrity.refund() {
+            self.containment_charges += 1;
+        }
+    }
+
+    /// Recompute [`Self::won`], publishing [`GameEvent::Won`] the first
+    /// time the board is cleared.
+    fn check_win(&mut self) {
+        if !self.objectives.is_empty() {
+            self.check_objectives();
+            return;
+        }
+        let won = self.is_win_condition_met();
+        if won && !self.won {
+            self.publish(GameEvent::Won);
+        }
+        self.won = won;
+    }
+
+    /// Time the splits the last move reached, `resolved_before` being the
+    /// resolved-cell count before it.
+    fn check_splits(&mut self, resolved_before: u32, t_ms: u64) {
+        if self.splits.is_empty() {
+            return;
+        }
+        let elapsed_ms = t_ms.saturating_sub(self.clock.first_action_ms.unwrap_or(t_ms));
+        let mut splits = core::mem::take(&mut self.splits);
+        let events = splits.evaluate(self, resolved_before, elapsed_ms);
+        self.splits = splits;
+        for event in events {
+            self.publish(event);
+        }
+    }
+
+    /// Scenario play: evaluate the objectives, win once all are complete
+    /// and end the game on the first to fail.
+    fn check_objectives(&mut self) {
+        if self.game_over || self.won {
+            return;
+        }
+        let mut objectives = core::mem::take(&mut self.objectives);
+        let events = objectives.evaluate(self);
+        self.objectives = objectives;
+        for event in events {
+            self.publish(event);
+        }
+        if self.objectives.failed() {
+            self.game_over = true;
+        } else if self.objectives.complete() {
+            self.won = true;
+            self.publish(GameEvent::Won);
+        }
+    }
+
+    fn fire(&mut self, index: usize, trigger: CellTrigger) {
+        let (x, y) = self.coords_of(index);
+        match trigger {
+            CellTrigger::Oracle { circuit } => {
+                let kicked =
+                    circuit.apply_region(self.width, self.height, Region::Cross { x, y }, |i| {
+                        self.hint_at(i).filter(|_| !self.zeno.is_frozen(i))
+                    });
+                for (i, probability) in kicked {
+                    self.set_state(i, CellState::Superposition { probability });
+                }
+                self.publish(GameEvent::OracleFired { x, y });
+            }
+        }
+    }
+
+    /// Queue the [`SoundCue`]s for a reveal or containment that started with
+    /// `resolved_before` cells resolved. `risk` is the hint the player
+    /// revealed against, if any.
+    fn cue_move(&mut self, resolved_before: u32, risk: Option<f64>) {
+        if self.game_over {
+            let intensity = 1.0 - self.entropy();
+            self.cues.push(SoundCue::Detonation { intensity });
+            return;
+        }
+        let len = self.resolved_cells().saturating_sub(resolved_before);
+        self.cues
+            .push(SoundCue::for_resolution(len, self.cells.len() as u32));
+        if let Some(probability) = risk.filter(|&p| p >= NEAR_MISS) {
+            self.cues
+                .push(SoundCue::NearMissHighProbability { probability });
+        }
+        if self.won {
+            self.cues.push(SoundCue::Victory);
+        }
+    }
+
+    /// Bookkeeping shared by every accepted move: age interference waves
+    /// and Zeno freezes, and extend or break the measurement streak.
+    fn advance_move(&mut self, measured: Option<usize>) {
+        self.objectives.moves = self.objectives.moves.saturating_add(1);
+        self.interference.tick();
+        for (&index, isotope) in &mut self.isotopes {
+            let state = self.cells.get_mut(index).map(|cell| &mut cell.state);
+            if let Some(CellState::Superposition { probability }) = state {
+                *probability = isotope.beat(*probability);
+                let (x, y) = (index as u32 % self.width, index as u32 / self.width);
+                self.chunks.touch(self.width, self.height, x, y);
+            }
+        }
+        if let Some(index) = self.zeno.advance(measured) {
+            let (x, y) = self.coords_of(index);
+            self.publish(GameEvent::ZenoFrozen { x, y });
+        }
+    }
+
+    fn coords_of(&self, index: usize) -> (u32, u32) {
+        let x = index as u32 % self.width;
+        let y = index as u32 / self.width;
+        (x, y)
+    }
+
+    /// Place mines with the configured [`BoardGenerator`], excluding the
+    /// first-click safe zone around `safe_index` as dictated by the
+    /// [`FirstClick`] policy.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "debug", skip(self)))]
+    fn place_mines(&mut self, safe_index: usize) {
+        let mut excluded = vec![false; self.cells.len()];
+        for index in self.safe_zone(safe_index) {
+            if let Some(slot) = excluded.get_mut(index) {
+                *slot = true;
+            }
+        }
+        // Isotopes hold their mine unless the first click needs the cell;
+        // a dropped isotope's mine goes elsewhere, and its extra charges go.
+        let dropped: Vec<usize> = self
+            .isotopes
+            .keys()
+            .copied()
+            .filter(|&index| excluded.get(index).copied().unwrap_or(true))
+            .collect();
+        for index in dropped {
+            if let Some(isotope) = self.isotopes.remove(&index) {
+                self.containment_charges = self
+                    .containment_charges
+                    .saturating_sub(isotope.stages_left - 1);
+            }
+        }
+        for &index in self.triggers.keys().chain(self.isotopes.keys()) {
+            if let Some(slot) = excluded.get_mut(index) {
+                *slot = true;
+            }
+        }
+        let weights = self.config.density.weights(self.width, self.height);
+        let placement = Placement {
+            width: self.width,
+            height: self.height,
+            mine_count: self.mine_count.saturating_sub(self.isotopes.len() as u32),
+            excluded: &excluded,
+            weights: weights.as_deref(),
+        };
+        self.mine_map = self
+            .config
+            .generator
+            .generate(&placement, &mut self.rng)
+            .into();
+        for &index in self.isotopes.keys() {
+            if let Some(mine) = self.mine_map.get_mut(index) {
+                *mine = true;
+            }
+        }
+        self.correlate_bell_pairs(&placement);
+
+        self.mines_placed = true;
+        telemetry::debug!(
+            mines = self.mine_map.iter().filter(|&&mine| mine).count(),
+            generator = ?self.config.generator,
+            "mines placed"
+        );
+
+        // Sprinkle rare oracles over the remaining safe cells.
+        if self.config.oracles.rate > 0.0 {
+            for index in 0..self.cells.len() {
+                if self.is_mine(index) || self.triggers.contains_key(&index) {
+                    continue;
+                }
+                if self.rng.next_f64() < self.config.oracles.rate {
+                    let circuit = self.config.oracles.circuit.clone();
+                    self.triggers.insert(index, CellTrigger::Oracle { circuit });
+                }
+            }
+        }
+
+        // Recalculate probability hints: neighbor-aware hinting
+        self.recalculate_probabilities(0..self.cells.len());
+    }
+
+    /// Move mines so every BellState pair holds its [`Correlation`]: one
+    /// mine between an anti-correlated pair, none or two in a positive one.
+    /// One end of a pair that disagrees flips, and a mine moves to or from
+    /// a cell no settled pair uses to keep the count. Excluded cells (the
+    /// safe zone, triggers, isotopes) never flip; a pair that cannot be
+    /// settled, such as the last link of an odd loop, keeps its generated
+    /// mines. Generators' layouts (symmetry, veins) yield to the links.
+    fn correlate_bell_pairs(&mut self, placement: &Placement) {
+        let mut settled = placement.excluded.to_vec();
+        settled.resize(self.mine_map.len(), true);
+        let pairs: Vec<(usize, usize, Correlation)> = self
+            .entanglement
+            .pairs
+            .iter()
+            .filter(|pair| pair.link_type == LinkType::BellState && pair.left != pair.right)
+            .map(|pair| (pair.left, pair.right, pair.correlation))
+            .collect();
+        for (a, b, correlation) in pairs {
+            let (Some(&mine_a), Some(&mine_b)) = (self.mine_map.get(a), self.mine_map.get(b))
+            else {
+                continue;
+            };
+            let mut ends: Vec<usize> = [a, b]
+                .into_iter()
+                .filter(|&end| settled.get(end) == Some(&false))
+                .collect();
+            for end in [a, b] {
+                if let Some(flag) = settled.get_mut(end) {
+                    *flag = true;
+                }
+            }
+            if correlation.predict(mine_a) == mine_b || ends.is_empty() {
+                continue;
+            }
+            if ends.len() == 2 && self.rng.next_u64() & 1 == 1 {
+                ends.swap(0, 1);
+            }
+            for end in ends {
+                // Flipping `end` to `wanted` takes the opposite flip elsewhere.
+                let wanted = !self.is_mine(end);
+                let elsewhere: Vec<usize> = (0..self.mine_map.len())
+                    .filter(|&i| settled.get(i) == Some(&false) && self.is_mine(i) == wanted)
+                    .collect();
+                // The density weights say where a mine should go, not
+                // which one should leave.
+                let other = if wanted {
+                    self.rng.pick(&elsewhere).copied()
+                } else {
+                    placement.weighted_pick(&elsewhere, &mut self.rng)
+                };
+                let Some(other) = other else {
+                    continue;
+                };
+                self.set_mine(end, wanted);
+                self.set_mine(other, !wanted);
+                break;
+            }
+        }
+    }
+
+    /// 60% neighbouring mine density (ground truth), 40% prior: the hint
+    /// before noise and circuit scrambling.
+    fn local_blend(&self, index: usize, baseline: f64) -> f64 {
+        let (x, y) = self.coords_of(index);
+        let max_neighbors = self.neighbor_count(x, y);
+        let local_density = if max_neighbors > 0 {
+            self.adjacent_mines(x, y) as f64 / max_neighbors as f64
+        } else {
+            baseline
+        };
+        local_density * 0.6 + baseline * 0.4
+    }
+
+    /// Cells that must stay mine-free when the first interaction is at `index`.
+    fn safe_zone(&self, index: usize) -> Vec<usize> {
+        let radius = match self.config.first_click {
+            FirstClick::GuaranteedZero => self.config.safe_zone_radius,
+            FirstClick::GuaranteedSafe => 0,
+            FirstClick::NoGuarantee => return Vec::new(),
+        };
+        let (sx, sy) = self.coords_of(index);
+        square(self.width, self.height, sx, sy, radius)
+            .map(|(_, _, index)| index)
+            .collect()
+    }
+
+    /// Recalculate displayed probabilities for the Superposition cells
+    /// among `cells` based on the actual mine map + circuit scrambling.
+    /// This gives heterogeneous hints without revealing exact positions.
+    ///
+    /// The noise is keyed by seed, cell and [`Self::recalc_epoch`] rather
+    /// than drawn from the grid RNG, so a pass gives the same hints
+    /// whatever order cells were played in, and re-running it changes
+    /// nothing.
+    fn recalculate_probabilities(&mut self, cells: impl IntoIterator<Item = usize>) {
+        let baselines = baselines(&self.config, self.mine_count);
+        for i in cells {
+            if !self.is_unresolved(i) {
+                continue;
+            }
+            let baseline = baselines.get(i).copied().unwrap_or_default();
+            let blended = self.local_blend(i, baseline);
+            // Add per-cell noise so identical neighbor counts don't look identical
+            let noise =
+                hint_noise(self.config.seed, i, self.recalc_epoch) * self.zones.noise_scale(i);
+            let raw = (blended + noise).clamp(0.01, 0.99);
+            let scrambled = self.circuit.apply_probability(raw);
+
+            self.set_state(
+                i,
+                CellState::Superposition {
+                    probability: scrambled,
+                },
+            );
+        }
+    }
+
+    /// Reveal a cell known to be safe. Computes adjacent count, does flood fill
+    /// if zero, and checks win condition.
+    fn reveal_safe(&mut self, index: usize) -> RevealOutcome {
+        let (x, y) = self.coords_of(index);
+        let adj = self.adjacent_mines(x, y);
+        self.set_state(
+            index,
+            CellState::Revealed {
+                adjacent_mines: adj,
+            },
+        );
+        self.propagate_entanglement(index, false);
+
+        if adj == 0 {
+            self.flood_fill(x, y);
+        }
+
+        self.check_win();
+        match self.cells.get(index) {
+            Some(cell) => RevealOutcome::Revealed { cell: cell.clone() },
+            None => RevealOutcome::OutOfBounds,
+        }
+    }
+
+    /// Shift a measured Superposition cell by `drift` and charge it to the
+    /// decoherence meter.
+    fn drift(&mut self, index: usize, probability: f64, drift: f64) {
+        if self.zeno.is_frozen(index) {
+            return;
+        }
+        let drift = drift * self.zones.drift_scale(index);
+        let perturbed = (probability + drift).clamp(0.01, 0.99);
+        self.set_state(
+            index,
+            CellState::Superposition {
+                probability: perturbed,
+            },
+        );
+        self.decohere(index, perturbed - probability);
+    }
+
+    /// Charge a measurement's disturbance to the meter and, if that pushes
+    /// it over the limit, collapse a random unresolved cell.
+    fn decohere(&mut self, index: usize, amount: f64) {
+        self.decoherence.disturb(index, amount, self.cells.len());
+        self.collapse_if_decohered();
+    }
+
+    /// Collapse a random unresolved cell if the meter is over the limit.
+    fn collapse_if_decohered(&mut self) {
+        let Some(limit) = self.config.decoherence_limit else {
+            return;
+        };
+        // Nothing to collapse into before the board exists.
+        if self.game_over || self.won || !self.mines_placed {
+            return;
+        }
+        if !self.decoherence.overflowed(limit) {
+            return;
+        }
+        let unresolved: Vec<usize> = (0..self.cells.len())
+            .filter(|&i| self.is_unresolved(i))
+            .filter(|&i| !self.zeno.is_frozen(i))
+            .collect();
+        let Some(&victim) = self.rng.pick(&unresolved) else {
+            return;
+        };
+        let (x, y) = self.coords_of(victim);
+        let mine = self.is_mine(victim);
+        self.publish(GameEvent::Decohered { x, y, mine });
+        if mine {
+            self.detonate(victim);
+            self.propagate_entanglement(victim, true);
+            self.check_win();
+        } else {
+            self.reveal_safe(victim);
+            self.apply_assists();
+        }
+    }
+
+    fn stamp(&mut self, outcome: &RevealOutcome, t_ms: u64) {
+        if !outcome.is_rejection() {
+            self.clock.record(t_ms, self.game_over || self.won);
+        }
+    }
+
+    /// Run the opt-in end-game assists from [`GridConfig`] after an action.
+    ///
+    /// Auto-reveal runs first so that, in trusting mode, the flagged cells it
+    /// leaves behind can then be picked up by auto-contain.
+    fn apply_assists(&mut self) {
+        if self.game_over || self.won {
+            return;
+        }
+        self.auto_reveal();
+        if self.game_over {
+            return;
+        }
+        if self.config.auto_contain {
+            let unresolved: Vec<usize> = (0..self.cells.len())
+                .filter(|&i| self.is_unresolved(i))
+                .collect();
+            if !unresolved.is_empty()
+                && unresolved.len() as u32 == self.mines_remaining()
+                && unresolved.iter().all(|&i| self.is_mine(i))
+            {
+                for index in unresolved {
+                    self.set_state(index, CellState::Contained);
+                    let (x, y) = self.coords_of(index);
+                    self.publish(GameEvent::AutoContained { x, y });
+                }
+                self.check_win();
+            }
+        }
+    }
+
+    /// [`AutoReveal`]: open every remaining cell once the mines are
+    /// accounted for (by containment, or in trusting mode also by flags).
+    fn auto_reveal(&mut self) {
+        let trusting = match self.config.auto_reveal {
+            AutoReveal::Off => return,
+            AutoReveal::Trusting => true,
+            AutoReveal::GroundTruth => false,
+        };
+        let unresolved = |cell: &QuantumCell| matches!(cell.state, CellState::Superposition { .. });
+        let flagged = if trusting {
+            self.cells
+                .iter()
+                .filter(|c| unresolved(c) && c.mark == Mark::Flag)
+                .count() as u32
+        } else {
+            0
+        };
+        if flagged != self.mines_remaining() {
+            return;
+        }
+        let targets: Vec<usize> = (0..self.cells.len())
+            .zip(&self.cells)
+            .filter(|&(_, cell)| unresolved(cell) && !(trusting && cell.mark == Mark::Flag))
+            .map(|(i, _)| i)
+            .collect();
+        for index in targets {
+            let (x, y) = self.coords_of(index);
+            if self.is_mine(index) {
+                // Only reachable when trusting a wrong flag.
+                self.detonate(index);
+                self.publish(GameEvent::AutoDetonated { x, y });
+                if self.game_over {
+                    return;
+                }
+                continue;
+            }
+            self.set_state(
+                index,
+                CellState::Revealed {
+                    adjacent_mines: self.adjacent_mines(x, y),
+                },
+            );
+            self.publish(GameEvent::AutoRevealed { x, y });
+        }
+        self.check_win();
+    }
+
+    /// Score the cells a safe reveal resolved (`resolved` counts those
+    /// resolved before it) and extend the combo.
+    fn score_safe_reveal(&mut self, resolved: u32) {
+        self.score += self
+            .combo
+            .points(self.resolved_cells().saturating_sub(resolved));
+        let Some(rules) = self.config.combo else {
+            return;
+        };
+        let multiplier = self.combo.multiplier;
+        if self.combo.extend(&rules) {
+            self.swap_charges = self.swap_charges.saturating_add(1);
+            self.publish(GameEvent::ComboCharge);
+        }
+        if self.combo.multiplier > multiplier {
+            self.publish(GameEvent::ComboRaised {
+                multiplier: self.combo.multiplier,
+            });
+        }
+    }
+
+    /// Credit a safe reveal toward [`ChargePolicy::Regenerating`].
+    fn regenerate_charge(&mut self) {
+        let ChargePolicy::Regenerating { every } = self.config.charge_policy else {
+            return;
+        };
+        if every == 0 {
+            return;
+        }
+        self.reveals_toward_charge = self.reveals_toward_charge.saturating_add(1);
+        if self.reveals_toward_charge >= every {
+            self.reveals_toward_charge = 0;
+            if self.containment_charges < self.mines_remaining() {
+                self.containment_charges += 1;
+            }
+        }
+    }
+
+    /// Stack-based flood fill for zero-adjacent safe cells.
+    #[cfg_attr(feature = "tracing", tracing::instrument(level = "trace", skip(self)))]
+    fn flood_fill(&mut self, start_x: u32, start_y: u32) {
+        let mut stack = vec![(start_x, start_y)];
+
+        while let Some((cx, cy)) = stack.pop() {
+            for (nx, ny, idx) in square(self.width, self.height, cx, cy, 1) {
+                if nx == cx && ny == cy {
+                    continue;
+                }
+                // Only process cells still in superposition and not mines
+                if !self.is_unresolved(idx) {
+                    continue;
+                }
+                if self.is_mine(idx) {
+                    continue;
+                }
+
+                let adj = self.adjacent_mines(nx, ny);
+                self.set_state(
+                    idx,
+                    CellState::Revealed {
+                        adjacent_mines: adj,
+                    },
+                );
+
+                if adj == 0 {
+                    stack.push((nx, ny));
+                }
+            }
+        }
+    }
+
+    /// The mine map changed under `changed` after placement: start a new
+    /// [`Self::recalc_epoch`], recount the numbers around them and
+    /// recalculate the hints next to them. The changed cells keep whatever
+    /// hint the change gave them.
+    fn ground_truth_changed(&mut self, changed: &[usize]) {
+        self.recalc_epoch += 1;
+        telemetry::debug!(epoch = self.recalc_epoch, ?changed, "ground truth changed");
+        let mut around = BTreeSet::new();
+        for &index in changed {
+            self.recount_neighbours(index);
+            around.extend(neighbors(self.width, self.height, index));
+        }
+        around.retain(|index| !changed.contains(index));
+        self.recalculate_probabilities(around);
+    }
+
+    /// Refresh the numbers on revealed neighbours of `index` after the mine
+    /// map under it changed.
+    fn recount_neighbours(&mut self, index: usize) {
+        let (x, y) = self.coords_of(index);
+        for (nx, ny, idx) in square(self.width, self.height, x, y, 1) {
+            let Some(&CellState::Revealed {
+                adjacent_mines: shown,
+            }) = self.cells.get(idx).map(|cell| &cell.state)
+            else {
+                continue;
+            };
+            if idx == index {
+                continue;
+            }
+            let adjacent_mines = self.adjacent_mines(nx, ny);
+            if shown != adjacent_mines {
+                self.set_state(idx, CellState::Revealed { adjacent_mines });
+            }
+        }
+    }
+
+    /// Count adjacent mines using the ground-truth mine_map.
+    fn adjacent_mines(&self, x: u32, y: u32) -> u8 {
+        square(self.width, self.height, x, y, 1)
+            .filter(|&(nx, ny, idx)| (nx, ny) != (x, y) && self.is_mine(idx))
+            .count() as u8
+    }
+
+    /// Number of valid neighbor cells for (x, y).
+    fn neighbor_count(&self, x: u32, y: u32) -> u8 {
+        square(self.width, self.height, x, y, 1)
+            .filter(|&(nx, ny, _)| (nx, ny) != (x, y))
+            .count() as u8
+    }
+
+    /// Propagate entanglement: after resolving a cell, handle its partners.
+    ///
+    /// - **BellState** links trigger `propagate_collapse` — the partner is
+    ///   force-collapsed (revealed if safe, contained if mine) and the
+    ///   cascade continues recursively through any further Bell partners.
+    /// - **Probabilistic** links just shift the displayed probability.
+    fn propagate_entanglement(&mut self, index: usize, was_mine: bool) {
+        if self.zones.isolated(index) {
+            return;
+        }
+        // Collect partner info first to avoid borrow issues.
+        let partners: Vec<(usize, EntanglementPair)> = self
+            .entanglement
             .partners_of(index)
             .iter()
-            .map(|(pair, partner_idx)| (*partner_idx, pair.link_type, pair.strength))
+            .map(|(pair, partner_idx)| (*partner_idx, (*pair).clone()))
             .collect();
 
-        for (partner_index, link_type, _strength) in &partners {
-            if !matches!(
-                self.cells[*partner_index].state,
-                CellState::Superposition { .. }
-            ) {
+        for (partner_index, pair) in &partners {
+            if !self.is_unresolved(*partner_index)
+                || self.zeno.is_frozen(*partner_index)
+                || self.zones.isolated(*partner_index)
+            {
                 continue;
             }
 
-            match link_type {
-                LinkType::BellState => {
-                    // Force-collapse the partner and cascade.
-                    let mut visited = std::collections::HashSet::new();
-                    visited.insert(index);
-                    self.propagate_collapse(*partner_index, was_mine, &mut visited);
-                }
-                LinkType::Probabilistic => {
-                    // Legacy Bayesian adjustment.
-                    if let CellState::Superposition { probability } =
-                        self.cells[*partner_index].state
+            match pair.link_type {
+                LinkType::BellState => {
+                    if !self.bell_link_holds(pair) {
+                        self.degrade_bell(*partner_index, pair, was_mine);
+                        continue;
+                    }
+                    // Force-collapse the partner and cascade.
+                    let mut visited = BTreeSet::new();
+                    visited.insert(index);
+                    self.propagate_collapse(
+                        *partner_index,
+                        Cause::Cell(index),
+                        pair.correlation.predict(was_mine),
+                        &mut visited,
+                    );
+                }
+                // Handled below so the ripple can spread past direct partners.
+                LinkType::Probabilistic => {}
+            }
+        }
+
+        self.ripple_probabilistic(index, was_mine);
+    }
+
+    /// Roll whether a BellState link fires as a full collapse. A link of
+    /// strength `s < 1.0` holds with probability `s`, drawn from the grid RNG
+    /// so replays stay deterministic; full-strength links never draw.
+    fn bell_link_holds(&mut self, pair: &EntanglementPair) -> bool {
+        pair.strength >= 1.0 || self.rng.next_f64() < pair.strength
+    }
+
+    /// A Bell link that failed its reliability roll only nudges the partner's
+    /// probability, exactly like a Probabilistic link of the same strength.
+    fn degrade_bell(&mut self, partner: usize, pair: &EntanglementPair, observed_mine: bool) {
+        let Some(probability) = self.hint_at(partner) else {
+            return;
+        };
+        if self.zeno.is_frozen(partner) {
+            return;
+        }
+        let weak = EntanglementPair {
+            link_type: LinkType::Probabilistic,
+            ..pair.clone()
+        };
+        let probability =
+            self.entanglement
+                .collapse_partner_probability(&weak, observed_mine, probability);
+        self.set_state(partner, CellState::Superposition { probability });
+        let (x, y) = self.coords_of(partner);
+        self.publish(GameEvent::BellDegraded { x, y, probability });
+    }
+
+    /// Bayesian adjustment along **Probabilistic** links, breadth-first from
+    /// `origin` up to `link_propagation.max_depth` hops. Each hop further out
+    /// scales the link strength by `decay`, and every cell is adjusted at
+    /// most once (so rings and other cycles terminate). The ripple stops at
+    /// cells that are already resolved.
+    fn ripple_probabilistic(&mut self, origin: usize, was_mine: bool) {
+        let LinkPropagation { max_depth, decay } = self.config.link_propagation;
+        let mut visited = BTreeSet::from([origin]);
+        let mut frontier = vec![origin];
+        let mut attenuation = 1.0;
+
+        for _ in 0..max_depth {
+            let mut next = Vec::new();
+            for &cell in &frontier {
+                let links: Vec<(EntanglementPair, usize)> = self
+                    .entanglement
+                    .partners_of(cell)
+                    .into_iter()
+                    .filter(|(pair, _)| pair.link_type == LinkType::Probabilistic)
+                    .map(|(pair, partner)| (pair.clone(), partner))
+                    .collect();
+                for (mut pair, partner) in links {
+                    let Some(probability) = self.hint_at(partner) else {
+                        continue;
+                    };
+                    if self.zeno.is_frozen(partner)
+                        || self.zones.isolated(partner)
+                        || !visited.insert(partner)
                     {
-                        // Reconstruct a temporary pair for the calculation
-                        let pair_ref = self
-                            .entanglement
-                            .partners_of(index)
-                            .into_iter()
-                            .find(|(_, pi)| *pi == *partner_index)
-                            .map(|(p, _)| p.clone());
-                        if let Some(pair) = pair_ref {
-                            let adjusted = self.entanglement.collapse_partner_probability(
-                                &pair,
-                                was_mine,
-                                probability,
-                            );
-                            self.cells[*partner_index].state = CellState::Superposition {
-                                probability: adjusted,
-                            };
-                        }
+                        continue;
                     }
+                    pair.strength *= attenuation;
+                    let adjusted = self.entanglement.collapse_partner_probability(
+                        &pair,
+                        was_mine,
+                        probability,
+                    );
+                    self.set_state(
+                        partner,
+                        CellState::Superposition {
+                            probability: adjusted,
+                        },
+                    );
+                    next.push(partner);
                 }
             }
+            if next.is_empty() {
+                break;
+            }
+            frontier = next;
+            attenuation *= decay;
         }
     }
 
     /// Recursive (stack-based) Bell State collapse propagation.
     ///
     /// When a cell with a BellState partner is observed, the partner is
-    /// instantly force-collapsed to a definite state (anti-correlated).
+    /// instantly force-collapsed to a definite state (as predicted by the
+    /// pair's [`Correlation`]).
     /// If *that* partner also has BellState partners, the cascade continues
-    /// (GHZ-state chain reaction).
+    /// (GHZ-state chain reaction). `cause` is the observed cell; each step is
+    /// traced as a [`GameEvent::BellCollapsed`], or a
+    /// [`GameEvent::LinkCollapsed`] when a cell on another board set it off.
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip(self, visited))
+    )]
     fn propagate_collapse(
         &mut self,
         index: usize,
-        triggering_cell_was_mine: bool,
-        visited: &mut std::collections::HashSet<usize>,
+        cause: Cause,
+        predicted_mine: bool,
+        visited: &mut BTreeSet<usize>,
     ) {
         // Stack-based iteration to prevent deep recursion stack overflows.
-        let mut stack = vec![(index, triggering_cell_was_mine)];
+        let mut stack = vec![(index, predicted_mine, 1, cause)];
 
-        while let Some((current, was_mine)) = stack.pop() {
+        while let Some((current, partner_is_mine, depth, cause)) = stack.pop() {
             if !visited.insert(current) {
                 continue; // already processed — avoid infinite loops
             }
 
-            if !matches!(self.cells[current].state, CellState::Superposition { .. }) {
+            if !self.is_unresolved(current) {
                 continue; // already resolved
             }
+            if self.zeno.is_frozen(current) {
+                continue; // held in place by the Zeno effect
+            }
+            if self.zones.isolated(current) {
+                continue; // in a stabilized zone
+            }
 
-            // Anti-correlation: if trigger was a mine, partner is safe; vice versa.
-            let partner_is_mine = !was_mine;
-
-            if self.mine_map[current] && partner_is_mine {
+            if self.is_mine(current) && partner_is_mine {
                 // Mine, and Bell collapse says it's a mine → Contain it.
-                self.cells[current].state = CellState::Contained;
-            } else if !self.mine_map[current] && !partner_is_mine {
+                self.set_state(current, CellState::Contained);
+            } else if !self.is_mine(current) && !partner_is_mine {
                 // Safe, and Bell collapse says it's safe → Reveal it.
                 let (cx, cy) = self.coords_of(current);
                 let adj = self.adjacent_mines(cx, cy);
-                self.cells[current].state = CellState::Revealed {
-                    adjacent_mines: adj,
-                };
+                self.set_state(
+                    current,
+                    CellState::Revealed {
+                        adjacent_mines: adj,
+                    },
+                );
                 // Note: we intentionally do NOT flood-fill from collapse
                 // to avoid cascading the entire board. Only explicit clicks
                 // trigger flood fill.
             } else {
                 // Ground truth disagrees with Bell prediction. The physics
-                // is "correct" (anti-correlated) but the mine map is the
+                // is "correct" (correlated) but the mine map is the
                 // source of truth for what the cell actually *is*. Resolve
                 // it according to reality.
-                if self.mine_map[current] {
-                    self.cells[current].state = CellState::Contained;
+                if self.is_mine(current) {
+                    self.set_state(current, CellState::Contained);
                 } else {
                     let (cx, cy) = self.coords_of(current);
                     let adj = self.adjacent_mines(cx, cy);
-                    self.cells[current].state = CellState::Revealed {
-                        adjacent_mines: adj,
-                    };
+                    self.set_state(
+                        current,
+                        CellState::Revealed {
+                            adjacent_mines: adj,
+                        },
+                    );
                 }
             }
 
+            let (cx, cy) = self.coords_of(current);
+            telemetry::trace!(x = cx, y = cy, depth, "bell collapse");
+            let event = match cause {
+                Cause::Cell(cause) => {
+                    let (cause_x, cause_y) = self.coords_of(cause);
+                    GameEvent::BellCollapsed {
+                        x: cx,
+                        y: cy,
+                        depth,
+                        cause_x,
+                        cause_y,
+                    }
+                }
+                Cause::Board(cause) => GameEvent::LinkCollapsed {
+                    x: cx,
+                    y: cy,
+                    cause,
+                },
+            };
+            self.publish(event);
+
             // Continue the cascade: find Bell partners of `current`
-            let next_partners: Vec<usize> = self
+            let next_partners: Vec<(usize, EntanglementPair)> = self
                 .entanglement
                 .partners_of(current)
                 .iter()
                 .filter(|(pair, _)| pair.link_type == LinkType::BellState)
-                .map(|(_, pi)| *pi)
+                .map(|(pair, pi)| (*pi, (*pair).clone()))
                 .collect();
 
-            for partner in next_partners {
-                if !visited.contains(&partner) {
-                    stack.push((partner, self.mine_map[current]));
+            let current_is_mine = self.is_mine(current);
+            for (partner, pair) in next_partners {
+                if visited.contains(&partner) {
+                    continue;
                 }
+                if self.bell_link_holds(&pair) {
+                    stack.push((
+                        partner,
+                        pair.correlation.predict(current_is_mine),
+                        depth + 1,
+                        Cause::Cell(current),
+                    ));
+                } else {
+                    self.degrade_bell(partner, &pair, current_is_mine);
+                }
+            }
+        }
+    }
+
+    /// Follow a [`CrossLink`] into this board: `cause`, on another board of
+    /// a lab, resolved as `observed_mine`, and `index` is its partner here.
+    /// A holding BellState link collapses `index` and cascades through its
+    /// Bell partners on this board; anything else shifts its probability.
+    /// A board that has not placed its mines yet has nothing to collapse.
+    pub(crate) fn follow_cross_link(
+        &mut self,
+        index: usize,
+        link: &CrossLink,
+        observed_mine: bool,
+        cause: CellRef,
+    ) {
+        let Some(probability) = self.hint_at(index) else {
+            return;
+        };
+        if self.game_over
+            || !self.mines_placed
+            || self.zeno.is_frozen(index)
+            || self.zones.isolated(index)
+        {
+            return;
+        }
+        let pair = link.local(index);
+        match pair.link_type {
+            LinkType::BellState if self.bell_link_holds(&pair) => {
+                let mut visited = BTreeSet::new();
+                self.propagate_collapse(
+                    index,
+                    Cause::Board(cause),
+                    pair.correlation.predict(observed_mine),
+                    &mut visited,
+                );
+                self.check_win();
+            }
+            LinkType::BellState => self.degrade_bell(index, &pair, observed_mine),
+            LinkType::Probabilistic => {
+                let probability = self.entanglement.collapse_partner_probability(
+                    &pair,
+                    observed_mine,
+                    probability,
+                );
+                self.set_state(index, CellState::Superposition { probability });
             }
         }
     }
@@ -621,6 +2822,60 @@ impl QuantumGrid {
     }
 }
 
+/// What set off one step of a Bell cascade.
+#[derive(Debug, Clone, Copy)]
+enum Cause {
+    /// A cell on this board.
+    Cell(usize),
+    /// A cell on another board of a lab, across a [`CrossLink`].
+    Board(CellRef),
+}
+
+/// Append a list's length to [`QuantumGrid::state_hash`]'s input, so
+/// neighbouring lists cannot trade entries and still hash the same.
+fn push_len(bytes: &mut Vec<u8>, len: usize) {
+    bytes.extend_from_slice(&(len as u64).to_le_bytes());
+}
+
+/// Shannon entropy, in bits, of a cell that is a mine with probability `p`.
+pub(crate) fn binary_entropy(p: f64) -> f64 {
+    [p, 1.0 - p]
+        .into_iter()
+        .filter(|&q| q > 0.0 && q < 1.0)
+        .map(|q| -q * q.log2())
+        .sum()
+}
+
+/// Difficulty-scaled entanglement: pair every `step`-th cell, with links of
+/// `strength`, and whether every other link is a BellState.
+pub(crate) fn entanglement_profile(difficulty: &str) -> (usize, f64, bool) {
+    match difficulty {
+        "observer" => (11, 0.2, false),
+        "theorist" => (5, 0.5, true), // BellState pairs at highest difficulty
+        _ => (7, 0.35, false),        // "researcher" default
+    }
+}
+
+/// Uniform noise in `±HINT_NOISE` for cell `index` in recalculation
+/// `epoch`.
+pub(crate) fn hint_noise(seed: u64, index: usize, epoch: u64) -> f64 {
+    keyed_f64(seed, &[index as u64, epoch]) * (2.0 * HINT_NOISE) - HINT_NOISE
+}
+
+/// Prior mine probability per cell: the global density, scaled by the
+/// configured [`Density`](crate::config::Density) weights.
+fn baselines(config: &GridConfig, mine_count: u32) -> Vec<f64> {
+    let total = config.total_cells() as usize;
+    let baseline = mine_count as f64 / total.max(1) as f64;
+    match config.density.weights(config.width, config.height) {
+        Some(weights) => weights
+            .into_iter()
+            .map(|w| (baseline * w).clamp(0.0, 1.0))
+            .collect(),
+        None => vec![baseline.clamp(0.0, 1.0); total],
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -628,6 +2883,14 @@ impl QuantumGrid {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::chunks::ChunkCoord;
+    use crate::config::{
+        ComboRules, DaemonRules, Density, Generator, IsotopeCell, MeasurementCurve, OracleCell,
+        Oracles,
+    };
+    use crate::interference;
+    use crate::rules::Rule;
+    use crate::zeno;
 
     fn make_grid(w: u32, h: u32, mines: u32) -> QuantumGrid {
         QuantumGrid::new(w, h, mines, 42, "observer")
@@ -660,7 +2923,7 @@ mod tests {
                 for dx in -1_i32..=1 {
                     let nx = 4 + dx;
                     let ny = 4 + dy;
-                    if nx >= 0 && nx < 8 && ny >= 0 && ny < 8 {
+                    if (0..8).contains(&nx) && (0..8).contains(&ny) {
                         let idx = (ny * 8 + nx) as usize;
                         assert!(
                             !g.mine_map[idx],
@@ -672,6 +2935,97 @@ mod tests {
         }
     }
 
+    #[test]
+    fn guaranteed_safe_only_protects_clicked_cell() {
+        // 3x3 with 8 mines: only possible when the zone is a single cell.
+        for seed in 0..20 {
+            let config = GridConfig::new(3, 3, 8, seed, "observer")
+                .with_first_click(FirstClick::GuaranteedSafe);
+            let mut g = QuantumGrid::from_config(config);
+            assert_eq!(g.mine_count, 8);
+            let outcome = g.reveal_cell(1, 1);
+            assert!(matches!(outcome, RevealOutcome::Revealed { .. }));
+            assert_eq!(g.mine_map.iter().filter(|&&m| m).count(), 8);
+        }
+    }
+
+    #[test]
+    fn no_guarantee_can_detonate_on_first_click() {
+        let config =
+            GridConfig::new(4, 4, 16, 7, "observer").with_first_click(FirstClick::NoGuarantee);
+        let mut g = QuantumGrid::from_config(config);
+        assert_eq!(g.mine_count, 16);
+        assert!(matches!(
+            g.reveal_cell(0, 0),
+            RevealOutcome::MineDetonated { .. }
+        ));
+    }
+
+    #[test]
+    fn safe_zone_radius_widens_exclusion() {
+        for seed in 0..20 {
+            let config = GridConfig::new(9, 9, 20, seed, "observer").with_safe_zone_radius(2);
+            let mut g = QuantumGrid::from_config(config);
+            g.reveal_cell(4, 4);
+            for y in 2..=6 {
+                for x in 2..=6 {
+                    assert!(!g.mine_map[y * 9 + x], "seed {seed}: mine at ({x},{y})");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn clustered_generator_keeps_first_click_guarantee() {
+        for seed in 0..20 {
+            let config = GridConfig::new(12, 12, 30, seed, "researcher")
+                .with_generator(Generator::Clustered { veins: 0 });
+            let mut g = QuantumGrid::from_config(config);
+            assert!(matches!(
+                g.reveal_cell(5, 5),
+                RevealOutcome::Revealed {
+                    cell: QuantumCell {
+                        state: CellState::Revealed { adjacent_mines: 0 },
+                        ..
+                    }
+                }
+            ));
+            assert_eq!(g.mine_map.iter().filter(|&&m| m).count(), 30);
+        }
+    }
+
+    #[test]
+    fn density_map_shapes_placement_and_hints() {
+        let left_only = Density::Map {
+            width: 2,
+            height: 1,
+            weights: vec![1.0, 0.0],
+        };
+        let config = GridConfig::new(16, 16, 40, 3, "researcher").with_density(left_only);
+        let base = baselines(&config, 40);
+        assert_eq!(base[15], 0.0);
+        assert!((base[0] - 80.0 / 256.0).abs() < 1e-9);
+
+        let mut g = QuantumGrid::from_config(config);
+        g.reveal_cell(12, 8);
+        assert_eq!(g.mine_map.iter().filter(|&&m| m).count(), 40);
+        assert!((0..256).all(|i| !g.mine_map[i] || i % 16 < 8));
+    }
+
+    #[test]
+    fn try_from_config_rejects_overfull_board() {
+        let config = GridConfig::new(4, 4, 10, 0, "observer");
+        assert!(matches!(
+            QuantumGrid::try_from_config(config.clone()),
+            Err(ConfigError::TooManyMines {
+                requested: 10,
+                max: 7
+            })
+        ));
+        // The relaxed constructor still clamps.
+        assert_eq!(QuantumGrid::from_config(config).mine_count, 7);
+    }
+
     #[test]
     fn mine_count_matches_requested() {
         for seed in 0..20 {
@@ -733,6 +3087,368 @@ mod tests {
         assert!(matches!(outcome, RevealOutcome::NoChargesRemaining));
     }
 
+    #[test]
+    fn mines_remaining_tracks_containment() {
+        let mut g = make_grid(8, 8, 10);
+        assert_eq!(g.mines_remaining(), 10);
+        g.reveal_cell(0, 0);
+        let mine_idx = g.mine_map.iter().position(|&m| m).unwrap();
+        let (mx, my) = g.coords_of(mine_idx);
+        g.contain_cell(mx, my);
+        assert_eq!(g.mines_remaining(), 9);
+        assert_eq!(g.snapshot().mines_remaining, 9);
+    }
+
+    #[test]
+    fn spare_policy_grants_extra_charges() {
+        let config = GridConfig::new(8, 8, 10, 42, "observer")
+            .with_charge_policy(ChargePolicy::Spare { extra: 3 });
+        let g = QuantumGrid::from_config(config);
+        assert_eq!(g.containment_charges, 13);
+    }
+
+    #[test]
+    fn regenerating_policy_refunds_after_reveals() {
+        let config = GridConfig::new(8, 8, 10, 42, "observer")
+            .with_charge_policy(ChargePolicy::Regenerating { every: 2 });
+        let mut g = QuantumGrid::from_config(config);
+        g.reveal_cell(0, 0); // first safe reveal
+        let unresolved_safe = |g: &QuantumGrid| {
+            let idx = g
+                .cells
+                .iter()
+                .position(|c| {
+                    matches!(c.state, CellState::Superposition { .. })
+                        && !g.mine_map[(c.y * g.width + c.x) as usize]
+                })
+                .unwrap();
+            g.coords_of(idx)
+        };
+
+        // Waste a charge on a safe cell.
+        let (sx, sy) = unresolved_safe(&g);
+        g.contain_cell(sx, sy);
+        assert_eq!(g.containment_charges, 9);
+
+        // Second safe reveal earns it back.
+        let (sx, sy) = unresolved_safe(&g);
+        g.reveal_cell(sx, sy);
+        assert_eq!(g.containment_charges, 10);
+
+        // Never exceeds the mines still uncontained.
+        for _ in 0..2 {
+            let (sx, sy) = unresolved_safe(&g);
+            g.reveal_cell(sx, sy);
+        }
+        assert_eq!(g.containment_charges, 10);
+    }
+
+    #[test]
+    fn marks_are_pure_annotation() {
+        let mut g = make_grid(8, 8, 10);
+        let before = g.clone();
+        assert_eq!(g.set_mark(3, 3, Mark::Flag), Ok(Mark::None));
+        assert_eq!(g.set_mark(3, 3, Mark::Question), Ok(Mark::Flag));
+        let idx = g.index_of(3, 3).unwrap();
+        assert_eq!(g.snapshot().cells[idx].mark, Mark::Question);
+        // Nothing else moved.
+        assert_eq!(g.containment_charges, before.containment_charges);
+        assert_eq!(g.cells[idx].state, before.cells[idx].state);
+        assert!(!g.mines_placed);
+        // Undo by restoring the previous mark.
+        assert_eq!(g.set_mark(3, 3, Mark::Flag), Ok(Mark::Question));
+        assert!(g.set_mark(99, 0, Mark::Flag).is_err());
+    }
+
+    #[test]
+    fn notes_round_trip_but_stay_out_of_snapshot() {
+        let mut g = make_grid(8, 8, 10);
+        let note = CellNote::Number(0.3);
+        assert_eq!(g.set_note(2, 5, Some(note.clone())), Ok(None));
+        assert_eq!(g.note(2, 5), Some(&note));
+        assert!(g
+            .set_note(2, 5, Some(CellNote::Number(f64::INFINITY)))
+            .is_err());
+        assert_eq!(g.note(2, 5), Some(&note));
+
+        let restored: QuantumGrid =
+            serde_json::from_str(&serde_json::to_string(&g).unwrap()).unwrap();
+        assert_eq!(restored.note(2, 5), Some(&note));
+        let snapshot = serde_json::to_string(&g.snapshot()).unwrap();
+        assert!(!snapshot.contains("notes"));
+
+        assert_eq!(g.set_note(2, 5, None), Ok(Some(note)));
+        assert_eq!(g.note(2, 5), None);
+    }
+
+    /// Reveal every safe Superposition cell directly (ignores assists).
+    fn reveal_all_safe(g: &mut QuantumGrid) {
+        for i in 0..g.cells.len() {
+            let (x, y) = g.coords_of(i);
+            if !g.mine_map[i] && matches!(g.cells[i].state, CellState::Superposition { .. }) {
+                g.reveal_cell(x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn auto_contain_finishes_the_board() {
+        let config = GridConfig::new(5, 5, 2, 100, "observer").with_auto_contain(true);
+        let mut g = QuantumGrid::from_config(config);
+        g.reveal_cell(2, 2);
+        g.entanglement.pairs.clear();
+        let charges = g.containment_charges;
+        reveal_all_safe(&mut g);
+
+        assert!(g.won);
+        assert_eq!(g.mines_remaining(), 0);
+        assert_eq!(
+            g.containment_charges, charges,
+            "assist must not spend charges"
+        );
+        let events = g.drain_events();
+        assert_eq!(events.last(), Some(&GameEvent::Won));
+        let assists = assists(events);
+        assert_eq!(assists.len(), 2);
+        assert!(assists
+            .iter()
+            .all(|e| matches!(e, GameEvent::AutoContained { .. })));
+        assert!(g.drain_events().is_empty());
+    }
+
+    #[test]
+    fn auto_contain_counts_spared_detonations() {
+        let config = GridConfig::new(8, 8, 10, 42, "observer")
+            .with_auto_contain(true)
+            .with_rule(Rule::Lives { remaining: 1 });
+        let mut g = QuantumGrid::from_config(config);
+        g.reveal_cell(0, 0);
+        g.entanglement.pairs.clear();
+        let first = g.mine_map.iter().position(|&m| m).unwrap();
+        let (x, y) = g.coords_of(first);
+        g.reveal_cell(x, y);
+        assert!(!g.game_over);
+        assert_eq!(g.mines_remaining(), 9);
+
+        let safe_left = |g: &QuantumGrid| {
+            (0..g.cells.len())
+                .filter(|&i| {
+                    !g.mine_map[i] && matches!(g.cells[i].state, CellState::Superposition { .. })
+                })
+                .collect::<Vec<_>>()
+        };
+        while safe_left(&g).len() > 1 {
+            let (x, y) = g.coords_of(safe_left(&g)[0]);
+            g.reveal_cell(x, y);
+        }
+        // The mines and one safe cell left: nothing to auto-contain yet.
+        assert!(!g.won);
+        assert!(!g.cells.iter().any(|c| c.state == CellState::Contained));
+
+        reveal_all_safe(&mut g);
+        assert!(g.won);
+        assert_eq!(g.mines_remaining(), 0);
+    }
+
+    #[test]
+    fn auto_contain_is_opt_in() {
+        let mut g = QuantumGrid::new(5, 5, 2, 100, "observer");
+        g.reveal_cell(2, 2);
+        g.entanglement.pairs.clear();
+        reveal_all_safe(&mut g);
+        assert!(!g.won);
+        assert_eq!(g.mines_remaining(), 2);
+        assert!(assists(g.drain_events()).is_empty());
+    }
+
+    #[test]
+    fn every_outcome_reaches_every_sink() {
+        let mut g = QuantumGrid::new(8, 8, 10, 42, "observer");
+        g.reveal_cell(0, 0);
+        let mine = g.mine_map.iter().position(|&m| m).unwrap();
+        let (x, y) = g.coords_of(mine);
+        g.reveal_cell(x, y);
+
+        let mut collected = Vec::new();
+        let mut reveals = 0;
+        let mut count =
+            |e: &GameEvent| reveals += usize::from(matches!(e, GameEvent::Revealed { .. }));
+        g.dispatch_events(&mut [&mut collected, &mut count]);
+        let revealed = g
+            .cells
+            .iter()
+            .filter(|c| matches!(c.state, CellState::Revealed { .. }))
+            .count();
+        assert!(revealed > 0);
+        assert_eq!(reveals, revealed);
+        assert!(collected.contains(&GameEvent::Detonated { x, y }));
+        assert!(g.drain_events().is_empty());
+    }
+
+    #[test]
+    fn lives_rule_spares_detonations_until_spent() {
+        let config =
+            GridConfig::new(8, 8, 10, 42, "observer").with_rule(Rule::Lives { remaining: 1 });
+        let mut g = QuantumGrid::from_config(config);
+        g.reveal_cell(0, 0);
+        g.entanglement.pairs.clear();
+        let mut mines = (0..g.cells.len()).filter(|&i| g.mine_map[i]);
+        let (first, second) = (mines.next().unwrap(), mines.next().unwrap());
+
+        let (x, y) = g.coords_of(first);
+        assert_eq!(g.reveal_cell(x, y), RevealOutcome::MineDetonated { x, y });
+        assert_eq!(g.cells[first].state, CellState::Detonated);
+        assert!(!g.game_over);
+        assert_eq!(g.rules.lives(), Some(0));
+
+        let (x, y) = g.coords_of(second);
+        g.reveal_cell(x, y);
+        assert!(g.game_over);
+    }
+
+    /// The events the engine raised on its own, without cell outcomes.
+    fn assists(events: Vec<GameEvent>) -> Vec<GameEvent> {
+        events.into_iter().filter(|e| !e.is_outcome()).collect()
+    }
+
+    /// Contain every mine directly (ignores assists).
+    fn contain_all_mines(g: &mut QuantumGrid) {
+        for i in 0..g.cells.len() {
+            let (x, y) = g.coords_of(i);
+            if g.mine_map[i] && matches!(g.cells[i].state, CellState::Superposition { .. }) {
+                g.contain_cell(x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn ground_truth_auto_reveal_opens_rest_of_board() {
+        let config =
+            GridConfig::new(8, 8, 10, 42, "observer").with_auto_reveal(AutoReveal::GroundTruth);
+        let mut g = QuantumGrid::from_config(config);
+        g.reveal_cell(0, 0);
+        g.entanglement.pairs.clear();
+        contain_all_mines(&mut g);
+        assert!(g.won);
+        assert!(assists(g.drain_events())
+            .iter()
+            .all(|e| matches!(e, GameEvent::AutoRevealed { .. })));
+    }
+
+    #[test]
+    fn trusting_auto_reveal_detonates_on_wrong_flag() {
+        let config =
+            GridConfig::new(8, 8, 10, 42, "observer").with_auto_reveal(AutoReveal::Trusting);
+        let mut g = QuantumGrid::from_config(config);
+        g.reveal_cell(0, 0);
+        g.entanglement.pairs.clear();
+        // Flag nine real mines and one safe cell, leaving one mine unflagged.
+        let mines: Vec<usize> = (0..64)
+            .filter(|&i| {
+                g.mine_map[i] && matches!(g.cells[i].state, CellState::Superposition { .. })
+            })
+            .collect();
+        let safe = (0..64)
+            .find(|&i| {
+                !g.mine_map[i] && matches!(g.cells[i].state, CellState::Superposition { .. })
+            })
+            .unwrap();
+        for &i in mines.iter().skip(1).chain(std::iter::once(&safe)) {
+            let (x, y) = g.coords_of(i);
+            g.set_mark(x, y, Mark::Flag).unwrap();
+        }
+        // Assists run after the next action.
+        let other = (0..64)
+            .find(|&i| {
+                i != safe
+                    && !g.mine_map[i]
+                    && matches!(g.cells[i].state, CellState::Superposition { .. })
+            })
+            .unwrap();
+        let (x, y) = g.coords_of(other);
+        g.reveal_cell(x, y);
+        assert!(g.game_over);
+        assert!(g
+            .drain_events()
+            .iter()
+            .any(|e| matches!(e, GameEvent::AutoDetonated { .. })));
+    }
+
+    #[test]
+    fn trusting_auto_reveal_hands_off_to_auto_contain() {
+        let config = GridConfig::new(8, 8, 10, 42, "observer")
+            .with_auto_reveal(AutoReveal::Trusting)
+            .with_auto_contain(true);
+        let mut g = QuantumGrid::from_config(config);
+        g.reveal_cell(0, 0);
+        g.entanglement.pairs.clear();
+        for i in 0..64 {
+            if g.mine_map[i] {
+                let (x, y) = g.coords_of(i);
+                g.set_mark(x, y, Mark::Flag).unwrap();
+            }
+        }
+        let safe = (0..64)
+            .find(|&i| {
+                !g.mine_map[i] && matches!(g.cells[i].state, CellState::Superposition { .. })
+            })
+            .unwrap();
+        let (x, y) = g.coords_of(safe);
+        g.reveal_cell(x, y);
+        assert!(g.won);
+        assert_eq!(g.mines_remaining(), 0);
+    }
+
+    #[test]
+    fn timed_actions_are_validated_and_recorded() {
+        let mut g = make_grid(8, 8, 10);
+        assert!(g.reveal_cell_at(0, 0, 1_000).is_ok());
+        // Rejected outcomes don't count as actions.
+        assert_eq!(
+            g.reveal_cell_at(0, 0, 1_200),
+            Ok(RevealOutcome::AlreadyResolved)
+        );
+        assert!(matches!(
+            g.reveal_cell_at(0, 0, 900),
+            Err(ClockError::NonMonotonic { .. })
+        ));
+        let mine_idx = g.mine_map.iter().position(|&m| m).unwrap();
+        let (mx, my) = g.coords_of(mine_idx);
+        g.reveal_cell_at(mx, my, 4_000).unwrap();
+        let stats = g.timing_stats();
+        assert_eq!(stats.actions, 2);
+        assert_eq!(stats.elapsed_ms, 3_000);
+        assert!(stats.finished);
+    }
+
+    #[test]
+    fn apply_dispatches_and_validates() {
+        let mut g = make_grid(8, 8, 10);
+        assert_eq!(
+            g.validate_action(&Action::Reveal { x: 9, y: 0 }),
+            Err(IllegalAction::OutOfBounds)
+        );
+        assert!(g.validate_action(&Action::Reveal { x: 0, y: 0 }).is_ok());
+        let result = g.apply(Action::Reveal { x: 0, y: 0 });
+        assert!(matches!(
+            result,
+            ActionResult::Outcome {
+                outcome: RevealOutcome::Revealed { .. }
+            }
+        ));
+        assert_eq!(
+            g.validate_action(&Action::Hadamard { x: 0, y: 0 }),
+            Err(IllegalAction::AlreadyResolved)
+        );
+        assert!(g.apply(Action::Hadamard { x: 0, y: 0 }).is_rejection());
+
+        g.game_over = true;
+        assert_eq!(
+            g.validate_action(&Action::MeasureWeak { x: 7, y: 7 }),
+            Err(IllegalAction::GameAlreadyOver)
+        );
+    }
+
     #[test]
     fn clicking_mine_detonates() {
         let mut g = make_grid(8, 8, 10);
@@ -853,6 +3569,107 @@ mod tests {
         );
     }
 
+    #[test]
+    fn longest_bell_chain_measures_linked_groups() {
+        let mut e = Entanglement::default();
+        assert_eq!(e.longest_bell_chain(), 0);
+        e.add_pair(0, 1, 1.0, LinkType::BellState);
+        e.add_pair(5, 6, 1.0, LinkType::BellState);
+        e.add_pair(6, 7, 1.0, LinkType::BellState);
+        e.add_pair(7, 8, 1.0, LinkType::BellState);
+        e.add_pair(1, 2, 0.5, LinkType::Probabilistic);
+        assert_eq!(e.longest_bell_chain(), 4);
+        let theorist = QuantumGrid::new(16, 16, 50, 1, "theorist");
+        assert_eq!(theorist.entanglement.longest_bell_chain(), 2);
+    }
+
+    #[test]
+    fn positive_bell_correlation_matches_observation() {
+        let mut ent = Entanglement::default();
+        ent.add_correlated_pair(0, 1, 1.0, LinkType::BellState, Correlation::Positive);
+        let pair = &ent.pairs[0];
+        assert!((ent.collapse_partner_probability(pair, true, 0.5) - 1.0).abs() < 1e-10);
+        assert!((ent.collapse_partner_probability(pair, false, 0.5) - 0.0).abs() < 1e-10);
+    }
+
+    #[test]
+    fn theorist_generates_both_correlations() {
+        let g = QuantumGrid::new(16, 16, 50, 1, "theorist");
+        let bell: Vec<_> = g
+            .entanglement
+            .pairs
+            .iter()
+            .filter(|p| p.link_type == LinkType::BellState)
+            .collect();
+        assert!(bell.iter().any(|p| p.correlation == Correlation::Anti));
+        assert!(bell.iter().any(|p| p.correlation == Correlation::Positive));
+    }
+
+    #[test]
+    fn bell_pairs_hold_across_seeds() {
+        let (mut pairs, mut collapses) = ([0, 0], 0);
+        for seed in 0..40 {
+            let mut g = QuantumGrid::new(16, 16, 50, seed, "theorist");
+            g.reveal_cell(8, 8);
+            let bell: Vec<EntanglementPair> = g
+                .entanglement
+                .pairs
+                .iter()
+                .filter(|p| p.link_type == LinkType::BellState)
+                .cloned()
+                .collect();
+            for pair in &bell {
+                let (left, right) = (g.mine_map[pair.left], g.mine_map[pair.right]);
+                assert_eq!(pair.correlation.predict(left), right, "seed {seed}");
+                pairs[pair.correlation as usize] += 1;
+            }
+            assert_eq!(g.mine_map.iter().filter(|&&m| m).count(), 50);
+
+            // Observing a safe end collapses its partner to the predicted
+            // state whenever the link holds.
+            for pair in &bell {
+                let open = |g: &QuantumGrid, i: usize| {
+                    matches!(g.cells[i].state, CellState::Superposition { .. })
+                };
+                if g.game_over
+                    || !open(&g, pair.left)
+                    || !open(&g, pair.right)
+                    || g.mine_map[pair.left]
+                {
+                    continue;
+                }
+                let (x, y) = g.coords_of(pair.left);
+                g.reveal_cell(x, y);
+                if !open(&g, pair.right) {
+                    let mine = matches!(g.cells[pair.right].state, CellState::Contained);
+                    assert_eq!(mine, pair.correlation.predict(false), "seed {seed}");
+                    collapses += 1;
+                }
+            }
+        }
+        assert!(pairs[0] > 50 && pairs[1] > 50, "{pairs:?}");
+        assert!(collapses > 20, "{collapses}");
+    }
+
+    #[test]
+    fn swaps_after_placement_keep_links_true() {
+        let mut swapped = 0;
+        for seed in 0..20 {
+            let mut g = QuantumGrid::from_config(
+                GridConfig::new(10, 10, 10, seed, "theorist").with_swap_charges(1),
+            );
+            g.reveal_cell(9, 9);
+            // Theorist boards Bell-link 0–2 and 10–12.
+            if g.swap_entanglement(0, 0, 0, 1).is_err() {
+                continue;
+            }
+            let (pair, partner) = g.entanglement.bell_partner_of(2).unwrap();
+            assert_eq!(pair.correlation.predict(g.mine_map[2]), g.mine_map[partner]);
+            swapped += 1;
+        }
+        assert!(swapped > 5, "{swapped}");
+    }
+
     #[test]
     fn reveal_cell_auto_resolves_bell_partner() {
         // Build a small grid with a manually-injected BellState pair.
@@ -898,6 +3715,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn weak_bell_links_sometimes_degrade() {
+        let mut collapsed = 0;
+        let mut degraded = 0;
+        for seed in 0..40 {
+            let mut g = QuantumGrid::new(8, 8, 10, seed, "observer");
+            g.reveal_cell(0, 0);
+            let open: Vec<usize> = (0..64)
+                .filter(|&i| {
+                    !g.mine_map[i] && matches!(g.cells[i].state, CellState::Superposition { .. })
+                })
+                .collect();
+            g.entanglement.pairs.clear();
+            g.entanglement
+                .add_pair(open[0], open[1], 0.5, LinkType::BellState);
+            g.drain_events();
+
+            let (x, y) = g.coords_of(open[0]);
+            g.reveal_cell(x, y);
+            let events = g.drain_events();
+            if events
+                .iter()
+                .any(|e| matches!(e, GameEvent::BellCollapsed { .. }))
+            {
+                assert!(!matches!(
+                    g.cells[open[1]].state,
+                    CellState::Superposition { .. }
+                ));
+                collapsed += 1;
+            } else {
+                assert!(events
+                    .iter()
+                    .any(|e| matches!(e, GameEvent::BellDegraded { .. })));
+                assert!(matches!(
+                    g.cells[open[1]].state,
+                    CellState::Superposition { .. }
+                ));
+                degraded += 1;
+            }
+        }
+        assert!(collapsed > 0 && degraded > 0, "{collapsed} vs {degraded}");
+    }
+
     #[test]
     fn ghz_chain_propagation() {
         // Test multi-qubit chain: A → B → C all collapse from revealing A.
@@ -935,8 +3795,30 @@ mod tests {
 
         // Reveal cell A
         let (ax, ay) = g.coords_of(a);
+        g.drain_events();
         g.reveal_cell(ax, ay);
 
+        // The cascade is traced in propagation order: A → B → C.
+        let trace: Vec<(usize, u32, usize)> = g
+            .drain_events()
+            .into_iter()
+            .filter_map(|event| match event {
+                GameEvent::BellCollapsed {
+                    x,
+                    y,
+                    depth,
+                    cause_x,
+                    cause_y,
+                } => Some((
+                    g.index_of(x, y).unwrap(),
+                    depth,
+                    g.index_of(cause_x, cause_y).unwrap(),
+                )),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(trace, vec![(b, 1, a), (c, 2, b)]);
+
         // B should now be resolved (no longer Superposition)
         assert!(
             !matches!(g.cells[b].state, CellState::Superposition { .. }),
@@ -952,6 +3834,78 @@ mod tests {
         );
     }
 
+    #[test]
+    fn moves_queue_sound_cues() {
+        let mut g = make_grid(8, 8, 10);
+        g.reveal_cell(0, 0);
+        let opened = g.resolved_cells();
+        assert_eq!(g.drain_cues(), vec![SoundCue::for_resolution(opened, 64)]);
+
+        let mine = g.mine_map.iter().position(|&m| m).unwrap();
+        let (x, y) = g.coords_of(mine);
+        g.measure_weak(x, y).unwrap();
+        assert!(g.drain_cues().is_empty(), "tools make no sound");
+        g.reveal_cell(x, y);
+        let cues = g.drain_cues();
+        assert_eq!(cues.len(), 1);
+        assert!(matches!(cues[0], SoundCue::Detonation { intensity } if intensity > 0.0));
+    }
+
+    /// Probability hint of a Superposition cell.
+    fn hint(g: &QuantumGrid, index: usize) -> f64 {
+        match g.cells[index].state {
+            CellState::Superposition { probability } => probability,
+            ref other => panic!("expected superposition, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn probabilistic_ripple_decays_around_a_ring() {
+        // Ring 0-1-2-3-4-5-0 on the bottom row; observe cell 0.
+        let config =
+            GridConfig::new(8, 8, 10, 42, "observer").with_link_propagation(LinkPropagation {
+                max_depth: 3,
+                decay: 0.5,
+            });
+        let mut g = QuantumGrid::from_config(config);
+        g.entanglement.pairs.clear();
+        let ring: Vec<usize> = (56..62).collect();
+        for i in 0..ring.len() {
+            let next = ring[(i + 1) % ring.len()];
+            g.entanglement
+                .add_pair(ring[i], next, 0.8, LinkType::Probabilistic);
+        }
+        for &i in &ring {
+            g.cells[i].state = CellState::Superposition { probability: 0.3 };
+        }
+
+        g.ripple_probabilistic(ring[0], true);
+
+        // Mine observed: target is 0.7, so shift = 0.4 * effective strength.
+        let expected = |strength: f64| 0.3 + 0.4 * strength;
+        for (hop, cells) in [(1, [1, 5]), (2, [2, 4])] {
+            let strength = 0.8 * 0.5_f64.powi(hop - 1);
+            for c in cells {
+                assert!((hint(&g, ring[c]) - expected(strength)).abs() < 1e-10);
+            }
+        }
+        // The antipode is reached from both sides but adjusted only once.
+        assert!((hint(&g, ring[3]) - expected(0.2)).abs() < 1e-10);
+    }
+
+    #[test]
+    fn default_ripple_only_reaches_direct_partners() {
+        let mut g = make_grid(8, 8, 10);
+        g.entanglement.pairs.clear();
+        g.entanglement
+            .add_pair(56, 57, 0.5, LinkType::Probabilistic);
+        g.entanglement
+            .add_pair(57, 58, 0.5, LinkType::Probabilistic);
+        let before = hint(&g, 58);
+        g.ripple_probabilistic(56, true);
+        assert!((hint(&g, 58) - before).abs() < 1e-12);
+    }
+
     #[test]
     fn hadamard_flips_probability() {
         let mut g = make_grid(8, 8, 10);
@@ -1043,4 +3997,828 @@ mod tests {
             "Probabilistic: expected 0.3, got {result}"
         );
     }
+
+    #[test]
+    fn cells_in_rect_clips_to_the_board() {
+        let grid = QuantumGrid::new(10, 8, 10, 42, "observer");
+        let window: Vec<_> = grid
+            .cells_in_rect(8, 6, 20, 20)
+            .map(|c| (c.x, c.y))
+            .collect();
+        assert_eq!(window, vec![(8, 6), (9, 6), (8, 7), (9, 7)]);
+        assert_eq!(grid.cells_in_rect(0, 0, 10, 8).count(), 80);
+        assert_eq!(grid.cells_in_rect(5, 0, 3, 8).count(), 0);
+        assert_eq!(grid.cells_in_rect(11, 9, 12, 12).count(), 0);
+    }
+
+    #[test]
+    fn mutations_mark_their_chunks_dirty() {
+        let mut grid = QuantumGrid::new(40, 40, 10, 42, "observer");
+        grid.reveal_cell(20, 20);
+        let seen = grid.chunks.revision();
+        assert!(seen > 0);
+        assert!(grid.chunks.changed_since(seen).is_empty());
+
+        let far = grid
+            .cells
+            .iter()
+            .position(|c| {
+                matches!(c.state, CellState::Superposition { .. }) && c.x < 16 && c.y < 16
+            })
+            .unwrap();
+        let (x, y) = grid.coords_of(far);
+        grid.set_mark(x, y, Mark::Flag).unwrap();
+        assert_eq!(
+            grid.chunks.changed_since(seen),
+            vec![ChunkCoord { cx: 0, cy: 0 }]
+        );
+    }
+
+    #[test]
+    fn state_hash_tracks_play_but_not_clock_or_notes() {
+        let mut a = QuantumGrid::new(9, 9, 10, 42, "researcher");
+        let mut b = a.clone();
+        assert_eq!(a.state_hash(), b.state_hash());
+
+        a.reveal_cell(4, 4);
+        assert_ne!(a.state_hash(), b.state_hash());
+        b.reveal_cell_at(4, 4, 1_000).unwrap();
+        b.set_note(0, 0, Some(CellNote::Number(0.5))).unwrap();
+        assert_eq!(a.state_hash(), b.state_hash());
+
+        let round_trip: QuantumGrid =
+            serde_json::from_str(&serde_json::to_string(&a).unwrap()).unwrap();
+        assert_eq!(round_trip.state_hash(), a.state_hash());
+    }
+
+    #[test]
+    fn state_hash_covers_state_off_the_board() {
+        let mut base = QuantumGrid::new(9, 9, 10, 42, "researcher");
+        base.reveal_cell(4, 4);
+        type Tweak = (&'static str, fn(&mut QuantumGrid));
+        let tweaks: Vec<Tweak> = vec![
+            ("score", |g| g.score += 1),
+            ("decoherence lifetime", |g| g.decoherence.lifetime += 1),
+            ("decoherence cell", |g| g.decoherence.cells = vec![0, 5]),
+            ("combo streak", |g| g.combo.streak += 1),
+            ("combo multiplier", |g| g.combo.multiplier += 1),
+            ("isotope", |g| {
+                g.isotopes.insert(0, Isotope::new(2));
+            }),
+            ("shields", |g| g.inventory.shields += 1),
+            ("integrity", |g| {
+                g.integrity.cells.insert(0, 50);
+            }),
+            ("refund credit", |g| g.integrity.refund_credit += 10),
+            ("recalc epoch", |g| g.recalc_epoch += 1),
+            ("mercy", |g| g.mercy_used = !g.mercy_used),
+            ("objective moves", |g| g.objectives.moves += 1),
+            ("turn", |g| g.turns.turn += 1),
+            ("action points", |g| g.turns.action_points += 1),
+            ("lives", |g| {
+                g.rules.rules.push(Rule::Lives { remaining: 1 })
+            }),
+            ("daemon generator", |g| g.daemon = NoiseDaemon::new(7)),
+            ("splits", |g| {
+                g.splits = Splits::new(&[SplitTrigger::FirstCascade])
+            }),
+            ("daemon budget", |g| {
+                g.config.daemon = Some(DaemonRules::default());
+            }),
+            ("objective", |g| {
+                g.objectives = Objectives::new(&[Objective::ResolveAll]);
+            }),
+        ];
+        for (name, tweak) in tweaks {
+            let mut g = base.clone();
+            tweak(&mut g);
+            assert_ne!(g.state_hash(), base.state_hash(), "{name}");
+        }
+        let mut sized = base.clone();
+        sized.decoherence.cells = vec![0; 81];
+        assert_eq!(sized.state_hash(), base.state_hash());
+    }
+
+    #[test]
+    fn decoherence_overflow_collapses_a_cell() {
+        let config = GridConfig::new(9, 9, 10, 42, "observer").with_decoherence_limit(300);
+        let mut grid = QuantumGrid::from_config(config);
+        grid.reveal_cell(4, 4);
+        grid.drain_events();
+        // Alternate between two cells so the Zeno effect never kicks in.
+        let targets: Vec<usize> = (0..grid.cells.len())
+            .filter(|&i| matches!(grid.cells[i].state, CellState::Superposition { .. }))
+            .take(2)
+            .collect();
+
+        let mut event = None;
+        for round in 0..100 {
+            let (x, y) = grid.coords_of(targets[round % 2]);
+            grid.measure_weak(x, y).unwrap();
+            event = grid
+                .drain_events()
+                .into_iter()
+                .find(|e| matches!(e, GameEvent::Decohered { .. }));
+            if event.is_some() {
+                break;
+            }
+        }
+        let Some(GameEvent::Decohered { x, y, mine }) = event else {
+            panic!("meter never overflowed: {:?}", grid.decoherence);
+        };
+        assert_eq!(grid.decoherence.penalties, 1);
+        assert!(grid.decoherence.lifetime >= 300);
+        let per_cell: u32 = targets.iter().map(|&i| grid.decoherence.cell(i)).sum();
+        assert_eq!(per_cell, grid.decoherence.lifetime);
+        let state = &grid.cells[(y * 9 + x) as usize].state;
+        if mine {
+            assert_eq!(*state, CellState::Detonated);
+            assert!(grid.game_over);
+        } else {
+            assert!(matches!(state, CellState::Revealed { .. }));
+        }
+    }
+
+    #[test]
+    fn advancing_time_decoheres_and_decays_links() {
+        let timed = TimedRules {
+            ambient_decoherence: 150,
+            link_half_life_ms: Some(1_000),
+            ..TimedRules::default()
+        };
+        let config = GridConfig::new(9, 9, 10, 42, "theorist")
+            .with_decoherence_limit(300)
+            .with_timed(timed);
+        let mut grid = QuantumGrid::from_config(config);
+        grid.reveal_cell(4, 4);
+        grid.drain_events();
+        let strengths: Vec<f64> = grid.entanglement.pairs.iter().map(|p| p.strength).collect();
+        assert!(!strengths.is_empty());
+
+        // Many small steps add up exactly like one big one.
+        let mut stepped = grid.clone();
+        for _ in 0..150 {
+            stepped.advance(10);
+        }
+        grid.advance(1_500);
+        assert_eq!(grid.time_ms, 1_500);
+        assert_eq!(stepped.state_hash(), grid.state_hash());
+
+        assert_eq!(grid.decoherence.lifetime, 225);
+        assert_eq!(grid.decoherence.penalties, 0);
+        let halved: Vec<f64> = strengths.iter().map(|s| s * 0.5).collect();
+        let now: Vec<f64> = grid.entanglement.pairs.iter().map(|p| p.strength).collect();
+        assert_eq!(now, halved);
+
+        grid.advance(500);
+        assert_eq!(grid.decoherence.penalties, 1);
+        assert!(grid
+            .drain_events()
+            .iter()
+            .any(|e| matches!(e, GameEvent::Decohered { .. })));
+    }
+
+    #[test]
+    fn precision_trades_reading_noise_for_drift() {
+        let config =
+            GridConfig::new(9, 9, 10, 42, "researcher").with_measurement_curve(MeasurementCurve {
+                noise: 0.2,
+                min_drift: 0.0,
+                max_drift: 0.1,
+            });
+        let mut grid = QuantumGrid::from_config(config);
+        grid.reveal_cell(4, 4);
+        let (x, y) = (0..81)
+            .map(|i| grid.coords_of(i))
+            .find(|&(x, y)| {
+                matches!(
+                    grid.cells[(y * 9 + x) as usize].state,
+                    CellState::Superposition { .. }
+                )
+            })
+            .unwrap();
+        let probability = |grid: &QuantumGrid| match grid.cells[(y * 9 + x) as usize].state {
+            CellState::Superposition { probability } => probability,
+            _ => unreachable!(),
+        };
+
+        // Zero precision: noisy reading, no disturbance at all.
+        let before = probability(&grid);
+        let reading = grid.measure_weak_with_precision(x, y, 0.0).unwrap();
+        assert!((reading - before).abs() <= 0.2);
+        assert_eq!(probability(&grid), before);
+        assert_eq!(grid.decoherence.lifetime, 0);
+
+        // Full precision: exact reading, paid for with drift.
+        let before = probability(&grid);
+        let action = Action::MeasureWeakWithPrecision { x, y, percent: 100 };
+        assert_eq!(
+            grid.apply(action),
+            ActionResult::Probability { value: before }
+        );
+        assert!((probability(&grid) - before).abs() <= 0.1);
+
+        assert!(grid.measure_weak_with_precision(x, y, 1.5).is_err());
+    }
+
+    #[test]
+    fn nearby_hadamards_interfere_and_fade() {
+        let mut grid = QuantumGrid::new(12, 12, 10, 42, "observer");
+        let hint =
+            |grid: &QuantumGrid, x: u32, y: u32| match grid.cells[(y * 12 + x) as usize].state {
+                CellState::Superposition { probability } => probability,
+                _ => unreachable!(),
+            };
+
+        grid.apply_hadamard(3, 5).unwrap();
+        let (between, beside) = (hint(&grid, 4, 5), hint(&grid, 3, 4));
+        grid.apply_hadamard(5, 5).unwrap();
+        assert!(hint(&grid, 4, 5) > between, "constructive overlap");
+        assert!(hint(&grid, 3, 4) < beside, "destructive overlap");
+
+        // Once both waves have faded, a third Hadamard ripples nothing.
+        for _ in 0..interference::LIFETIME {
+            grid.measure_weak(11, 11).unwrap();
+        }
+        assert!(grid.interference.waves.is_empty());
+        let far = hint(&grid, 4, 5);
+        grid.apply_hadamard(4, 4).unwrap();
+        assert_eq!(hint(&grid, 4, 5), far);
+    }
+
+    #[test]
+    fn zeno_freeze_shields_a_cell_until_it_thaws() {
+        let config = GridConfig::new(9, 9, 10, 42, "observer").with_decoherence_limit(100_000);
+        let mut grid = QuantumGrid::from_config(config);
+        grid.reveal_cell(4, 4);
+        grid.drain_events();
+        let target = (0..81)
+            .find(|&i| matches!(grid.cells[i].state, CellState::Superposition { .. }))
+            .unwrap();
+        let (x, y) = grid.coords_of(target);
+
+        for _ in 0..zeno::STREAK {
+            grid.measure_weak(x, y).unwrap();
+        }
+        assert_eq!(grid.drain_events(), vec![GameEvent::ZenoFrozen { x, y }]);
+        assert_eq!(
+            grid.snapshot().frozen,
+            vec![FrozenCell {
+                x,
+                y,
+                moves_left: zeno::FROZEN_MOVES
+            }]
+        );
+
+        // Frozen: measuring costs nothing and moves nothing.
+        let (before, meter) = (grid.cells[target].clone(), grid.decoherence.lifetime);
+        grid.measure_weak_with_precision(x, y, 1.0).unwrap();
+        assert_eq!(grid.cells[target], before);
+        assert_eq!(grid.decoherence.lifetime, meter);
+
+        // Other moves run the freeze down.
+        let other = (0..81)
+            .rev()
+            .find(|&i| matches!(grid.cells[i].state, CellState::Superposition { .. }))
+            .unwrap();
+        let (ox, oy) = grid.coords_of(other);
+        for _ in 0..zeno::FROZEN_MOVES {
+            grid.apply_hadamard(ox, oy).unwrap();
+        }
+        assert!(grid.frozen_cells().is_empty());
+    }
+
+    #[test]
+    fn swapping_entanglement_relinks_bell_partners() {
+        // Theorist boards Bell-link 0–2 (anti) and 10–12 (positive).
+        let mut grid = QuantumGrid::from_config(
+            GridConfig::new(10, 10, 10, 42, "theorist").with_swap_charges(1),
+        );
+        assert_eq!(
+            grid.swap_entanglement(0, 0, 5, 0),
+            Err("both cells need a Bell partner")
+        );
+        let hash = grid.state_hash();
+        assert_eq!(
+            grid.apply(Action::SwapEntanglement {
+                x: 0,
+                y: 0,
+                other_x: 0,
+                other_y: 1,
+            }),
+            ActionResult::Relinked {
+                x: 2,
+                y: 0,
+                partner_x: 2,
+                partner_y: 1,
+            }
+        );
+        assert_ne!(grid.state_hash(), hash);
+        assert_eq!(grid.swap_charges, 0);
+        let (pair, partner) = grid.entanglement.bell_partner_of(2).unwrap();
+        assert_eq!(partner, 12);
+        assert_eq!(pair.correlation, Correlation::Anti);
+        assert!(grid.entanglement.bell_partner_of(0).is_none());
+        assert_eq!(
+            grid.drain_events(),
+            vec![GameEvent::EntanglementSwapped {
+                x: 2,
+                y: 0,
+                partner_x: 2,
+                partner_y: 1,
+            }]
+        );
+
+        // The charge is spent.
+        let action = Action::SwapEntanglement {
+            x: 2,
+            y: 0,
+            other_x: 4,
+            other_y: 2,
+        };
+        assert_eq!(
+            grid.validate_action(&action),
+            Err(IllegalAction::NoChargesRemaining)
+        );
+        assert!(grid.apply(action).is_rejection());
+    }
+
+    #[test]
+    fn teleport_moves_a_mine_and_consumes_a_bell_pair() {
+        let mut grid = QuantumGrid::new(10, 10, 15, 42, "theorist");
+        assert_eq!(grid.teleport(0, 0, 9, 9), Err("board not generated yet"));
+        grid.reveal_cell(5, 5);
+        let unresolved =
+            |g: &QuantumGrid, i: usize| matches!(g.cells[i].state, CellState::Superposition { .. });
+        // A mine, given a Bell partner, and a safe cell to send it to.
+        let src = (0..100)
+            .find(|&i| grid.mine_map[i] && unresolved(&grid, i))
+            .unwrap();
+        let dst = (0..100)
+            .rev()
+            .find(|&i| !grid.mine_map[i] && unresolved(&grid, i))
+            .unwrap();
+        let partner = (0..100)
+            .find(|&i| i != src && i != dst && unresolved(&grid, i))
+            .unwrap();
+        grid.entanglement
+            .add_pair(src, partner, 1.0, LinkType::BellState);
+        let ((sx, sy), (dx, dy)) = (grid.coords_of(src), grid.coords_of(dst));
+        let links = grid.entanglement.pairs.len();
+
+        let CellState::Superposition { probability } = grid.cells[src].state else {
+            unreachable!()
+        };
+        let hints = grid.get_probability_cloud();
+        assert_eq!(grid.teleport(sx, sy, dx, dy), Ok(probability));
+        assert!(grid.mine_map[dst] && !grid.mine_map[src]);
+        // Only hints next to the two cells are recalculated, in a new epoch.
+        assert_eq!(grid.recalc_epoch, 1);
+        let near = |i: usize| neighbors(10, 10, i).any(|n| n == src || n == dst);
+        let after = grid.get_probability_cloud();
+        assert!((0..100)
+            .filter(|&i| i != src && i != dst && !near(i))
+            .all(|i| after[i] == hints[i]));
+        assert!((0..100).any(|i| near(i) && unresolved(&grid, i) && after[i] != hints[i]));
+        assert_eq!(grid.mine_count, 15);
+        assert!(matches!(grid.cells[src].state, CellState::Revealed { .. }));
+        assert_eq!(grid.entanglement.pairs.len(), links - 1);
+        for (i, cell) in grid.cells.iter().enumerate() {
+            if let CellState::Revealed { adjacent_mines } = cell.state {
+                let (x, y) = grid.coords_of(i);
+                assert_eq!(adjacent_mines, grid.adjacent_mines(x, y));
+            }
+        }
+        assert!(grid.drain_events().contains(&GameEvent::Teleported {
+            x: sx,
+            y: sy,
+            to_x: dx,
+            to_y: dy,
+            destroyed_mine: false,
+        }));
+        crate::invariants::check(&grid).unwrap();
+
+        // The source is spent.
+        assert_eq!(
+            grid.teleport(sx, sy, dx, dy),
+            Err("cell is already resolved")
+        );
+    }
+
+    #[test]
+    fn oracles_kick_their_circuit_back_onto_row_and_column() {
+        let oracles = Oracles {
+            placed: vec![OracleCell {
+                x: 4,
+                y: 4,
+                circuit: None,
+            }],
+            ..Oracles::default()
+        };
+        let config = GridConfig::new(9, 9, 30, 5, "researcher")
+            .with_first_click(FirstClick::GuaranteedSafe)
+            .with_oracles(oracles);
+        let mut grid = QuantumGrid::from_config(config);
+        grid.entanglement = Shared::default();
+        grid.pregenerate(0, 0).unwrap();
+        assert_eq!(grid.oracles(), vec![(4, 4)]);
+        assert!(!grid.mine_map[40]);
+
+        let before = grid.get_probability_cloud();
+        grid.reveal_cell(4, 4);
+        assert!(grid.oracles().is_empty());
+        assert!(grid
+            .drain_events()
+            .contains(&GameEvent::OracleFired { x: 4, y: 4 }));
+        let mut kicked = 0;
+        let cross = Region::Cross { x: 4, y: 4 };
+        for i in cross.cells(9, 9) {
+            if let CellState::Superposition { probability } = grid.cells[i].state {
+                assert!((probability - (1.0 - before[i])).abs() < 1e-12);
+                kicked += 1;
+            }
+        }
+        assert!(kicked > 0);
+        // Off the cross, nothing moved.
+        assert_eq!(
+            grid.cells[10].state,
+            CellState::Superposition {
+                probability: before[10]
+            }
+        );
+    }
+
+    #[test]
+    fn oracle_rate_sprinkles_oracles_on_safe_cells() {
+        let oracles = Oracles {
+            rate: 1.0,
+            ..Oracles::default()
+        };
+        let mut grid = QuantumGrid::from_config(
+            GridConfig::new(8, 8, 10, 3, "observer").with_oracles(oracles),
+        );
+        assert!(grid.oracles().is_empty());
+        grid.pregenerate(3, 3).unwrap();
+        assert_eq!(grid.oracles().len(), 54);
+        assert!(grid.triggers.keys().all(|&i| !grid.mine_map[i]));
+    }
+
+    #[test]
+    fn quantum_entropy_tells_confident_boards_apart() {
+        let mut grid = make_grid(4, 4, 2);
+        grid.entanglement = Shared::default();
+        let set_all = |grid: &mut QuantumGrid, probability: f64| {
+            for cell in &mut grid.cells {
+                cell.state = CellState::Superposition { probability };
+            }
+        };
+        set_all(&mut grid, 0.5);
+        assert!((grid.quantum_entropy() - 16.0).abs() < 1e-9);
+        set_all(&mut grid, 0.95);
+        let confident = grid.quantum_entropy();
+        assert!(confident < 5.0);
+        assert_eq!(grid.entropy(), 1.0);
+
+        // An unresolved Bell pair adds a bit; resolving one end removes it.
+        grid.entanglement.add_pair(0, 1, 1.0, LinkType::BellState);
+        assert!((grid.quantum_entropy() - confident - 1.0).abs() < 1e-9);
+        grid.cells[1].state = CellState::Contained;
+        assert!(grid.quantum_entropy() < confident);
+        assert_eq!(grid.snapshot().quantum_entropy, grid.quantum_entropy());
+    }
+
+    #[test]
+    fn evaluate_move_predicts_without_playing() {
+        let mut g = make_grid(8, 8, 10);
+        let first = g.evaluate_move(Action::Reveal { x: 0, y: 0 }).unwrap();
+        assert_eq!(first.detonation_risk, 0.0);
+        g.reveal_cell(0, 0);
+
+        let hash = g.state_hash();
+        let mine = g.mine_map.iter().position(|&m| m).unwrap();
+        let (mx, my) = g.coords_of(mine);
+        let risky = g.evaluate_move(Action::Reveal { x: mx, y: my }).unwrap();
+        assert_eq!(risky.detonation_risk, 1.0);
+        let contain = g.evaluate_move(Action::Contain { x: mx, y: my }).unwrap();
+        assert_eq!(contain.detonation_risk, 0.0);
+        assert!(contain.cascade >= 1);
+        assert!(contain.information_gain > 0.0);
+
+        let safe = (0..64)
+            .find(|&i| {
+                !g.mine_map[i] && matches!(g.cells[i].state, CellState::Superposition { .. })
+            })
+            .unwrap();
+        let (sx, sy) = g.coords_of(safe);
+        let open = g.evaluate_move(Action::Reveal { x: sx, y: sy }).unwrap();
+        assert_eq!(open.detonation_risk, 0.0);
+        assert!(open.cascade >= 1);
+        assert_eq!(g.state_hash(), hash);
+
+        assert_eq!(
+            g.evaluate_move(Action::Reveal { x: 0, y: 0 }),
+            Err(IllegalAction::AlreadyResolved)
+        );
+    }
+
+    #[test]
+    fn assist_level_gates_analysis() {
+        let config = GridConfig::new(8, 8, 10, 4, "researcher");
+        let mut g = QuantumGrid::from_config(config.clone().with_assist(AssistLevel::Hints));
+        g.reveal_cell(0, 0);
+        let action = Action::Reveal { x: 7, y: 7 };
+        assert_eq!(g.evaluate_move(action), Err(IllegalAction::AssistDisabled));
+        assert_eq!(
+            g.unscrambled_probabilities(),
+            Err(IllegalAction::AssistDisabled)
+        );
+
+        g.config.assist = AssistLevel::FullAnalysis;
+        assert!(g.evaluate_move(action).is_ok());
+        let raw = g.unscrambled_probabilities().unwrap();
+        for (i, &p) in raw.iter().enumerate() {
+            match g.cells[i].state {
+                CellState::Superposition { .. } => assert!((0.0..=1.0).contains(&p)),
+                CellState::Revealed { .. } => assert_eq!(p, 0.0),
+                _ => assert_eq!(p, 1.0),
+            }
+        }
+    }
+
+    #[test]
+    fn combo_multiplies_score_until_a_wasted_containment() {
+        let config = GridConfig::new(8, 8, 30, 42, "observer").with_combo(ComboRules {
+            reveals_per_step: 2,
+            max_multiplier: 4,
+            charge_every: 3,
+        });
+        let mut g = QuantumGrid::from_config(config);
+        g.reveal_cell(0, 0);
+        g.entanglement.pairs.clear();
+        let mut safe = (0..g.cells.len())
+            .filter(|&i| !g.mine_map[i])
+            .filter(|&i| matches!(g.cells[i].state, CellState::Superposition { .. }))
+            .map(|i| g.coords_of(i))
+            .collect::<Vec<_>>()
+            .into_iter();
+
+        let swaps = g.swap_charges;
+        let mut score = g.score;
+        for _ in 0..2 {
+            let (x, y) = safe.next().unwrap();
+            g.reveal_cell(x, y);
+            assert!(g.score > score);
+            score = g.score;
+        }
+        assert_eq!(
+            g.combo,
+            Combo {
+                streak: 3,
+                multiplier: 2
+            }
+        );
+        assert_eq!(g.swap_charges, swaps + 1);
+        let events = g.drain_events();
+        assert!(events.contains(&GameEvent::ComboRaised { multiplier: 2 }));
+        assert!(events.contains(&GameEvent::ComboCharge));
+
+        let (x, y) = safe.next().unwrap();
+        g.contain_cell(x, y);
+        assert_eq!(g.combo, Combo::default());
+        assert!(g.drain_events().contains(&GameEvent::ComboBroken));
+        assert_eq!(g.snapshot().score, g.score);
+    }
+
+    #[test]
+    fn isotopes_take_a_charge_per_stage() {
+        let isotope = IsotopeCell {
+            x: 6,
+            y: 6,
+            stages: 3,
+        };
+        let mut g = QuantumGrid::from_config(
+            GridConfig::new(8, 8, 10, 42, "observer").with_isotope(isotope),
+        );
+        assert_eq!(g.containment_charges, 12);
+        g.reveal_cell(0, 0);
+        assert!(g.mine_map[6 * 8 + 6]);
+        assert_eq!(g.mine_map.iter().filter(|&&m| m).count(), 10);
+
+        let hint = |g: &QuantumGrid| match g.cells[6 * 8 + 6].state {
+            CellState::Superposition { probability } => probability,
+            _ => panic!("isotope resolved early"),
+        };
+        let before = hint(&g);
+        assert_eq!(
+            g.contain_cell(6, 6),
+            RevealOutcome::ContainmentProgress {
+                x: 6,
+                y: 6,
+                stages_left: 2
+            }
+        );
+        // The hint swings every move.
+        assert_ne!(hint(&g), before);
+        assert!(g.drain_events().contains(&GameEvent::IsotopeStabilized {
+            x: 6,
+            y: 6,
+            stages_left: 2
+        }));
+        let snapshot = g.snapshot();
+        assert_eq!(snapshot.isotopes[0].stages_left, 2);
+        assert_eq!(
+            crate::codec::decode_snapshot(&crate::codec::encode_snapshot(&snapshot))
+                .unwrap()
+                .isotopes,
+            snapshot.isotopes
+        );
+
+        g.contain_cell(6, 6);
+        assert_eq!(
+            g.contain_cell(6, 6),
+            RevealOutcome::ContainmentSuccess { x: 6, y: 6 }
+        );
+        assert!(g.isotopes.is_empty());
+        assert_eq!(g.containment_charges, 9);
+    }
+
+    #[test]
+    fn shield_absorbs_one_clicked_mine() {
+        let mut g =
+            QuantumGrid::from_config(GridConfig::new(8, 8, 10, 42, "observer").with_shields(1));
+        g.reveal_cell(0, 0);
+        g.entanglement.pairs.clear();
+        let mut mines = (0..g.cells.len())
+            .filter(|&i| g.mine_map[i])
+            .map(|i| g.coords_of(i))
+            .collect::<Vec<_>>()
+            .into_iter();
+
+        let (x, y) = mines.next().unwrap();
+        assert_eq!(g.reveal_cell(x, y), RevealOutcome::ShieldAbsorbed { x, y });
+        assert_eq!(g.cells[(y * 8 + x) as usize].state, CellState::Contained);
+        assert!(!g.game_over);
+        assert_eq!(g.inventory.shields, 0);
+        assert!(g
+            .drain_events()
+            .contains(&GameEvent::ShieldAbsorbed { x, y }));
+
+        let (x, y) = mines.next().unwrap();
+        assert_eq!(g.reveal_cell(x, y), RevealOutcome::MineDetonated { x, y });
+        assert!(g.game_over);
+    }
+
+    #[test]
+    fn worn_out_containments_breach() {
+        let timed = TimedRules {
+            containment_decay: 100,
+            ..TimedRules::default()
+        };
+        let mut g =
+            QuantumGrid::from_config(GridConfig::new(8, 8, 10, 42, "observer").with_timed(timed));
+        g.reveal_cell(0, 0);
+        g.entanglement.pairs.clear();
+        let mine = (0..g.cells.len())
+            .find(|&i| g.mine_map[i] && matches!(g.cells[i].state, CellState::Superposition { .. }))
+            .unwrap();
+        let (x, y) = g.coords_of(mine);
+        g.contain_cell(x, y);
+        let charges = g.containment_charges;
+
+        g.advance(9_999);
+        assert_eq!(g.integrity.cells.get(&mine), Some(&1));
+        assert_eq!(g.snapshot().integrity[0].integrity, 1);
+        g.drain_events();
+        g.advance(1);
+        assert_eq!(
+            g.cells[mine].state,
+            CellState::Superposition {
+                probability: BREACH_HINT
+            }
+        );
+        assert!(g
+            .drain_events()
+            .contains(&GameEvent::ContainmentBreached { x, y }));
+        // Half a charge back: nothing whole yet.
+        assert_eq!(g.containment_charges, charges);
+        assert_eq!(g.integrity.refund_credit, 50);
+    }
+
+    #[test]
+    fn degenerate_boards_and_wild_coordinates_never_panic() {
+        let far = [0, 1, 8, u32::MAX / 2, u32::MAX];
+        for (width, height, mines) in [(0, 0, 3), (1, 1, 1), (1, 3, 5), (8, 8, 10)] {
+            let mut g =
+                QuantumGrid::from_config(GridConfig::new(width, height, mines, 7, "theorist"));
+            assert_eq!(crate::invariants::check(&g), Ok(()));
+            for (x, y) in far.iter().flat_map(|&x| far.map(|y| (x, y))) {
+                for action in [
+                    Action::Reveal { x, y },
+                    Action::Contain { x, y },
+                    Action::Hadamard { x, y },
+                    Action::MeasureWeak { x, y },
+                    Action::Teleport {
+                        x,
+                        y,
+                        to_x: y,
+                        to_y: x,
+                    },
+                ] {
+                    let _ = g.clone().apply(action);
+                }
+                assert_eq!(g.cell(x, y).is_some(), x < g.width && y < g.height);
+                let _ = (g.explain(x, y), g.set_mark(x, y, Mark::Flag));
+            }
+            g.reveal_cell(0, 0);
+            let _ = (g.snapshot(), g.is_forced_guess(), g.state_hash());
+        }
+    }
+
+    #[test]
+    fn reconstructs_an_observed_board_for_analysis() {
+        let seen = |x, y, seen| ObservedCell { x, y, seen };
+        let cells = [
+            seen(1, 1, Seen::Revealed { adjacent_mines: 2 }),
+            seen(0, 0, Seen::Contained),
+            seen(2, 2, Seen::Flagged),
+        ];
+        let config =
+            GridConfig::new(3, 3, 2, 0, "researcher").with_assist(AssistLevel::FullAnalysis);
+        let mut g = QuantumGrid::reconstruct(&cells, config.clone()).unwrap();
+        assert!(g.reconstructed);
+        assert_eq!(crate::invariants::check(&g), Ok(()));
+        assert!(g.entanglement.pairs.is_empty());
+        assert_eq!(g.cells[8].mark, Mark::Flag);
+        assert_eq!(
+            g.cells[8].state,
+            CellState::Superposition {
+                probability: 1.0 / 7.0
+            }
+        );
+
+        let action = Action::Reveal { x: 2, y: 0 };
+        assert_eq!(
+            g.evaluate_move(action),
+            Err(IllegalAction::UnknownGroundTruth)
+        );
+        assert!(g.apply(action).is_rejection());
+        assert!(g.set_mark(2, 0, Mark::Question).is_ok());
+
+        let impossible = [seen(1, 1, Seen::Revealed { adjacent_mines: 0 }), cells[1]];
+        assert_eq!(
+            QuantumGrid::reconstruct(&impossible, config.clone()).unwrap_err(),
+            ReconstructError::Contradiction { x: 1, y: 1 }
+        );
+        assert_eq!(
+            QuantumGrid::reconstruct(&[cells[1], cells[1]], config).unwrap_err(),
+            ReconstructError::Duplicate { x: 0, y: 0 }
+        );
+    }
+
+    #[test]
+    fn mercy_contains_one_mine_clicked_in_a_forced_guess() {
+        // Four cells, two mines, nothing revealed: every cell is a coin flip.
+        let config = GridConfig::new(4, 1, 2, 1, "observer").with_mercy(true);
+        let mut g = QuantumGrid::from_config(config);
+        g.mine_count = 2;
+        g.mine_map = vec![true, false, true, false].into();
+        g.mines_placed = true;
+        g.entanglement = Shared::default();
+        assert!(g.is_forced_guess());
+
+        let spent = {
+            let mut g = g.clone();
+            g.mercy_used = true;
+            g
+        };
+        assert_eq!(
+            g.reveal_cell(0, 0),
+            RevealOutcome::MercyGranted { x: 0, y: 0 }
+        );
+        assert_eq!(g.cells[0].state, CellState::Contained);
+        assert!(g
+            .drain_events()
+            .contains(&GameEvent::MercyGranted { x: 0, y: 0 }));
+        // One mine in three cells left: no longer a forced guess.
+        assert!(!g.is_forced_guess());
+
+        let mut spent = spent;
+        assert_eq!(
+            spent.reveal_cell(0, 0),
+            RevealOutcome::MineDetonated { x: 0, y: 0 }
+        );
+    }
+
+    #[test]
+    fn recalculated_hints_do_not_depend_on_draw_order() {
+        let mut g = QuantumGrid::from_config(GridConfig::new(8, 8, 10, 42, "researcher"));
+        g.reveal_cell(4, 4);
+        let hints = g.get_probability_cloud();
+        g.rng.next_u64();
+        g.recalculate_probabilities(0..64);
+        assert_eq!(g.get_probability_cloud(), hints);
+        g.recalc_epoch = 1;
+        g.recalculate_probabilities(0..64);
+        assert_ne!(g.get_probability_cloud(), hints);
+    }
 }
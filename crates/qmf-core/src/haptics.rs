@@ -0,0 +1,110 @@
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::events::GameEvent;
+use crate::prelude::*;
+use crate::sound::SoundCue;
+
+/// How hard a controller or phone should buzz for something, in `[0, 1]`.
+/// Computed in core so every wrapper maps the same moment to the same
+/// vibration pattern.
+pub trait Haptic {
+    fn haptic(&self) -> f64;
+}
+
+impl Haptic for SoundCue {
+    fn haptic(&self) -> f64 {
+        match *self {
+            Self::SoftReveal { intensity } => 0.1 + 0.3 * intensity,
+            Self::BigCascade { intensity, .. } => 0.4 + 0.4 * intensity,
+            // The riskier the reveal, the bigger the jolt of relief.
+            Self::NearMissHighProbability { probability } => probability,
+            Self::Detonation { .. } => 1.0,
+            Self::Victory => 0.6,
+        }
+    }
+}
+
+impl Haptic for GameEvent {
+    fn haptic(&self) -> f64 {
+        match *self {
+            // Outcomes already buzz through the move's [`SoundCue`].
+            Self::Revealed { .. } | Self::Contained { .. } | Self::Detonated { .. } | Self::Won => {
+                0.0
+            }
+            Self::AutoRevealed { .. } => 0.1,
+            Self::BellDegraded { .. } => 0.15,
+            Self::AutoContained { .. } | Self::ZenoFrozen { .. } => 0.2,
+            // Each hop down a Bell cascade lands a little harder.
+            Self::BellCollapsed { depth, .. } => (0.2 + 0.1 * f64::from(depth)).min(0.8),
+            Self::LinkCollapsed { .. } => 0.4,
+            Self::EntanglementSwapped { .. } => 0.3,
+            Self::Teleported { destroyed_mine, .. } => {
+                if destroyed_mine {
+                    0.7
+                } else {
+                    0.4
+                }
+            }
+            Self::OracleFired { .. } | Self::IsotopeStabilized { .. } => 0.5,
+            Self::ComboRaised { .. } | Self::ComboCharge => 0.3,
+            Self::ComboBroken => 0.4,
+            Self::ShieldAbsorbed { .. } | Self::MercyGranted { .. } => 0.8,
+            Self::ContainmentBreached { .. } => 0.6,
+            Self::ObjectiveCompleted { .. } => 0.5,
+            Self::ObjectiveFailed { .. } => 0.9,
+            Self::SplitReached { .. } => 0.2,
+            Self::TurnStarted { .. } => 0.2,
+            Self::DaemonScrambled { .. } | Self::DaemonStrengthened { .. } => 0.3,
+            Self::DaemonShiftedMine => 0.5,
+            Self::Decohered { mine, .. } => {
+                if mine {
+                    1.0
+                } else {
+                    0.3
+                }
+            }
+            Self::AutoDetonated { .. } => 1.0,
+        }
+    }
+}
+
+/// An event or cue with its haptic intensity alongside, flattened into one
+/// payload for frontends.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct WithHaptic<T> {
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub inner: T,
+    pub haptic: f64,
+}
+
+/// Annotate each item with its [`Haptic::haptic`] intensity.
+pub fn annotate<T: Haptic>(items: Vec<T>) -> Vec<WithHaptic<T>> {
+    items
+        .into_iter()
+        .map(|inner| WithHaptic {
+            haptic: inner.haptic().clamp(0.0, 1.0),
+            inner,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn annotations_flatten_into_the_payload() {
+        let annotated = annotate(vec![GameEvent::ZenoFrozen { x: 1, y: 2 }]);
+        let json = serde_json::to_value(&annotated).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!([{ "kind": "zeno_frozen", "x": 1, "y": 2, "haptic": 0.2 }])
+        );
+
+        let soft = SoundCue::for_resolution(1, 64).haptic();
+        let big = SoundCue::for_resolution(40, 64).haptic();
+        assert!(soft < big && big < SoundCue::Detonation { intensity: 0.0 }.haptic());
+    }
+}
@@ -0,0 +1,247 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::action::Action;
+use crate::cursor::{Cursor, CursorCommand, Direction, Tool};
+
+/// How far an analog trigger must be pulled to count as pressed.
+pub const TRIGGER_THRESHOLD: f32 = 0.5;
+
+/// Controller buttons, named by position rather than by label, so one
+/// layout fits every pad.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Button {
+    DpadUp,
+    DpadDown,
+    DpadLeft,
+    DpadRight,
+    /// Bottom face button (A on Xbox, Cross on PlayStation).
+    South,
+    East,
+    West,
+    North,
+    LeftShoulder,
+    RightShoulder,
+    LeftTrigger,
+    RightTrigger,
+    Start,
+    Select,
+}
+
+/// An abstract controller event, as reported by a frontend's gamepad API.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum ControllerEvent {
+    Pressed {
+        button: Button,
+    },
+    Released {
+        button: Button,
+    },
+    /// Analog position in `[0, 1]` of a trigger (or any button that
+    /// reports pressure).
+    Analog {
+        button: Button,
+        value: f32,
+    },
+}
+
+/// What a button does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum Binding {
+    Move { direction: Direction },
+    Command { command: CursorCommand },
+}
+
+/// Button assignments, saved in the player profile. Unbound buttons do
+/// nothing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Bindings {
+    pub buttons: BTreeMap<Button, Binding>,
+}
+
+impl Default for Bindings {
+    /// D-pad moves, face buttons reveal / contain / Hadamard / measure,
+    /// triggers swap and teleport, Select cancels a selection.
+    fn default() -> Self {
+        let tool = |tool| Binding::Command {
+            command: CursorCommand::Tool { tool },
+        };
+        let buttons = [
+            (
+                Button::DpadUp,
+                Binding::Move {
+                    direction: Direction::Up,
+                },
+            ),
+            (
+                Button::DpadDown,
+                Binding::Move {
+                    direction: Direction::Down,
+                },
+            ),
+            (
+                Button::DpadLeft,
+                Binding::Move {
+                    direction: Direction::Left,
+                },
+            ),
+            (
+                Button::DpadRight,
+                Binding::Move {
+                    direction: Direction::Right,
+                },
+            ),
+            (
+                Button::South,
+                Binding::Command {
+                    command: CursorCommand::Reveal,
+                },
+            ),
+            (
+                Button::East,
+                Binding::Command {
+                    command: CursorCommand::Contain,
+                },
+            ),
+            (Button::West, tool(Tool::Hadamard)),
+            (Button::North, tool(Tool::MeasureWeak)),
+            (Button::LeftTrigger, tool(Tool::SwapEntanglement)),
+            (Button::RightTrigger, tool(Tool::Teleport)),
+            (
+                Button::Select,
+                Binding::Command {
+                    command: CursorCommand::Cancel,
+                },
+            ),
+        ];
+        Self {
+            buttons: buttons.into_iter().collect(),
+        }
+    }
+}
+
+/// Turns controller events into cursor moves and [`Action`]s. Tracks which
+/// buttons are held so an analog trigger fires once per pull.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InputMapper {
+    pub bindings: Bindings,
+    held: BTreeSet<Button>,
+}
+
+impl InputMapper {
+    pub fn new(bindings: Bindings) -> Self {
+        Self {
+            bindings,
+            held: BTreeSet::new(),
+        }
+    }
+
+    /// Handle one event on a `width × height` board, moving `cursor` as
+    /// needed. Returns the action to apply, if the event produced one.
+    pub fn handle(
+        &mut self,
+        event: ControllerEvent,
+        cursor: &mut Cursor,
+        width: u32,
+        height: u32,
+    ) -> Option<Action> {
+        let button = match event {
+            ControllerEvent::Pressed { button } => button,
+            ControllerEvent::Released { button } => {
+                self.held.remove(&button);
+                return None;
+            }
+            ControllerEvent::Analog { button, value } if value < TRIGGER_THRESHOLD => {
+                self.held.remove(&button);
+                return None;
+            }
+            ControllerEvent::Analog { button, .. } => button,
+        };
+        // Analog input repeats while held; only the first crossing counts.
+        if matches!(event, ControllerEvent::Analog { .. }) && !self.held.insert(button) {
+            return None;
+        }
+        match self.bindings.buttons.get(&button)? {
+            Binding::Move { direction } => {
+                cursor.step(*direction, width, height);
+                None
+            }
+            Binding::Command { command } => cursor.command(*command),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_layout_moves_and_acts() {
+        let mut mapper = InputMapper::default();
+        let mut cursor = Cursor::default();
+        let press = |button| ControllerEvent::Pressed { button };
+        assert_eq!(
+            mapper.handle(press(Button::DpadRight), &mut cursor, 5, 5),
+            None
+        );
+        assert_eq!(
+            mapper.handle(press(Button::DpadDown), &mut cursor, 5, 5),
+            None
+        );
+        assert_eq!(
+            mapper.handle(press(Button::South), &mut cursor, 5, 5),
+            Some(Action::Reveal { x: 1, y: 1 })
+        );
+        assert_eq!(mapper.handle(press(Button::Start), &mut cursor, 5, 5), None);
+    }
+
+    #[test]
+    fn triggers_fire_once_per_pull() {
+        let mut mapper = InputMapper::default();
+        let mut cursor = Cursor::default();
+        let pull = |value| ControllerEvent::Analog {
+            button: Button::RightTrigger,
+            value,
+        };
+        // First pull selects the teleport source; holding does nothing more.
+        assert_eq!(mapper.handle(pull(0.9), &mut cursor, 5, 5), None);
+        assert_eq!(cursor.selection, Some((0, 0)));
+        assert_eq!(mapper.handle(pull(1.0), &mut cursor, 5, 5), None);
+        assert_eq!(cursor.selection, Some((0, 0)));
+
+        cursor.step(Direction::Right, 5, 5);
+        mapper.handle(pull(0.1), &mut cursor, 5, 5);
+        assert_eq!(
+            mapper.handle(pull(0.8), &mut cursor, 5, 5),
+            Some(Action::Teleport {
+                x: 0,
+                y: 0,
+                to_x: 1,
+                to_y: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn bindings_round_trip_through_json() {
+        let mut bindings = Bindings::default();
+        bindings.buttons.insert(
+            Button::RightShoulder,
+            Binding::Command {
+                command: CursorCommand::Contain,
+            },
+        );
+        let json = serde_json::to_string(&bindings).unwrap();
+        let back: Bindings = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, bindings);
+    }
+}
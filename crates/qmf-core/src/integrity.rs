@@ -0,0 +1,80 @@
+use alloc::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+/// Integrity of a fresh containment.
+pub const FULL_INTEGRITY: u32 = 1000;
+/// Hint a breached cell returns to Superposition with.
+pub const BREACH_HINT: f64 = 0.9;
+/// Share of a charge, in percent, refunded per breach.
+pub const BREACH_REFUND_PERCENT: u32 = 50;
+
+/// Containments wearing out under
+/// [`TimedRules::containment_decay`](crate::config::TimedRules::containment_decay):
+/// each Contained cell's integrity drops as game time passes, and at zero
+/// the cell breaches back into Superposition and must be contained again.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Integrity {
+    /// Integrity per Contained cell index.
+    pub cells: BTreeMap<usize, u32>,
+    /// Refund owed toward the next whole charge, in percent.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub refund_credit: u32,
+}
+
+impl Integrity {
+    /// Wear every containment down by `loss`, returning the cells that
+    /// breached. They are no longer tracked.
+    pub(crate) fn decay(&mut self, loss: u32) -> Vec<usize> {
+        let mut breached = Vec::new();
+        self.cells.retain(|&index, integrity| {
+            *integrity = integrity.saturating_sub(loss);
+            if *integrity == 0 {
+                breached.push(index);
+            }
+            *integrity > 0
+        });
+        breached
+    }
+
+    /// Credit a breach's partial refund. Returns whether it completed a
+    /// whole charge.
+    pub(crate) fn refund(&mut self) -> bool {
+        self.refund_credit += BREACH_REFUND_PERCENT;
+        if self.refund_credit < 100 {
+            return false;
+        }
+        self.refund_credit -= 100;
+        true
+    }
+}
+
+/// A containment's integrity as frontends see it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CellIntegrity {
+    pub x: u32,
+    pub y: u32,
+    pub integrity: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn breaches_at_zero_and_refunds_in_halves() {
+        let mut integrity = Integrity::default();
+        integrity.cells.insert(3, FULL_INTEGRITY);
+        integrity.cells.insert(5, 200);
+        assert_eq!(integrity.decay(200), vec![5]);
+        assert_eq!(integrity.cells.get(&3), Some(&800));
+        assert!(!integrity.refund());
+        assert!(integrity.refund());
+        assert_eq!(integrity.refund_credit, 0);
+    }
+}
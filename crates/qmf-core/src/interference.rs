@@ -0,0 +1,122 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::generator::square;
+use crate::prelude::*;
+
+/// Chebyshev radius of the neighbourhood a Hadamard disturbs.
+pub const RADIUS: u32 = 2;
+/// Moves a wave lives for before it fades out completely.
+pub const LIFETIME: u32 = 4;
+/// Amplitude kept per move.
+pub const DECAY: f64 = 0.5;
+/// Largest probability shift one overlap can cause.
+pub const STRENGTH: f64 = 0.08;
+
+/// The trace a recent Hadamard leaves on the board.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Wave {
+    pub x: u32,
+    pub y: u32,
+    /// Moves since the Hadamard.
+    pub age: u32,
+}
+
+impl Wave {
+    pub fn amplitude(&self) -> f64 {
+        DECAY.powi(self.age as i32)
+    }
+
+    fn distance(&self, x: u32, y: u32) -> u32 {
+        self.x.abs_diff(x).max(self.y.abs_diff(y))
+    }
+}
+
+/// Recently Hadamard'd cells. A new Hadamard whose neighbourhood overlaps a
+/// live wave's makes the overlap ripple: cells whose distances to the two
+/// sources differ by an even number interfere constructively (probability
+/// rises), odd differences destructively (it falls), scaled by both
+/// amplitudes.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct InterferenceField {
+    pub waves: Vec<Wave>,
+}
+
+impl InterferenceField {
+    /// Probability shifts, by cell index, caused by a Hadamard at `(x, y)`
+    /// on a `width × height` board. The source cells themselves are left
+    /// alone.
+    pub fn ripples(&self, width: u32, height: u32, x: u32, y: u32) -> Vec<(usize, f64)> {
+        let incoming = Wave { x, y, age: 0 };
+        let mut shifts: Vec<(usize, f64)> = Vec::new();
+        for wave in &self.waves {
+            if wave.distance(x, y) > 2 * RADIUS {
+                continue;
+            }
+            for (cx, cy, index) in square(width, height, x, y, RADIUS) {
+                let (a, b) = (wave.distance(cx, cy), incoming.distance(cx, cy));
+                if a == 0 || b == 0 || a > RADIUS {
+                    continue;
+                }
+                let sign = if a.abs_diff(b) % 2 == 0 { 1.0 } else { -1.0 };
+                let shift = sign * STRENGTH * wave.amplitude();
+                match shifts.iter_mut().find(|(i, _)| *i == index) {
+                    Some((_, total)) => *total += shift,
+                    None => shifts.push((index, shift)),
+                }
+            }
+        }
+        shifts
+    }
+
+    /// Age every wave by one move, dropping those that have faded.
+    pub fn tick(&mut self) {
+        for wave in &mut self.waves {
+            wave.age += 1;
+        }
+        self.waves.retain(|wave| wave.age < LIFETIME);
+    }
+
+    pub fn emit(&mut self, x: u32, y: u32) {
+        self.waves.push(Wave { x, y, age: 0 });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlapping_waves_ripple_with_alternating_sign() {
+        let mut field = InterferenceField::default();
+        assert!(field.ripples(10, 10, 5, 5).is_empty());
+
+        field.emit(3, 5);
+        let ripples = field.ripples(10, 10, 5, 5);
+        let at = |x: u32, y: u32| ripples.iter().find(|(i, _)| *i == (y * 10 + x) as usize);
+        // (4, 5) is 1 from both sources: constructive.
+        assert_eq!(at(4, 5).map(|r| r.1), Some(STRENGTH));
+        // (4, 7) is 2 from (3, 5) but also 2 from (5, 5): constructive.
+        assert_eq!(at(4, 7).map(|r| r.1), Some(STRENGTH));
+        // (3, 4) is 1 from (3, 5) and 2 from (5, 5): destructive.
+        assert_eq!(at(3, 4).map(|r| r.1), Some(-STRENGTH));
+        // Sources are untouched; cells outside the overlap too.
+        assert!(at(3, 5).is_none() && at(5, 5).is_none() && at(7, 5).is_none());
+
+        assert!(field.ripples(10, 10, 9, 9).is_empty());
+    }
+
+    #[test]
+    fn waves_fade_after_their_lifetime() {
+        let mut field = InterferenceField::default();
+        field.emit(0, 0);
+        field.tick();
+        assert_eq!(field.waves[0].amplitude(), DECAY);
+        for _ in 1..LIFETIME {
+            field.tick();
+        }
+        assert!(field.waves.is_empty());
+    }
+}
@@ -0,0 +1,457 @@
+use core::fmt;
+
+use crate::grid::{CellState, QuantumGrid};
+use crate::lab::{link_fits, BoardId, Lab};
+use crate::zone::ZoneMap;
+
+/// A broken internal guarantee of [`QuantumGrid`]. Seeing one of these is
+/// always an engine bug, never a player mistake.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    /// `cells` or `mine_map` does not cover `width × height`.
+    SizeMismatch {
+        expected: usize,
+        cells: usize,
+        mine_map: usize,
+    },
+    /// The embedded `GridConfig` describes a different board.
+    ConfigMismatch,
+    /// Placed mines differ from `mine_count`.
+    MineCountChanged { expected: u32, actual: u32 },
+    /// More mines contained or detonated than exist.
+    TooManyResolvedMines { resolved: u32, mine_count: u32 },
+    /// A `Revealed` cell sits on a mine.
+    RevealedMine { index: usize },
+    /// A `Contained` or `Detonated` cell has no mine under it.
+    PhantomMine { index: usize },
+    /// A Superposition hint outside `[0, 1]` (or NaN).
+    ProbabilityOutOfRange { index: usize },
+    /// An entanglement pair points outside the board.
+    EntanglementOutOfRange { pair: usize },
+    /// An isotope is keyed to a cell outside the board.
+    IsotopeOutOfRange { index: usize },
+    /// A note is keyed to a cell outside the board.
+    NoteOutOfRange { index: usize },
+    /// A note [`CellNote::validate`](crate::notes::CellNote::validate)
+    /// would refuse.
+    InvalidNote { index: usize },
+    /// A trigger is keyed to a cell outside the board.
+    TriggerOutOfRange { index: usize },
+    /// A Zeno freeze is keyed to a cell outside the board.
+    FrozenOutOfRange { index: usize },
+    /// Integrity damage is keyed to a cell outside the board.
+    IntegrityOutOfRange { index: usize },
+    /// The per-cell zone modifiers differ from what the config's zones
+    /// lay out.
+    ZoneMismatch,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SizeMismatch {
+                expected,
+                cells,
+                mine_map,
+            } => write!(
+                f,
+                "expected {expected} cells, found {cells} cells and {mine_map} mine-map entries"
+            ),
+            Self::ConfigMismatch => write!(f, "config does not match the board"),
+            Self::MineCountChanged { expected, actual } => {
+                write!(f, "{actual} mines placed, mine_count is {expected}")
+            }
+            Self::TooManyResolvedMines {
+                resolved,
+                mine_count,
+            } => write!(f, "{resolved} mines resolved out of {mine_count}"),
+            Self::RevealedMine { index } => write!(f, "cell {index} is revealed but is a mine"),
+            Self::PhantomMine { index } => {
+                write!(f, "cell {index} is contained or detonated but is safe")
+            }
+            Self::ProbabilityOutOfRange { index } => {
+                write!(f, "cell {index} has an out-of-range probability")
+            }
+            Self::EntanglementOutOfRange { pair } => {
+                write!(f, "entanglement pair {pair} points outside the board")
+            }
+            Self::IsotopeOutOfRange { index } => {
+                write!(f, "isotope at cell {index} is outside the board")
+            }
+            Self::NoteOutOfRange { index } => {
+                write!(f, "note at cell {index} is outside the board")
+            }
+            Self::InvalidNote { index } => write!(f, "note at cell {index} is invalid"),
+            Self::TriggerOutOfRange { index } => {
+                write!(f, "trigger at cell {index} is outside the board")
+            }
+            Self::FrozenOutOfRange { index } => {
+                write!(f, "frozen cell {index} is outside the board")
+            }
+            Self::IntegrityOutOfRange { index } => {
+                write!(f, "integrity of cell {index} is outside the board")
+            }
+            Self::ZoneMismatch => write!(f, "zone modifiers do not match the config"),
+        }
+    }
+}
+
+impl core::error::Error for Violation {}
+
+/// A broken guarantee of a [`Lab`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LabViolation {
+    /// One of the boards fails [`check`].
+    Board {
+        board: BoardId,
+        violation: Violation,
+    },
+    /// A cross-board link [`Lab::add_link`] would refuse.
+    InvalidLink { link: usize },
+}
+
+impl fmt::Display for LabViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Board { board, violation } => write!(f, "board {board}: {violation}"),
+            Self::InvalidLink { link } => write!(f, "cross-board link {link} does not fit"),
+        }
+    }
+}
+
+impl core::error::Error for LabViolation {}
+
+/// Check every invariant, returning the first violation found.
+///
+/// Also the gate for grids deserialized from untrusted input (shared
+/// saves): it covers the board's size and every per-cell map, so nothing
+/// in a grid that passes points off the board.
+pub fn check(grid: &QuantumGrid) -> Result<(), Violation> {
+    let total = (grid.width as usize).saturating_mul(grid.height as usize);
+    if grid.cells.len() != total || grid.mine_map.len() != total {
+        return Err(Violation::SizeMismatch {
+            expected: total,
+            cells: grid.cells.len(),
+            mine_map: grid.mine_map.len(),
+        });
+    }
+
+    if grid.config.width != grid.width
+        || grid.config.height != grid.height
+        || grid.mine_count > grid.config.total_cells()
+    {
+        return Err(Violation::ConfigMismatch);
+    }
+
+    for (pair, link) in grid.entanglement.pairs.iter().enumerate() {
+        if link.left >= total || link.right >= total {
+            return Err(Violation::EntanglementOutOfRange { pair });
+        }
+    }
+
+    if let Some(&index) = grid.isotopes.keys().find(|&&index| index >= total) {
+        return Err(Violation::IsotopeOutOfRange { index });
+    }
+
+    for (&index, note) in &grid.notes {
+        if index >= total {
+            return Err(Violation::NoteOutOfRange { index });
+        }
+        if note.validate().is_err() {
+            return Err(Violation::InvalidNote { index });
+        }
+    }
+
+    if let Some(&index) = grid.triggers.keys().find(|&&index| index >= total) {
+        return Err(Violation::TriggerOutOfRange { index });
+    }
+    if let Some(&index) = grid.zeno.frozen.keys().find(|&&index| index >= total) {
+        return Err(Violation::FrozenOutOfRange { index });
+    }
+    if let Some(&index) = grid.integrity.cells.keys().find(|&&index| index >= total) {
+        return Err(Violation::IntegrityOutOfRange { index });
+    }
+    if grid.zones != ZoneMap::new(&grid.config.zones, grid.width, grid.height) {
+        return Err(Violation::ZoneMismatch);
+    }
+
+    let mut resolved_mines = 0;
+    for (index, cell) in grid.cells.iter().enumerate() {
+        match cell.state {
+            CellState::Superposition { probability } => {
+                if !(0.0..=1.0).contains(&probability) {
+                    return Err(Violation::ProbabilityOutOfRange { index });
+                }
+            }
+            CellState::Revealed { .. } if grid.is_mine(index) => {
+                return Err(Violation::RevealedMine { index });
+            }
+            CellState::Revealed { .. } => {}
+            CellState::Contained | CellState::Detonated => {
+                if !grid.is_mine(index) {
+                    return Err(Violation::PhantomMine { index });
+                }
+                resolved_mines += 1;
+            }
+        }
+    }
+    if resolved_mines > grid.mine_count {
+        return Err(Violation::TooManyResolvedMines {
+            resolved: resolved_mines,
+            mine_count: grid.mine_count,
+        });
+    }
+
+    // A reconstructed board only knows the mines it saw contained.
+    if grid.mines_placed && !grid.reconstructed {
+        let actual = grid.mine_map.iter().filter(|&&m| m).count() as u32;
+        if actual != grid.mine_count {
+            return Err(Violation::MineCountChanged {
+                expected: grid.mine_count,
+                actual,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// [`check`] every board of `lab`, then its cross-board links: the gate
+/// for labs deserialized from untrusted input.
+pub fn check_lab(lab: &Lab) -> Result<(), LabViolation> {
+    for (board, grid) in lab.boards.iter().enumerate() {
+        check(grid).map_err(|violation| LabViolation::Board {
+            board: board as BoardId,
+            violation,
+        })?;
+    }
+    match lab
+        .links
+        .iter()
+        .position(|link| !link_fits(&lab.boards, link))
+    {
+        Some(link) => Err(LabViolation::InvalidLink { link }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action::Action;
+    use crate::circuit::Region;
+    use crate::config::{
+        AutoReveal, ChargePolicy, Generator, GridConfig, OracleCell, Oracles, Symmetry,
+    };
+    use crate::entanglement::{CellRef, CrossLink, LinkType};
+    use crate::isotope::Isotope;
+    use crate::lab::LabConfig;
+    use crate::notes::{CellNote, MAX_NOTE_LEN};
+    use crate::zone::Zone;
+    use proptest::prelude::*;
+
+    fn action(kind: u8, x: u32, y: u32) -> Action {
+        match kind % 7 {
+            0 => Action::Reveal { x, y },
+            1 => Action::Contain { x, y },
+            2 => Action::Hadamard { x, y },
+            3 => Action::MeasureWeakWithPrecision {
+                x,
+                y,
+                percent: kind / 7,
+            },
+            4 => Action::SwapEntanglement {
+                x,
+                y,
+                other_x: y,
+                other_y: x,
+            },
+            5 => Action::Teleport {
+                x,
+                y,
+                to_x: y,
+                to_y: x,
+            },
+            _ => Action::MeasureWeak { x, y },
+        }
+    }
+
+    fn config() -> impl Strategy<Value = GridConfig> {
+        (
+            4_u32..12,
+            4_u32..12,
+            0_u32..40,
+            any::<u64>(),
+            prop::sample::select(vec!["observer", "researcher", "theorist"]),
+            prop::sample::select(vec![
+                Generator::Uniform,
+                Generator::Clustered { veins: 0 },
+                Generator::Symmetric {
+                    symmetry: Symmetry::Point,
+                },
+            ]),
+            any::<bool>(),
+            prop::sample::select(vec![AutoReveal::Off, AutoReveal::Trusting]),
+            prop::option::of(100_u32..2_000),
+            prop::option::of((0_u32..12, 0_u32..12)),
+        )
+            .prop_map(
+                |(
+                    width,
+                    height,
+                    mines,
+                    seed,
+                    difficulty,
+                    generator,
+                    auto,
+                    reveal,
+                    limit,
+                    oracle,
+                )| {
+                    let config = GridConfig::new(width, height, mines, seed, difficulty);
+                    let oracles = Oracles {
+                        placed: oracle
+                            .map(|(x, y)| OracleCell {
+                                x,
+                                y,
+                                circuit: None,
+                            })
+                            .into_iter()
+                            .collect(),
+                        rate: if oracle.is_some() { 0.05 } else { 0.0 },
+                        ..Oracles::default()
+                    };
+                    GridConfig {
+                        decoherence_limit: limit,
+                        oracles,
+                        ..config
+                    }
+                    .with_generator(generator)
+                    .with_charge_policy(ChargePolicy::Regenerating { every: 3 })
+                    .with_auto_contain(auto)
+                    .with_auto_reveal(reveal)
+                    .clamped()
+                    .0
+                },
+            )
+    }
+
+    proptest! {
+        #[test]
+        fn random_play_preserves_invariants(
+            config in config(),
+            moves in prop::collection::vec((any::<u8>(), 0_u32..14, 0_u32..14), 0..60),
+        ) {
+            let mut grid = QuantumGrid::from_config(config);
+            prop_assert_eq!(check(&grid), Ok(()));
+            for (kind, x, y) in moves {
+                let action = action(kind, x, y);
+                grid.apply(action);
+                prop_assert_eq!(check(&grid), Ok(()), "after {:?}", action);
+            }
+        }
+    }
+
+    #[test]
+    fn rejects_a_save_whose_config_disagrees() {
+        let mut grid = QuantumGrid::new(8, 8, 10, 42, "observer");
+        grid.config.width = 9;
+        assert_eq!(check(&grid), Err(Violation::ConfigMismatch));
+    }
+
+    #[test]
+    fn detects_a_revealed_mine() {
+        let mut grid = QuantumGrid::new(8, 8, 10, 42, "observer");
+        grid.reveal_cell(0, 0);
+        assert_eq!(check(&grid), Ok(()));
+        let mine = grid.mine_map.iter().position(|&m| m).unwrap();
+        grid.cells[mine].state = CellState::Revealed { adjacent_mines: 0 };
+        assert_eq!(check(&grid), Err(Violation::RevealedMine { index: mine }));
+    }
+
+    #[test]
+    fn detects_an_isotope_off_the_board() {
+        let mut grid = QuantumGrid::new(8, 8, 10, 42, "observer");
+        grid.isotopes.insert(64, Isotope::new(2));
+        assert_eq!(
+            check(&grid),
+            Err(Violation::IsotopeOutOfRange { index: 64 })
+        );
+        // Played anyway, it is dropped rather than panicking.
+        grid.reveal_cell(0, 0);
+        grid.apply(Action::Hadamard { x: 7, y: 7 });
+        assert!(grid.isotopes.is_empty());
+    }
+
+    #[test]
+    fn detects_notes_set_around_validation() {
+        let mut grid = QuantumGrid::new(8, 8, 10, 42, "observer");
+        grid.set_note(7, 7, Some(CellNote::Text("mine?".into())))
+            .unwrap();
+        assert_eq!(check(&grid), Ok(()));
+        grid.notes.insert(64, CellNote::Number(0.5));
+        assert_eq!(check(&grid), Err(Violation::NoteOutOfRange { index: 64 }));
+        grid.notes.remove(&64);
+        grid.notes
+            .insert(3, CellNote::Text("x".repeat(MAX_NOTE_LEN + 1)));
+        assert_eq!(check(&grid), Err(Violation::InvalidNote { index: 3 }));
+    }
+
+    #[test]
+    fn detects_per_cell_state_off_the_board() {
+        let config = GridConfig::new(8, 8, 10, 42, "observer")
+            .with_zone(Zone::stabilized(Region::Row { y: 2 }));
+        let grid = QuantumGrid::from_config(config);
+        assert_eq!(check(&grid), Ok(()));
+
+        let mut frozen = grid.clone();
+        frozen.zeno.frozen.insert(64, 3);
+        assert_eq!(
+            check(&frozen),
+            Err(Violation::FrozenOutOfRange { index: 64 })
+        );
+        let mut damaged = grid.clone();
+        damaged.integrity.cells.insert(99, 50);
+        assert_eq!(
+            check(&damaged),
+            Err(Violation::IntegrityOutOfRange { index: 99 })
+        );
+        let mut rezoned = grid;
+        rezoned.zones = ZoneMap::default();
+        assert_eq!(check(&rezoned), Err(Violation::ZoneMismatch));
+    }
+
+    #[test]
+    fn lab_checks_cover_boards_and_links() {
+        let board = GridConfig::new(6, 6, 4, 7, "observer");
+        let mut lab = Lab::new(LabConfig {
+            boards: vec![board.clone(), board],
+            shields: 0,
+            links: Vec::new(),
+        });
+        assert_eq!(check_lab(&lab), Ok(()));
+        lab.links.push(CrossLink {
+            left: CellRef {
+                board: 0,
+                x: 0,
+                y: 0,
+            },
+            right: CellRef {
+                board: 2,
+                x: 0,
+                y: 0,
+            },
+            strength: 1.0,
+            link_type: LinkType::BellState,
+            correlation: Default::default(),
+        });
+        assert_eq!(check_lab(&lab), Err(LabViolation::InvalidLink { link: 0 }));
+        lab.boards[1].config.width = 7;
+        assert_eq!(
+            check_lab(&lab),
+            Err(LabViolation::Board {
+                board: 1,
+                violation: Violation::ConfigMismatch
+            })
+        );
+    }
+}
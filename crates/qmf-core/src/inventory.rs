@@ -0,0 +1,24 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::rules::RuleHooks;
+
+/// Consumables the player holds, starting from
+/// [`GridConfig::shields`](crate::config::GridConfig::shields). They act
+/// through the same [`RuleHooks`] as game variants.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ToolInventory {
+    /// Each one turns a clicked mine into a containment instead of a
+    /// detonation.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub shields: u32,
+}
+
+impl RuleHooks for ToolInventory {
+    fn on_mine_clicked(&mut self, _x: u32, _y: u32) -> bool {
+        let absorbed = self.shields > 0;
+        self.shields = self.shields.saturating_sub(1);
+        absorbed
+    }
+}
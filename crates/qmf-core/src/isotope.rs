@@ -0,0 +1,63 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// How far an isotope's hint swings away from its resting value.
+pub const PULSE: f64 = 0.1;
+
+/// An unstable isotope: a mine that takes several containment charges,
+/// each on a move of its own, before it is Contained. Its hint pulses
+/// between moves while it is still in Superposition.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Isotope {
+    /// Containments still needed, the final one included.
+    pub stages_left: u32,
+    /// Current swing of the hint, undone on the next move.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub pulse: f64,
+}
+
+impl Isotope {
+    pub fn new(stages: u32) -> Self {
+        Self {
+            stages_left: stages.max(1),
+            pulse: 0.0,
+        }
+    }
+
+    /// Swing `probability` out or back for the next move.
+    pub(crate) fn beat(&mut self, probability: f64) -> f64 {
+        if self.pulse != 0.0 {
+            let rest = probability - self.pulse;
+            self.pulse = 0.0;
+            return rest.clamp(0.0, 1.0);
+        }
+        self.pulse = PULSE.min(1.0 - probability).max(0.0);
+        probability + self.pulse
+    }
+}
+
+/// An isotope as frontends see it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct IsotopeView {
+    pub x: u32,
+    pub y: u32,
+    pub stages_left: u32,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pulse_returns_to_rest() {
+        let mut isotope = Isotope::new(3);
+        assert_eq!(isotope.beat(0.5), 0.6);
+        assert_eq!(isotope.beat(0.6), 0.5);
+        // Near certainty the swing shrinks rather than overshooting.
+        let high = isotope.beat(0.95);
+        assert!((high - 1.0).abs() < 1e-12);
+        assert!((isotope.beat(high) - 0.95).abs() < 1e-12);
+    }
+}
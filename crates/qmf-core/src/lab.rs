@@ -0,0 +1,438 @@
+use alloc::collections::{BTreeSet, VecDeque};
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::action::{Action, ActionResult};
+use crate::config::GridConfig;
+use crate::entanglement::{CellRef, CrossLink};
+use crate::events::GameEvent;
+use crate::grid::{GridSnapshot, QuantumGrid};
+use crate::inventory::ToolInventory;
+use crate::prelude::*;
+
+/// Index of a board (an experiment) in a [`Lab`].
+pub type BoardId = u32;
+
+/// A laboratory scenario: the experiments to run side by side and what
+/// they share.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LabConfig {
+    pub boards: Vec<GridConfig>,
+    /// Shields in the shared inventory. Each board's own
+    /// [`GridConfig::shields`] is ignored.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub shields: u32,
+    /// Entanglement across boards. Links with an end off its board are
+    /// dropped.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub links: Vec<CrossLink>,
+}
+
+/// An [`Action`] on one board of a [`Lab`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LabAction {
+    pub board: BoardId,
+    pub action: Action,
+}
+
+/// A [`GameEvent`] from one board of a [`Lab`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LabEvent {
+    pub board: BoardId,
+    pub event: GameEvent,
+}
+
+/// Why a [`Lab`] refused an action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum LabError {
+    UnknownBoard {
+        board: BoardId,
+    },
+    /// A [`CrossLink`] with an end off its board, both ends on one cell,
+    /// or a strength outside `[0, 1]`.
+    InvalidLink,
+}
+
+impl fmt::Display for LabError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownBoard { board } => write!(f, "no board {board} in this lab"),
+            Self::InvalidLink => write!(f, "cross-board link does not fit this lab"),
+        }
+    }
+}
+
+impl core::error::Error for LabError {}
+
+/// The laboratory meta-game: several small boards played at once, drawing
+/// on one tool inventory and running on one clock. Saves and replays
+/// serialize the whole lab.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Lab {
+    pub boards: Vec<QuantumGrid>,
+    pub inventory: ToolInventory,
+    /// Game time on the shared clock; every board is kept at it.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub time_ms: u64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub links: Vec<CrossLink>,
+}
+
+impl Lab {
+    pub fn new(config: LabConfig) -> Self {
+        let boards = config
+            .boards
+            .into_iter()
+            .map(|board| {
+                QuantumGrid::from_config(GridConfig {
+                    shields: 0,
+                    ..board
+                })
+            })
+            .collect::<Vec<_>>();
+        let links = config
+            .links
+            .into_iter()
+            .filter(|link| link_fits(&boards, link))
+            .collect();
+        Self {
+            boards,
+            inventory: ToolInventory {
+                shields: config.shields,
+            },
+            time_ms: 0,
+            links,
+        }
+    }
+
+    pub fn board(&self, board: BoardId) -> Option<&QuantumGrid> {
+        self.boards.get(board as usize)
+    }
+
+    /// Entangle two cells of the lab, refusing a link that does not fit.
+    pub fn add_link(&mut self, link: CrossLink) -> Result<(), LabError> {
+        if !link_fits(&self.boards, &link) {
+            return Err(LabError::InvalidLink);
+        }
+        self.links.push(link);
+        Ok(())
+    }
+
+    /// Apply `action` to its board, lending it the shared inventory.
+    /// [`Action::Advance`] moves the shared clock, so it advances every
+    /// board whichever one it names.
+    pub fn apply(&mut self, action: LabAction) -> Result<ActionResult, LabError> {
+        let LabAction { board, action } = action;
+        if board as usize >= self.boards.len() {
+            return Err(LabError::UnknownBoard { board });
+        }
+        if let Action::Advance { dt_ms } = action {
+            self.advance(dt_ms);
+            return Ok(ActionResult::Advanced {
+                time_ms: self.time_ms,
+            });
+        }
+        let grid = self
+            .boards
+            .get_mut(board as usize)
+            .ok_or(LabError::UnknownBoard { board })?;
+        let before = unresolved(grid);
+        grid.inventory = self.inventory;
+        let result = grid.apply(action);
+        self.inventory = core::mem::take(&mut grid.inventory);
+        let resolved = resolved_since(grid, board, &before);
+        self.propagate_links(resolved);
+        Ok(result)
+    }
+
+    /// Let `dt_ms` pass on every board. Finished boards stand still (see
+    /// [`QuantumGrid::advance`]).
+    pub fn advance(&mut self, dt_ms: u32) {
+        self.time_ms = self.time_ms.saturating_add(u64::from(dt_ms));
+        let mut resolved = Vec::new();
+        for (board, grid) in (0..).zip(&mut self.boards) {
+            let before = unresolved(grid);
+            grid.advance(dt_ms);
+            resolved.extend(resolved_since(grid, board, &before));
+        }
+        self.propagate_links(resolved);
+    }
+
+    /// Carry collapses across [`CrossLink`]s, breadth-first, until no link
+    /// resolves anything new. Within a board, cascades follow the board's
+    /// own rules; every cell is followed out of at most once. Links that do
+    /// not fit the lab (say, from a hand-edited save) carry nothing.
+    fn propagate_links(&mut self, resolved: Vec<CellRef>) {
+        if self.links.is_empty() {
+            return;
+        }
+        let mut visited: BTreeSet<CellRef> = resolved.iter().copied().collect();
+        let mut queue: VecDeque<CellRef> = resolved.into();
+        while let Some(cause) = queue.pop_front() {
+            let Some(&observed_mine) = self
+                .boards
+                .get(cause.board as usize)
+                .and_then(|grid| grid.mine_map.get(grid.index_of(cause.x, cause.y)?))
+            else {
+                continue;
+            };
+            let targets: Vec<(CrossLink, CellRef)> = self
+                .links
+                .iter()
+                .filter_map(|link| Some((link.clone(), link.partner_of(cause)?)))
+                .collect();
+            for (link, target) in targets {
+                let Some(grid) = self.boards.get_mut(target.board as usize) else {
+                    continue;
+                };
+                let Some(index) = grid.index_of(target.x, target.y) else {
+                    continue;
+                };
+                let before = unresolved(grid);
+                grid.follow_cross_link(index, &link, observed_mine, cause);
+                for cell in resolved_since(grid, target.board, &before) {
+                    if visited.insert(cell) {
+                        queue.push_back(cell);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Every board cleared.
+    pub fn won(&self) -> bool {
+        self.boards.iter().all(|grid| grid.won)
+    }
+
+    /// Any board lost ends the run.
+    pub fn game_over(&self) -> bool {
+        self.boards.iter().any(|grid| grid.game_over)
+    }
+
+    /// Take every board's queued events, board by board.
+    pub fn drain_events(&mut self) -> Vec<LabEvent> {
+        self.boards
+            .iter_mut()
+            .enumerate()
+            .flat_map(|(board, grid)| {
+                grid.drain_events().into_iter().map(move |event| LabEvent {
+                    board: board as BoardId,
+                    event,
+                })
+            })
+            .collect()
+    }
+
+    pub fn snapshots(&self) -> Vec<GridSnapshot> {
+        self.boards
+            .iter()
+            .map(|grid| GridSnapshot {
+                inventory: self.inventory,
+                ..grid.snapshot()
+            })
+            .collect()
+    }
+}
+
+/// Whether `link` joins two different cells that exist on `boards`.
+pub(crate) fn link_fits(boards: &[QuantumGrid], link: &CrossLink) -> bool {
+    let on_board = |cell: CellRef| {
+        boards
+            .get(cell.board as usize)
+            .is_some_and(|grid| cell.x < grid.width && cell.y < grid.height)
+    };
+    on_board(link.left)
+        && on_board(link.right)
+        && link.left != link.right
+        && (0.0..=1.0).contains(&link.strength)
+}
+
+/// Indexes of `grid`'s cells still in Superposition.
+fn unresolved(grid: &QuantumGrid) -> Vec<usize> {
+    (0..grid.cells.len())
+        .filter(|&index| grid.is_unresolved(index))
+        .collect()
+}
+
+/// Cells of `grid`, board `board` of its lab, resolved since `before` was
+/// taken with [`unresolved`].
+fn resolved_since(grid: &QuantumGrid, board: BoardId, before: &[usize]) -> Vec<CellRef> {
+    before
+        .iter()
+        .filter(|&&index| !grid.is_unresolved(index))
+        .map(|&index| CellRef {
+            board,
+            x: index as u32 % grid.width,
+            y: index as u32 / grid.width,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entanglement::{Correlation, LinkType};
+    use crate::grid::{CellState, RevealOutcome};
+
+    #[test]
+    fn boards_share_the_inventory_and_the_clock() {
+        let board = GridConfig::new(6, 6, 5, 42, "observer");
+        let mut lab = Lab::new(LabConfig {
+            boards: vec![board.clone(), board.with_shields(3)],
+            shields: 1,
+            links: Vec::new(),
+        });
+        let reveal = |board, x, y| LabAction {
+            board,
+            action: Action::Reveal { x, y },
+        };
+        lab.apply(reveal(0, 0, 0)).unwrap();
+        lab.apply(reveal(1, 0, 0)).unwrap();
+        assert_eq!(lab.boards[1].inventory.shields, 0);
+
+        let mine = |lab: &Lab, board: usize| {
+            let grid = &lab.boards[board];
+            let index = (0..grid.cells.len())
+                .find(|&i| grid.mine_map[i] && grid.cells[i].state != CellState::Contained)
+                .unwrap();
+            (index as u32 % 6, index as u32 / 6)
+        };
+        let (x, y) = mine(&lab, 0);
+        let result = lab.apply(reveal(0, x, y)).unwrap();
+        assert!(matches!(
+            result,
+            ActionResult::Outcome {
+                outcome: RevealOutcome::ShieldAbsorbed { .. }
+            }
+        ));
+        // The shield is spent for every board.
+        let (x, y) = mine(&lab, 1);
+        lab.apply(reveal(1, x, y)).unwrap();
+        assert!(lab.game_over());
+        assert!(lab
+            .drain_events()
+            .iter()
+            .any(|e| e.board == 1 && matches!(e.event, GameEvent::Detonated { .. })));
+
+        assert_eq!(
+            lab.apply(LabAction {
+                board: 7,
+                action: Action::Advance { dt_ms: 10 }
+            }),
+            Err(LabError::UnknownBoard { board: 7 })
+        );
+        lab.apply(LabAction {
+            board: 0,
+            action: Action::Advance { dt_ms: 500 },
+        })
+        .unwrap();
+        assert_eq!(lab.time_ms, 500);
+    }
+
+    #[test]
+    fn bell_links_collapse_cells_on_other_boards() {
+        let board = GridConfig::new(8, 8, 14, 7, "observer");
+        let cell = |board, x, y| CellRef { board, x, y };
+        let bell = |left, right| CrossLink {
+            left,
+            right,
+            strength: 1.0,
+            link_type: LinkType::BellState,
+            correlation: Correlation::Positive,
+        };
+        let mut lab = Lab::new(LabConfig {
+            boards: vec![board.clone(), board.clone(), board],
+            shields: 0,
+            links: vec![
+                bell(cell(0, 0, 0), cell(1, 7, 7)),
+                bell(cell(1, 7, 7), cell(2, 7, 0)),
+                // Off the board: dropped.
+                bell(cell(0, 0, 0), cell(2, 9, 9)),
+            ],
+        });
+        assert_eq!(lab.links.len(), 2);
+        let json = serde_json::to_string(&lab.links[0]).unwrap();
+        assert!(json.contains(r#""left":{"board":0,"x":0,"y":0}"#));
+
+        let reveal = |board, x, y| LabAction {
+            board,
+            action: Action::Reveal { x, y },
+        };
+        for board in [1, 2] {
+            lab.apply(reveal(board, 0, 4)).unwrap();
+        }
+        for (board, index) in [(1, 63), (2, 7)] {
+            assert!(matches!(
+                lab.boards[board].cells[index].state,
+                CellState::Superposition { .. }
+            ));
+        }
+        lab.drain_events();
+
+        lab.apply(reveal(0, 0, 0)).unwrap();
+        for (board, index) in [(1, 63), (2, 7)] {
+            assert!(!matches!(
+                lab.boards[board].cells[index].state,
+                CellState::Superposition { .. }
+            ));
+        }
+        let events = lab.drain_events();
+        assert!(events.iter().any(|e| e.board == 2
+            && e.event
+                == GameEvent::LinkCollapsed {
+                    x: 7,
+                    y: 0,
+                    cause: cell(1, 7, 7)
+                }));
+    }
+
+    #[test]
+    fn links_off_the_lab_are_refused_or_ignored() {
+        let board = GridConfig::new(6, 6, 5, 42, "observer");
+        let mut lab = Lab::new(LabConfig {
+            boards: vec![board.clone(), board],
+            shields: 0,
+            links: Vec::new(),
+        });
+        let link = |right| CrossLink {
+            left: CellRef {
+                board: 0,
+                x: 0,
+                y: 0,
+            },
+            right,
+            strength: 1.0,
+            link_type: LinkType::BellState,
+            correlation: Correlation::Positive,
+        };
+        let off = CellRef {
+            board: 1,
+            x: 40,
+            y: 0,
+        };
+        assert_eq!(lab.add_link(link(off)), Err(LabError::InvalidLink));
+        let on = CellRef {
+            board: 1,
+            x: 5,
+            y: 5,
+        };
+        assert_eq!(lab.add_link(link(on)), Ok(()));
+
+        // A save edited to point past the board plays on without it.
+        lab.links.push(link(off));
+        lab.links.push(link(CellRef { board: 9, ..on }));
+        lab.apply(LabAction {
+            board: 0,
+            action: Action::Reveal { x: 0, y: 0 },
+        })
+        .unwrap();
+    }
+}
@@ -1,4 +1,102 @@
+//! The Quantum Minefield engine: the board with its tools and rules, and
+//! the replays, matchmaking and analysis built on it.
+//!
+//! Failures on player input come back as typed errors, and these lints
+//! keep `unwrap`, `expect`, explicit panics and unchecked indexing out of
+//! engine code. Loaded data is only as safe as the check it passed: see
+//! [`invariants::check`].
+//!
+//! Built with `--no-default-features` the engine is `no_std` + `alloc`:
+//! the board plays as usual. The solver, serialisation and storage, and
+//! the self-play and balancing tools, each sit behind their own feature.
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(
+    not(test),
+    deny(
+        clippy::unwrap_used,
+        clippy::expect_used,
+        clippy::panic,
+        clippy::indexing_slicing,
+        clippy::todo,
+        clippy::unimplemented
+    )
+)]
+
+extern crate alloc;
+
+#[cfg(not(feature = "std"))]
+mod float;
+mod prelude;
+
+pub mod action;
+#[cfg(feature = "analysis")]
+pub mod analysis;
+#[cfg(feature = "analysis")]
+pub mod calibration;
+pub mod chunks;
 pub mod circuit;
+pub mod clock;
+pub mod codec;
+pub mod combo;
+pub mod commitment;
+pub mod compatibility;
+pub mod config;
+pub mod cosmetics;
+pub mod cursor;
+pub mod daemon;
+pub mod decoherence;
+pub mod diff;
 pub mod entanglement;
+pub mod events;
+pub mod fixed;
+pub mod generator;
 pub mod grid;
+pub mod haptics;
+pub mod input;
+pub mod integrity;
+pub mod interference;
+pub mod invariants;
+pub mod inventory;
+pub mod isotope;
+pub mod lab;
+#[cfg(feature = "manifest")]
+pub mod manifest;
+pub mod matchmaking;
+#[cfg(all(feature = "analysis", feature = "serde", feature = "solver"))]
+pub mod metrics;
+pub mod notes;
+pub mod objective;
+pub mod observation;
+pub mod ops;
+#[cfg(feature = "serde")]
+pub mod pack;
+pub mod practice;
+#[cfg(feature = "serde")]
+pub mod profile;
+pub mod progress;
+pub mod rating;
+pub mod reconstruct;
+pub mod referee;
+pub mod replay;
+pub mod resume;
 pub mod rng;
+pub mod rules;
+#[cfg(feature = "analysis")]
+pub mod selfplay;
+pub mod session;
+pub mod shared;
+#[cfg(feature = "solver")]
+pub mod solver;
+pub mod sound;
+pub mod splits;
+#[cfg(feature = "serde")]
+pub mod storage;
+mod telemetry;
+pub mod timeline;
+pub mod tournament;
+pub mod trigger;
+#[cfg(feature = "analysis")]
+pub mod tuner;
+pub mod turns;
+pub mod zeno;
+pub mod zone;
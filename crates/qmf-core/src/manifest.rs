@@ -0,0 +1,243 @@
+use core::fmt;
+
+use ed25519_compact::{KeyPair, PublicKey, Seed, Signature};
+use serde::{Deserialize, Serialize};
+
+use crate::commitment::{from_hex, to_hex};
+use crate::config::{ConfigError, GridConfig};
+use crate::prelude::*;
+
+/// Storage key the last verified [`SignedManifest`] is kept under, so a
+/// client that goes offline still has the dailies it was sent.
+pub const MANIFEST_KEY: &str = "manifest";
+
+/// The board everyone plays on one day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DailyPuzzle {
+    /// Days since the Unix epoch, in UTC.
+    pub day: u32,
+    pub config: GridConfig,
+}
+
+/// A scenario pack the server recommends, pinned by digest so a client can
+/// fetch it from any mirror.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PackListing {
+    pub id: String,
+    pub title: String,
+    pub url: String,
+    /// Hex SHA-256 of the pack file.
+    pub sha256: String,
+}
+
+/// A board picked out for the front page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeaturedSeed {
+    pub title: String,
+    pub config: GridConfig,
+}
+
+/// What the server publishes ahead of time: the coming days' dailies,
+/// recommended packs and featured seeds.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// Day the manifest was issued (see [`DailyPuzzle::day`]).
+    pub issued: u32,
+    #[serde(default)]
+    pub dailies: Vec<DailyPuzzle>,
+    #[serde(default)]
+    pub packs: Vec<PackListing>,
+    #[serde(default)]
+    pub featured: Vec<FeaturedSeed>,
+}
+
+impl Manifest {
+    /// The puzzle for `day`, if the manifest covers it.
+    pub fn daily(&self, day: u32) -> Option<&DailyPuzzle> {
+        self.dailies.iter().find(|daily| daily.day == day)
+    }
+
+    /// The puzzles from `today` through the next `days - 1` days, in day
+    /// order: what an offline client should have downloaded.
+    pub fn upcoming(&self, today: u32, days: u32) -> Vec<&DailyPuzzle> {
+        let end = today.saturating_add(days);
+        let mut upcoming: Vec<&DailyPuzzle> = self
+            .dailies
+            .iter()
+            .filter(|daily| (today..end).contains(&daily.day))
+            .collect();
+        upcoming.sort_by_key(|daily| daily.day);
+        upcoming
+    }
+
+    /// Sign for publication with the server's 32-byte Ed25519 seed.
+    pub fn sign(&self, secret: &[u8; 32]) -> Result<SignedManifest, ManifestError> {
+        let body = serde_json::to_string(self).map_err(|error| ManifestError::Malformed {
+            message: error.to_string(),
+        })?;
+        let key_pair = KeyPair::from_seed(Seed::new(*secret));
+        let signature = key_pair.sk.sign(body.as_bytes(), None);
+        Ok(SignedManifest {
+            body,
+            signature: to_hex(signature.as_ref()),
+        })
+    }
+
+    /// Why a client should not use this manifest, if anything: two dailies
+    /// on one day, or a board no client can build.
+    pub fn check(&self) -> Result<(), ManifestError> {
+        for (i, daily) in self.dailies.iter().enumerate() {
+            if self
+                .dailies
+                .iter()
+                .take(i)
+                .any(|other| other.day == daily.day)
+            {
+                return Err(ManifestError::DuplicateDay { day: daily.day });
+            }
+        }
+        let boards = self
+            .dailies
+            .iter()
+            .map(|daily| &daily.config)
+            .chain(self.featured.iter().map(|featured| &featured.config));
+        for config in boards {
+            config
+                .validate()
+                .map_err(|error| ManifestError::InvalidBoard { error })?;
+        }
+        Ok(())
+    }
+}
+
+/// The public key clients embed to check manifests signed with `secret`.
+pub fn public_key(secret: &[u8; 32]) -> [u8; 32] {
+    *KeyPair::from_seed(Seed::new(*secret)).pk
+}
+
+/// A [`Manifest`] as published: the exact JSON that was signed and its
+/// hex Ed25519 signature. Keeping the body as text means checking it never
+/// depends on serialising the manifest the same way twice.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedManifest {
+    pub body: String,
+    pub signature: String,
+}
+
+impl SignedManifest {
+    /// The manifest, once its signature checks out against `public_key`
+    /// and it passes [`Manifest::check`].
+    pub fn verify(&self, public_key: &[u8; 32]) -> Result<Manifest, ManifestError> {
+        let signature = from_hex(&self.signature)
+            .ok()
+            .and_then(|bytes| Signature::from_slice(&bytes).ok())
+            .ok_or(ManifestError::BadSignature)?;
+        PublicKey::new(*public_key)
+            .verify(self.body.as_bytes(), &signature)
+            .map_err(|_| ManifestError::BadSignature)?;
+        let manifest: Manifest =
+            serde_json::from_str(&self.body).map_err(|error| ManifestError::Malformed {
+                message: error.to_string(),
+            })?;
+        manifest.check()?;
+        Ok(manifest)
+    }
+}
+
+/// Why a [`SignedManifest`] was refused.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ManifestError {
+    /// The signature is missing, garbled, or not from the embedded key.
+    BadSignature,
+    /// The signed body is not a manifest.
+    Malformed { message: String },
+    /// Two dailies claim the same day.
+    DuplicateDay { day: u32 },
+    /// A daily or featured board would be refused by
+    /// [`GridConfig::validate`].
+    InvalidBoard { error: ConfigError },
+}
+
+impl fmt::Display for ManifestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BadSignature => write!(f, "manifest signature does not verify"),
+            Self::Malformed { message } => write!(f, "manifest is malformed: {message}"),
+            Self::DuplicateDay { day } => write!(f, "manifest has two dailies on day {day}"),
+            Self::InvalidBoard { error } => write!(f, "manifest has an invalid board: {error}"),
+        }
+    }
+}
+
+impl core::error::Error for ManifestError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: [u8; 32] = [7; 32];
+
+    fn week(from: u32) -> Manifest {
+        Manifest {
+            issued: from,
+            dailies: (from..from + 7)
+                .rev()
+                .map(|day| DailyPuzzle {
+                    day,
+                    config: GridConfig::new(9, 9, 10, u64::from(day), "researcher"),
+                })
+                .collect(),
+            packs: vec![PackListing {
+                id: "intro".to_string(),
+                title: "Introductions".to_string(),
+                url: "https://example.com/intro.qmfpack".to_string(),
+                sha256: to_hex(&[0; 32]),
+            }],
+            featured: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn a_signed_week_verifies_and_lists_in_order() {
+        let signed = week(20_000).sign(&SECRET).unwrap();
+        let manifest = signed.verify(&public_key(&SECRET)).unwrap();
+        assert_eq!(manifest, week(20_000));
+        let days: Vec<u32> = manifest
+            .upcoming(20_002, 7)
+            .iter()
+            .map(|daily| daily.day)
+            .collect();
+        assert_eq!(days, [20_002, 20_003, 20_004, 20_005, 20_006]);
+        assert_eq!(manifest.daily(20_003).unwrap().config.seed, 20_003);
+        assert!(manifest.daily(20_007).is_none());
+    }
+
+    #[test]
+    fn tampered_or_unsound_manifests_are_refused() {
+        let key = public_key(&SECRET);
+        let mut signed = week(1).sign(&SECRET).unwrap();
+        signed.body = signed.body.replacen("\"seed\":1", "\"seed\":2", 1);
+        assert_eq!(signed.verify(&key), Err(ManifestError::BadSignature));
+        assert_eq!(
+            week(1).sign(&[8; 32]).unwrap().verify(&key),
+            Err(ManifestError::BadSignature)
+        );
+
+        let mut doubled = week(1);
+        doubled.dailies.push(doubled.dailies[0].clone());
+        assert_eq!(
+            doubled.sign(&SECRET).unwrap().verify(&key),
+            Err(ManifestError::DuplicateDay { day: 7 })
+        );
+        let mut crowded = week(1);
+        crowded.featured.push(FeaturedSeed {
+            title: "Full".to_string(),
+            config: GridConfig::new(4, 4, 16, 1, "observer"),
+        });
+        assert!(matches!(
+            crowded.sign(&SECRET).unwrap().verify(&key),
+            Err(ManifestError::InvalidBoard { .. })
+        ));
+    }
+}
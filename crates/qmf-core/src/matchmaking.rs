@@ -0,0 +1,328 @@
+use alloc::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::commitment::SeedCommitment;
+use crate::config::GridConfig;
+use crate::grid::QuantumGrid;
+use crate::prelude::*;
+use crate::rating::{RatedResult, Rating};
+use crate::referee::{PlayerId, Referee};
+use crate::tournament::MatchScore;
+
+/// Rating gap accepted the moment a player joins the queue.
+pub const BASE_WINDOW: f64 = 100.0;
+/// How fast the accepted gap widens, per second in the queue.
+pub const WIDEN_PER_SECOND: f64 = 25.0;
+/// The gap never widens past this.
+pub const MAX_WINDOW: f64 = 600.0;
+/// How long a disconnected player may be gone before forfeiting.
+pub const GRACE_MS: u64 = 30_000;
+
+/// A player waiting for a ranked match.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct QueueEntry {
+    pub player: PlayerId,
+    pub rating: Rating,
+    pub joined_ms: u64,
+}
+
+impl QueueEntry {
+    /// Rating gap this player accepts at `now_ms`.
+    pub fn window(&self, now_ms: u64) -> f64 {
+        let waited = now_ms.saturating_sub(self.joined_ms) as f64 / 1000.0;
+        (BASE_WINDOW + WIDEN_PER_SECOND * waited).min(MAX_WINDOW)
+    }
+}
+
+/// The secret half of a match's seed commitment, revealed once it ends.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MatchSecret {
+    pub seed: u64,
+    pub salt: [u8; 16],
+}
+
+/// A ranked match the queue made. `commitment` is published to both
+/// players before the first move. The secret is left out when the match
+/// is serialized, so sending a match to its players does not give the
+/// board away.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RankedMatch {
+    pub id: u64,
+    pub home: QueueEntry,
+    pub away: QueueEntry,
+    pub commitment: SeedCommitment,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    secret: MatchSecret,
+    /// Players currently disconnected, with when they dropped.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub disconnected: Vec<(PlayerId, u64)>,
+}
+
+impl RankedMatch {
+    /// A [`Referee`] for this match on a board like `config`, seeded with
+    /// the committed seed.
+    pub fn referee(&self, config: &GridConfig, max_actions_per_second: u32) -> Referee {
+        let grid = QuantumGrid::from_config(GridConfig {
+            seed: self.secret.seed,
+            ..config.clone()
+        });
+        Referee::new(
+            grid,
+            vec![self.home.player, self.away.player],
+            max_actions_per_second,
+        )
+    }
+
+    /// Seed and salt for the reveal. Only publish once the match is over.
+    pub fn reveal(&self) -> MatchSecret {
+        self.secret
+    }
+
+    /// Rating results for home and away from their final scores, ready
+    /// for [`Rating::update`].
+    pub fn results(&self, home: &MatchScore, away: &MatchScore) -> [(PlayerId, RatedResult); 2] {
+        [
+            (
+                self.home.player,
+                RatedResult::from_scores(self.away.rating, home, away),
+            ),
+            (
+                self.away.player,
+                RatedResult::from_scores(self.home.rating, away, home),
+            ),
+        ]
+    }
+
+    fn has(&self, player: PlayerId) -> bool {
+        self.home.player == player || self.away.player == player
+    }
+}
+
+/// Transport-free ranked matchmaking: a queue pairing players by rating
+/// with search windows that widen the longer they wait, and the matches in
+/// play with reconnect grace. A server drives it with authoritative
+/// timestamps and persists it between restarts through serde.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Matchmaker {
+    pub queue: Vec<QueueEntry>,
+    /// Matches in play. After a restart their secrets are only restored
+    /// by [`Self::reconnect`] and [`Self::finish`].
+    pub matches: Vec<RankedMatch>,
+    next_id: u64,
+    /// Each match's secret by id, persisted here since the matches leave
+    /// theirs out.
+    #[cfg_attr(feature = "serde", serde(default))]
+    secrets: BTreeMap<u64, MatchSecret>,
+}
+
+impl Matchmaker {
+    /// Queue `player`; joining twice keeps the original place. Returns
+    /// false, queueing nothing, while the player is in a match.
+    pub fn join(&mut self, player: PlayerId, rating: Rating, now_ms: u64) -> bool {
+        if self.matches.iter().any(|m| m.has(player)) {
+            return false;
+        }
+        if !self.queue.iter().any(|entry| entry.player == player) {
+            self.queue.push(QueueEntry {
+                player,
+                rating,
+                joined_ms: now_ms,
+            });
+        }
+        true
+    }
+
+    pub fn leave(&mut self, player: PlayerId) {
+        self.queue.retain(|entry| entry.player != player);
+    }
+
+    /// Pair whoever can be paired at `now_ms`, longest-waiting first, each
+    /// with the closest rating both players' windows accept. `entropy`
+    /// supplies the randomness for each match's seed and salt. Returns the
+    /// new matches.
+    pub fn poll(&mut self, now_ms: u64, mut entropy: impl FnMut() -> u64) -> Vec<RankedMatch> {
+        self.queue.sort_by_key(|entry| entry.joined_ms);
+        let mut made = Vec::new();
+        let mut i = 0;
+        while let Some(&home) = self.queue.get(i) {
+            let gap = |other: &QueueEntry| (other.rating.rating - home.rating.rating).abs();
+            let partner = self
+                .queue
+                .iter()
+                .enumerate()
+                .skip(i + 1)
+                .filter(|(_, other)| gap(other) <= home.window(now_ms).min(other.window(now_ms)))
+                .min_by(|(_, a), (_, b)| gap(a).total_cmp(&gap(b)))
+                .map(|(j, _)| j);
+            let Some(j) = partner else {
+                i += 1;
+                continue;
+            };
+            let away = self.queue.remove(j);
+            self.queue.remove(i);
+            let mut salt = [0; 16];
+            salt[..8].copy_from_slice(&entropy().to_le_bytes());
+            salt[8..].copy_from_slice(&entropy().to_le_bytes());
+            let secret = MatchSecret {
+                seed: entropy(),
+                salt,
+            };
+            let ranked = RankedMatch {
+                id: self.next_id,
+                home,
+                away,
+                commitment: SeedCommitment::commit(secret.seed, &secret.salt),
+                secret,
+                disconnected: Vec::new(),
+            };
+            self.next_id += 1;
+            self.secrets.insert(ranked.id, secret);
+            self.matches.push(ranked.clone());
+            made.push(ranked);
+        }
+        made
+    }
+
+    /// `player` dropped at `now_ms`; their grace period starts.
+    pub fn disconnect(&mut self, player: PlayerId, now_ms: u64) {
+        if let Some(ranked) = self.matches.iter_mut().find(|m| m.has(player)) {
+            if !ranked.disconnected.iter().any(|&(p, _)| p == player) {
+                ranked.disconnected.push((player, now_ms));
+            }
+        }
+    }
+
+    /// `player` is back; returns their match if it is still on.
+    pub fn reconnect(&mut self, player: PlayerId) -> Option<&RankedMatch> {
+        let ranked = self.matches.iter_mut().find(|m| m.has(player))?;
+        ranked.disconnected.retain(|&(p, _)| p != player);
+        if let Some(&secret) = self.secrets.get(&ranked.id) {
+            ranked.secret = secret;
+        }
+        Some(ranked)
+    }
+
+    /// Players whose grace period ran out by `now_ms`, as
+    /// `(match id, player)`. Their matches stay in play until
+    /// [`Self::finish`], so the server can score the forfeit.
+    pub fn forfeits(&self, now_ms: u64) -> Vec<(u64, PlayerId)> {
+        self.matches
+            .iter()
+            .flat_map(|m| {
+                m.disconnected
+                    .iter()
+                    .filter(move |&&(_, since)| now_ms.saturating_sub(since) >= GRACE_MS)
+                    .map(move |&(player, _)| (m.id, player))
+            })
+            .collect()
+    }
+
+    /// Take match `id` out of play, e.g. to reveal its seed and rate it.
+    pub fn finish(&mut self, id: u64) -> Option<RankedMatch> {
+        let index = self.matches.iter().position(|m| m.id == id)?;
+        let mut ranked = self.matches.remove(index);
+        if let Some(secret) = self.secrets.remove(&id) {
+            ranked.secret = secret;
+        }
+        Some(ranked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rated(rating: f64) -> Rating {
+        Rating {
+            rating,
+            ..Rating::default()
+        }
+    }
+
+    #[test]
+    fn windows_widen_until_players_pair() {
+        let mut queue = Matchmaker::default();
+        let mut entropy = (1..).map(|n: u64| n * 0x9E37_79B9);
+        queue.join(1, rated(1500.0), 0);
+        queue.join(2, rated(1800.0), 0);
+        queue.join(3, rated(1560.0), 1_000);
+        queue.join(1, rated(1500.0), 5_000);
+
+        let made = queue.poll(1_000, || entropy.next().unwrap());
+        assert_eq!(made.len(), 1);
+        assert_eq!((made[0].home.player, made[0].away.player), (1, 3));
+        assert!(queue.poll(4_000, || entropy.next().unwrap()).is_empty());
+
+        // 300 points apart needs both windows at 300: 8 s of waiting.
+        queue.join(4, rated(1500.0), 0);
+        let late = queue.poll(8_000, || entropy.next().unwrap());
+        assert_eq!((late[0].home.player, late[0].away.player), (2, 4));
+
+        let secret = late[0].reveal();
+        assert!(late[0].commitment.verify(secret.seed, &secret.salt));
+        let referee = late[0].referee(&GridConfig::new(8, 8, 10, 0, "observer"), 5);
+        assert_eq!(referee.grid.seed, secret.seed);
+
+        // Players already in a match cannot queue for another.
+        assert!(!queue.join(2, rated(1800.0), 9_000));
+        assert!(queue.queue.is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialized_matches_keep_the_secret_back() {
+        let mut queue = Matchmaker::default();
+        queue.join(1, rated(1500.0), 0);
+        queue.join(2, rated(1500.0), 0);
+        let made = queue.poll(0, || 0x5EED_5EED);
+        let secret = made[0].reveal();
+
+        let sent = serde_json::to_string(&made[0]).unwrap();
+        assert!(!sent.contains(&secret.seed.to_string()), "{sent}");
+        let received: RankedMatch = serde_json::from_str(&sent).unwrap();
+        assert_eq!(received.reveal(), MatchSecret::default());
+
+        // A restarted server still has it.
+        let saved = serde_json::to_string(&queue).unwrap();
+        let mut restarted: Matchmaker = serde_json::from_str(&saved).unwrap();
+        assert_eq!(restarted.reconnect(1).unwrap().reveal(), secret);
+        assert_eq!(restarted.finish(made[0].id).unwrap().reveal(), secret);
+    }
+
+    #[test]
+    fn disconnected_players_get_a_grace_period() {
+        let mut queue = Matchmaker::default();
+        queue.join(1, rated(1500.0), 0);
+        queue.join(2, rated(1500.0), 0);
+        let id = queue.poll(0, || 7)[0].id;
+
+        queue.disconnect(2, 1_000);
+        assert!(queue.forfeits(1_000 + GRACE_MS - 1).is_empty());
+        assert!(queue.reconnect(2).is_some());
+        assert!(queue.forfeits(1_000 + GRACE_MS).is_empty());
+
+        queue.disconnect(1, 2_000);
+        assert_eq!(queue.forfeits(2_000 + GRACE_MS), vec![(id, 1)]);
+        let ranked = queue.finish(id).unwrap();
+        assert!(queue.reconnect(1).is_none());
+
+        let win = MatchScore {
+            won: true,
+            resolved: 54,
+            points: 540,
+            entropy_millibits: 0,
+            decoherence: 0,
+            elapsed_ms: 90_000,
+        };
+        let loss = MatchScore { won: false, ..win };
+        let [(home, home_result), (away, away_result)] = ranked.results(&loss, &win);
+        assert_eq!((home, home_result.score), (1, 0.0));
+        assert_eq!((away, away_result.score), (2, 1.0));
+    }
+}
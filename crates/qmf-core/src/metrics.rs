@@ -0,0 +1,171 @@
+use crate::action::Action;
+use crate::config::GridConfig;
+use crate::generator::three_bv;
+use crate::grid::QuantumGrid;
+use crate::pack::{Pack, Scenario, ScenarioRating};
+use crate::rng::SplitMix64;
+use crate::selfplay::Strategy;
+use crate::solver::{self, SolveBudget};
+
+/// The bots a scenario is rated with, each playing every game.
+pub const ENSEMBLE: [Strategy; 2] = [Strategy::LowestHint, Strategy::Random];
+/// Games per bot in [`rate_scenario`].
+pub const RATING_GAMES: u32 = 20;
+
+const EPSILON: f64 = 1e-9;
+
+/// How one bot game went.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BotRun {
+    /// The scenario's goal was met.
+    pub won: bool,
+    pub moves: u32,
+    /// Moves the solver could not prove safe (or, for a containment, a
+    /// mine) when the bot made them.
+    pub guesses: u32,
+    /// [`three_bv`] of the board the bot played.
+    pub three_bv: u32,
+}
+
+/// Play `config`'s own board to the end with `strategy`, asking the solver
+/// before each move whether it was proven.
+pub fn run(config: &GridConfig, strategy: Strategy) -> BotRun {
+    let mut grid = QuantumGrid::from_config(config.clone());
+    let mut rng = SplitMix64::new(config.seed);
+    let limit = 4 * grid.cells.len();
+    let (mut moves, mut guesses) = (0, 0);
+    while !grid.game_over && !grid.won && (moves as usize) < limit {
+        let Some(action) = strategy.choose(&grid, &mut rng) else {
+            break;
+        };
+        let index = action.coords().and_then(|(x, y)| grid.index_of(x, y));
+        if let Some(index) = index.filter(|_| grid.mines_placed) {
+            let solved = solver::probabilities(&grid, SolveBudget::default());
+            let p = solved.cells.get(index).copied().unwrap_or(0.5);
+            let proven = match action {
+                Action::Contain { .. } => p >= 1.0 - EPSILON,
+                _ => p <= EPSILON,
+            };
+            guesses += u32::from(!proven);
+        }
+        if grid.apply(action).is_rejection() {
+            break;
+        }
+        moves += 1;
+    }
+    BotRun {
+        won: grid.won,
+        moves,
+        guesses,
+        three_bv: three_bv(&grid),
+    }
+}
+
+/// Whether `config`'s own board can be finished without a guess: after an
+/// opening click in the middle, every move is one the solver proves, a
+/// reveal safe or a containment a mine. Unlike a bot run, luck cannot pass
+/// a board that needs guessing, nor fail one that does not.
+pub fn solves_without_guessing(config: &GridConfig) -> bool {
+    let mut grid = QuantumGrid::from_config(config.clone());
+    let limit = 4 * grid.cells.len();
+    let mut action = Action::Reveal {
+        x: grid.width / 2,
+        y: grid.height / 2,
+    };
+    for _ in 0..limit {
+        if grid.apply(action).is_rejection() {
+            return false;
+        }
+        if grid.game_over || grid.won {
+            break;
+        }
+        let solved = solver::probabilities(&grid, SolveBudget::default());
+        if !solved.is_exact() {
+            return false;
+        }
+        let proven = (0..grid.cells.len())
+            .filter(|&index| grid.is_unresolved(index))
+            .find_map(|index| {
+                let (x, y) = (index as u32 % grid.width, index as u32 / grid.width);
+                let p = *solved.cells.get(index)?;
+                if p <= EPSILON {
+                    Some(Action::Reveal { x, y })
+                } else if p >= 1.0 - EPSILON {
+                    Some(Action::Contain { x, y })
+                } else {
+                    None
+                }
+            });
+        let Some(next) = proven else {
+            return false;
+        };
+        action = next;
+    }
+    grid.won
+}
+
+/// Rate `config` by having every [`ENSEMBLE`] bot play `games` boards of
+/// it, seeded `config.seed`, `config.seed + 1`, … so every bot meets the
+/// same boards.
+pub fn rate_config(config: &GridConfig, games: u32) -> ScenarioRating {
+    let runs: Vec<BotRun> = (0..games)
+        .flat_map(|offset| {
+            let config = GridConfig {
+                seed: config.seed.wrapping_add(u64::from(offset)),
+                ..config.clone()
+            };
+            ENSEMBLE.map(|strategy| run(&config, strategy))
+        })
+        .collect();
+    let total = runs.len().max(1) as f64;
+    let mean = |value: fn(&BotRun) -> u32| {
+        runs.iter().map(|run| f64::from(value(run))).sum::<f64>() / total
+    };
+    ScenarioRating {
+        games: runs.len() as u32,
+        clear_rate: runs.iter().filter(|run| run.won).count() as f64 / total,
+        mean_moves: mean(|run| run.moves),
+        mean_guesses: mean(|run| run.guesses),
+    }
+}
+
+/// [`rate_config`] on the scenario's board with [`RATING_GAMES`] games
+/// per bot. Bots have no clock, so moves stand in for time.
+pub fn rate_scenario(scenario: &Scenario) -> ScenarioRating {
+    rate_config(&scenario.config, RATING_GAMES)
+}
+
+/// Rate every scenario in `pack` and record it in the pack's index, ready
+/// for [`Pack::write`].
+pub fn rate_pack(pack: &mut Pack) {
+    for (entry, scenario) in pack.index.scenarios.iter_mut().zip(&pack.scenarios) {
+        entry.rating = Some(rate_scenario(scenario));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn denser_boards_rate_harder() {
+        let easy = rate_config(&GridConfig::new(8, 8, 4, 1, "observer"), 6);
+        let hard = rate_config(&GridConfig::new(8, 8, 20, 1, "observer"), 6);
+        assert_eq!(easy.games, 12);
+        assert!(easy.clear_rate > hard.clear_rate, "{easy:?} vs {hard:?}");
+        assert!(easy.mean_guesses < hard.mean_guesses);
+        assert!(easy.mean_moves > 0.0);
+
+        let proven = run(
+            &GridConfig::new(6, 6, 1, 3, "observer"),
+            Strategy::LowestHint,
+        );
+        assert!(proven.won && proven.guesses == 0 && proven.three_bv > 0);
+        assert!(solves_without_guessing(&GridConfig::new(
+            6, 6, 1, 3, "observer"
+        )));
+        assert!(!solves_without_guessing(&GridConfig::new(
+            8, 8, 20, 1, "observer"
+        )));
+    }
+}
@@ -0,0 +1,46 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+/// Longest text note accepted, in characters.
+pub const MAX_NOTE_LEN: usize = 64;
+
+/// Freeform analysis annotation attached to a cell (e.g. a player's own
+/// probability estimate). Saved with the grid but never part of a
+/// [`GridSnapshot`](crate::grid::GridSnapshot).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(tag = "kind", content = "value", rename_all = "snake_case")
+)]
+pub enum CellNote {
+    Text(String),
+    Number(f64),
+}
+
+impl CellNote {
+    pub fn validate(&self) -> Result<(), &'static str> {
+        match self {
+            Self::Text(text) if text.chars().count() > MAX_NOTE_LEN => Err("note is too long"),
+            Self::Number(value) if !value.is_finite() => Err("note must be a finite number"),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validation_limits() {
+        assert!(CellNote::Text("p≈0.3".into()).validate().is_ok());
+        assert!(CellNote::Text("x".repeat(MAX_NOTE_LEN + 1))
+            .validate()
+            .is_err());
+        assert!(CellNote::Number(0.25).validate().is_ok());
+        assert!(CellNote::Number(f64::NAN).validate().is_err());
+    }
+}
@@ -0,0 +1,264 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::events::GameEvent;
+use crate::grid::{CellState, QuantumGrid};
+use crate::prelude::*;
+
+/// A goal a scenario sets in place of clearing the board (see
+/// [`GridConfig::objectives`](crate::config::GridConfig::objectives)).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum Objective {
+    /// Wavefunction purification: no cell left in Superposition.
+    ResolveAll,
+    /// Contain the mine under each of `cells`. Fails if one of them turns
+    /// out safe or goes off.
+    ContainCells { cells: Vec<(u32, u32)> },
+    /// Bring [`QuantumGrid::quantum_entropy`] under `bits` within `moves`
+    /// moves.
+    EntropyBelow { bits: f64, moves: u32 },
+    /// Keep the lifetime [decoherence](crate::decoherence::DecoherenceMeter)
+    /// under `points`. Holds until broken, and completes when every other
+    /// objective does (or, when it stands alone, when the board is
+    /// cleared).
+    DecoherenceUnder { points: u32 },
+}
+
+impl Objective {
+    fn is_standing(&self) -> bool {
+        matches!(self, Self::DecoherenceUnder { .. })
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ObjectiveStatus {
+    #[default]
+    Pending,
+    Complete,
+    Failed,
+}
+
+/// One objective and how far along it is.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ObjectiveState {
+    pub objective: Objective,
+    pub status: ObjectiveStatus,
+    /// Partial credit in `[0, 1]`: 1 once complete, 0 once failed.
+    pub progress: f64,
+}
+
+/// The scenario's objectives, evaluated after every move. With none, the
+/// grid falls back to the classic purification win.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Objectives {
+    pub list: Vec<ObjectiveState>,
+    /// Moves made so far, for [`Objective::EntropyBelow`].
+    pub moves: u32,
+}
+
+impl Objectives {
+    pub fn new(objectives: &[Objective]) -> Self {
+        Self {
+            list: objectives
+                .iter()
+                .map(|objective| ObjectiveState {
+                    objective: objective.clone(),
+                    status: ObjectiveStatus::Pending,
+                    progress: 0.0,
+                })
+                .collect(),
+            moves: 0,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Every objective done: the scenario is won.
+    pub fn complete(&self) -> bool {
+        !self.is_empty()
+            && self
+                .list
+                .iter()
+                .all(|state| state.status == ObjectiveStatus::Complete)
+    }
+
+    pub fn failed(&self) -> bool {
+        self.list
+            .iter()
+            .any(|state| state.status == ObjectiveStatus::Failed)
+    }
+
+    /// Partial-credit score in `[0, 1]`: the mean progress over all
+    /// objectives.
+    pub fn credit(&self) -> f64 {
+        if self.is_empty() {
+            return 0.0;
+        }
+        self.list.iter().map(|state| state.progress).sum::<f64>() / self.list.len() as f64
+    }
+
+    /// Re-evaluate the pending objectives against `grid`, returning the
+    /// events for those that just completed or failed.
+    pub(crate) fn evaluate(&mut self, grid: &QuantumGrid) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+        for (index, state) in self.list.iter_mut().enumerate() {
+            if state.status != ObjectiveStatus::Pending {
+                continue;
+            }
+            let (status, progress) = assess(&state.objective, grid, self.moves);
+            state.progress = progress;
+            state.status = status;
+            let index = index as u32;
+            match status {
+                ObjectiveStatus::Complete => events.push(GameEvent::ObjectiveCompleted { index }),
+                ObjectiveStatus::Failed => events.push(GameEvent::ObjectiveFailed { index }),
+                ObjectiveStatus::Pending => {}
+            }
+        }
+        // With only standing objectives, clearing the board is the goal.
+        let others_done = if self.list.iter().all(|s| s.objective.is_standing()) {
+            assess(&Objective::ResolveAll, grid, self.moves).0 == ObjectiveStatus::Complete
+        } else {
+            self.list.iter().all(|state| {
+                state.objective.is_standing() || state.status == ObjectiveStatus::Complete
+            })
+        };
+        if others_done && !self.failed() {
+            for (index, state) in self.list.iter_mut().enumerate() {
+                if state.status == ObjectiveStatus::Pending {
+                    state.status = ObjectiveStatus::Complete;
+                    state.progress = 1.0;
+                    events.push(GameEvent::ObjectiveCompleted {
+                        index: index as u32,
+                    });
+                }
+            }
+        }
+        events
+    }
+}
+
+/// Where `objective` stands on `grid` after `moves` moves.
+fn assess(objective: &Objective, grid: &QuantumGrid, moves: u32) -> (ObjectiveStatus, f64) {
+    let ratio = |done: usize, total: usize| done as f64 / total.max(1) as f64;
+    match objective {
+        Objective::ResolveAll => {
+            let resolved = grid
+                .cells
+                .iter()
+                .filter(|cell| !matches!(cell.state, CellState::Superposition { .. }))
+                .count();
+            let status = if resolved == grid.cells.len() {
+                ObjectiveStatus::Complete
+            } else {
+                ObjectiveStatus::Pending
+            };
+            (status, ratio(resolved, grid.cells.len()))
+        }
+        Objective::ContainCells { cells } => {
+            let states: Vec<Option<&CellState>> = cells
+                .iter()
+                .map(|&(x, y)| grid.cell(x, y).map(|cell| &cell.state))
+                .collect();
+            let lost = states.iter().any(|state| {
+                matches!(
+                    state,
+                    None | Some(CellState::Revealed { .. } | CellState::Detonated)
+                )
+            });
+            let contained = states
+                .iter()
+                .filter(|state| matches!(state, Some(CellState::Contained)))
+                .count();
+            if lost {
+                (ObjectiveStatus::Failed, 0.0)
+            } else if contained == cells.len() {
+                (ObjectiveStatus::Complete, 1.0)
+            } else {
+                (ObjectiveStatus::Pending, ratio(contained, cells.len()))
+            }
+        }
+        Objective::EntropyBelow { bits, moves: limit } => {
+            let entropy = grid.quantum_entropy();
+            if entropy < *bits {
+                (ObjectiveStatus::Complete, 1.0)
+            } else if moves >= *limit {
+                (ObjectiveStatus::Failed, 0.0)
+            } else {
+                (ObjectiveStatus::Pending, (bits / entropy).clamp(0.0, 1.0))
+            }
+        }
+        Objective::DecoherenceUnder { points } => {
+            let lifetime = grid.decoherence.lifetime;
+            if lifetime >= *points {
+                (ObjectiveStatus::Failed, 0.0)
+            } else {
+                (
+                    ObjectiveStatus::Pending,
+                    1.0 - f64::from(lifetime) / f64::from(*points),
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GridConfig;
+
+    #[test]
+    fn objectives_replace_the_purification_win() {
+        let config = GridConfig::new(6, 6, 5, 42, "observer");
+        let mut grid = QuantumGrid::from_config(
+            config
+                .clone()
+                .with_objective(Objective::ResolveAll)
+                .with_objective(Objective::DecoherenceUnder { points: 1_000_000 }),
+        );
+        grid.reveal_cell(0, 0);
+        assert!(grid.objectives.credit() > 0.0 && !grid.won);
+
+        let mine = (0..36).find(|&i| grid.mine_map[i]).unwrap();
+        let mut target = QuantumGrid::from_config(
+            config
+                .clone()
+                .with_objective(Objective::ContainCells {
+                    cells: vec![(mine as u32 % 6, mine as u32 / 6)],
+                })
+                .with_objective(Objective::DecoherenceUnder { points: 1_000_000 }),
+        );
+        target.reveal_cell(0, 0);
+        target.drain_events();
+        target.contain_cell(mine as u32 % 6, mine as u32 / 6);
+        assert!(target.won && !target.game_over);
+        assert_eq!(target.objectives.credit(), 1.0);
+        let events = target.drain_events();
+        for index in [0, 1] {
+            assert!(events.contains(&GameEvent::ObjectiveCompleted { index }));
+        }
+        assert_eq!(events.last(), Some(&GameEvent::Won));
+
+        let mut careful = QuantumGrid::from_config(
+            config.with_objective(Objective::DecoherenceUnder { points: 1 }),
+        );
+        careful.reveal_cell(0, 0);
+        let (x, y) = (0..36)
+            .find(|&i| matches!(careful.cells[i].state, CellState::Superposition { .. }))
+            .map(|i| (i as u32 % 6, i as u32 / 6))
+            .unwrap();
+        careful.measure_weak(x, y).unwrap();
+        assert!(careful.game_over && !careful.won);
+        assert!(careful
+            .drain_events()
+            .contains(&GameEvent::ObjectiveFailed { index: 0 }));
+    }
+}
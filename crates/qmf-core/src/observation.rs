@@ -0,0 +1,95 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::action::IllegalAction;
+use crate::config::{AssistLevel, GridConfig};
+
+/// Which probabilities an agent is shown.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ProbabilityView {
+    /// The hints a player sees, scrambled by the circuit and noise.
+    #[default]
+    Displayed,
+    /// Hints before scrambling (see
+    /// [`QuantumGrid::unscrambled_probabilities`](crate::grid::QuantumGrid::unscrambled_probabilities)).
+    True,
+    /// No probability channel at all.
+    Hidden,
+}
+
+/// What an automated agent may see of a board. Training code and bots
+/// record the spec next to their results so experiments are reproducible.
+/// The default matches what a human player sees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ObservationSpec {
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub probabilities: ProbabilityView,
+    /// Show entanglement link strengths.
+    #[cfg_attr(feature = "serde", serde(default = "default_entanglement"))]
+    pub entanglement: bool,
+    /// Show the true mine map, e.g. as labels for imitation learning.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub ground_truth: bool,
+}
+
+fn default_entanglement() -> bool {
+    true
+}
+
+impl Default for ObservationSpec {
+    fn default() -> Self {
+        Self {
+            probabilities: ProbabilityView::default(),
+            entanglement: default_entanglement(),
+            ground_truth: false,
+        }
+    }
+}
+
+impl ObservationSpec {
+    pub fn with_probabilities(mut self, probabilities: ProbabilityView) -> Self {
+        self.probabilities = probabilities;
+        self
+    }
+
+    pub fn with_entanglement(mut self, entanglement: bool) -> Self {
+        self.entanglement = entanglement;
+        self
+    }
+
+    pub fn with_ground_truth(mut self, ground_truth: bool) -> Self {
+        self.ground_truth = ground_truth;
+        self
+    }
+
+    /// True probabilities and the mine map are analysis, so they need
+    /// [`AssistLevel::FullAnalysis`] like the other analysis APIs.
+    pub fn validate(&self, config: &GridConfig) -> Result<(), IllegalAction> {
+        let analysis = self.probabilities == ProbabilityView::True || self.ground_truth;
+        if analysis && !config.assist.allows(AssistLevel::FullAnalysis) {
+            return Err(IllegalAction::AssistDisabled);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn analysis_views_need_full_analysis() {
+        let ranked = GridConfig::new(8, 8, 10, 42, "observer").with_assist(AssistLevel::None);
+        assert_eq!(ObservationSpec::default().validate(&ranked), Ok(()));
+        let truth = ObservationSpec::default().with_ground_truth(true);
+        assert_eq!(truth.validate(&ranked), Err(IllegalAction::AssistDisabled));
+        let open = GridConfig::new(8, 8, 10, 42, "observer");
+        assert_eq!(truth.validate(&open), Ok(()));
+
+        let spec: ObservationSpec = serde_json::from_str("{}").unwrap();
+        assert_eq!(spec, ObservationSpec::default());
+    }
+}
@@ -0,0 +1,135 @@
+use crate::action::{Action, ActionResult, IllegalAction};
+use crate::entanglement::{Correlation, LinkType};
+use crate::events::GameEvent;
+use crate::grid::{CellState, GridSnapshot, Mark, QuantumCell, QuantumGrid, RevealOutcome};
+use crate::prelude::*;
+
+/// What every kind of board can do, with the same action, outcome and
+/// event types as [`QuantumGrid`], so the solver, bots, replays and
+/// bindings can drive a board without knowing which kind it is. Tools go
+/// through [`Self::apply`] like every other action.
+pub trait GridOps {
+    fn width(&self) -> u32;
+    fn height(&self) -> u32;
+    fn seed(&self) -> u64;
+    fn mine_count(&self) -> u32;
+    /// Whether the mines are down yet; they are placed on the first reveal.
+    fn mines_placed(&self) -> bool;
+    fn containment_charges(&self) -> u32;
+    /// The cell at `(x, y)`; `None` off the board.
+    fn cell_at(&self, x: u32, y: u32) -> Option<QuantumCell>;
+    /// Every cell's state, row by row.
+    fn states(&self) -> Vec<CellState> {
+        let width = self.width();
+        (0..self.height())
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .filter_map(|(x, y)| self.cell_at(x, y))
+            .map(|cell| cell.state)
+            .collect()
+    }
+    /// Every BellState link as `(left, right, correlation)` indices; none
+    /// by default.
+    fn bell_links(&self) -> Vec<(usize, usize, Correlation)> {
+        Vec::new()
+    }
+    fn is_game_over(&self) -> bool;
+    fn is_won(&self) -> bool;
+    /// Mines not yet contained.
+    fn mines_remaining(&self) -> u32;
+    fn reveal_cell(&mut self, x: u32, y: u32) -> RevealOutcome;
+    fn contain_cell(&mut self, x: u32, y: u32) -> RevealOutcome;
+    /// Annotate an unresolved cell, returning its previous mark.
+    fn set_mark(&mut self, x: u32, y: u32, mark: Mark) -> Result<Mark, &'static str>;
+    /// Why [`Self::apply`] would refuse `action` right now, if it would.
+    fn validate_action(&self, action: &Action) -> Result<(), IllegalAction>;
+    /// Apply any [`Action`]. A board answers the ones it has no use for
+    /// with [`ActionResult::ToolRejected`] and stays unchanged.
+    fn apply(&mut self, action: Action) -> ActionResult;
+    /// The whole board as the frontend draws it.
+    fn snapshot(&self) -> GridSnapshot;
+    /// Events since the last call, oldest first.
+    fn drain_events(&mut self) -> Vec<GameEvent>;
+}
+
+impl GridOps for QuantumGrid {
+    fn width(&self) -> u32 {
+        self.width
+    }
+
+    fn height(&self) -> u32 {
+        self.height
+    }
+
+    fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    fn mine_count(&self) -> u32 {
+        self.mine_count
+    }
+
+    fn mines_placed(&self) -> bool {
+        self.mines_placed
+    }
+
+    fn containment_charges(&self) -> u32 {
+        self.containment_charges
+    }
+
+    fn cell_at(&self, x: u32, y: u32) -> Option<QuantumCell> {
+        self.cell(x, y).cloned()
+    }
+
+    fn states(&self) -> Vec<CellState> {
+        self.cells.iter().map(|cell| cell.state.clone()).collect()
+    }
+
+    fn bell_links(&self) -> Vec<(usize, usize, Correlation)> {
+        self.entanglement
+            .pairs
+            .iter()
+            .filter(|pair| pair.link_type == LinkType::BellState)
+            .map(|pair| (pair.left, pair.right, pair.correlation))
+            .collect()
+    }
+
+    fn is_game_over(&self) -> bool {
+        self.game_over
+    }
+
+    fn is_won(&self) -> bool {
+        self.won
+    }
+
+    fn mines_remaining(&self) -> u32 {
+        QuantumGrid::mines_remaining(self)
+    }
+
+    fn reveal_cell(&mut self, x: u32, y: u32) -> RevealOutcome {
+        QuantumGrid::reveal_cell(self, x, y)
+    }
+
+    fn contain_cell(&mut self, x: u32, y: u32) -> RevealOutcome {
+        QuantumGrid::contain_cell(self, x, y)
+    }
+
+    fn set_mark(&mut self, x: u32, y: u32, mark: Mark) -> Result<Mark, &'static str> {
+        QuantumGrid::set_mark(self, x, y, mark)
+    }
+
+    fn validate_action(&self, action: &Action) -> Result<(), IllegalAction> {
+        QuantumGrid::validate_action(self, action)
+    }
+
+    fn apply(&mut self, action: Action) -> ActionResult {
+        QuantumGrid::apply(self, action)
+    }
+
+    fn snapshot(&self) -> GridSnapshot {
+        QuantumGrid::snapshot(self)
+    }
+
+    fn drain_events(&mut self) -> Vec<GameEvent> {
+        QuantumGrid::drain_events(self)
+    }
+}
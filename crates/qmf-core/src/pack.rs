@@ -0,0 +1,567 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::commitment::{sha256, to_hex};
+use crate::config::{ConfigError, GridConfig};
+use crate::prelude::*;
+
+/// The file in every pack that lists its scenarios and assets.
+pub const PACK_INDEX: &str = "pack.json";
+/// Largest pack file [`Pack::read`] accepts, in bytes.
+pub const MAX_PACK_BYTES: usize = 16 << 20;
+
+const BLOCK: usize = 512;
+
+/// A scenario to play: a board with its objectives, and how it is
+/// introduced.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Scenario {
+    pub title: String,
+    /// Shown before the scenario starts.
+    #[serde(default)]
+    pub briefing: String,
+    pub config: GridConfig,
+}
+
+/// When a scenario in a pack can be played.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Unlock {
+    /// From the start.
+    #[default]
+    Open,
+    /// Once the earlier scenario `id` is complete.
+    After { id: String },
+    /// Once `count` of the pack's scenarios are complete.
+    Completed { count: u32 },
+}
+
+/// How bots fared on a scenario, for sorting community levels by how hard
+/// they actually play rather than by their settings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScenarioRating {
+    /// Bot games the rating is drawn from.
+    pub games: u32,
+    /// Share of those games that met the scenario's goal.
+    pub clear_rate: f64,
+    /// Moves per game, standing in for play time.
+    pub mean_moves: f64,
+    /// Unprovable moves per game.
+    pub mean_guesses: f64,
+}
+
+/// A scenario's place in a pack: its file, digest and unlock condition.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScenarioEntry {
+    pub id: String,
+    pub file: String,
+    /// Hex SHA-256 of the file.
+    pub sha256: String,
+    #[serde(default)]
+    pub unlock: Unlock,
+    /// Filled in by `metrics::rate_pack`. Authors can write anything here,
+    /// so servers re-rate packs they list.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rating: Option<ScenarioRating>,
+}
+
+/// An image, sound or other file a pack ships for its frontends.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AssetEntry {
+    pub file: String,
+    /// Hex SHA-256 of the file.
+    pub sha256: String,
+}
+
+/// The [`PACK_INDEX`] of a pack: what it is, and its scenarios in play
+/// order.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PackIndex {
+    pub id: String,
+    pub title: String,
+    #[serde(default)]
+    pub author: String,
+    #[serde(default)]
+    pub description: String,
+    pub scenarios: Vec<ScenarioEntry>,
+    #[serde(default)]
+    pub assets: Vec<AssetEntry>,
+}
+
+/// A campaign of scenarios in one file, for sharing between players. On
+/// disk it is an uncompressed tar archive holding [`PACK_INDEX`], one JSON
+/// [`Scenario`] per entry, and the assets; every file is checked against
+/// the digest the index gives it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Pack {
+    pub index: PackIndex,
+    /// In the order of [`PackIndex::scenarios`].
+    pub scenarios: Vec<Scenario>,
+    /// Asset contents by file name.
+    pub assets: BTreeMap<String, Vec<u8>>,
+}
+
+impl Pack {
+    /// Unpack and check a pack file.
+    pub fn read(bytes: &[u8]) -> Result<Self, PackError> {
+        if bytes.len() > MAX_PACK_BYTES {
+            return Err(PackError::TooLarge {
+                bytes: bytes.len() as u64,
+            });
+        }
+        let files = untar(bytes)?;
+        let index_bytes = files.get(PACK_INDEX).ok_or(PackError::MissingFile {
+            file: PACK_INDEX.to_string(),
+        })?;
+        let index: PackIndex = parse(PACK_INDEX, index_bytes)?;
+        let checked = |file: &str, digest: &str| {
+            let bytes = files.get(file).ok_or_else(|| PackError::MissingFile {
+                file: file.to_string(),
+            })?;
+            if to_hex(&sha256(bytes)) != digest.to_ascii_lowercase() {
+                return Err(PackError::HashMismatch {
+                    file: file.to_string(),
+                });
+            }
+            Ok(*bytes)
+        };
+        let scenarios = index
+            .scenarios
+            .iter()
+            .map(|entry| parse(&entry.file, checked(&entry.file, &entry.sha256)?))
+            .collect::<Result<Vec<Scenario>, _>>()?;
+        let assets = index
+            .assets
+            .iter()
+            .map(|asset| {
+                Ok((
+                    asset.file.clone(),
+                    checked(&asset.file, &asset.sha256)?.to_vec(),
+                ))
+            })
+            .collect::<Result<_, PackError>>()?;
+        let pack = Self {
+            index,
+            scenarios,
+            assets,
+        };
+        pack.check()?;
+        Ok(pack)
+    }
+
+    /// Bundle the pack into a file for [`Self::read`]. The digests in
+    /// [`Self::index`] are recomputed, so authors can leave them empty.
+    pub fn write(&self) -> Result<Vec<u8>, PackError> {
+        self.check_paths()?;
+        let mut index = self.index.clone();
+        let mut files = Vec::new();
+        for (entry, scenario) in index.scenarios.iter_mut().zip(&self.scenarios) {
+            let bytes = to_json(&entry.file, scenario)?;
+            entry.sha256 = to_hex(&sha256(&bytes));
+            files.push((entry.file.clone(), bytes));
+        }
+        for asset in &mut index.assets {
+            let bytes = self
+                .assets
+                .get(&asset.file)
+                .ok_or_else(|| PackError::MissingFile {
+                    file: asset.file.clone(),
+                })?;
+            asset.sha256 = to_hex(&sha256(bytes));
+            files.push((asset.file.clone(), bytes.clone()));
+        }
+        files.insert(0, (PACK_INDEX.to_string(), to_json(PACK_INDEX, &index)?));
+        tar(&files)
+    }
+
+    /// The scenario with `id`.
+    pub fn scenario(&self, id: &str) -> Option<&Scenario> {
+        let position = self
+            .index
+            .scenarios
+            .iter()
+            .position(|entry| entry.id == id)?;
+        self.scenarios.get(position)
+    }
+
+    /// Whether the scenario at `position` can be played, given the ids of
+    /// the pack's scenarios the player has completed.
+    pub fn is_unlocked(&self, position: usize, completed: &BTreeSet<String>) -> bool {
+        let Some(entry) = self.index.scenarios.get(position) else {
+            return false;
+        };
+        match &entry.unlock {
+            Unlock::Open => true,
+            Unlock::After { id } => completed.contains(id),
+            Unlock::Completed { count } => {
+                let done = self
+                    .index
+                    .scenarios
+                    .iter()
+                    .filter(|entry| completed.contains(&entry.id))
+                    .count();
+                done >= *count as usize
+            }
+        }
+    }
+
+    /// Why the pack cannot be played as it stands, if anything.
+    pub fn check(&self) -> Result<(), PackError> {
+        self.check_paths()?;
+        if self.scenarios.len() != self.index.scenarios.len() {
+            return Err(PackError::MissingFile {
+                file: self
+                    .index
+                    .scenarios
+                    .get(self.scenarios.len())
+                    .map_or_else(String::new, |entry| entry.file.clone()),
+            });
+        }
+        let mut earlier = BTreeSet::new();
+        for (entry, scenario) in self.index.scenarios.iter().zip(&self.scenarios) {
+            let unlockable = match &entry.unlock {
+                Unlock::Open => true,
+                Unlock::After { id } => earlier.contains(id),
+                Unlock::Completed { count } => (*count as usize) <= earlier.len(),
+            };
+            if !unlockable {
+                return Err(PackError::BadUnlock {
+                    id: entry.id.clone(),
+                });
+            }
+            scenario
+                .config
+                .validate()
+                .map_err(|error| PackError::InvalidBoard {
+                    id: entry.id.clone(),
+                    error,
+                })?;
+            if !earlier.insert(entry.id.clone()) {
+                return Err(PackError::DuplicateId {
+                    id: entry.id.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn check_paths(&self) -> Result<(), PackError> {
+        let files = self
+            .index
+            .scenarios
+            .iter()
+            .map(|entry| &entry.file)
+            .chain(self.index.assets.iter().map(|asset| &asset.file));
+        let mut seen = BTreeSet::from([PACK_INDEX]);
+        for file in files {
+            if !is_safe_path(file) || !seen.insert(file.as_str()) {
+                return Err(PackError::UnsafePath { file: file.clone() });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Why a pack could not be read or written.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PackError {
+    /// Over [`MAX_PACK_BYTES`].
+    TooLarge { bytes: u64 },
+    /// Not a tar archive, or a damaged one.
+    Archive { message: String },
+    /// A file the index lists is not in the archive.
+    MissingFile { file: String },
+    /// A file does not match the digest the index gives it.
+    HashMismatch { file: String },
+    /// A file is not the JSON it should be.
+    Malformed { file: String, message: String },
+    /// A file name that is absolute, climbs out with `..`, is too long for
+    /// the archive, or is used twice.
+    UnsafePath { file: String },
+    /// Two scenarios share an id.
+    DuplicateId { id: String },
+    /// The scenario's unlock names a scenario that does not come before
+    /// it, or needs more completions than come before it, so it could
+    /// never open.
+    BadUnlock { id: String },
+    /// The scenario's board would be refused by [`GridConfig::validate`].
+    InvalidBoard { id: String, error: ConfigError },
+}
+
+impl fmt::Display for PackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooLarge { bytes } => write!(f, "pack is {bytes} bytes, over the limit"),
+            Self::Archive { message } => write!(f, "pack archive is damaged: {message}"),
+            Self::MissingFile { file } => write!(f, "pack is missing {file:?}"),
+            Self::HashMismatch { file } => write!(f, "{file:?} does not match its digest"),
+            Self::Malformed { file, message } => write!(f, "{file:?} is malformed: {message}"),
+            Self::UnsafePath { file } => write!(f, "unusable file name {file:?}"),
+            Self::DuplicateId { id } => write!(f, "two scenarios have the id {id:?}"),
+            Self::BadUnlock { id } => write!(f, "scenario {id:?} can never unlock"),
+            Self::InvalidBoard { id, error } => write!(f, "scenario {id:?}: {error}"),
+        }
+    }
+}
+
+impl core::error::Error for PackError {}
+
+fn parse<T: serde::de::DeserializeOwned>(file: &str, bytes: &[u8]) -> Result<T, PackError> {
+    serde_json::from_slice(bytes).map_err(|error| PackError::Malformed {
+        file: file.to_string(),
+        message: error.to_string(),
+    })
+}
+
+fn to_json<T: Serialize>(file: &str, value: &T) -> Result<Vec<u8>, PackError> {
+    serde_json::to_vec_pretty(value).map_err(|error| PackError::Malformed {
+        file: file.to_string(),
+        message: error.to_string(),
+    })
+}
+
+/// Relative, `/`-separated, no `..`, and short enough for a plain tar
+/// header.
+fn is_safe_path(file: &str) -> bool {
+    !file.is_empty()
+        && file.len() < 100
+        && !file.starts_with('/')
+        && !file.contains('\\')
+        && file.split('/').all(|part| !part.is_empty() && part != "..")
+}
+
+fn archive_error(message: &str) -> PackError {
+    PackError::Archive {
+        message: message.to_string(),
+    }
+}
+
+/// An octal header field, as tar writes them: digits, then NULs or spaces.
+fn octal(field: &[u8]) -> Result<u64, PackError> {
+    let mut digits = field
+        .iter()
+        .take_while(|&&b| b != 0 && b != b' ')
+        .skip_while(|&&b| b == b' ');
+    digits.try_fold(0u64, |value, &digit| match digit {
+        b'0'..=b'7' => value
+            .checked_mul(8)
+            .map(|value| value + u64::from(digit - b'0'))
+            .ok_or_else(|| archive_error("header number overflows")),
+        _ => Err(archive_error("header number is not octal")),
+    })
+}
+
+fn text(field: &[u8]) -> Result<&str, PackError> {
+    let name = field.split(|&b| b == 0).next().unwrap_or_default();
+    core::str::from_utf8(name).map_err(|_| archive_error("file name is not UTF-8"))
+}
+
+fn checksum(header: &[u8]) -> u64 {
+    header
+        .iter()
+        .enumerate()
+        .map(|(i, &b)| {
+            if (148..156).contains(&i) {
+                32
+            } else {
+                u64::from(b)
+            }
+        })
+        .sum()
+}
+
+/// The regular files in a ustar archive, by path. Directories, links and
+/// pax headers are skipped.
+fn untar(bytes: &[u8]) -> Result<BTreeMap<String, &[u8]>, PackError> {
+    let mut files = BTreeMap::new();
+    let mut offset = 0;
+    while let Some(header) = bytes
+        .get(offset..offset + BLOCK)
+        .and_then(|block| <&[u8; BLOCK]>::try_from(block).ok())
+    {
+        if header.iter().all(|&b| b == 0) {
+            return Ok(files);
+        }
+        if octal(&header[148..156])? != checksum(header) {
+            return Err(archive_error("header checksum mismatch"));
+        }
+        let size = usize::try_from(octal(&header[124..136])?)
+            .map_err(|_| archive_error("file is too large"))?;
+        let start = offset + BLOCK;
+        let data = start
+            .checked_add(size)
+            .and_then(|end| bytes.get(start..end))
+            .ok_or_else(|| archive_error("archive is truncated"))?;
+        if matches!(header[156], b'0' | 0) {
+            let name = text(&header[..100])?;
+            let path = match text(&header[345..500])? {
+                prefix if header[257..262] == *b"ustar" && !prefix.is_empty() => {
+                    format!("{prefix}/{name}")
+                }
+                _ => name.to_string(),
+            };
+            if files.insert(path.clone(), data).is_some() {
+                return Err(PackError::UnsafePath { file: path });
+            }
+        }
+        offset = start + size.div_ceil(BLOCK) * BLOCK;
+    }
+    Err(archive_error("archive has no end marker"))
+}
+
+/// A ustar archive of `files`, in order.
+fn tar(files: &[(String, Vec<u8>)]) -> Result<Vec<u8>, PackError> {
+    let mut out = Vec::new();
+    for (path, data) in files {
+        if !is_safe_path(path) {
+            return Err(PackError::UnsafePath { file: path.clone() });
+        }
+        let mut header = [0u8; BLOCK];
+        header
+            .get_mut(..path.len())
+            .ok_or_else(|| PackError::UnsafePath { file: path.clone() })?
+            .copy_from_slice(path.as_bytes());
+        header[100..108].copy_from_slice(b"0000644\0");
+        header[108..116].copy_from_slice(b"0000000\0");
+        header[116..124].copy_from_slice(b"0000000\0");
+        header[124..136].copy_from_slice(format!("{:011o}\0", data.len()).as_bytes());
+        header[136..148].copy_from_slice(b"00000000000\0");
+        header[156] = b'0';
+        header[257..263].copy_from_slice(b"ustar\0");
+        header[263..265].copy_from_slice(b"00");
+        let sum = checksum(&header);
+        header[148..156].copy_from_slice(format!("{sum:06o}\0 ").as_bytes());
+        out.extend_from_slice(&header);
+        out.extend_from_slice(data);
+        out.resize(out.len().div_ceil(BLOCK) * BLOCK, 0);
+    }
+    out.resize(out.len() + 2 * BLOCK, 0);
+    if out.len() > MAX_PACK_BYTES {
+        return Err(PackError::TooLarge {
+            bytes: out.len() as u64,
+        });
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::objective::Objective;
+
+    fn entry(id: &str, unlock: Unlock) -> ScenarioEntry {
+        ScenarioEntry {
+            id: id.to_string(),
+            file: format!("scenarios/{id}.json"),
+            sha256: String::new(),
+            unlock,
+            rating: None,
+        }
+    }
+
+    fn campaign() -> Pack {
+        let scenario = |title: &str, seed| Scenario {
+            title: title.to_string(),
+            briefing: "Contain everything.".to_string(),
+            config: GridConfig::new(9, 9, 10, seed, "observer")
+                .with_objective(Objective::ResolveAll),
+        };
+        Pack {
+            index: PackIndex {
+                id: "intro".to_string(),
+                title: "Introductions".to_string(),
+                author: "qmf".to_string(),
+                description: String::new(),
+                scenarios: vec![
+                    entry("first", Unlock::Open),
+                    entry(
+                        "second",
+                        Unlock::After {
+                            id: "first".to_string(),
+                        },
+                    ),
+                    entry("finale", Unlock::Completed { count: 2 }),
+                ],
+                assets: vec![AssetEntry {
+                    file: "cover.png".to_string(),
+                    sha256: String::new(),
+                }],
+            },
+            scenarios: vec![
+                scenario("First", 1),
+                scenario("Second", 2),
+                scenario("Finale", 3),
+            ],
+            assets: BTreeMap::from([("cover.png".to_string(), vec![0x89, b'P', b'N', b'G'])]),
+        }
+    }
+
+    #[test]
+    fn packs_round_trip_and_unlock_in_order() {
+        let bytes = campaign().write().unwrap();
+        assert_eq!(bytes.len() % BLOCK, 0);
+        let pack = Pack::read(&bytes).unwrap();
+        assert_eq!(pack.scenarios, campaign().scenarios);
+        assert_eq!(pack.assets, campaign().assets);
+        assert_eq!(pack.index.scenarios[0].sha256.len(), 64);
+        assert_eq!(pack.scenario("second").unwrap().title, "Second");
+
+        let mut completed = BTreeSet::new();
+        let open = |completed: &BTreeSet<String>| -> Vec<bool> {
+            (0..4).map(|i| pack.is_unlocked(i, completed)).collect()
+        };
+        assert_eq!(open(&completed), [true, false, false, false]);
+        completed.insert("first".to_string());
+        assert_eq!(open(&completed), [true, true, false, false]);
+        completed.insert("second".to_string());
+        assert_eq!(open(&completed), [true, true, true, false]);
+    }
+
+    #[test]
+    fn damaged_or_unsound_packs_are_refused() {
+        let bytes = campaign().write().unwrap();
+        // Flip a byte inside the first scenario's file.
+        let needle = b"\"Second\"";
+        let at = bytes
+            .windows(needle.len())
+            .position(|window| window == needle)
+            .unwrap();
+        let mut tampered = bytes.clone();
+        tampered[at + 1] = b'Z';
+        assert_eq!(
+            Pack::read(&tampered).err(),
+            Some(PackError::HashMismatch {
+                file: "scenarios/second.json".to_string()
+            })
+        );
+        assert!(matches!(
+            Pack::read(&bytes[..bytes.len() - 2 * BLOCK - 1]),
+            Err(PackError::Archive { .. })
+        ));
+
+        let mut escape = campaign();
+        escape.index.assets[0].file = "../cover.png".to_string();
+        assert!(matches!(escape.write(), Err(PackError::UnsafePath { .. })));
+
+        let mut forward = campaign();
+        forward.index.scenarios[0].unlock = Unlock::After {
+            id: "second".to_string(),
+        };
+        assert_eq!(
+            Pack::read(&forward.write().unwrap()).err(),
+            Some(PackError::BadUnlock {
+                id: "first".to_string()
+            })
+        );
+
+        let mut crowded = campaign();
+        crowded.scenarios[2].config = GridConfig::new(4, 4, 16, 1, "observer");
+        assert!(matches!(
+            Pack::read(&crowded.write().unwrap()),
+            Err(PackError::InvalidBoard { .. })
+        ));
+    }
+}
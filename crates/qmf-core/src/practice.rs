@@ -0,0 +1,120 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::action::Action;
+use crate::prelude::*;
+use crate::replay::ReplayStep;
+
+/// A practice run restarted from a point in a replay (see
+/// [`QuantumGrid::from_replay_at`](crate::grid::QuantumGrid::from_replay_at)),
+/// and whether it is still following the original game.
+///
+/// Only [`Action::Advance`] is left out of the comparison on both sides,
+/// since a frontend's frame loop never passes time in the same steps twice.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Practice {
+    /// Replay steps played before practice began.
+    pub from_move: u32,
+    /// The original game's moves after that point.
+    pub script: Vec<Action>,
+    /// Moves made since practice began.
+    pub moves: u32,
+    /// Index (into the practice moves) of the first move the original game
+    /// did not make; `None` while the run follows it.
+    pub diverged_at: Option<u32>,
+}
+
+impl Practice {
+    /// Practice from after the first `from_move` of `steps`.
+    pub fn new(steps: &[ReplayStep], from_move: usize) -> Self {
+        let from_move = from_move.min(steps.len());
+        Self {
+            from_move: from_move as u32,
+            script: steps
+                .iter()
+                .skip(from_move)
+                .map(|step| step.action)
+                .filter(|action| !matches!(action, Action::Advance { .. }))
+                .collect(),
+            moves: 0,
+            diverged_at: None,
+        }
+    }
+
+    pub fn is_following(&self) -> bool {
+        self.diverged_at.is_none()
+    }
+
+    /// The move the original game made next, while the run still follows
+    /// it: what a "show me what I did" overlay points at.
+    pub fn expected(&self) -> Option<Action> {
+        self.diverged_at
+            .is_none()
+            .then(|| self.script.get(self.moves as usize).copied())
+            .flatten()
+    }
+
+    /// Count an accepted move, noting where it first departs from the
+    /// script.
+    pub(crate) fn follow(&mut self, action: Action) {
+        if matches!(action, Action::Advance { .. }) {
+            return;
+        }
+        if self.is_following() && self.expected() != Some(action) {
+            self.diverged_at = Some(self.moves);
+        }
+        self.moves = self.moves.saturating_add(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GridConfig;
+    use crate::grid::QuantumGrid;
+    use crate::replay::Replay;
+
+    #[test]
+    fn practice_resumes_the_original_and_notes_divergence() {
+        let config = GridConfig::new(10, 10, 12, 5, "researcher");
+        let mut live = QuantumGrid::from_config(config.clone());
+        let mut replay = Replay::new(config);
+        for (i, (x, y)) in (0..40).map(|i| (u64::from(i), (i % 10, i / 10))) {
+            let action = match i {
+                0 => Action::Reveal { x: 5, y: 5 },
+                _ if i % 2 == 0 => Action::MeasureWeak { x, y },
+                _ => Action::Hadamard { x, y },
+            };
+            if live.validate_action(&action).is_ok() {
+                live.apply_at(action, i * 100).unwrap();
+                replay.record(i * 100, action);
+            }
+        }
+
+        let mut practice = QuantumGrid::from_replay_at(&replay, 10).unwrap();
+        assert_eq!(practice.rng.state(), replay.seek(10).unwrap().rng.state());
+        for step in &replay.steps[10..] {
+            let expected = practice.practice.as_ref().unwrap().expected();
+            assert_eq!(expected, Some(step.action));
+            practice.apply_at(step.action, step.t_ms).unwrap();
+        }
+        assert_eq!(practice.cells, live.cells);
+        assert!(practice.practice.as_ref().unwrap().is_following());
+
+        let mut seeker_run = replay.seeker(4).unwrap().practice_at(10).unwrap();
+        let detour = Action::Hadamard { x: 9, y: 9 };
+        assert_ne!(
+            Some(detour),
+            seeker_run.practice.as_ref().unwrap().expected()
+        );
+        seeker_run.apply(replay.steps[10].action);
+        seeker_run.apply(detour);
+        let run = seeker_run.practice.unwrap();
+        assert_eq!(
+            (run.from_move, run.moves, run.diverged_at),
+            (10, 2, Some(1))
+        );
+        assert_eq!(run.expected(), None);
+    }
+}
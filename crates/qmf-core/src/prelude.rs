@@ -0,0 +1,10 @@
+//! The parts of the `std` prelude that live in `alloc`, so modules that
+//! build without the standard library can name them the same way.
+
+#[cfg(not(feature = "std"))]
+pub(crate) use crate::float::Float;
+
+pub(crate) use alloc::boxed::Box;
+pub(crate) use alloc::string::{String, ToString};
+pub(crate) use alloc::vec::Vec;
+pub(crate) use alloc::{format, vec};
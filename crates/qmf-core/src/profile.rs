@@ -0,0 +1,233 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::input::Bindings;
+use crate::prelude::*;
+use crate::rating::Rating;
+use crate::session::{GameStats, SessionManager};
+
+/// Fastest win on one kind of board.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PersonalBest {
+    pub elapsed_ms: u64,
+    /// When it was set (ms since the Unix epoch). Of two equal times, the
+    /// earlier one stands.
+    pub achieved_ms: u64,
+    /// The run's split times, to compare later runs against (see
+    /// [`splits::compare`](crate::splits::compare)).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub splits: Vec<Option<u64>>,
+}
+
+/// Per-player settings and progress that follow the player between games
+/// and frontends.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PlayerProfile {
+    /// Controller layout (see [`crate::input`]).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bindings: Bindings,
+    /// Adaptive difficulty state (see [`crate::session`]).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub session: SessionManager,
+    /// Glicko-2 rating for versus and race modes.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rating: Rating,
+    /// When `bindings`, `session` or `rating` last changed (ms since the
+    /// Unix epoch). Decides which device's copy wins in [`Self::merge`].
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub updated_ms: u64,
+    /// Unlocked achievement IDs.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub achievements: BTreeSet<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub games_played: u64,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub games_won: u64,
+    /// Fastest wins by board (see [`board_key`]).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub bests: BTreeMap<String, PersonalBest>,
+}
+
+/// Key of a board in [`PlayerProfile::bests`], e.g. `"16x16-40-observer"`.
+pub fn board_key(stats: &GameStats) -> String {
+    format!(
+        "{}x{}-{}-{}",
+        stats.width, stats.height, stats.mine_count, stats.difficulty
+    )
+}
+
+impl PlayerProfile {
+    /// Count a finished game, finished at `now_ms`, keeping a faster win
+    /// as the board's best.
+    pub fn record(&mut self, stats: &GameStats, now_ms: u64) {
+        self.games_played += 1;
+        if stats.won {
+            self.games_won += 1;
+            let best = PersonalBest {
+                elapsed_ms: stats.elapsed_ms,
+                achieved_ms: now_ms,
+                splits: stats.splits.clone(),
+            };
+            keep_better(&mut self.bests, board_key(stats), best);
+        }
+    }
+
+    /// Combine two devices' copies of a profile without losing progress on
+    /// either: counters take the larger value, achievements are united,
+    /// each board keeps the better best, and the settings come from the
+    /// copy updated last. Every rule is commutative, associative and
+    /// idempotent, so copies may sync in any order, any number of times,
+    /// without needing their common ancestor.
+    pub fn merge(&self, other: &Self) -> Self {
+        let settings = if self.settings_key() >= other.settings_key() {
+            self
+        } else {
+            other
+        };
+        let mut bests = self.bests.clone();
+        for (board, best) in &other.bests {
+            keep_better(&mut bests, board.clone(), best.clone());
+        }
+        Self {
+            bindings: settings.bindings.clone(),
+            session: settings.session.clone(),
+            rating: settings.rating,
+            updated_ms: settings.updated_ms,
+            achievements: self
+                .achievements
+                .union(&other.achievements)
+                .cloned()
+                .collect(),
+            games_played: self.games_played.max(other.games_played),
+            games_won: self.games_won.max(other.games_won),
+            bests,
+        }
+    }
+
+    /// Orders copies for last-writer-wins; equal timestamps fall back to
+    /// the serialized settings so both sides pick the same copy.
+    fn settings_key(&self) -> (u64, String) {
+        let settings = (&self.bindings, &self.session, &self.rating);
+        (
+            self.updated_ms,
+            serde_json::to_string(&settings).unwrap_or_default(),
+        )
+    }
+}
+
+fn keep_better(bests: &mut BTreeMap<String, PersonalBest>, board: String, best: PersonalBest) {
+    match bests.get_mut(&board) {
+        Some(current) if best < *current => *current = best,
+        Some(_) => {}
+        None => {
+            bests.insert(board, best);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn profile() -> impl Strategy<Value = PlayerProfile> {
+        (
+            0_u64..4,
+            0_u32..4,
+            500.0_f64..2500.0,
+            prop::collection::btree_set("[a-d]", 0..4),
+            (0_u64..50, 0_u64..50),
+            prop::collection::btree_map("[a-c]", (0_u64..9, 0_u64..9), 0..3),
+        )
+            .prop_map(|(updated_ms, level, rating, achievements, games, bests)| {
+                PlayerProfile {
+                    session: SessionManager {
+                        level,
+                        ..SessionManager::default()
+                    },
+                    rating: Rating {
+                        rating,
+                        ..Rating::default()
+                    },
+                    updated_ms,
+                    achievements,
+                    games_played: games.0.max(games.1),
+                    games_won: games.0.min(games.1),
+                    bests: bests
+                        .into_iter()
+                        .map(|(board, (elapsed_ms, achieved_ms))| {
+                            let best = PersonalBest {
+                                elapsed_ms,
+                                achieved_ms,
+                                splits: Vec::new(),
+                            };
+                            (board, best)
+                        })
+                        .collect(),
+                    ..PlayerProfile::default()
+                }
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn merge_is_order_independent(a in profile(), b in profile(), c in profile()) {
+            prop_assert_eq!(a.merge(&b), b.merge(&a));
+            prop_assert_eq!(a.merge(&b).merge(&c), a.merge(&b.merge(&c)));
+            prop_assert_eq!(a.merge(&a), a.clone());
+        }
+
+        #[test]
+        fn merge_loses_no_progress(a in profile(), b in profile()) {
+            let merged = a.merge(&b);
+            prop_assert!(merged.achievements.is_superset(&a.achievements));
+            prop_assert!(merged.games_played >= a.games_played.max(b.games_played));
+            for (board, best) in a.bests.iter().chain(&b.bests) {
+                prop_assert!(merged.bests[board] <= *best);
+            }
+        }
+    }
+
+    #[test]
+    fn records_faster_wins_as_bests() {
+        let mut profile = PlayerProfile::default();
+        let stats = GameStats {
+            won: true,
+            width: 9,
+            height: 9,
+            difficulty: "observer".into(),
+            mine_count: 10,
+            progress: 1.0,
+            elapsed_ms: 60_000,
+            splits: vec![Some(4_000), None],
+        };
+        profile.record(&stats, 1);
+        profile.record(
+            &GameStats {
+                elapsed_ms: 90_000,
+                ..stats.clone()
+            },
+            2,
+        );
+        profile.record(
+            &GameStats {
+                won: false,
+                ..stats
+            },
+            3,
+        );
+        assert_eq!((profile.games_played, profile.games_won), (3, 2));
+        assert_eq!(
+            profile.bests["9x9-10-observer"],
+            PersonalBest {
+                elapsed_ms: 60_000,
+                achieved_ms: 1,
+                splits: vec![Some(4_000), None],
+            }
+        );
+    }
+}
@@ -0,0 +1,36 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Which long-running operation a progress report belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Phase {
+    /// Scanning seeds for a board that fits (see `generator::SeedSearch`).
+    SeedSearch,
+    /// Playing a replay back, e.g. to build checkpoints.
+    Replaying,
+    /// Solving mine probabilities, one frontier component at a time (see
+    /// `solver::probabilities_with`).
+    Solving,
+    /// Playing bot games (see `selfplay::batch_with`).
+    SelfPlay,
+}
+
+/// Receives progress from long operations, for spinners and progress bars.
+///
+/// `fraction` is in `[0, 1]` and never decreases within one phase. Closures
+/// `FnMut(f64, Phase)` are sinks, and `()` discards everything.
+pub trait ProgressSink {
+    fn on_progress(&mut self, fraction: f64, phase: Phase);
+}
+
+impl ProgressSink for () {
+    fn on_progress(&mut self, _fraction: f64, _phase: Phase) {}
+}
+
+impl<F: FnMut(f64, Phase)> ProgressSink for F {
+    fn on_progress(&mut self, fraction: f64, phase: Phase) {
+        self(fraction, phase)
+    }
+}
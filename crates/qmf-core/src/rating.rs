@@ -0,0 +1,247 @@
+use core::cmp::Ordering;
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+use crate::replay::{Replay, ReplayError};
+use crate::tournament::MatchScore;
+
+/// Glicko-2 system constant: how much volatility may change per period.
+pub const TAU: f64 = 0.5;
+/// Converts between the Glicko and Glicko-2 scales.
+const SCALE: f64 = 173.7178;
+/// Convergence tolerance of the volatility iteration.
+const EPSILON: f64 = 1e-6;
+
+/// A player's Glicko-2 rating for versus and race modes. Pure math on
+/// match results, so servers and offline tournaments rate identically.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Rating {
+    pub rating: f64,
+    /// Rating deviation: how uncertain `rating` is.
+    pub deviation: f64,
+    /// How erratic the player's results are.
+    pub volatility: f64,
+}
+
+impl Default for Rating {
+    /// An unrated newcomer.
+    fn default() -> Self {
+        Self {
+            rating: 1500.0,
+            deviation: 350.0,
+            volatility: 0.06,
+        }
+    }
+}
+
+/// Why two replays could not be rated against each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum RatingError {
+    /// The replays were not played on the same board and seed.
+    WrongBoard,
+    InvalidReplay {
+        error: ReplayError,
+    },
+}
+
+impl fmt::Display for RatingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongBoard => write!(f, "the replays were played on different boards"),
+            Self::InvalidReplay { error } => write!(f, "replay is invalid: {error}"),
+        }
+    }
+}
+
+impl core::error::Error for RatingError {}
+
+/// One game against `opponent`: `score` is `1.0` for a win, `0.5` for a
+/// draw and `0.0` for a loss.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RatedResult {
+    pub opponent: Rating,
+    pub score: f64,
+}
+
+impl RatedResult {
+    /// The result of `mine` against `theirs`, ranked by
+    /// [`MatchScore::rank`].
+    pub fn from_scores(opponent: Rating, mine: &MatchScore, theirs: &MatchScore) -> Self {
+        let score = match mine.rank(theirs) {
+            Ordering::Greater => 1.0,
+            Ordering::Equal => 0.5,
+            Ordering::Less => 0.0,
+        };
+        Self { opponent, score }
+    }
+
+    /// The result of two replays of the same board, each played back and
+    /// scored, so ratings only move on verified games. Replays of
+    /// different boards (or seeds) are refused.
+    pub fn from_replays(
+        opponent: Rating,
+        mine: &Replay,
+        theirs: &Replay,
+    ) -> Result<Self, RatingError> {
+        if mine.config != theirs.config {
+            return Err(RatingError::WrongBoard);
+        }
+        let score = |replay: &Replay| {
+            replay
+                .play()
+                .map(|grid| MatchScore::from_grid(&grid))
+                .map_err(|error| RatingError::InvalidReplay { error })
+        };
+        Ok(Self::from_scores(opponent, &score(mine)?, &score(theirs)?))
+    }
+}
+
+impl Rating {
+    /// The rating after one rating period with `results`, using system
+    /// constant `tau` (see [`TAU`]). A period without games only widens
+    /// the deviation.
+    pub fn update(&self, results: &[RatedResult], tau: f64) -> Self {
+        let mu = (self.rating - 1500.0) / SCALE;
+        let phi = self.deviation / SCALE;
+        let sigma = self.volatility;
+        if results.is_empty() {
+            return Self {
+                deviation: (phi * phi + sigma * sigma).sqrt() * SCALE,
+                ..*self
+            };
+        }
+
+        let terms: Vec<(f64, f64, f64)> = results
+            .iter()
+            .map(|result| {
+                let mu_j = (result.opponent.rating - 1500.0) / SCALE;
+                let g = g(result.opponent.deviation / SCALE);
+                let e = 1.0 / (1.0 + (-g * (mu - mu_j)).exp());
+                (g, e, result.score)
+            })
+            .collect();
+        let v = 1.0
+            / terms
+                .iter()
+                .map(|&(g, e, _)| g * g * e * (1.0 - e))
+                .sum::<f64>();
+        let delta = v * terms.iter().map(|&(g, e, s)| g * (s - e)).sum::<f64>();
+
+        let sigma = new_volatility(phi, sigma, v, delta, tau);
+        let phi_star = (phi * phi + sigma * sigma).sqrt();
+        let phi = 1.0 / (1.0 / (phi_star * phi_star) + 1.0 / v).sqrt();
+        let mu = mu + phi * phi * terms.iter().map(|&(g, e, s)| g * (s - e)).sum::<f64>();
+        Self {
+            rating: mu * SCALE + 1500.0,
+            deviation: phi * SCALE,
+            volatility: sigma,
+        }
+    }
+}
+
+fn g(phi: f64) -> f64 {
+    1.0 / (1.0 + 3.0 * phi * phi / (core::f64::consts::PI * core::f64::consts::PI)).sqrt()
+}
+
+/// Step 5 of Glicko-2: solve for the new volatility with the Illinois
+/// algorithm.
+fn new_volatility(phi: f64, sigma: f64, v: f64, delta: f64, tau: f64) -> f64 {
+    let a = (sigma * sigma).ln();
+    let f = |x: f64| {
+        let ex = x.exp();
+        let d = phi * phi + v + ex;
+        ex * (delta * delta - d) / (2.0 * d * d) - (x - a) / (tau * tau)
+    };
+    let mut big_a = a;
+    let mut big_b = if delta * delta > phi * phi + v {
+        (delta * delta - phi * phi - v).ln()
+    } else {
+        let mut k = 1.0;
+        while f(a - k * tau) < 0.0 {
+            k += 1.0;
+        }
+        a - k * tau
+    };
+    let (mut f_a, mut f_b) = (f(big_a), f(big_b));
+    while (big_b - big_a).abs() > EPSILON {
+        let c = big_a + (big_a - big_b) * f_a / (f_b - f_a);
+        let f_c = f(c);
+        if f_c * f_b <= 0.0 {
+            big_a = big_b;
+            f_a = f_b;
+        } else {
+            f_a /= 2.0;
+        }
+        big_b = c;
+        f_b = f_c;
+    }
+    (big_a / 2.0).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action::Action;
+    use crate::config::GridConfig;
+
+    #[test]
+    fn matches_the_glicko2_reference_example() {
+        // Worked example from Glickman's "Example of the Glicko-2 system".
+        let player = Rating {
+            rating: 1500.0,
+            deviation: 200.0,
+            volatility: 0.06,
+        };
+        let opponent = |rating, deviation| Rating {
+            rating,
+            deviation,
+            volatility: 0.06,
+        };
+        let results = [
+            RatedResult {
+                opponent: opponent(1400.0, 30.0),
+                score: 1.0,
+            },
+            RatedResult {
+                opponent: opponent(1550.0, 100.0),
+                score: 0.0,
+            },
+            RatedResult {
+                opponent: opponent(1700.0, 300.0),
+                score: 0.0,
+            },
+        ];
+        let updated = player.update(&results, TAU);
+        assert!((updated.rating - 1464.06).abs() < 0.01, "{updated:?}");
+        assert!((updated.deviation - 151.52).abs() < 0.01, "{updated:?}");
+        assert!((updated.volatility - 0.05999).abs() < 1e-5, "{updated:?}");
+
+        let idle = player.update(&[], TAU);
+        assert_eq!(idle.rating, player.rating);
+        assert!(idle.deviation > player.deviation);
+    }
+
+    #[test]
+    fn replays_are_rated_by_their_verified_scores() {
+        let config = GridConfig::new(8, 8, 10, 42, "observer");
+        let mut winner = Replay::new(config.clone());
+        winner.record(0, Action::Reveal { x: 0, y: 0 });
+        let idle = Replay::new(config.clone());
+        let result = RatedResult::from_replays(Rating::default(), &winner, &idle).unwrap();
+        assert_eq!(result.score, 1.0);
+        assert!(Rating::default().update(&[result], TAU).rating > 1500.0);
+
+        let reseeded = Replay::new(GridConfig { seed: 43, ..config });
+        assert_eq!(
+            RatedResult::from_replays(Rating::default(), &winner, &reseeded),
+            Err(RatingError::WrongBoard)
+        );
+    }
+}
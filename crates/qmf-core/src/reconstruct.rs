@@ -0,0 +1,77 @@
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::config::ConfigError;
+
+/// What the player could see on one cell of a board they are copying in,
+/// e.g. from a screenshot. Cells left out are unknown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ObservedCell {
+    pub x: u32,
+    pub y: u32,
+    pub seen: Seen,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum Seen {
+    /// A revealed number.
+    Revealed { adjacent_mines: u8 },
+    /// A locked-down mine: certainly a mine.
+    Contained,
+    /// The player's flag: a guess, so the cell stays unknown.
+    Flagged,
+}
+
+/// Why [`QuantumGrid::reconstruct`](crate::grid::QuantumGrid::reconstruct)
+/// could not build a board from the observations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum ReconstructError {
+    InvalidConfig {
+        error: ConfigError,
+    },
+    OutOfBounds {
+        x: u32,
+        y: u32,
+    },
+    /// The same cell was observed twice.
+    Duplicate {
+        x: u32,
+        y: u32,
+    },
+    /// A revealed number no arrangement of mines around it can produce.
+    Contradiction {
+        x: u32,
+        y: u32,
+    },
+    /// More cells are contained than the board has mines.
+    TooManyMines {
+        contained: u32,
+        mine_count: u32,
+    },
+}
+
+impl fmt::Display for ReconstructError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidConfig { error } => write!(f, "invalid board: {error}"),
+            Self::OutOfBounds { x, y } => write!(f, "({x}, {y}) is off the board"),
+            Self::Duplicate { x, y } => write!(f, "({x}, {y}) was observed twice"),
+            Self::Contradiction { x, y } => {
+                write!(f, "the number at ({x}, {y}) cannot be satisfied")
+            }
+            Self::TooManyMines {
+                contained,
+                mine_count,
+            } => write!(f, "{contained} cells contained but only {mine_count} mines"),
+        }
+    }
+}
+
+impl core::error::Error for ReconstructError {}
@@ -0,0 +1,349 @@
+use alloc::collections::VecDeque;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::action::{Action, ActionResult, AppliedActions, IllegalAction};
+use crate::clock::ClockError;
+#[cfg(doc)]
+use crate::config::TurnRules;
+use crate::grid::QuantumGrid;
+use crate::prelude::*;
+
+pub type PlayerId = u32;
+
+/// Why the [`Referee`] refused an action. The grid is never touched when an
+/// action is rejected, and the turn does not advance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum Rejection {
+    UnknownPlayer { player: PlayerId },
+    NotYourTurn { expected: PlayerId },
+    RateLimited { retry_after_ms: u64 },
+    Illegal { reason: IllegalAction },
+    Clock { error: ClockError },
+}
+
+/// Server-side rules enforcement over a [`QuantumGrid`]: turn order,
+/// per-player action caps, and legality, so the game rules live in one place.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Referee {
+    pub grid: QuantumGrid,
+    /// Seat order. Under [`TurnRules`] the seat follows the grid's turn
+    /// counter, so a player keeps it until their action points run out or
+    /// they end the turn; in real-time play it passes after every accepted
+    /// action. A single player is simply always on turn.
+    pub players: Vec<PlayerId>,
+    /// Maximum accepted actions per player in any 1000 ms window. `0`
+    /// leaves actions uncapped.
+    pub max_actions_per_second: u32,
+    /// Real-time seat; turn-based play reads the grid's turn instead.
+    turn: usize,
+    /// Accepted-action timestamps per player (same order as `players`).
+    recent: Vec<VecDeque<u64>>,
+    /// Accepted action IDs per player, so one player's ID never answers
+    /// for another's.
+    applied: Vec<AppliedActions>,
+}
+
+const RATE_WINDOW_MS: u64 = 1_000;
+
+impl Referee {
+    pub fn new(grid: QuantumGrid, players: Vec<PlayerId>, max_actions_per_second: u32) -> Self {
+        let recent = vec![VecDeque::new(); players.len()];
+        let applied = vec![AppliedActions::default(); players.len()];
+        Self {
+            grid,
+            players,
+            max_actions_per_second,
+            turn: 0,
+            recent,
+            applied,
+        }
+    }
+
+    /// Whose turn it is, if there are any players.
+    pub fn current_player(&self) -> Option<PlayerId> {
+        self.players.get(self.seat_on_turn()).copied()
+    }
+
+    fn seat_on_turn(&self) -> usize {
+        match self.grid.config.turns {
+            Some(_) => {
+                let turn = self.grid.turns.turn.saturating_sub(1) as usize;
+                turn.checked_rem(self.players.len()).unwrap_or_default()
+            }
+            None => self.turn,
+        }
+    }
+
+    /// Validate and apply `action` from `player` at authoritative time `t_ms`.
+    pub fn submit(
+        &mut self,
+        player: PlayerId,
+        action: Action,
+        t_ms: u64,
+    ) -> Result<ActionResult, Rejection> {
+        self.submit_with_id(player, action, t_ms, None)
+    }
+
+    /// [`Self::submit`], deduplicated by `action_id` per player (see
+    /// [`QuantumGrid::apply_with_id`]). A retry of an accepted action gets
+    /// its original result back even though the turn has moved on.
+    pub fn submit_with_id(
+        &mut self,
+        player: PlayerId,
+        action: Action,
+        t_ms: u64,
+        action_id: Option<u64>,
+    ) -> Result<ActionResult, Rejection> {
+        let seat = self
+            .players
+            .iter()
+            .position(|&p| p == player)
+            .ok_or(Rejection::UnknownPlayer { player })?;
+        if let Some(result) = action_id.and_then(|id| self.applied.get(seat)?.get(id)) {
+            return Ok(result.clone());
+        }
+        if seat != self.seat_on_turn() {
+            return Err(Rejection::NotYourTurn {
+                expected: self.current_player().unwrap_or_default(),
+            });
+        }
+        self.grid
+            .clock
+            .check(t_ms)
+            .map_err(|error| Rejection::Clock { error })?;
+
+        let Some(window) = self.recent.get_mut(seat) else {
+            return Err(Rejection::UnknownPlayer { player });
+        };
+        while window
+            .front()
+            .is_some_and(|&t| t_ms.saturating_sub(t) >= RATE_WINDOW_MS)
+        {
+            window.pop_front();
+        }
+        if self.max_actions_per_second > 0 && window.len() as u32 >= self.max_actions_per_second {
+            let oldest = window.front().copied().unwrap_or(t_ms);
+            return Err(Rejection::RateLimited {
+                retry_after_ms: (oldest + RATE_WINDOW_MS).saturating_sub(t_ms),
+            });
+        }
+
+        self.grid
+            .validate_action(&action)
+            .map_err(|reason| Rejection::Illegal { reason })?;
+        let result = self
+            .grid
+            .apply_at(action, t_ms)
+            .map_err(|error| Rejection::Clock { error })?;
+        if result.is_rejection() {
+            return Err(Rejection::Illegal {
+                reason: IllegalAction::Refused,
+            });
+        }
+        if let (Some(id), Some(applied)) = (action_id, self.applied.get_mut(seat)) {
+            applied.record(id, result.clone());
+        }
+        if let Some(window) = self.recent.get_mut(seat) {
+            window.push_back(t_ms);
+        }
+        if self.grid.config.turns.is_none() {
+            self.turn = (self.turn + 1) % self.players.len();
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{GridConfig, TurnRules};
+
+    fn referee(players: Vec<PlayerId>, cap: u32) -> Referee {
+        Referee::new(QuantumGrid::new(8, 8, 10, 42, "observer"), players, cap)
+    }
+
+    #[test]
+    fn enforces_turn_order() {
+        let mut r = referee(vec![1, 2], 10);
+        assert_eq!(
+            r.submit(3, Action::Reveal { x: 0, y: 0 }, 0),
+            Err(Rejection::UnknownPlayer { player: 3 })
+        );
+        assert_eq!(
+            r.submit(2, Action::Reveal { x: 0, y: 0 }, 0),
+            Err(Rejection::NotYourTurn { expected: 1 })
+        );
+        assert!(r.submit(1, Action::Reveal { x: 0, y: 0 }, 0).is_ok());
+        assert_eq!(r.current_player(), Some(2));
+    }
+
+    #[test]
+    fn illegal_actions_do_not_consume_the_turn() {
+        let mut r = referee(vec![1, 2], 10);
+        r.submit(1, Action::Reveal { x: 0, y: 0 }, 0).unwrap();
+        assert_eq!(
+            r.submit(2, Action::Reveal { x: 0, y: 0 }, 10),
+            Err(Rejection::Illegal {
+                reason: IllegalAction::AlreadyResolved
+            })
+        );
+        assert_eq!(r.current_player(), Some(2));
+    }
+
+    #[test]
+    fn teleports_without_a_bell_pair_do_not_consume_the_turn() {
+        let mut r = referee(vec![1, 2], 10);
+        r.submit(1, Action::Reveal { x: 0, y: 0 }, 0).unwrap();
+        r.grid.entanglement.pairs.clear();
+        let open: Vec<(u32, u32)> = r
+            .grid
+            .cells
+            .iter()
+            .filter(|c| matches!(c.state, crate::grid::CellState::Superposition { .. }))
+            .map(|c| (c.x, c.y))
+            .collect();
+        let ((x, y), (to_x, to_y)) = (open[0], open[1]);
+        let before = r.grid.state_hash();
+        assert_eq!(
+            r.submit(2, Action::Teleport { x, y, to_x, to_y }, 10),
+            Err(Rejection::Illegal {
+                reason: IllegalAction::NoBellPair
+            })
+        );
+        assert_eq!(
+            r.submit(
+                2,
+                Action::Teleport {
+                    x,
+                    y,
+                    to_x: x,
+                    to_y: y
+                },
+                10
+            ),
+            Err(Rejection::Illegal {
+                reason: IllegalAction::SameCell
+            })
+        );
+        assert_eq!(r.grid.state_hash(), before);
+        assert_eq!(r.current_player(), Some(2));
+    }
+
+    #[test]
+    fn overprecise_measurements_do_not_consume_the_turn() {
+        let mut r = referee(vec![1, 2], 10);
+        r.submit(1, Action::Reveal { x: 0, y: 0 }, 0).unwrap();
+        let target = r
+            .grid
+            .cells
+            .iter()
+            .find(|c| matches!(c.state, crate::grid::CellState::Superposition { .. }))
+            .map(|c| (c.x, c.y))
+            .unwrap();
+        let (x, y) = target;
+        let before = r.grid.state_hash();
+        assert_eq!(
+            r.submit(
+                2,
+                Action::MeasureWeakWithPrecision { x, y, percent: 150 },
+                10
+            ),
+            Err(Rejection::Illegal {
+                reason: IllegalAction::InvalidPrecision
+            })
+        );
+        assert_eq!(r.grid.state_hash(), before);
+        assert_eq!(r.current_player(), Some(2));
+        let action = Action::MeasureWeakWithPrecision { x, y, percent: 100 };
+        assert!(r.submit(2, action, 20).is_ok());
+        assert_eq!(r.current_player(), Some(1));
+    }
+
+    #[test]
+    fn caps_actions_per_second() {
+        let mut r = referee(vec![1], 2);
+        r.submit(1, Action::Hadamard { x: 0, y: 7 }, 0).unwrap();
+        r.submit(1, Action::Hadamard { x: 1, y: 7 }, 400).unwrap();
+        assert_eq!(
+            r.submit(1, Action::Hadamard { x: 2, y: 7 }, 600),
+            Err(Rejection::RateLimited {
+                retry_after_ms: 400
+            })
+        );
+        assert!(r.submit(1, Action::Hadamard { x: 2, y: 7 }, 1_000).is_ok());
+    }
+
+    #[test]
+    fn zero_cap_leaves_actions_uncapped() {
+        let mut r = referee(vec![1], 0);
+        for x in 0..4 {
+            assert!(r.submit(1, Action::Hadamard { x, y: 7 }, 0).is_ok());
+        }
+    }
+
+    #[test]
+    fn turn_based_seats_follow_the_grid_turn() {
+        let config = GridConfig::new(8, 8, 10, 42, "observer").with_turns(TurnRules::default());
+        let mut r = Referee::new(QuantumGrid::from_config(config), vec![1, 2], 10);
+        // Player 1 keeps the seat while action points remain.
+        r.submit(1, Action::Reveal { x: 0, y: 0 }, 0).unwrap();
+        assert_eq!(r.current_player(), Some(1));
+        r.submit(1, Action::Hadamard { x: 7, y: 7 }, 10)
+            .unwrap_err();
+        r.submit(1, Action::EndTurn, 20).unwrap();
+        assert_eq!(r.current_player(), Some(2));
+        assert_eq!(
+            r.submit(1, Action::Hadamard { x: 7, y: 7 }, 30),
+            Err(Rejection::NotYourTurn { expected: 2 })
+        );
+        r.submit(2, Action::Hadamard { x: 7, y: 7 }, 30).unwrap();
+        assert_eq!(r.grid.turns.turn, 3);
+        assert_eq!(r.current_player(), Some(1));
+    }
+
+    #[test]
+    fn rejects_backwards_clock() {
+        let mut r = referee(vec![1], 10);
+        r.submit(1, Action::Hadamard { x: 1, y: 1 }, 500).unwrap();
+        assert!(matches!(
+            r.submit(1, Action::Hadamard { x: 1, y: 1 }, 100),
+            Err(Rejection::Clock { .. })
+        ));
+    }
+
+    #[test]
+    fn retried_actions_are_applied_once() {
+        let mut r = referee(vec![1, 2], 10);
+        r.submit(1, Action::Reveal { x: 0, y: 0 }, 0).unwrap();
+        let target = r
+            .grid
+            .cells
+            .iter()
+            .find(|c| matches!(c.state, crate::grid::CellState::Superposition { .. }))
+            .map(|c| Action::Contain { x: c.x, y: c.y })
+            .unwrap();
+        let charges = r.grid.containment_charges;
+        let first = r.submit_with_id(2, target, 10, Some(9)).unwrap();
+        // The retry arrives after the turn moved on and still succeeds.
+        assert_eq!(r.submit_with_id(2, target, 20, Some(9)), Ok(first));
+        assert_eq!(r.grid.containment_charges, charges - 1);
+        assert_eq!(r.current_player(), Some(1));
+
+        // Another player's ID 9 is their own move, and strangers get nothing.
+        assert_eq!(
+            r.submit_with_id(3, target, 30, Some(9)),
+            Err(Rejection::UnknownPlayer { player: 3 })
+        );
+        let hadamard = Action::Hadamard { x: 7, y: 7 };
+        assert!(matches!(
+            r.submit_with_id(1, hadamard, 30, Some(9)),
+            Ok(ActionResult::Probability { .. })
+        ));
+        assert_eq!(r.current_player(), Some(2));
+    }
+}
@@ -0,0 +1,800 @@
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::action::{Action, IllegalAction};
+use crate::clock::ClockError;
+use crate::compatibility::{self, Compatibility, RulesetStamp};
+use crate::config::{ConfigError, GridConfig, Limits};
+use crate::grid::{QuantumCell, QuantumGrid};
+use crate::ops::GridOps;
+use crate::prelude::*;
+use crate::progress::{Phase, ProgressSink};
+use crate::telemetry;
+
+/// One accepted move and the authoritative time it was made at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ReplayStep {
+    pub t_ms: u64,
+    pub action: Action,
+}
+
+/// A remark attached to a replay: a chat line, commentary or an
+/// annotation. Never part of playback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ReplayNote {
+    pub t_ms: u64,
+    /// Who wrote it, as the recording service knows them.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub author: Option<String>,
+    pub text: String,
+}
+
+/// How a move looked to someone who could only see the board, as judged
+/// by the solver after the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum MoveClass {
+    /// Proven correct: a safe reveal or a mine contained.
+    Proven,
+    /// A guess when nothing could be proven, at the lowest risk on offer.
+    Forced,
+    /// A guess when nothing could be proven, close to but not the safest.
+    Suboptimal,
+    /// A guess while a proven move was available, or one well above the
+    /// lowest risk.
+    UnnecessaryRisk,
+    /// Against a proof: revealing a proven mine or containing a proven
+    /// safe cell.
+    Blunder,
+}
+
+/// A move worth reviewing, for a jump-to list in the replay viewer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CoachingTag {
+    /// Index into [`Replay::steps`].
+    pub step: u32,
+    pub class: MoveClass,
+    /// Chance the move was wrong: a mine revealed or a safe cell contained.
+    pub risk: f64,
+    /// The lowest such chance any move had at the time.
+    pub safest: f64,
+}
+
+/// Why a [`Replay`] could not be re-simulated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum ReplayError {
+    Illegal {
+        step: usize,
+        reason: IllegalAction,
+    },
+    Clock {
+        step: usize,
+        error: ClockError,
+    },
+    /// The step drew a different number of random values than the
+    /// recorded [`Replay::rng_audit`] says it did.
+    RngDiverged {
+        step: usize,
+    },
+    /// The replay's [`RulesetStamp`] does not match this engine.
+    Incompatible {
+        compatibility: Compatibility,
+    },
+    /// The recorded board is over the default [`Limits`]; refused before
+    /// building it.
+    TooLarge {
+        error: ConfigError,
+    },
+    /// The recorded board's config does not
+    /// [validate](GridConfig::validate).
+    InvalidConfig {
+        error: ConfigError,
+    },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Illegal { step, reason } => write!(f, "step {step} is illegal: {reason:?}"),
+            Self::Clock { step, error } => write!(f, "step {step}: {error}"),
+            Self::RngDiverged { step } => write!(f, "step {step} diverged from the RNG audit"),
+            Self::Incompatible { compatibility } => match compatibility {
+                Compatibility::NeedsRuleset { version } => {
+                    write!(f, "replay needs ruleset version {version}")
+                }
+                Compatibility::Incompatible { reason } => write!(f, "{reason}"),
+                Compatibility::Compatible => write!(f, "replay is compatible"),
+            },
+            Self::TooLarge { error } | Self::InvalidConfig { error } => write!(f, "{error}"),
+        }
+    }
+}
+
+impl core::error::Error for ReplayError {}
+
+/// Label of the grid's RNG stream in an audit.
+pub const GRID_STREAM: &str = "grid";
+
+/// Random values one step drew from one RNG stream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RngDraws {
+    pub stream: String,
+    pub count: u64,
+}
+
+/// A recorded game: the configuration it was played on plus every accepted
+/// action, in order.
+///
+/// The game is fully deterministic given its config, so a replay is verified
+/// simply by playing it back — any illegal or out-of-order step rejects it,
+/// as does a [`stamp`](Self::stamp) from rules this engine does not play.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Replay {
+    pub config: GridConfig,
+    pub steps: Vec<ReplayStep>,
+    /// Engine and rules the replay was recorded under; `None` for replays
+    /// from before stamping (see [`compatibility::check`]).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub stamp: Option<RulesetStamp>,
+    /// Optional RNG draws per step (see [`Self::audited`]). When present,
+    /// playback checks every covered step against it, pinpointing where a
+    /// platform or engine version took a different random path.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub rng_audit: Option<Vec<Vec<RngDraws>>>,
+    /// Who played, as the recording service knows them.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub player: Option<String>,
+    /// Chat and annotations made during or after the game.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub notes: Vec<ReplayNote>,
+    /// The moves a review should stop at, in step order (see
+    /// `analysis::coach`).
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    pub tags: Vec<CoachingTag>,
+}
+
+impl Replay {
+    pub fn new(config: GridConfig) -> Self {
+        Self {
+            stamp: Some(RulesetStamp::current(&config)),
+            config,
+            steps: Vec::new(),
+            rng_audit: None,
+            player: None,
+            notes: Vec::new(),
+            tags: Vec::new(),
+        }
+    }
+
+    /// The replay as it is safe to publish: no player, no notes, and step
+    /// times counted from the earliest step rather than whatever clock the
+    /// recorder used. Playback only depends on the gaps between steps, so
+    /// the scrubbed replay still verifies.
+    pub fn scrubbed(&self) -> Self {
+        let start = self.steps.iter().map(|step| step.t_ms).min().unwrap_or(0);
+        Self {
+            config: self.config.clone(),
+            steps: self
+                .steps
+                .iter()
+                .map(|step| ReplayStep {
+                    t_ms: step.t_ms.saturating_sub(start),
+                    ..*step
+                })
+                .collect(),
+            stamp: self.stamp.clone(),
+            rng_audit: self.rng_audit.clone(),
+            player: None,
+            notes: Vec::new(),
+            tags: self.tags.clone(),
+        }
+    }
+
+    /// Play the replay and record every step's RNG draws into
+    /// [`Self::rng_audit`], replacing any earlier audit. Steps recorded
+    /// afterwards are not covered until this is called again.
+    pub fn audited(mut self) -> Result<Self, ReplayError> {
+        self.rng_audit = None;
+        let mut audit = Vec::with_capacity(self.steps.len());
+        self.simulate_audited(|_, _, draws| audit.push(draws))?;
+        self.rng_audit = Some(audit);
+        Ok(self)
+    }
+
+    /// Append an action that the live game accepted at `t_ms`.
+    pub fn record(&mut self, t_ms: u64, action: Action) {
+        self.steps.push(ReplayStep { t_ms, action });
+    }
+
+    /// Re-simulate the game from scratch and return its final state.
+    pub fn play(&self) -> Result<QuantumGrid, ReplayError> {
+        self.simulate(|_, _| {})
+    }
+
+    /// Play the steps onto `grid`, a board of any kind the caller built to
+    /// match [`Self::config`]. Step times are not applied, since only a
+    /// [`QuantumGrid`] keeps a clock; use [`Self::play`] to check a
+    /// recorded engine game.
+    pub fn play_on(&self, grid: &mut impl GridOps) -> Result<(), ReplayError> {
+        for (step, replay_step) in self.steps.iter().enumerate() {
+            grid.validate_action(&replay_step.action)
+                .map_err(|reason| ReplayError::Illegal { step, reason })?;
+            grid.apply(replay_step.action);
+        }
+        Ok(())
+    }
+
+    /// Play the game back once and index its progress by time, so a ghost
+    /// can be queried every frame with [`ReplayIndex::cursor_at`].
+    pub fn index(&self) -> Result<ReplayIndex, ReplayError> {
+        let mut entries = Vec::with_capacity(self.steps.len());
+        let grid = self.simulate(|&step, grid| {
+            entries.push(IndexEntry {
+                step,
+                resolved: grid.resolved_cells(),
+                game_over: grid.game_over,
+                won: grid.won,
+            });
+        })?;
+        Ok(ReplayIndex {
+            total_cells: grid.cells.len() as u32,
+            entries,
+        })
+    }
+
+    /// One-off [`ReplayIndex::cursor_at`]; re-simulates on every call.
+    pub fn cursor_at(&self, t_ms: u64) -> Result<GhostCursor, ReplayError> {
+        Ok(self.index()?.cursor_at(t_ms))
+    }
+
+    /// The game as it stood after the first `move_index` steps (clamped to
+    /// the replay's length). Re-simulates from the start; build a
+    /// [`ReplaySeeker`] to seek repeatedly.
+    pub fn seek(&self, move_index: usize) -> Result<QuantumGrid, ReplayError> {
+        let mut grid = self.new_game()?;
+        for (step, replay_step) in self.steps.iter().enumerate().take(move_index) {
+            apply_step(&mut grid, step, replay_step)?;
+        }
+        Ok(grid)
+    }
+
+    /// Play the replay once, keeping a full copy of the game every `every`
+    /// steps so [`ReplaySeeker::seek`] only re-applies the steps since the
+    /// nearest checkpoint.
+    pub fn seeker(&self, every: usize) -> Result<ReplaySeeker, ReplayError> {
+        self.seeker_with(every, &mut ())
+    }
+
+    /// [`Self::seeker`], reporting playback progress to `sink`.
+    pub fn seeker_with(
+        &self,
+        every: usize,
+        sink: &mut impl ProgressSink,
+    ) -> Result<ReplaySeeker, ReplayError> {
+        let every = every.max(1);
+        let mut checkpoints = vec![self.new_game()?];
+        let mut played = 0;
+        self.simulate(|_, grid| {
+            played += 1;
+            if played % every == 0 {
+                checkpoints.push(grid.clone());
+            }
+            sink.on_progress(played as f64 / self.steps.len() as f64, Phase::Replaying);
+        })?;
+        Ok(ReplaySeeker {
+            steps: self.steps.clone(),
+            every,
+            checkpoints,
+        })
+    }
+
+    /// The game before the first step, once its board is known to fit the
+    /// default [`Limits`].
+    fn new_game(&self) -> Result<QuantumGrid, ReplayError> {
+        self.config
+            .check_limits(&Limits::default())
+            .map_err(|error| ReplayError::TooLarge { error })?;
+        self.config
+            .validate()
+            .map_err(|error| ReplayError::InvalidConfig { error })?;
+        Ok(QuantumGrid::from_config(self.config.clone()))
+    }
+
+    /// Export for spectators: the public board plus the observed outcome of
+    /// every step, without the seed or mine map, so watching a match does
+    /// not let anyone solve its board before playing it.
+    pub fn spectator(&self) -> Result<SpectatorReplay, ReplayError> {
+        let initial = self.new_game()?.cells;
+        let mut previous = initial.clone();
+        let mut frames = Vec::with_capacity(self.steps.len());
+        self.simulate(|&ReplayStep { t_ms, action }, grid| {
+            let changed = grid
+                .cells
+                .iter()
+                .zip(&previous)
+                .filter(|(now, before)| now != before)
+                .map(|(now, _)| now.clone())
+                .collect();
+            frames.push(SpectatorFrame {
+                t_ms,
+                action,
+                changed,
+                containment_charges: grid.containment_charges,
+                game_over: grid.game_over,
+                won: grid.won,
+            });
+            previous.clone_from(&grid.cells);
+        })?;
+        Ok(SpectatorReplay {
+            width: self.config.width,
+            height: self.config.height,
+            mine_count: self.config.mine_count,
+            difficulty: self.config.difficulty.clone(),
+            initial: initial.to_vec(),
+            frames,
+        })
+    }
+
+    /// Apply every step in order, calling `on_step` after each one.
+    fn simulate(
+        &self,
+        mut on_step: impl FnMut(&ReplayStep, &QuantumGrid),
+    ) -> Result<QuantumGrid, ReplayError> {
+        self.simulate_audited(|replay_step, grid, _| on_step(replay_step, grid))
+    }
+
+    /// [`Self::simulate`], also passing each step's RNG draws and checking
+    /// them against the audit, if any.
+    fn simulate_audited(
+        &self,
+        mut on_step: impl FnMut(&ReplayStep, &QuantumGrid, Vec<RngDraws>),
+    ) -> Result<QuantumGrid, ReplayError> {
+        if self.stamp.is_some() {
+            let compatibility = compatibility::check(self);
+            if compatibility != Compatibility::Compatible {
+                return Err(ReplayError::Incompatible { compatibility });
+            }
+        }
+        let mut grid = self.new_game()?;
+        for (step, replay_step) in self.steps.iter().enumerate() {
+            let before = grid.rng.draws();
+            apply_step(&mut grid, step, replay_step)?;
+            let draws = vec![RngDraws {
+                stream: GRID_STREAM.to_string(),
+                count: grid.rng.draws().wrapping_sub(before),
+            }];
+            let recorded = self.rng_audit.as_ref().and_then(|audit| audit.get(step));
+            if recorded.is_some_and(|recorded| *recorded != draws) {
+                telemetry::warning!(
+                    step,
+                    ?recorded,
+                    ?draws,
+                    "replay diverged from its RNG audit"
+                );
+                return Err(ReplayError::RngDiverged { step });
+            }
+            on_step(replay_step, &grid, draws);
+        }
+        Ok(grid)
+    }
+}
+
+pub(crate) fn apply_step(
+    grid: &mut QuantumGrid,
+    step: usize,
+    &ReplayStep { t_ms, action }: &ReplayStep,
+) -> Result<(), ReplayError> {
+    grid.validate_action(&action)
+        .map_err(|reason| ReplayError::Illegal { step, reason })?;
+    grid.apply_at(action, t_ms)
+        .map_err(|error| ReplayError::Clock { step, error })?;
+    Ok(())
+}
+
+/// A verified [`Replay`] with periodic full-state checkpoints, for
+/// scrubbing back and forth through a long game.
+///
+/// Not serializable on purpose: checkpoints are trusted state, so they are
+/// always rebuilt by playing the replay rather than loaded from a file.
+#[derive(Debug, Clone)]
+pub struct ReplaySeeker {
+    steps: Vec<ReplayStep>,
+    every: usize,
+    /// State after `i * every` steps.
+    checkpoints: Vec<QuantumGrid>,
+}
+
+impl ReplaySeeker {
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// The game after the first `move_index` steps (clamped to the replay's
+    /// length), re-applying at most `every - 1` steps. Every step was
+    /// accepted when the seeker was built and play is deterministic, so an
+    /// error here means the engine broke that promise.
+    pub fn seek(&self, move_index: usize) -> Result<QuantumGrid, ReplayError> {
+        let move_index = move_index.min(self.steps.len());
+        let nearest = (move_index / self.every).min(self.checkpoints.len().saturating_sub(1));
+        let mut grid = self
+            .checkpoints
+            .get(nearest)
+            .ok_or(ReplayError::RngDiverged {
+                step: nearest * self.every,
+            })?
+            .clone();
+        for (step, replay_step) in self
+            .steps
+            .iter()
+            .enumerate()
+            .take(move_index)
+            .skip(nearest * self.every)
+        {
+            apply_step(&mut grid, step, replay_step)?;
+        }
+        Ok(grid)
+    }
+
+    /// [`QuantumGrid::from_replay_at`] from the nearest checkpoint, for
+    /// restarting a botched endgame over and over.
+    pub fn practice_at(&self, move_index: usize) -> Result<QuantumGrid, ReplayError> {
+        let grid = self.seek(move_index)?;
+        Ok(grid.into_practice(&self.steps, move_index))
+    }
+}
+
+/// Where a "ghost" of a previous run is at a given moment.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GhostCursor {
+    /// Steps the ghost has made so far.
+    pub steps_played: u32,
+    pub last_action: Option<ReplayStep>,
+    /// Cells the ghost has revealed or contained.
+    pub resolved: u32,
+    /// `resolved` as a fraction of the board.
+    pub progress: f64,
+    pub game_over: bool,
+    pub won: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+struct IndexEntry {
+    step: ReplayStep,
+    resolved: u32,
+    game_over: bool,
+    won: bool,
+}
+
+/// Per-step progress of a [`Replay`], searchable by time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ReplayIndex {
+    total_cells: u32,
+    entries: Vec<IndexEntry>,
+}
+
+impl ReplayIndex {
+    /// The ghost's state `t_ms` after its first action, so a frontend can
+    /// pass the live game's elapsed time directly.
+    pub fn cursor_at(&self, t_ms: u64) -> GhostCursor {
+        let start = self.entries.first().map_or(0, |e| e.step.t_ms);
+        let t_ms = start.saturating_add(t_ms);
+        let played = self.entries.partition_point(|e| e.step.t_ms <= t_ms);
+        let current = played.checked_sub(1).and_then(|i| self.entries.get(i));
+        let resolved = current.map_or(0, |e| e.resolved);
+        GhostCursor {
+            steps_played: played as u32,
+            last_action: current.map(|e| e.step),
+            resolved,
+            progress: if self.total_cells == 0 {
+                0.0
+            } else {
+                resolved as f64 / self.total_cells as f64
+            },
+            game_over: current.is_some_and(|e| e.game_over),
+            won: current.is_some_and(|e| e.won),
+        }
+    }
+}
+
+/// What the board looked like after one step of a [`SpectatorReplay`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SpectatorFrame {
+    pub t_ms: u64,
+    pub action: Action,
+    /// Cells whose public state changed on this step.
+    pub changed: Vec<QuantumCell>,
+    pub containment_charges: u32,
+    pub game_over: bool,
+    pub won: bool,
+}
+
+/// Partial-information replay built by [`Replay::spectator`]: enough to
+/// re-render a match, but not to regenerate its board.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SpectatorReplay {
+    pub width: u32,
+    pub height: u32,
+    pub mine_count: u32,
+    pub difficulty: String,
+    /// The board before the first move, as players saw it.
+    pub initial: Vec<QuantumCell>,
+    pub frames: Vec<SpectatorFrame>,
+}
+
+impl SpectatorReplay {
+    /// The board after the first `steps` frames.
+    pub fn cells_after(&self, steps: usize) -> Vec<QuantumCell> {
+        let mut cells = self.initial.clone();
+        for frame in self.frames.iter().take(steps) {
+            for cell in &frame.changed {
+                if cell.x >= self.width || cell.y >= self.height {
+                    continue;
+                }
+                let index = (cell.y as usize) * (self.width as usize) + cell.x as usize;
+                if let Some(slot) = cells.get_mut(index) {
+                    slot.clone_from(cell);
+                }
+            }
+        }
+        cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::TimedRules;
+
+    #[test]
+    fn playback_matches_the_live_game() {
+        let config = GridConfig::new(8, 8, 10, 42, "observer");
+        let mut live = QuantumGrid::from_config(config.clone());
+        let mut replay = Replay::new(config);
+        for (t_ms, action) in [
+            (0, Action::Reveal { x: 0, y: 0 }),
+            (250, Action::Hadamard { x: 7, y: 7 }),
+            (900, Action::Reveal { x: 7, y: 0 }),
+        ] {
+            if live.validate_action(&action).is_ok() {
+                live.apply_at(action, t_ms).unwrap();
+                replay.record(t_ms, action);
+            }
+        }
+
+        let played = replay.play().unwrap();
+        assert_eq!(played.cells, live.cells);
+        assert_eq!(played.clock, live.clock);
+        assert_eq!(played.game_over, live.game_over);
+    }
+
+    #[test]
+    fn scrubbed_replays_drop_identity_and_still_verify() {
+        let config = GridConfig::new(8, 8, 10, 42, "observer");
+        let mut replay = Replay::new(config);
+        let epoch = 1_790_000_000_000;
+        replay.record(epoch, Action::Reveal { x: 0, y: 0 });
+        replay.record(epoch + 400, Action::Hadamard { x: 7, y: 7 });
+        replay.player = Some("player-1234".to_string());
+        replay.notes.push(ReplayNote {
+            t_ms: epoch + 100,
+            author: Some("player-1234".to_string()),
+            text: "gg".to_string(),
+        });
+        let replay = replay.audited().unwrap();
+
+        let scrubbed = replay.scrubbed();
+        assert_eq!(scrubbed.player, None);
+        assert!(scrubbed.notes.is_empty());
+        let times: Vec<u64> = scrubbed.steps.iter().map(|step| step.t_ms).collect();
+        assert_eq!(times, [0, 400]);
+        assert_eq!(
+            scrubbed.play().unwrap().timing_stats(),
+            replay.play().unwrap().timing_stats()
+        );
+
+        // A hand-edited replay may run backwards; the gaps survive.
+        let mut shuffled = Replay::new(GridConfig::new(8, 8, 10, 42, "observer"));
+        shuffled.record(epoch + 300, Action::Reveal { x: 0, y: 0 });
+        shuffled.record(epoch, Action::Hadamard { x: 7, y: 7 });
+        let times: Vec<u64> = shuffled
+            .scrubbed()
+            .steps
+            .iter()
+            .map(|step| step.t_ms)
+            .collect();
+        assert_eq!(times, [300, 0]);
+    }
+
+    #[test]
+    fn recorded_time_steps_replay_exactly() {
+        let config = GridConfig::new(8, 8, 10, 42, "theorist")
+            .with_decoherence_limit(100)
+            .with_timed(TimedRules {
+                ambient_decoherence: 40,
+                link_half_life_ms: Some(700),
+                ..TimedRules::default()
+            });
+        let mut live = QuantumGrid::from_config(config.clone());
+        let mut replay = Replay::new(config);
+        for (t_ms, action) in [
+            (0, Action::Reveal { x: 0, y: 0 }),
+            (16, Action::Advance { dt_ms: 1_800 }),
+            (1_816, Action::Advance { dt_ms: 2_300 }),
+            (4_116, Action::Reveal { x: 7, y: 7 }),
+        ] {
+            if live.validate_action(&action).is_ok() {
+                live.apply_at(action, t_ms).unwrap();
+                replay.record(t_ms, action);
+            }
+        }
+        assert_eq!(live.time_ms, 4_100);
+
+        let played = replay.play().unwrap();
+        assert_eq!(played.state_hash(), live.state_hash());
+        assert_eq!(played.clock, live.clock);
+    }
+
+    #[test]
+    fn ghost_cursor_tracks_progress_over_time() {
+        let mut replay = Replay::new(GridConfig::new(8, 8, 10, 42, "observer"));
+        replay.record(1_000, Action::Hadamard { x: 7, y: 7 });
+        replay.record(1_500, Action::Reveal { x: 0, y: 0 });
+        let index = replay.index().unwrap();
+
+        // Times are relative to the ghost's first action.
+        let first = index.cursor_at(0);
+        assert_eq!(first.steps_played, 1);
+        assert_eq!(first.resolved, 0);
+        assert_eq!(
+            first.last_action.map(|s| s.action),
+            Some(Action::Hadamard { x: 7, y: 7 })
+        );
+
+        let before = index.cursor_at(499);
+        assert_eq!(before.steps_played, 1);
+        let after = index.cursor_at(500);
+        assert_eq!(after.steps_played, 2);
+        assert!(after.resolved > 0);
+        assert!(after.progress > 0.0 && after.progress <= 1.0);
+        assert_eq!(replay.cursor_at(10_000), Ok(after));
+
+        let empty = Replay::new(GridConfig::new(8, 8, 10, 42, "observer"));
+        let idle = empty.cursor_at(5).unwrap();
+        assert_eq!((idle.steps_played, idle.last_action), (0, None));
+    }
+
+    #[test]
+    fn spectator_replay_rebuilds_the_board_without_the_seed() {
+        let mut replay = Replay::new(GridConfig::new(8, 8, 10, 42, "observer"));
+        replay.record(0, Action::Reveal { x: 0, y: 0 });
+        replay.record(300, Action::Reveal { x: 7, y: 7 });
+        let full = replay.play().unwrap();
+        let spectator = replay.spectator().unwrap();
+
+        assert_eq!(spectator.frames.len(), 2);
+        assert!(!spectator.frames[0].changed.is_empty());
+        assert_eq!(spectator.cells_after(2), *full.cells);
+        assert_eq!(spectator.cells_after(0), spectator.initial);
+
+        let json = serde_json::to_string(&spectator).unwrap();
+        assert!(!json.contains("seed"));
+        assert!(!json.contains("mine_map"));
+    }
+
+    #[test]
+    fn tampered_replays_are_rejected() {
+        let mut replay = Replay::new(GridConfig::new(8, 8, 10, 42, "observer"));
+        replay.record(100, Action::Reveal { x: 0, y: 0 });
+        replay.record(50, Action::Hadamard { x: 7, y: 7 });
+        assert!(matches!(
+            replay.play(),
+            Err(ReplayError::Clock { step: 1, .. })
+        ));
+
+        replay.steps[1] = ReplayStep {
+            t_ms: 200,
+            action: Action::Reveal { x: 0, y: 0 },
+        };
+        assert_eq!(
+            replay.play().unwrap_err(),
+            ReplayError::Illegal {
+                step: 1,
+                reason: IllegalAction::AlreadyResolved
+            }
+        );
+    }
+
+    #[test]
+    fn rng_audit_pinpoints_divergence() {
+        let mut replay = Replay::new(GridConfig::new(8, 8, 10, 42, "observer"));
+        replay.record(0, Action::Reveal { x: 0, y: 0 });
+        replay.record(100, Action::MeasureWeak { x: 7, y: 7 });
+        replay.record(200, Action::Hadamard { x: 7, y: 0 });
+        let mut audited = replay.audited().unwrap();
+        let audit = audited.rng_audit.clone().unwrap();
+        assert_eq!(audit.len(), 3);
+        assert!(audit[0][0].count > 0, "placing mines draws");
+        assert_eq!(audit[2][0].count, 0, "a Hadamard is deterministic");
+        assert!(audited.play().is_ok());
+
+        let json = serde_json::to_string(&audited).unwrap();
+        let back: Replay = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, audited);
+
+        audited.rng_audit.as_mut().unwrap()[1][0].count += 1;
+        assert_eq!(
+            audited.play().unwrap_err(),
+            ReplayError::RngDiverged { step: 1 }
+        );
+    }
+
+    #[test]
+    fn seeking_from_checkpoints_matches_a_full_replay() {
+        let config = GridConfig::new(12, 12, 20, 9, "researcher");
+        let mut live = QuantumGrid::from_config(config.clone());
+        let mut replay = Replay::new(config);
+        for (t_ms, (x, y)) in (0..144).map(|i| (i as u64 * 10, (i % 12, i / 12))) {
+            // One safe opening reveal, then moves that never end the game.
+            let action = match t_ms {
+                0 => Action::Reveal { x: 6, y: 6 },
+                _ if t_ms % 20 == 0 => Action::MeasureWeak { x, y },
+                _ => Action::Hadamard { x, y },
+            };
+            if live.validate_action(&action).is_ok() {
+                live.apply_at(action, t_ms).unwrap();
+                replay.record(t_ms, action);
+            }
+        }
+        assert!(replay.steps.len() > 40);
+
+        let seeker = replay.seeker(4).unwrap();
+        for move_index in [0, 1, 4, 7, replay.steps.len(), replay.steps.len() + 5] {
+            let expected = replay.seek(move_index).unwrap();
+            assert_eq!(
+                seeker.seek(move_index).unwrap().state_hash(),
+                expected.state_hash()
+            );
+        }
+        assert_eq!(
+            seeker.seek(usize::MAX).unwrap().state_hash(),
+            live.state_hash()
+        );
+    }
+}
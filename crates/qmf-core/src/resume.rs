@@ -0,0 +1,221 @@
+use alloc::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::chunks::CHUNK_SIZE;
+use crate::codec::{self, DecodeError};
+use crate::commitment::to_hex;
+use crate::grid::{GridSnapshot, QuantumCell, QuantumGrid};
+use crate::prelude::*;
+use crate::referee::PlayerId;
+
+/// Above this share of the board changed, a full snapshot is cheaper to
+/// send than the changed cells.
+pub const FULL_RESYNC_SHARE: f64 = 0.5;
+
+/// What a reconnecting client needs to catch up from the last revision it
+/// saw (see [`ChunkTracker`](crate::chunks::ChunkTracker)).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum Resync {
+    /// Nothing changed.
+    UpToDate { revision: u64 },
+    /// The cells of every chunk changed since, plus the board's current
+    /// summary: a snapshot with `cells` left empty.
+    Diff {
+        revision: u64,
+        cells: Vec<QuantumCell>,
+        summary: Box<GridSnapshot>,
+    },
+    /// The whole board in the compact wire format (see [`codec`]).
+    Full { revision: u64, snapshot: Vec<u8> },
+}
+
+impl Resync {
+    /// Catch-up for a client that last saw `since`. Falls back to
+    /// [`Resync::Full`] when most of the board changed, or when `since` is
+    /// ahead of the grid, e.g. after the server restarted.
+    pub fn since(grid: &QuantumGrid, since: u64) -> Self {
+        let revision = grid.chunks.revision();
+        let chunks = grid.chunks.changed_since(since);
+        if since <= revision && chunks.is_empty() {
+            return Self::UpToDate { revision };
+        }
+        let cells: Vec<QuantumCell> = chunks
+            .iter()
+            .flat_map(|chunk| {
+                let (x0, y0) = (chunk.cx * CHUNK_SIZE, chunk.cy * CHUNK_SIZE);
+                grid.cells_in_rect(x0, y0, x0 + CHUNK_SIZE, y0 + CHUNK_SIZE)
+            })
+            .cloned()
+            .collect();
+        let share = cells.len() as f64 / grid.cells.len().max(1) as f64;
+        let snapshot = grid.snapshot();
+        if since > revision || share > FULL_RESYNC_SHARE {
+            return Self::Full {
+                revision,
+                snapshot: codec::encode_snapshot(&snapshot),
+            };
+        }
+        Self::Diff {
+            revision,
+            cells,
+            summary: Box::new(GridSnapshot {
+                cells: Vec::new(),
+                cosmetics: Vec::new(),
+                ..snapshot
+            }),
+        }
+    }
+
+    /// Bring the client's `snapshot` up to date, returning the revision to
+    /// resume from. A diff for a board of another size, or with a cell off
+    /// the board, is refused and leaves `snapshot` as it was.
+    pub fn apply(self, snapshot: &mut GridSnapshot) -> Result<u64, DecodeError> {
+        match self {
+            Self::UpToDate { revision } => Ok(revision),
+            Self::Diff {
+                revision,
+                cells,
+                summary,
+            } => {
+                let (width, height) = (summary.width as usize, summary.height as usize);
+                if width.checked_mul(height) != Some(snapshot.cells.len()) {
+                    return Err(DecodeError::Corrupt);
+                }
+                let index = |cell: &QuantumCell| {
+                    let (x, y) = (cell.x as usize, cell.y as usize);
+                    (x < width && y < height)
+                        .then(|| y.checked_mul(width)?.checked_add(x))
+                        .flatten()
+                };
+                let indexes = cells
+                    .iter()
+                    .map(index)
+                    .collect::<Option<Vec<usize>>>()
+                    .ok_or(DecodeError::Corrupt)?;
+                let mut board = core::mem::take(&mut snapshot.cells);
+                for (index, cell) in indexes.into_iter().zip(cells) {
+                    if let Some(slot) = board.get_mut(index) {
+                        *slot = cell;
+                    }
+                }
+                *snapshot = GridSnapshot {
+                    cells: board,
+                    cosmetics: core::mem::take(&mut snapshot.cosmetics),
+                    ..*summary
+                };
+                Ok(revision)
+            }
+            Self::Full {
+                revision,
+                snapshot: bytes,
+            } => {
+                *snapshot = codec::decode_snapshot(&bytes)?;
+                Ok(revision)
+            }
+        }
+    }
+}
+
+/// Resumable sessions: a server hands each connection a token and accepts
+/// it back after a reconnect to restore the player without logging in
+/// again.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Sessions {
+    tokens: BTreeMap<String, PlayerId>,
+}
+
+impl Sessions {
+    /// Issue a fresh token for `player`; `entropy` supplies its
+    /// randomness.
+    pub fn open(&mut self, player: PlayerId, mut entropy: impl FnMut() -> u64) -> String {
+        let mut bytes = [0; 16];
+        bytes[..8].copy_from_slice(&entropy().to_le_bytes());
+        bytes[8..].copy_from_slice(&entropy().to_le_bytes());
+        let token = to_hex(&bytes);
+        self.tokens.insert(token.clone(), player);
+        token
+    }
+
+    /// The player a presented token belongs to.
+    pub fn resume(&self, token: &str) -> Option<PlayerId> {
+        self.tokens.get(token).copied()
+    }
+
+    pub fn close(&mut self, token: &str) {
+        self.tokens.remove(token);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::CellState;
+
+    #[test]
+    fn reconnecting_clients_catch_up() {
+        let mut server = QuantumGrid::new(40, 40, 300, 42, "observer");
+        server.reveal_cell(0, 0);
+        let mut client = server.snapshot();
+        let seen = server.chunks.revision();
+        assert_eq!(
+            Resync::since(&server, seen),
+            Resync::UpToDate { revision: seen }
+        );
+
+        // Missed moves arrive as the changed chunks only.
+        let far = server
+            .cells
+            .iter()
+            .rev()
+            .find(|c| matches!(c.state, CellState::Superposition { .. }))
+            .unwrap();
+        let (x, y) = (far.x, far.y);
+        server.entanglement.pairs.clear();
+        server.reveal_cell(x, y);
+        let resync = Resync::since(&server, seen);
+        let Resync::Diff { ref cells, .. } = resync else {
+            panic!("expected a diff, got {resync:?}");
+        };
+        assert!(cells.len() < server.cells.len() / 2);
+        assert_eq!(resync.apply(&mut client), Ok(server.chunks.revision()));
+        assert_eq!(client, server.snapshot());
+
+        // A client from before a server restart gets everything.
+        let restarted = Resync::since(&server, u64::MAX);
+        assert!(matches!(restarted, Resync::Full { .. }));
+        let mut stale = QuantumGrid::new(40, 40, 300, 42, "observer").snapshot();
+        restarted.apply(&mut stale).unwrap();
+        assert_eq!(stale.cells.len(), client.cells.len());
+        assert_eq!(stale.mines_remaining, client.mines_remaining);
+
+        // A diff pointing off the board changes nothing.
+        let before = client.clone();
+        let mut stray = server.cells[0].clone();
+        stray.x = u32::MAX;
+        let forged = Resync::Diff {
+            revision: seen,
+            cells: vec![stray],
+            summary: Box::new(GridSnapshot {
+                cells: Vec::new(),
+                ..server.snapshot()
+            }),
+        };
+        assert_eq!(forged.apply(&mut client), Err(DecodeError::Corrupt));
+        assert_eq!(client, before);
+    }
+
+    #[test]
+    fn tokens_restore_the_player() {
+        let mut sessions = Sessions::default();
+        let token = sessions.open(7, || 0xABCD);
+        assert_eq!(token.len(), 32);
+        assert_eq!(sessions.resume(&token), Some(7));
+        sessions.close(&token);
+        assert_eq!(sessions.resume(&token), None);
+    }
+}
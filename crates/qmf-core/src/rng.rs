@@ -1,21 +1,40 @@
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// SplitMix64 — a fast, high-quality PRNG suitable for game logic.
 ///
 /// Deterministic: same seed → same sequence, enabling reproducible games
 /// and replay/sharing via seed.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SplitMix64 {
     state: u64,
+    /// Values drawn so far, for auditing replays.
+    #[cfg_attr(feature = "serde", serde(default))]
+    draws: u64,
 }
 
 impl SplitMix64 {
     pub fn new(seed: u64) -> Self {
-        Self { state: seed }
+        Self {
+            state: seed,
+            draws: 0,
+        }
+    }
+
+    /// Raw internal state, e.g. for state checksums.
+    pub fn state(&self) -> u64 {
+        self.state
+    }
+
+    /// How many values have been drawn since the generator was seeded.
+    pub fn draws(&self) -> u64 {
+        self.draws
     }
 
     /// Advance internal state and return next u64.
     pub fn next_u64(&mut self) -> u64 {
+        self.draws = self.draws.wrapping_add(1);
         self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
         let mut z = self.state;
         z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
@@ -42,6 +61,26 @@ impl SplitMix64 {
             }
         }
     }
+
+    /// A uniformly chosen item of `items`, drawing as
+    /// [`next_usize`](Self::next_usize) does; `None` (and no draw) when
+    /// `items` is empty.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> Option<&'a T> {
+        if items.is_empty() {
+            return None;
+        }
+        items.get(self.next_usize(items.len()))
+    }
+}
+
+/// A float in [0.0, 1.0) determined by `seed` and `keys` alone: the same
+/// whatever has been drawn from any generator before, so anything computed
+/// from it can be recomputed in any order.
+pub fn keyed_f64(seed: u64, keys: &[u64]) -> f64 {
+    let hash = keys
+        .iter()
+        .fold(seed, |hash, &key| SplitMix64::new(hash ^ key).next_u64());
+    SplitMix64::new(hash).next_f64()
 }
 
 #[cfg(test)]
@@ -77,6 +116,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn draws_are_counted() {
+        let mut rng = SplitMix64::new(7);
+        rng.next_f64();
+        rng.next_usize(10);
+        assert!(rng.draws() >= 2);
+        assert_eq!(SplitMix64::new(7).draws(), 0);
+    }
+
     #[test]
     fn different_seeds_diverge() {
         let mut a = SplitMix64::new(0);
@@ -85,4 +133,13 @@ mod tests {
         let same = (0..10).all(|_| a.next_u64() == b.next_u64());
         assert!(!same);
     }
+
+    #[test]
+    fn keyed_draws_ignore_draw_order() {
+        let a = keyed_f64(42, &[3, 0]);
+        assert_eq!(a, keyed_f64(42, &[3, 0]));
+        assert_ne!(a, keyed_f64(42, &[3, 1]));
+        assert_ne!(a, keyed_f64(42, &[0, 3]));
+        assert!((0.0..1.0).contains(&a));
+    }
 }
@@ -0,0 +1,123 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::prelude::*;
+
+/// Hooks a game variant uses to modify outcomes, so modes (lives, versus
+/// attacks, scenario triggers) are rules in a [`RuleSet`] rather than more
+/// flags on the grid. Every hook defaults to the standard game.
+pub trait RuleHooks {
+    /// Before the reveal of `(x, y)`, hinted at `probability`, is resolved.
+    /// Returning `false` refuses it and leaves the board untouched.
+    fn on_before_reveal(&mut self, _x: u32, _y: u32, _probability: f64) -> bool {
+        true
+    }
+
+    /// The player revealed the mine at `(x, y)`. Returning `true` absorbs
+    /// it: the mine is contained instead of going off.
+    fn on_mine_clicked(&mut self, _x: u32, _y: u32) -> bool {
+        false
+    }
+
+    /// A mine at `(x, y)` went off. Returning `true` spares the player: the
+    /// cell stays detonated but the game goes on.
+    fn on_detonation(&mut self, _x: u32, _y: u32) -> bool {
+        false
+    }
+
+    /// The player contained `(x, y)`; `correct` means it was a mine.
+    fn on_contain(&mut self, _x: u32, _y: u32, _correct: bool) {}
+}
+
+/// A built-in rule, carried in
+/// [`GridConfig::rules`](crate::config::GridConfig::rules) so replays and
+/// saves reproduce the variant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum Rule {
+    /// Each detonation spends one of `remaining` lives instead of ending
+    /// the game; the detonation after the last life ends it as usual.
+    Lives { remaining: u32 },
+}
+
+impl Rule {
+    fn lives(&self) -> Option<u32> {
+        match *self {
+            Self::Lives { remaining } => Some(remaining),
+        }
+    }
+}
+
+impl RuleHooks for Rule {
+    fn on_detonation(&mut self, _x: u32, _y: u32) -> bool {
+        match self {
+            Self::Lives { remaining } => {
+                let spared = *remaining > 0;
+                *remaining = remaining.saturating_sub(1);
+                spared
+            }
+        }
+    }
+}
+
+/// The rules in play, consulted in order: the first rule to refuse a reveal,
+/// absorb a clicked mine or spare a detonation decides it, and later rules never see that event.
+/// Every rule sees every containment.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RuleSet {
+    pub rules: Vec<Rule>,
+}
+
+impl RuleSet {
+    pub fn new(rules: Vec<Rule>) -> Self {
+        Self { rules }
+    }
+
+    /// Lives left under [`Rule::Lives`], if that rule is in play.
+    pub fn lives(&self) -> Option<u32> {
+        self.rules.iter().find_map(Rule::lives)
+    }
+}
+
+impl RuleHooks for RuleSet {
+    fn on_before_reveal(&mut self, x: u32, y: u32, probability: f64) -> bool {
+        self.rules
+            .iter_mut()
+            .all(|rule| rule.on_before_reveal(x, y, probability))
+    }
+
+    fn on_mine_clicked(&mut self, x: u32, y: u32) -> bool {
+        self.rules.iter_mut().any(|rule| rule.on_mine_clicked(x, y))
+    }
+
+    fn on_detonation(&mut self, x: u32, y: u32) -> bool {
+        self.rules.iter_mut().any(|rule| rule.on_detonation(x, y))
+    }
+
+    fn on_contain(&mut self, x: u32, y: u32, correct: bool) {
+        for rule in &mut self.rules {
+            rule.on_contain(x, y, correct);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_sparing_rule_wins() {
+        let mut rules = RuleSet::new(vec![
+            Rule::Lives { remaining: 1 },
+            Rule::Lives { remaining: 1 },
+        ]);
+        assert!(rules.on_detonation(0, 0));
+        assert_eq!(rules.lives(), Some(0));
+        assert_eq!(rules.rules[1], Rule::Lives { remaining: 1 });
+        assert!(rules.on_detonation(0, 0));
+        assert!(!rules.on_detonation(0, 0));
+        assert!(!RuleSet::default().on_detonation(0, 0));
+    }
+}
@@ -0,0 +1,276 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::action::Action;
+use crate::config::GridConfig;
+use crate::generator::square;
+use crate::grid::{CellState, QuantumGrid};
+use crate::ops::GridOps;
+use crate::prelude::*;
+use crate::progress::{Phase, ProgressSink};
+use crate::rng::SplitMix64;
+
+/// How a simulated player picks its next move. Once only mines can be
+/// left, every strategy contains them to finish the board.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Strategy {
+    /// Play what the revealed numbers prove, else reveal the unresolved
+    /// cell with the lowest displayed hint, trusting the board as a player
+    /// would.
+    #[default]
+    LowestHint,
+    /// Reveal a uniformly random unresolved cell.
+    Random,
+}
+
+impl Strategy {
+    /// The next action on `grid`, or `None` once nothing is left to play.
+    pub fn choose(self, grid: &impl GridOps, rng: &mut SplitMix64) -> Option<Action> {
+        let states = grid.states();
+        let width = grid.width();
+        let unresolved: Vec<(usize, f64)> = states
+            .iter()
+            .enumerate()
+            .filter_map(|(i, state)| match *state {
+                CellState::Superposition { probability } => Some((i, probability)),
+                _ => None,
+            })
+            .collect();
+        if let Some(&(index, _)) = unresolved.first() {
+            if unresolved.len() as u32 == grid.mines_remaining() {
+                let (x, y) = (index as u32 % width, index as u32 / width);
+                return Some(Action::Contain { x, y });
+            }
+        }
+        let index = match self {
+            Self::LowestHint => {
+                if let Some(action) = deduce(grid, &states) {
+                    return Some(action);
+                }
+                unresolved
+                    .iter()
+                    .min_by(|a, b| a.1.total_cmp(&b.1))
+                    .map(|&(i, _)| i)?
+            }
+            Self::Random => rng.pick(&unresolved)?.0,
+        };
+        let (x, y) = (index as u32 % width, index as u32 / width);
+        Some(Action::Reveal { x, y })
+    }
+}
+
+/// A move the revealed numbers prove: around a number whose mines are all
+/// accounted for, the other cells are safe; around one with exactly as
+/// many unresolved cells as missing mines, they are all mines.
+fn deduce(grid: &impl GridOps, states: &[CellState]) -> Option<Action> {
+    let (width, height) = (grid.width(), grid.height());
+    for (index, state) in states.iter().enumerate() {
+        let CellState::Revealed { adjacent_mines } = *state else {
+            continue;
+        };
+        let (x, y) = (index as u32 % width, index as u32 / width);
+        let mut known = 0;
+        let mut unknown = Vec::new();
+        for (nx, ny, neighbour) in square(width, height, x, y, 1) {
+            match states.get(neighbour) {
+                Some(CellState::Contained | CellState::Detonated) => known += 1,
+                Some(CellState::Superposition { .. }) => unknown.push((nx, ny)),
+                Some(CellState::Revealed { .. }) | None => {}
+            }
+        }
+        let Some(&(x, y)) = unknown.first() else {
+            continue;
+        };
+        if known == adjacent_mines {
+            return Some(Action::Reveal { x, y });
+        }
+        if known + unknown.len() as u8 == adjacent_mines && grid.containment_charges() > 0 {
+            return Some(Action::Contain { x, y });
+        }
+    }
+    None
+}
+
+/// One simulated move: the features of the state it was played from, the
+/// action, what it did, and how the game ended.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MoveRecord {
+    pub seed: u64,
+    /// Moves played before this one.
+    pub step: u32,
+    pub action: Action,
+    /// Displayed hint on the target cell.
+    pub hint: f64,
+    /// [`QuantumGrid::entropy`] before the move.
+    pub entropy: f64,
+    /// Share of the board resolved before the move.
+    pub progress: f64,
+    /// Cells the move resolved.
+    pub cleared: u32,
+    pub detonated: bool,
+    /// Whether the game this move belongs to was eventually won.
+    pub won: bool,
+}
+
+/// Play one game of `config` with `strategy` to the end.
+pub fn play(config: &GridConfig, strategy: Strategy) -> Vec<MoveRecord> {
+    let mut grid = QuantumGrid::from_config(config.clone());
+    let mut rng = SplitMix64::new(config.seed ^ 0x5E1F_F1A7);
+    let cells = grid.cells.len() as f64;
+    let mut records = Vec::new();
+    while !grid.game_over && !grid.won {
+        let Some(action) = strategy.choose(&grid, &mut rng) else {
+            break;
+        };
+        let cell = action.coords().and_then(|(x, y)| grid.cell(x, y));
+        let hint = match cell.map(|cell| &cell.state) {
+            Some(&CellState::Superposition { probability }) => probability,
+            _ => 0.0,
+        };
+        let resolved = grid.resolved_cells();
+        let entropy = grid.entropy();
+        if grid.apply(action).is_rejection() {
+            break;
+        }
+        records.push(MoveRecord {
+            seed: config.seed,
+            step: records.len() as u32,
+            action,
+            hint,
+            entropy,
+            progress: f64::from(resolved) / cells,
+            cleared: grid.resolved_cells().saturating_sub(resolved),
+            detonated: grid.game_over,
+            won: false,
+        });
+    }
+    for record in &mut records {
+        record.won = grid.won;
+    }
+    records
+}
+
+/// Play `games` games of `config`, seeded `config.seed`, `config.seed + 1`,
+/// …, and return every move in seed order. With the `rayon` feature games
+/// run in parallel; the output is the same either way.
+pub fn batch(config: &GridConfig, strategy: Strategy, games: u32) -> Vec<MoveRecord> {
+    batch_with(config, strategy, games, &mut ())
+}
+
+/// [`batch`], reporting the share of games played to `sink`. With the
+/// `rayon` feature games are played a thread pool's worth at a time, with
+/// a report after each round.
+pub fn batch_with(
+    config: &GridConfig,
+    strategy: Strategy,
+    games: u32,
+    sink: &mut impl ProgressSink,
+) -> Vec<MoveRecord> {
+    let game = |offset: u32| {
+        play(
+            &GridConfig {
+                seed: config.seed.wrapping_add(u64::from(offset)),
+                ..config.clone()
+            },
+            strategy,
+        )
+    };
+    #[cfg(feature = "rayon")]
+    let round = rayon::current_num_threads().max(1) as u32;
+    #[cfg(not(feature = "rayon"))]
+    let round = 1;
+    let mut records = Vec::new();
+    let mut played = 0;
+    while played < games {
+        let end = played.saturating_add(round).min(games);
+        #[cfg(feature = "rayon")]
+        {
+            use rayon::prelude::*;
+            let mut moves: Vec<MoveRecord> =
+                (played..end).into_par_iter().flat_map_iter(game).collect();
+            records.append(&mut moves);
+        }
+        #[cfg(not(feature = "rayon"))]
+        records.extend((played..end).flat_map(game));
+        played = end;
+        sink.on_progress(f64::from(played) / f64::from(games), Phase::SelfPlay);
+    }
+    records
+}
+
+/// Write `records` as CSV with a header row, one move per line, for
+/// balancing notebooks and training pipelines.
+#[cfg(feature = "std")]
+pub fn write_csv<W: std::io::Write>(records: &[MoveRecord], mut out: W) -> std::io::Result<()> {
+    writeln!(
+        out,
+        "seed,step,action,x,y,hint,entropy,progress,cleared,detonated,won"
+    )?;
+    for record in records {
+        let (kind, x, y) = match record.action {
+            Action::Reveal { x, y } => ("reveal", x, y),
+            Action::Contain { x, y } => ("contain", x, y),
+            other => {
+                let (x, y) = other.coords().unwrap_or_default();
+                ("other", x, y)
+            }
+        };
+        writeln!(
+            out,
+            "{},{},{kind},{x},{y},{},{},{},{},{},{}",
+            record.seed,
+            record.step,
+            record.hint,
+            record.entropy,
+            record.progress,
+            record.cleared,
+            record.detonated,
+            record.won,
+        )?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn self_play_runs_games_to_the_end() {
+        let config = GridConfig::new(8, 8, 10, 42, "observer");
+        let records = batch(&config, Strategy::LowestHint, 4);
+        let seeds: Vec<u64> = records.iter().map(|r| r.seed).collect();
+        assert!(seeds.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(seeds.first(), Some(&42));
+        for seed in 42..46 {
+            let game: Vec<&MoveRecord> = records.iter().filter(|r| r.seed == seed).collect();
+            let last = game.last().unwrap();
+            // Every game ends in a detonation or a win.
+            assert!(last.detonated || last.won);
+            assert!(game.iter().all(|r| r.won == last.won));
+        }
+        assert_eq!(records, batch(&config, Strategy::LowestHint, 4));
+
+        let mut reports = Vec::new();
+        let reported = batch_with(&config, Strategy::LowestHint, 4, &mut |f, _| {
+            reports.push(f)
+        });
+        assert_eq!(reported, records);
+        assert!(reports.windows(2).all(|w| w[0] < w[1]));
+        assert_eq!(reports.last(), Some(&1.0));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn csv_has_a_row_per_move() {
+        let records = play(&GridConfig::new(8, 8, 10, 42, "observer"), Strategy::Random);
+        let mut out = Vec::new();
+        write_csv(&records, &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text.lines().count(), records.len() + 1);
+        assert!(text.lines().nth(1).unwrap().starts_with("42,0,reveal,"));
+    }
+}
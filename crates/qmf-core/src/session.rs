@@ -0,0 +1,196 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::config::{GridConfig, DIFFICULTIES};
+use crate::grid::QuantumGrid;
+use crate::prelude::*;
+
+/// Mine densities stepped through within each difficulty, easiest first.
+pub const DENSITY_STEPS: [f64; 5] = [0.10, 0.12, 0.14, 0.16, 0.18];
+/// Consecutive wins before the recommendation gets harder.
+pub const PROMOTE_AFTER: u32 = 2;
+/// Consecutive losses before the recommendation gets easier.
+pub const DEMOTE_AFTER: u32 = 2;
+/// Share of the board a loss must clear to count as a near miss, which
+/// keeps the current level instead of counting toward a demotion.
+pub const NEAR_MISS: f64 = 0.9;
+
+/// The facts about a finished game the [`SessionManager`] learns from.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct GameStats {
+    pub won: bool,
+    pub width: u32,
+    pub height: u32,
+    pub difficulty: String,
+    pub mine_count: u32,
+    /// Share of the board resolved.
+    pub progress: f64,
+    pub elapsed_ms: u64,
+    /// Time of each of the config's splits, `None` where not reached.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub splits: Vec<Option<u64>>,
+}
+
+impl GameStats {
+    pub fn from_grid(grid: &QuantumGrid) -> Self {
+        Self {
+            won: grid.won,
+            width: grid.width,
+            height: grid.height,
+            difficulty: grid.config.difficulty.clone(),
+            mine_count: grid.mine_count,
+            progress: f64::from(grid.resolved_cells()) / grid.cells.len().max(1) as f64,
+            elapsed_ms: grid.timing_stats().elapsed_ms,
+            splits: grid.splits.times(),
+        }
+    }
+}
+
+/// Adaptive difficulty across a player's games. Levels walk
+/// [`DENSITY_STEPS`] within each of the tuner's difficulties, one level at
+/// a time, and only after a streak ([`PROMOTE_AFTER`], [`DEMOTE_AFTER`]),
+/// so a single lucky or unlucky game never swings the next board. Saved in
+/// the [`PlayerProfile`](crate::profile::PlayerProfile).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SessionManager {
+    pub level: u32,
+    /// Wins (positive) or losses (negative) in a row at this level, held
+    /// at [`PROMOTE_AFTER`] or [`DEMOTE_AFTER`] at either end of the
+    /// levels.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub streak: i32,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub games: u32,
+    /// Board size of the last game, kept for the next one.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub board: Option<(u32, u32)>,
+}
+
+impl SessionManager {
+    /// The hardest level.
+    pub fn max_level() -> u32 {
+        (DIFFICULTIES.len() * DENSITY_STEPS.len()) as u32 - 1
+    }
+
+    /// Learn from a finished game.
+    pub fn ingest(&mut self, stats: &GameStats) {
+        self.games = self.games.saturating_add(1);
+        self.board = Some((stats.width, stats.height));
+        if stats.won {
+            self.streak = self
+                .streak
+                .max(0)
+                .saturating_add(1)
+                .min(PROMOTE_AFTER as i32);
+        } else if stats.progress < NEAR_MISS {
+            self.streak = self
+                .streak
+                .min(0)
+                .saturating_sub(1)
+                .max(-(DEMOTE_AFTER as i32));
+        }
+        if self.streak >= PROMOTE_AFTER as i32 && self.level < Self::max_level() {
+            self.level += 1;
+            self.streak = 0;
+        } else if self.streak <= -(DEMOTE_AFTER as i32) && self.level > 0 {
+            self.level -= 1;
+            self.streak = 0;
+        }
+    }
+
+    /// Difficulty label and mine density for the current level.
+    pub fn params(&self) -> (&'static str, f64) {
+        let level = self.level.min(Self::max_level()) as usize;
+        let ([.., hardest], [.., densest]) = (DIFFICULTIES, DENSITY_STEPS);
+        (
+            DIFFICULTIES
+                .get(level / DENSITY_STEPS.len())
+                .copied()
+                .unwrap_or(hardest),
+            DENSITY_STEPS
+                .get(level % DENSITY_STEPS.len())
+                .copied()
+                .unwrap_or(densest),
+        )
+    }
+
+    /// Config for the next game, on the last game's board size (9×9 before
+    /// any game, or if that size does not fit a board).
+    pub fn recommend(&self, seed: u64) -> GridConfig {
+        let ((width, height), cells) = self
+            .board
+            .and_then(|(width, height)| Some(((width, height), width.checked_mul(height)?)))
+            .unwrap_or(((9, 9), 81));
+        let (difficulty, density) = self.params();
+        let mines = (density * f64::from(cells)).round().max(1.0) as u32;
+        GridConfig::new(width, height, mines, seed, difficulty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(won: bool, progress: f64) -> GameStats {
+        GameStats {
+            won,
+            width: 16,
+            height: 16,
+            difficulty: "observer".into(),
+            mine_count: 40,
+            progress,
+            elapsed_ms: 60_000,
+            splits: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn steps_one_level_after_a_streak() {
+        let mut session = SessionManager::default();
+        session.ingest(&game(true, 1.0));
+        assert_eq!(session.level, 0);
+        session.ingest(&game(true, 1.0));
+        assert_eq!(session.level, 1);
+
+        // One loss does not undo a promotion, and near misses don't count.
+        session.ingest(&game(false, 0.3));
+        session.ingest(&game(false, 0.95));
+        assert_eq!(session.level, 1);
+        session.ingest(&game(false, 0.3));
+        assert_eq!(session.level, 0);
+        session.ingest(&game(false, 0.3));
+        session.ingest(&game(false, 0.3));
+        assert_eq!(session.level, 0);
+
+        let config = session.recommend(7);
+        assert_eq!((config.width, config.height, config.seed), (16, 16, 7));
+        assert_eq!(config.difficulty, "observer");
+
+        session.level = SessionManager::max_level();
+        assert_eq!(session.params(), ("theorist", 0.18));
+    }
+
+    #[test]
+    fn streaks_stay_bounded_at_the_ends() {
+        let mut session = SessionManager::default();
+        for _ in 0..10 {
+            session.ingest(&game(false, 0.0));
+        }
+        assert_eq!((session.level, session.streak), (0, -(DEMOTE_AFTER as i32)));
+        // One win turns it around as usual.
+        session.ingest(&game(true, 1.0));
+        assert_eq!(session.streak, 1);
+
+        session.streak = i32::MAX;
+        session.level = SessionManager::max_level();
+        session.ingest(&game(true, 1.0));
+        assert_eq!(session.streak, PROMOTE_AFTER as i32);
+
+        // A board too big to count falls back to the default size.
+        session.board = Some((u32::MAX, 2));
+        let config = session.recommend(1);
+        assert_eq!((config.width, config.height), (9, 9));
+    }
+}
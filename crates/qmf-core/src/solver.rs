@@ -0,0 +1,1126 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::entanglement::Correlation;
+use crate::generator::neighbors;
+use crate::grid::CellState;
+#[cfg(doc)]
+use crate::grid::QuantumGrid;
+use crate::ops::GridOps;
+use crate::prelude::*;
+use crate::progress::{Phase, ProgressSink};
+use crate::rng::SplitMix64;
+use crate::telemetry;
+
+/// Why a [`ProofStep`] holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum Reason {
+    /// The number at `(x, y)` already touches all of its mines, so its
+    /// other unknown neighbours are safe.
+    Satisfied { x: u32, y: u32, adjacent_mines: u8 },
+    /// The number at `(x, y)` is missing as many mines as it has unknown
+    /// neighbours, so they are all mines.
+    Saturated { x: u32, y: u32, adjacent_mines: u8 },
+    /// Every unknown neighbour of the number at `(x, y)` also borders the
+    /// number at `(other_x, other_y)`, so the other number's remaining
+    /// neighbours hold exactly `mines` mines.
+    Subset {
+        x: u32,
+        y: u32,
+        other_x: u32,
+        other_y: u32,
+        mines: u8,
+    },
+    /// `(x, y)` is settled (a mine with `mine`) and Bell-linked to
+    /// `(partner_x, partner_y)`, which the link's `correlation` decides.
+    Linked {
+        x: u32,
+        y: u32,
+        mine: bool,
+        partner_x: u32,
+        partner_y: u32,
+        correlation: Correlation,
+    },
+    /// The anti-correlated Bell pair `(x, y)`–`(partner_x, partner_y)`
+    /// holds one mine and both border the number at
+    /// `(number_x, number_y)`, whose other unknown neighbours hold exactly
+    /// `mines` mines.
+    BellSubset {
+        x: u32,
+        y: u32,
+        partner_x: u32,
+        partner_y: u32,
+        number_x: u32,
+        number_y: u32,
+        mines: u8,
+    },
+}
+
+/// One deduction: `cells` are proven safe (or mines, with `mine`) by the
+/// numbers at `premises`, given the earlier steps in `uses`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ProofStep {
+    pub reason: Reason,
+    /// Revealed numbers (and settled Bell-linked cells) the step reads, to
+    /// highlight as its premises.
+    pub premises: Vec<(u32, u32)>,
+    /// Cells the step proves.
+    pub cells: Vec<(u32, u32)>,
+    pub mine: bool,
+    /// Indices of earlier steps in the same [`Explanation`] it relies on.
+    pub uses: Vec<usize>,
+    /// The step in words, for tutorials and post-mortems.
+    pub text: String,
+}
+
+/// Why the cell at `(x, y)` must be safe (or a mine): the chain of steps
+/// that proves it, in order, the last one proving the cell itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Explanation {
+    pub x: u32,
+    pub y: u32,
+    pub mine: bool,
+    pub steps: Vec<ProofStep>,
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Satisfied {
+                x,
+                y,
+                adjacent_mines,
+            } => write!(
+                f,
+                "the {adjacent_mines} at ({x}, {y}) already touches all its mines"
+            ),
+            Self::Saturated {
+                x,
+                y,
+                adjacent_mines,
+            } => write!(
+                f,
+                "the {adjacent_mines} at ({x}, {y}) needs every cell it has left"
+            ),
+            Self::Subset {
+                x,
+                y,
+                other_x,
+                other_y,
+                mines,
+            } => write!(
+                f,
+                "the unknowns around ({x}, {y}) all border ({other_x}, {other_y}), \
+                 leaving {mines} mine(s) for the rest"
+            ),
+            Self::Linked {
+                x,
+                y,
+                mine,
+                partner_x,
+                partner_y,
+                correlation,
+            } => write!(
+                f,
+                "({x}, {y}) is {} and {} with ({partner_x}, {partner_y})",
+                if mine { "a mine" } else { "safe" },
+                match correlation {
+                    Correlation::Anti => "anti-correlated",
+                    Correlation::Positive => "correlated",
+                }
+            ),
+            Self::BellSubset {
+                x,
+                y,
+                partner_x,
+                partner_y,
+                number_x,
+                number_y,
+                mines,
+            } => write!(
+                f,
+                "the Bell pair ({x}, {y})–({partner_x}, {partner_y}) holds one mine and \
+                 borders ({number_x}, {number_y}), leaving {mines} mine(s) for the rest"
+            ),
+        }
+    }
+}
+
+/// What the revealed numbers say about the unknown neighbours of one of
+/// them, or a Bell link about its ends.
+struct Constraint {
+    number: usize,
+    /// For a link, the other end and the correlation. `number` is then the
+    /// settled end, or one of two unknown ones.
+    link: Option<(usize, Correlation)>,
+    unknown: BTreeSet<usize>,
+    /// Mines still missing among `unknown`.
+    mines: u8,
+    /// Steps whose conclusions were needed to get here.
+    uses: BTreeSet<usize>,
+}
+
+/// Forward-chaining deduction from what a player can see: revealed
+/// numbers, resolved mines and Bell links, which mine placement and
+/// swapping keep true. A teleport onto a Bell-linked cell can still break
+/// its link.
+struct Deducer {
+    width: u32,
+    height: u32,
+    states: Vec<CellState>,
+    links: Vec<(usize, usize, Correlation)>,
+    /// Cells proven so far, with whether they are mines and the step.
+    known: BTreeMap<usize, (bool, usize)>,
+    steps: Vec<ProofStep>,
+}
+
+impl Deducer {
+    fn new(grid: &impl GridOps) -> Self {
+        let states = grid.states();
+        let links = grid
+            .bell_links()
+            .into_iter()
+            .filter(|&(a, b, _)| a != b && a.max(b) < states.len())
+            .collect();
+        Self {
+            width: grid.width(),
+            height: grid.height(),
+            states,
+            links,
+            known: BTreeMap::new(),
+            steps: Vec::new(),
+        }
+    }
+
+    fn coords(&self, index: usize) -> (u32, u32) {
+        (index as u32 % self.width, index as u32 / self.width)
+    }
+
+    fn constraint(&self, number: usize, adjacent_mines: u8) -> Option<Constraint> {
+        let mut unknown = BTreeSet::new();
+        let mut uses = BTreeSet::new();
+        let mut found = 0_u8;
+        for neighbor in neighbors(self.width, self.height, number) {
+            match (self.states.get(neighbor), self.known.get(&neighbor)) {
+                (Some(CellState::Contained | CellState::Detonated), _) => found += 1,
+                (Some(CellState::Superposition { .. }), Some(&(mine, step))) => {
+                    found += u8::from(mine);
+                    uses.insert(step);
+                }
+                (Some(CellState::Superposition { .. }), None) => {
+                    unknown.insert(neighbor);
+                }
+                (Some(CellState::Revealed { .. }) | None, _) => {}
+            }
+        }
+        let mines = adjacent_mines.checked_sub(found)?;
+        (!unknown.is_empty() && usize::from(mines) <= unknown.len()).then_some(Constraint {
+            number,
+            link: None,
+            unknown,
+            mines,
+            uses,
+        })
+    }
+
+    /// What a Bell link says: a settled end decides the other, and an
+    /// anti-correlated link between two unknowns holds one mine.
+    fn link_constraint(&self, a: usize, b: usize, correlation: Correlation) -> Option<Constraint> {
+        let settled = |cell: usize| match (self.states.get(cell)?, self.known.get(&cell)) {
+            (CellState::Contained | CellState::Detonated, _) => Some((true, None)),
+            (CellState::Revealed { .. }, _) => Some((false, None)),
+            (CellState::Superposition { .. }, Some(&(mine, step))) => Some((mine, Some(step))),
+            (CellState::Superposition { .. }, None) => None,
+        };
+        let decided = |end: usize, other: usize, (mine, step): (bool, Option<usize>)| Constraint {
+            number: end,
+            link: Some((other, correlation)),
+            unknown: BTreeSet::from([other]),
+            mines: u8::from(correlation.predict(mine)),
+            uses: step.into_iter().collect(),
+        };
+        match (settled(a), settled(b)) {
+            (Some(_), Some(_)) => None,
+            (Some(end), None) => Some(decided(a, b, end)),
+            (None, Some(end)) => Some(decided(b, a, end)),
+            (None, None) => (correlation == Correlation::Anti).then(|| Constraint {
+                number: a,
+                link: Some((b, correlation)),
+                unknown: BTreeSet::from([a, b]),
+                mines: 1,
+                uses: BTreeSet::new(),
+            }),
+        }
+    }
+
+    fn constraints(&self) -> Vec<Constraint> {
+        let numbers = self
+            .states
+            .iter()
+            .enumerate()
+            .filter_map(|(index, state)| match *state {
+                CellState::Revealed { adjacent_mines } => self.constraint(index, adjacent_mines),
+                _ => None,
+            });
+        let links = self
+            .links
+            .iter()
+            .filter_map(|&(a, b, correlation)| self.link_constraint(a, b, correlation));
+        numbers.chain(links).collect()
+    }
+
+    fn prove(
+        &mut self,
+        cells: impl IntoIterator<Item = usize>,
+        mine: bool,
+        reason: Reason,
+        premises: &[usize],
+        uses: &BTreeSet<usize>,
+    ) -> bool {
+        let cells: Vec<usize> = cells
+            .into_iter()
+            .filter(|cell| !self.known.contains_key(cell))
+            .collect();
+        if cells.is_empty() {
+            return false;
+        }
+        let step = self.steps.len();
+        let verdict = match (mine, cells.len()) {
+            (false, 1) => "is safe",
+            (false, _) => "are safe",
+            (true, 1) => "is a mine",
+            (true, _) => "are mines",
+        };
+        let coords: Vec<(u32, u32)> = cells.iter().map(|&cell| self.coords(cell)).collect();
+        let listed: Vec<String> = coords.iter().map(|(x, y)| format!("({x}, {y})")).collect();
+        self.steps.push(ProofStep {
+            reason,
+            premises: premises.iter().map(|&cell| self.coords(cell)).collect(),
+            cells: coords,
+            mine,
+            uses: uses.iter().copied().collect(),
+            text: format!("{reason}, so {} {verdict}", listed.join(", ")),
+        });
+        for cell in cells {
+            self.known.insert(cell, (mine, step));
+        }
+        true
+    }
+
+    /// Apply every single-number rule, else the first subset rule that
+    /// proves something. Returns whether anything new was proven.
+    fn round(&mut self) -> bool {
+        let constraints = self.constraints();
+        let mut progress = false;
+        for c in &constraints {
+            if let Some((partner, correlation)) = c.link {
+                if c.unknown.len() == 1 {
+                    let mine = c.mines == 1;
+                    let ((x, y), (partner_x, partner_y)) =
+                        (self.coords(c.number), self.coords(partner));
+                    let reason = Reason::Linked {
+                        x,
+                        y,
+                        // Either correlation is its own inverse.
+                        mine: correlation.predict(mine),
+                        partner_x,
+                        partner_y,
+                        correlation,
+                    };
+                    progress |= self.prove([partner], mine, reason, &[c.number], &c.uses);
+                }
+                continue;
+            }
+            let Some(&CellState::Revealed { adjacent_mines }) = self.states.get(c.number) else {
+                continue;
+            };
+            let (x, y) = self.coords(c.number);
+            if c.mines == 0 {
+                let reason = Reason::Satisfied {
+                    x,
+                    y,
+                    adjacent_mines,
+                };
+                progress |= self.prove(c.unknown.clone(), false, reason, &[c.number], &c.uses);
+            } else if usize::from(c.mines) == c.unknown.len() {
+                let reason = Reason::Saturated {
+                    x,
+                    y,
+                    adjacent_mines,
+                };
+                progress |= self.prove(c.unknown.clone(), true, reason, &[c.number], &c.uses);
+            }
+        }
+        if progress {
+            return true;
+        }
+        for a in &constraints {
+            for b in &constraints {
+                if b.link.is_some()
+                    || a.unknown.len() >= b.unknown.len()
+                    || !a.unknown.is_subset(&b.unknown)
+                {
+                    continue;
+                }
+                let Some(mines) = b.mines.checked_sub(a.mines) else {
+                    continue;
+                };
+                let rest: Vec<usize> = b.unknown.difference(&a.unknown).copied().collect();
+                if mines != 0 && usize::from(mines) != rest.len() {
+                    continue;
+                }
+                let ((x, y), (other_x, other_y)) = (self.coords(a.number), self.coords(b.number));
+                let (reason, premises) = match a.link {
+                    Some((partner, _)) => {
+                        let (partner_x, partner_y) = self.coords(partner);
+                        let reason = Reason::BellSubset {
+                            x,
+                            y,
+                            partner_x,
+                            partner_y,
+                            number_x: other_x,
+                            number_y: other_y,
+                            mines,
+                        };
+                        (reason, vec![b.number])
+                    }
+                    None => {
+                        let reason = Reason::Subset {
+                            x,
+                            y,
+                            other_x,
+                            other_y,
+                            mines,
+                        };
+                        (reason, vec![a.number, b.number])
+                    }
+                };
+                let uses = a.uses.union(&b.uses).copied().collect();
+                if self.prove(rest, mines != 0, reason, &premises, &uses) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Deduce until `target` is proven or nothing more follows.
+    fn run(&mut self, target: usize) {
+        while !self.known.contains_key(&target) && self.round() {}
+    }
+
+    /// The steps `target`'s proof needs, renumbered in order.
+    fn chain(&self, target: usize) -> Option<Explanation> {
+        let &(mine, last) = self.known.get(&target)?;
+        let mut needed = BTreeSet::from([last]);
+        let mut pending = vec![last];
+        while let Some(step) = pending.pop() {
+            let uses = self.steps.get(step).map(|step| &step.uses);
+            for &used in uses.into_iter().flatten() {
+                if needed.insert(used) {
+                    pending.push(used);
+                }
+            }
+        }
+        let renumber: BTreeMap<usize, usize> = needed
+            .iter()
+            .enumerate()
+            .map(|(new, &old)| (old, new))
+            .collect();
+        let steps = needed
+            .iter()
+            .filter_map(|&old| {
+                let mut step = self.steps.get(old)?.clone();
+                step.uses = step
+                    .uses
+                    .iter()
+                    .filter_map(|used| renumber.get(used).copied())
+                    .collect();
+                Some(step)
+            })
+            .collect();
+        let (x, y) = self.coords(target);
+        Some(Explanation { x, y, mine, steps })
+    }
+}
+
+/// Prove whether the unresolved cell at `index` is safe or a mine from the
+/// revealed numbers alone, or `None` when they do not decide it.
+pub fn explain(grid: &impl GridOps, index: usize) -> Option<Explanation> {
+    let mut deducer = Deducer::new(grid);
+    if !matches!(deducer.states.get(index)?, CellState::Superposition { .. }) {
+        return None;
+    }
+    deducer.run(index);
+    deducer.chain(index)
+}
+
+/// Work limits for [`probabilities`]. Work is counted in search nodes
+/// rather than timed, so results are the same on every platform; the
+/// default keeps a mid-game 30×16 expert board well under 50 ms in wasm.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SolveBudget {
+    /// Search nodes shared out between the frontier's components.
+    pub max_nodes: u64,
+    /// Components with more cells than this go straight to sampling.
+    pub max_exact_cells: usize,
+    /// Solutions drawn for each sampled component. Draws are not uniform
+    /// over the component's arrangements (see [`probabilities`]).
+    pub samples: u32,
+}
+
+impl Default for SolveBudget {
+    fn default() -> Self {
+        Self {
+            max_nodes: 400_000,
+            max_exact_cells: 48,
+            samples: 64,
+        }
+    }
+}
+
+/// Each cell's chance of being a mine given everything the player can see,
+/// and how it was worked out.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MineProbabilities {
+    /// Row by row; resolved cells read `0.0` or `1.0` as in
+    /// [`QuantumGrid::get_probability_cloud`].
+    pub cells: Vec<f64>,
+    /// Independent groups of frontier cells solved separately.
+    pub components: u32,
+    /// Components that ran out of budget and were estimated by sampling.
+    pub sampled: u32,
+}
+
+impl MineProbabilities {
+    /// Whether every component was enumerated exactly.
+    pub fn is_exact(&self) -> bool {
+        self.sampled == 0
+    }
+}
+
+/// Mine counts for one component: `weights[k]` is the (relative) number of
+/// arrangements with `k` mines and `mines[k][i]` how many of those put a
+/// mine on its `i`th cell.
+struct Tally {
+    cells: Vec<usize>,
+    weights: Vec<f64>,
+    mines: Vec<Vec<f64>>,
+}
+
+impl Tally {
+    fn new(cells: Vec<usize>) -> Self {
+        let n = cells.len();
+        Self {
+            cells,
+            weights: vec![0.0; n + 1],
+            mines: vec![vec![0.0; n]; n + 1],
+        }
+    }
+
+    fn record(&mut self, assignment: &[bool]) {
+        let k = assignment.iter().filter(|&&mine| mine).count();
+        let (Some(weight), Some(mines)) = (self.weights.get_mut(k), self.mines.get_mut(k)) else {
+            return;
+        };
+        *weight += 1.0;
+        for (count, &mine) in mines.iter_mut().zip(assignment) {
+            *count += f64::from(u8::from(mine));
+        }
+    }
+
+    /// Scale to a total weight of 1 so components multiply safely.
+    fn normalize(&mut self) -> bool {
+        let total: f64 = self.weights.iter().sum();
+        if total == 0.0 {
+            return false;
+        }
+        self.weights.iter_mut().for_each(|w| *w /= total);
+        self.mines.iter_mut().flatten().for_each(|m| *m /= total);
+        true
+    }
+}
+
+/// A component's constraints over its own cell indices.
+struct Component {
+    cells: Vec<usize>,
+    /// `(cells, mines)` per constraint.
+    rules: Vec<(Vec<usize>, u8)>,
+    /// Constraints each cell takes part in.
+    rules_of: Vec<Vec<usize>>,
+}
+
+/// State of one search over a [`Component`].
+struct Search<'t> {
+    assignment: Vec<bool>,
+    /// Mines placed so far per constraint.
+    placed: Vec<u8>,
+    /// Cells not yet assigned per constraint.
+    open: Vec<usize>,
+    nodes: u64,
+    tally: &'t mut Tally,
+}
+
+impl Component {
+    fn new(cells: Vec<usize>, constraints: &[&Constraint]) -> Self {
+        let local: BTreeMap<usize, usize> = cells
+            .iter()
+            .enumerate()
+            .map(|(i, &cell)| (cell, i))
+            .collect();
+        let rules: Vec<(Vec<usize>, u8)> = constraints
+            .iter()
+            .map(|c| {
+                let members = c.unknown.iter().filter_map(|cell| local.get(cell).copied());
+                (members.collect(), c.mines)
+            })
+            .collect();
+        let mut rules_of = vec![Vec::new(); cells.len()];
+        for (rule, (members, _)) in rules.iter().enumerate() {
+            for &cell in members {
+                if let Some(rules) = rules_of.get_mut(cell) {
+                    rules.push(rule);
+                }
+            }
+        }
+        Self {
+            cells,
+            rules,
+            rules_of,
+        }
+    }
+
+    /// Enumerate every arrangement into `tally`, within `nodes`. Returns
+    /// the nodes left, or `None` if they ran out.
+    fn enumerate(&self, tally: &mut Tally, nodes: u64) -> Option<u64> {
+        let mut search = self.start(tally, nodes);
+        self.descend(0, &mut search, None)?;
+        Some(search.nodes)
+    }
+
+    /// Draw one arrangement at random into `tally`, within `nodes`.
+    fn sample(&self, tally: &mut Tally, nodes: u64, rng: &mut SplitMix64) -> bool {
+        let mut search = self.start(tally, nodes);
+        self.descend(0, &mut search, Some(rng)) == Some(true)
+    }
+
+    fn start<'t>(&self, tally: &'t mut Tally, nodes: u64) -> Search<'t> {
+        Search {
+            assignment: vec![false; self.cells.len()],
+            placed: vec![0; self.rules.len()],
+            open: self.rules.iter().map(|(cells, _)| cells.len()).collect(),
+            nodes,
+            tally,
+        }
+    }
+
+    /// Depth-first over mine assignments, pruning on every constraint.
+    /// With `rng` values are tried in random order and the search stops at
+    /// the first arrangement (`Some(true)`), which weights each by its
+    /// branch rather than uniformly; `None` means out of nodes.
+    fn descend(
+        &self,
+        cell: usize,
+        search: &mut Search,
+        mut rng: Option<&mut SplitMix64>,
+    ) -> Option<bool> {
+        // Past the last cell: a full arrangement.
+        let Some(rules) = self.rules_of.get(cell) else {
+            search.tally.record(&search.assignment);
+            return Some(rng.is_some());
+        };
+        let first = rng.as_mut().is_some_and(|rng| rng.next_u64() & 1 == 1);
+        for value in [first, !first] {
+            search.nodes = search.nodes.checked_sub(1)?;
+            let fits = rules.iter().all(|&rule| {
+                match (
+                    search.placed.get(rule),
+                    search.open.get(rule),
+                    self.rules.get(rule),
+                ) {
+                    (Some(&placed), Some(&open), Some(&(_, mines))) => {
+                        let placed = placed + u8::from(value);
+                        placed <= mines && usize::from(placed) + open > usize::from(mines)
+                    }
+                    _ => false,
+                }
+            });
+            if !fits {
+                continue;
+            }
+            if let Some(slot) = search.assignment.get_mut(cell) {
+                *slot = value;
+            }
+            for &rule in rules {
+                if let (Some(placed), Some(open)) =
+                    (search.placed.get_mut(rule), search.open.get_mut(rule))
+                {
+                    *placed += u8::from(value);
+                    *open -= 1;
+                }
+            }
+            let found = self.descend(cell + 1, search, rng.as_deref_mut());
+            for &rule in rules {
+                if let (Some(placed), Some(open)) =
+                    (search.placed.get_mut(rule), search.open.get_mut(rule))
+                {
+                    *placed -= u8::from(value);
+                    *open += 1;
+                }
+            }
+            if found != Some(false) {
+                return found;
+            }
+        }
+        Some(false)
+    }
+}
+
+/// Split the frontier into groups of cells no constraint connects, so each
+/// can be solved on its own.
+fn components(constraints: &[Constraint]) -> Vec<(Vec<usize>, Vec<&Constraint>)> {
+    // Union-find over constraints, joined through shared cells.
+    let mut parent: Vec<usize> = (0..constraints.len()).collect();
+    fn root(parent: &mut [usize], mut i: usize) -> usize {
+        while let Some(&up) = parent.get(i).filter(|&&up| up != i) {
+            let grandparent = parent.get(up).copied().unwrap_or(up);
+            if let Some(slot) = parent.get_mut(i) {
+                *slot = grandparent;
+            }
+            i = grandparent;
+        }
+        i
+    }
+    let mut owner: BTreeMap<usize, usize> = BTreeMap::new();
+    for (i, c) in constraints.iter().enumerate() {
+        for &cell in &c.unknown {
+            if let Some(&other) = owner.get(&cell) {
+                let (a, b) = (root(&mut parent, i), root(&mut parent, other));
+                if let Some(slot) = parent.get_mut(a) {
+                    *slot = b;
+                }
+            } else {
+                owner.insert(cell, i);
+            }
+        }
+    }
+    let mut groups: BTreeMap<usize, Vec<&Constraint>> = BTreeMap::new();
+    for (i, c) in constraints.iter().enumerate() {
+        groups.entry(root(&mut parent, i)).or_default().push(c);
+    }
+    groups
+        .into_values()
+        .map(|group| {
+            // Cells in the order their numbers appear, so neighbouring
+            // cells are assigned close together and prune early.
+            let mut seen = BTreeSet::new();
+            let cells = group
+                .iter()
+                .flat_map(|c| c.unknown.iter().copied())
+                .filter(|&cell| seen.insert(cell))
+                .collect();
+            (cells, group)
+        })
+        .collect()
+}
+
+fn convolve(a: &[f64], b: &[f64]) -> Vec<f64> {
+    let mut out = vec![0.0; a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            if let Some(slot) = out.get_mut(i + j) {
+                *slot += x * y;
+            }
+        }
+    }
+    out
+}
+
+/// `ln C(n, k)`.
+fn ln_choose(n: usize, k: usize) -> f64 {
+    let k = k.min(n - k);
+    (1..=k).map(|i| ((n - k + i) as f64 / i as f64).ln()).sum()
+}
+
+/// Every cell's chance of being a mine given the revealed numbers and the
+/// total mine count. The frontier (unknown cells next to numbers) is split
+/// into independent components, each enumerated exactly within `budget`
+/// or, past it, estimated by sampling; unknown cells away from the numbers
+/// share what is left. Uses no ground truth, so it also works on
+/// [reconstructed](QuantumGrid::reconstruct) boards. Bell links constrain
+/// the frontier too: an anti-correlated pair holds one mine, and a settled
+/// end decides its partner. A positive pair with both ends unknown is
+/// left out, since it fixes no count.
+///
+/// A sample picks each cell's value by a coin flip and backtracks on a
+/// contradiction, so arrangements in thinly populated branches of the
+/// search are drawn more often than ones sharing a branch with many
+/// others. Sampled components are estimates skewed that way, but never
+/// contradict the numbers.
+pub fn probabilities(grid: &impl GridOps, budget: SolveBudget) -> MineProbabilities {
+    probabilities_with(grid, budget, &mut ())
+}
+
+/// [`probabilities`], reporting to `sink` as each frontier component is
+/// solved.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(level = "debug", skip(grid, sink))
+)]
+pub fn probabilities_with(
+    grid: &impl GridOps,
+    budget: SolveBudget,
+    sink: &mut impl ProgressSink,
+) -> MineProbabilities {
+    let deducer = Deducer::new(grid);
+    let constraints = deducer.constraints();
+    let groups = components(&constraints);
+    let count = groups.len();
+    let mut rng = SplitMix64::new(grid.seed() ^ 0x50_1E_D0);
+    let share = budget.max_nodes / groups.len().max(1) as u64;
+    let (mut sampled, mut solved) = (0, 0);
+    let mut tallies = Vec::new();
+    for (cells, group) in groups {
+        let component = Component::new(cells.clone(), &group);
+        let mut tally = Tally::new(cells.clone());
+        let exact = cells.len() <= budget.max_exact_cells
+            && component.enumerate(&mut tally, share).is_some();
+        if !exact {
+            sampled += 1;
+            tally = Tally::new(cells);
+            let per_sample = share / u64::from(budget.samples.max(1));
+            for _ in 0..budget.samples {
+                component.sample(&mut tally, per_sample, &mut rng);
+            }
+        }
+        if tally.normalize() {
+            tallies.push(tally);
+        }
+        solved += 1;
+        sink.on_progress(solved as f64 / count as f64, Phase::Solving);
+    }
+    if count == 0 {
+        sink.on_progress(1.0, Phase::Solving);
+    }
+
+    let states = deducer.states;
+    let mut cells: Vec<f64> = states
+        .iter()
+        .map(|state| match *state {
+            CellState::Superposition { probability } => probability,
+            CellState::Contained | CellState::Detonated => 1.0,
+            CellState::Revealed { .. } => 0.0,
+        })
+        .collect();
+    let frontier: BTreeSet<usize> = tallies.iter().flat_map(|t| t.cells.clone()).collect();
+    let interior: Vec<usize> = states
+        .iter()
+        .enumerate()
+        .filter(|&(i, state)| {
+            matches!(state, CellState::Superposition { .. }) && !frontier.contains(&i)
+        })
+        .map(|(i, _)| i)
+        .collect();
+    let resolved_mines = states
+        .iter()
+        .filter(|state| matches!(state, CellState::Contained | CellState::Detonated))
+        .count();
+    let left = (grid.mine_count() as usize).saturating_sub(resolved_mines);
+
+    // Weight of putting `n` of the remaining mines in the interior, scaled
+    // so the largest is 1.
+    let ln_interior: Vec<Option<f64>> = (0..=left)
+        .map(|n| (n <= interior.len()).then(|| ln_choose(interior.len(), n)))
+        .collect();
+    let peak = ln_interior
+        .iter()
+        .flatten()
+        .fold(f64::NEG_INFINITY, |a, &b| a.max(b));
+    let interior_weight = |frontier_mines: usize| {
+        left.checked_sub(frontier_mines)
+            .and_then(|n| ln_interior.get(n).copied().flatten())
+            .map_or(0.0, |ln| (ln - peak).exp())
+    };
+
+    let total = tallies
+        .iter()
+        .fold(vec![1.0], |acc, t| convolve(&acc, &t.weights));
+    let z: f64 = total
+        .iter()
+        .enumerate()
+        .map(|(k, &w)| w * interior_weight(k))
+        .sum();
+    if z > 0.0 {
+        for (c, tally) in tallies.iter().enumerate() {
+            let others = tallies
+                .iter()
+                .enumerate()
+                .filter(|&(other, _)| other != c)
+                .fold(vec![1.0], |acc, (_, t)| convolve(&acc, &t.weights));
+            // Weight of this component holding `k` mines, whatever the
+            // others and the interior hold.
+            let weight_of: Vec<f64> = (0..tally.weights.len())
+                .map(|k| {
+                    others
+                        .iter()
+                        .enumerate()
+                        .map(|(rest, &w)| w * interior_weight(k + rest))
+                        .sum()
+                })
+                .collect();
+            for (i, &cell) in tally.cells.iter().enumerate() {
+                let mines: f64 = tally
+                    .mines
+                    .iter()
+                    .zip(&weight_of)
+                    .map(|(mines, weight)| mines.get(i).copied().unwrap_or_default() * weight)
+                    .sum();
+                if let Some(p) = cells.get_mut(cell) {
+                    *p = (mines / z).clamp(0.0, 1.0);
+                }
+            }
+        }
+        let interior_mines: f64 = total
+            .iter()
+            .enumerate()
+            .map(|(k, &w)| w * interior_weight(k) * left.saturating_sub(k) as f64)
+            .sum();
+        let share = (interior_mines / z / interior.len() as f64).clamp(0.0, 1.0);
+        for &cell in &interior {
+            if let Some(p) = cells.get_mut(cell) {
+                *p = share;
+            }
+        }
+    } else {
+        // The mine count cannot be met (e.g. a mistyped reconstruction):
+        // fall back to each component on its own and the plain density.
+        for tally in &tallies {
+            for (i, &cell) in tally.cells.iter().enumerate() {
+                if let Some(p) = cells.get_mut(cell) {
+                    *p = tally
+                        .mines
+                        .iter()
+                        .filter_map(|m| m.get(i))
+                        .sum::<f64>()
+                        .clamp(0.0, 1.0);
+                }
+            }
+        }
+        let unknown = (interior.len() + frontier.len()).max(1);
+        let share = (left as f64 / unknown as f64).clamp(0.0, 1.0);
+        for &cell in &interior {
+            if let Some(p) = cells.get_mut(cell) {
+                *p = share;
+            }
+        }
+    }
+
+    telemetry::debug!(components = tallies.len(), sampled, "solved");
+    MineProbabilities {
+        cells,
+        components: tallies.len() as u32,
+        sampled,
+    }
+}
+
+/// Whether every move left is a guess at even odds or worse: the numbers
+/// and mine count prove no unresolved cell safe, and even the safest is at
+/// least as likely a mine as not. Cells proven to be mines do not count,
+/// since containing them reveals nothing. Only an exact solve can prove
+/// this, so a board that needs sampling is never a forced guess.
+pub fn is_forced_guess(grid: &impl GridOps) -> bool {
+    const EPSILON: f64 = 1e-9;
+    if grid.is_game_over() || grid.is_won() || !grid.mines_placed() {
+        return false;
+    }
+    let solved = probabilities(grid, SolveBudget::default());
+    if !solved.is_exact() {
+        return false;
+    }
+    solved
+        .cells
+        .iter()
+        .zip(grid.states())
+        .filter(|(_, state)| matches!(state, CellState::Superposition { .. }))
+        .map(|(&p, _)| p)
+        .filter(|&p| p < 1.0 - EPSILON)
+        .min_by(f64::total_cmp)
+        .is_some_and(|safest| safest >= 0.5 - EPSILON)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action::Action;
+    use crate::config::GridConfig;
+    use crate::entanglement::LinkType;
+    use crate::grid::QuantumGrid;
+    use crate::reconstruct::{ObservedCell, Seen};
+    use crate::selfplay::Strategy;
+
+    #[test]
+    fn chains_the_numbers_that_force_a_cell() {
+        //   ? ? ?
+        //   1 1 1
+        //   0 0 0
+        let seen = |x, y, adjacent_mines| ObservedCell {
+            x,
+            y,
+            seen: Seen::Revealed { adjacent_mines },
+        };
+        let cells = [
+            seen(0, 1, 1),
+            seen(1, 1, 1),
+            seen(2, 1, 1),
+            seen(0, 2, 0),
+            seen(1, 2, 0),
+            seen(2, 2, 0),
+        ];
+        let grid =
+            QuantumGrid::reconstruct(&cells, GridConfig::new(3, 3, 1, 0, "researcher")).unwrap();
+        let explanation = explain(&grid, 0).unwrap();
+        assert!(!explanation.mine);
+        let reasons: Vec<Reason> = explanation.steps.iter().map(|step| step.reason).collect();
+        assert!(matches!(
+            reasons[..],
+            [
+                Reason::Subset { mines: 0, .. },
+                Reason::Saturated { x: 2, y: 1, .. },
+                Reason::Satisfied { x: 0, y: 1, .. }
+            ]
+        ));
+        assert_eq!(explanation.steps[2].uses, vec![1]);
+        assert_eq!(explanation.steps[1].uses, vec![0]);
+        assert_eq!(
+            explanation.steps[2].text,
+            "the 1 at (0, 1) already touches all its mines, so (0, 0) is safe"
+        );
+
+        assert!(explain(&grid, 1).unwrap().mine);
+        assert_eq!(explain(&grid, 4), None);
+    }
+
+    #[test]
+    fn bell_links_take_part_in_explanations() {
+        //   ? ? ?
+        //   ? 1 ?    with (0, 0) and (1, 0) anti-correlated
+        let cells = [ObservedCell {
+            x: 1,
+            y: 1,
+            seen: Seen::Revealed { adjacent_mines: 1 },
+        }];
+        let config = GridConfig::new(3, 2, 1, 0, "researcher");
+        let mut grid = QuantumGrid::reconstruct(&cells, config).unwrap();
+        grid.entanglement.pairs.clear();
+        grid.entanglement
+            .add_correlated_pair(0, 1, 1.0, LinkType::BellState, Correlation::Anti);
+        let explanation = explain(&grid, 2).unwrap();
+        assert!(!explanation.mine);
+        assert!(matches!(
+            explanation.steps[..],
+            [ProofStep {
+                reason: Reason::BellSubset {
+                    x: 0,
+                    y: 0,
+                    partner_x: 1,
+                    partner_y: 0,
+                    number_x: 1,
+                    number_y: 1,
+                    mines: 0,
+                },
+                ..
+            }]
+        ));
+        let solved = probabilities(&grid, SolveBudget::default());
+        assert_eq!(solved.cells, [0.5, 0.5, 0.0, 0.0, 0.0, 0.0]);
+
+        // A settled end decides its partner outright.
+        grid.entanglement.pairs.clear();
+        grid.entanglement.add_correlated_pair(
+            4,
+            5,
+            1.0,
+            LinkType::BellState,
+            Correlation::Positive,
+        );
+        let explanation = explain(&grid, 5).unwrap();
+        assert!(!explanation.mine);
+        assert_eq!(
+            explanation.steps[0].text,
+            "(1, 1) is safe and correlated with (2, 1), so (2, 1) is safe"
+        );
+        assert_eq!(explain(&grid, 2), None);
+    }
+
+    #[test]
+    fn exact_probabilities_match_the_numbers() {
+        // ? 1 ? ? ? 1 ?  with 3 mines: one beside each 1, so the middle
+        // cell, away from both, must hold the third.
+        let one = |x| ObservedCell {
+            x,
+            y: 0,
+            seen: Seen::Revealed { adjacent_mines: 1 },
+        };
+        let config = GridConfig::new(7, 1, 3, 0, "researcher");
+        let grid = QuantumGrid::reconstruct(&[one(1), one(5)], config).unwrap();
+        let solved = probabilities(&grid, SolveBudget::default());
+        assert!(solved.is_exact());
+        assert_eq!(solved.components, 2);
+        assert_eq!(solved.cells, [0.5, 0.0, 0.5, 1.0, 0.5, 0.0, 0.5]);
+
+        let mut reports = Vec::new();
+        let reported = probabilities_with(&grid, SolveBudget::default(), &mut |f, phase| {
+            reports.push((f, phase))
+        });
+        assert_eq!(reported, solved);
+        assert_eq!(reports, [(0.5, Phase::Solving), (1.0, Phase::Solving)]);
+    }
+
+    #[test]
+    fn expert_boards_stay_within_budget() {
+        let mut grid = QuantumGrid::new(30, 16, 99, 7, "theorist");
+        grid.reveal_cell(15, 8);
+        while let Some(Action::Reveal { x, y }) = Strategy::LowestHint
+            .choose(&grid, &mut SplitMix64::new(0))
+            .filter(|_| grid.resolved_cells() < 200)
+        {
+            grid.reveal_cell(x, y);
+        }
+        assert!(!grid.game_over);
+        let solved = probabilities(&grid, SolveBudget::default());
+        assert!(solved.components > 1);
+        let unknown: f64 = solved
+            .cells
+            .iter()
+            .zip(&grid.cells)
+            .filter(|(_, cell)| matches!(cell.state, CellState::Superposition { .. }))
+            .map(|(p, _)| p)
+            .sum();
+        assert!(solved.is_exact());
+        assert!((unknown - f64::from(grid.mines_remaining())).abs() < 1e-6);
+
+        // Out of budget, every component is sampled instead, and samples
+        // still never contradict what the numbers prove.
+        let budget = SolveBudget {
+            max_exact_cells: 0,
+            ..SolveBudget::default()
+        };
+        let sampled = probabilities(&grid, budget);
+        assert_eq!(sampled.sampled, sampled.components);
+        for (index, (&exact, &estimate)) in solved.cells.iter().zip(&sampled.cells).enumerate() {
+            if (exact - 0.5).abs() > 0.5 - 1e-9 {
+                assert!((estimate - exact).abs() < 1e-9, "cell {index}");
+            }
+        }
+        assert_eq!(sampled, probabilities(&grid, budget));
+    }
+}
@@ -0,0 +1,80 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+#[cfg(not(feature = "std"))]
+use crate::float::Float;
+
+/// Cells one move must resolve to count as a [`SoundCue::BigCascade`].
+pub const BIG_CASCADE: u32 = 8;
+/// Displayed hint at or above which surviving a reveal is a near miss.
+pub const NEAR_MISS: f64 = 0.6;
+
+/// Semantic audio cue for a move, so every frontend plays the same sound
+/// for the same moment. Queued on the grid next to its
+/// [`GameEvent`](crate::events::GameEvent)s; intensities are in `[0, 1]`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum SoundCue {
+    /// A small resolution; `intensity` grows with the cells it opened.
+    SoftReveal {
+        intensity: f64,
+    },
+    /// At least [`BIG_CASCADE`] cells resolved at once; `intensity` is the
+    /// cascade's share of the board on a log scale.
+    BigCascade {
+        len: u32,
+        intensity: f64,
+    },
+    /// The player revealed a cell hinted at `probability` ≥ [`NEAR_MISS`]
+    /// and survived.
+    NearMissHighProbability {
+        probability: f64,
+    },
+    /// A mine went off; `intensity` is how much of the board was already
+    /// resolved, so late losses land harder.
+    Detonation {
+        intensity: f64,
+    },
+    Victory,
+}
+
+impl SoundCue {
+    /// The cue for a safe move that resolved `len` cells on a board of
+    /// `total` cells.
+    pub fn for_resolution(len: u32, total: u32) -> Self {
+        if len >= BIG_CASCADE {
+            let intensity = (f64::from(len).ln() / f64::from(total.max(2)).ln()).clamp(0.0, 1.0);
+            Self::BigCascade { len, intensity }
+        } else {
+            Self::SoftReveal {
+                intensity: f64::from(len) / f64::from(BIG_CASCADE),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolution_cues_scale_with_the_cascade() {
+        assert_eq!(
+            SoundCue::for_resolution(2, 64),
+            SoundCue::SoftReveal { intensity: 0.25 }
+        );
+        let SoundCue::BigCascade { len, intensity } = SoundCue::for_resolution(8, 64) else {
+            panic!("expected a big cascade");
+        };
+        assert_eq!(len, 8);
+        assert!((intensity - 0.5).abs() < 1e-9);
+        assert_eq!(
+            SoundCue::for_resolution(64, 64),
+            SoundCue::BigCascade {
+                len: 64,
+                intensity: 1.0
+            }
+        );
+    }
+}
@@ -0,0 +1,178 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::events::GameEvent;
+use crate::grid::QuantumGrid;
+use crate::prelude::*;
+
+/// A speedrun split point (see
+/// [`GridConfig::splits`](crate::config::GridConfig::splits)), checked
+/// after every timed move.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum SplitTrigger {
+    /// The first move that resolved more than one cell.
+    FirstCascade,
+    /// [`QuantumGrid::quantum_entropy`] down to `fraction` of what it was
+    /// before the first move (0.5 for the halfway split).
+    EntropyFraction { fraction: f64 },
+    /// Every mine contained.
+    AllMinesContained,
+}
+
+/// One split and, once reached, its time since the first action.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Split {
+    pub trigger: SplitTrigger,
+    pub elapsed_ms: Option<u64>,
+}
+
+/// The run's splits in configured order, filled in as play reaches them.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Splits {
+    pub list: Vec<Split>,
+    /// Entropy before the first timed move, for
+    /// [`SplitTrigger::EntropyFraction`].
+    pub start_entropy: Option<f64>,
+}
+
+impl Splits {
+    pub fn new(triggers: &[SplitTrigger]) -> Self {
+        Self {
+            list: triggers
+                .iter()
+                .map(|&trigger| Split {
+                    trigger,
+                    elapsed_ms: None,
+                })
+                .collect(),
+            start_entropy: None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Each split's time, `None` where it was not reached.
+    pub fn times(&self) -> Vec<Option<u64>> {
+        self.list.iter().map(|split| split.elapsed_ms).collect()
+    }
+
+    /// Record every pending split the move just made reaches at
+    /// `elapsed_ms`. `resolved_before` is the resolved-cell count before
+    /// the move.
+    pub(crate) fn evaluate(
+        &mut self,
+        grid: &QuantumGrid,
+        resolved_before: u32,
+        elapsed_ms: u64,
+    ) -> Vec<GameEvent> {
+        let mut events = Vec::new();
+        let mut entropy = None;
+        for (index, split) in self.list.iter_mut().enumerate() {
+            if split.elapsed_ms.is_some() {
+                continue;
+            }
+            let reached = match split.trigger {
+                SplitTrigger::FirstCascade => grid.resolved_cells() > resolved_before + 1,
+                SplitTrigger::EntropyFraction { fraction } => {
+                    let now = *entropy.get_or_insert_with(|| grid.quantum_entropy());
+                    self.start_entropy
+                        .is_some_and(|start| now <= start * fraction)
+                }
+                SplitTrigger::AllMinesContained => grid.mines_placed && grid.mines_remaining() == 0,
+            };
+            if reached {
+                split.elapsed_ms = Some(elapsed_ms);
+                events.push(GameEvent::SplitReached {
+                    index: index as u32,
+                    elapsed_ms,
+                });
+            }
+        }
+        events
+    }
+}
+
+/// A split against the personal best, as a livesplit-style delta.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SplitDelta {
+    pub elapsed_ms: Option<u64>,
+    pub best_ms: Option<u64>,
+    /// Behind (positive) or ahead (negative) of the best, once both runs
+    /// have reached the split.
+    pub delta_ms: Option<i64>,
+}
+
+/// Compare the run's splits with the best run's split times (see
+/// [`PersonalBest::splits`](crate::profile::PersonalBest::splits)), split
+/// by split. Splits the best run lacks compare against nothing.
+pub fn compare(splits: &Splits, best: &[Option<u64>]) -> Vec<SplitDelta> {
+    splits
+        .list
+        .iter()
+        .enumerate()
+        .map(|(index, split)| {
+            let best_ms = best.get(index).copied().flatten();
+            SplitDelta {
+                elapsed_ms: split.elapsed_ms,
+                best_ms,
+                delta_ms: split
+                    .elapsed_ms
+                    .zip(best_ms)
+                    .map(|(now, best)| now as i64 - best as i64),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action::Action;
+    use crate::config::GridConfig;
+
+    #[test]
+    fn splits_fire_once_and_compare_against_a_best() {
+        let config = GridConfig::new(8, 8, 4, 42, "observer")
+            .with_split(SplitTrigger::FirstCascade)
+            .with_split(SplitTrigger::EntropyFraction { fraction: 0.5 })
+            .with_split(SplitTrigger::AllMinesContained);
+        let mut grid = QuantumGrid::from_config(config);
+        let mut t_ms = 5_000;
+        grid.apply_at(Action::Reveal { x: 0, y: 0 }, t_ms).unwrap();
+        assert!(grid.splits.start_entropy.is_some());
+        let first = grid.splits.list[0].elapsed_ms;
+        assert_eq!(first, Some(0), "the opening click cascades");
+        assert!(grid.drain_events().contains(&GameEvent::SplitReached {
+            index: 0,
+            elapsed_ms: 0
+        }));
+
+        let mines: Vec<(u32, u32)> = grid
+            .mine_map
+            .iter()
+            .enumerate()
+            .filter(|&(_, &mine)| mine)
+            .map(|(index, _)| (index as u32 % 8, index as u32 / 8))
+            .collect();
+        for (x, y) in mines {
+            t_ms += 1_000;
+            grid.apply_at(Action::Contain { x, y }, t_ms).unwrap();
+        }
+        let times = grid.splits.times();
+        assert_eq!(times[0], first);
+        assert_eq!(times[2], Some(t_ms - 5_000));
+        assert!(times[1].is_some_and(|half| half <= t_ms - 5_000));
+
+        let deltas = compare(&grid.splits, &[Some(500), None]);
+        assert_eq!(deltas[0].delta_ms, Some(-500));
+        assert_eq!(deltas[1].delta_ms, None);
+        assert_eq!(deltas[2].best_ms, None);
+    }
+}
@@ -0,0 +1,278 @@
+use std::collections::BTreeMap;
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::de::DeserializeOwned;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Key the [`PlayerProfile`](crate::profile::PlayerProfile) is stored under.
+pub const PROFILE_KEY: &str = "profile";
+/// Prefix of saved games' keys.
+pub const SAVE_PREFIX: &str = "save.";
+/// Prefix of replays' keys.
+pub const REPLAY_PREFIX: &str = "replay.";
+
+/// Why a [`Storage`] operation failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum StorageError {
+    /// Keys may only hold ASCII letters, digits, `-`, `_` and `.`, and may
+    /// not start with `.`, so every backend can store them verbatim.
+    InvalidKey { key: String },
+    /// The backend itself failed, e.g. a full disk or a browser with
+    /// storage disabled.
+    Backend { message: String },
+    /// A stored value did not deserialize into the requested type.
+    Corrupt { key: String, message: String },
+}
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidKey { key } => write!(f, "invalid storage key {key:?}"),
+            Self::Backend { message } => write!(f, "storage failed: {message}"),
+            Self::Corrupt { key, message } => write!(f, "stored {key:?} is corrupt: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for StorageError {}
+
+/// Check that `key` is valid for every backend (see
+/// [`StorageError::InvalidKey`]).
+pub fn check_key(key: &str) -> Result<(), StorageError> {
+    let valid = !key.is_empty()
+        && !key.starts_with('.')
+        && key
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'));
+    if valid {
+        Ok(())
+    } else {
+        Err(StorageError::InvalidKey {
+            key: key.to_string(),
+        })
+    }
+}
+
+/// A string key-value store, so profiles, saves and replays persist the
+/// same way on every frontend: browser `localStorage` (in `qmf-wasm`), the
+/// filesystem ([`FileStorage`]), or sled on a server ([`SledStorage`],
+/// behind the `sled` feature). Values are JSON; see [`load`] and [`store`].
+pub trait Storage {
+    fn get(&self, key: &str) -> Result<Option<String>, StorageError>;
+
+    fn put(&mut self, key: &str, value: &str) -> Result<(), StorageError>;
+
+    fn remove(&mut self, key: &str) -> Result<(), StorageError>;
+
+    /// Every stored key starting with `prefix`, sorted.
+    fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError>;
+}
+
+/// Read and deserialize the value under `key`.
+pub fn load<T: DeserializeOwned>(
+    storage: &impl Storage,
+    key: &str,
+) -> Result<Option<T>, StorageError> {
+    let Some(json) = storage.get(key)? else {
+        return Ok(None);
+    };
+    serde_json::from_str(&json)
+        .map(Some)
+        .map_err(|error| StorageError::Corrupt {
+            key: key.to_string(),
+            message: error.to_string(),
+        })
+}
+
+/// Serialize `value` and write it under `key`.
+pub fn store<T: Serialize>(
+    storage: &mut impl Storage,
+    key: &str,
+    value: &T,
+) -> Result<(), StorageError> {
+    let json = serde_json::to_string(value).map_err(backend)?;
+    storage.put(key, &json)
+}
+
+/// In-memory storage, for tests and sessions that should not persist.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MemoryStorage {
+    pub entries: BTreeMap<String, String>,
+}
+
+impl Storage for MemoryStorage {
+    fn get(&self, key: &str) -> Result<Option<String>, StorageError> {
+        check_key(key)?;
+        Ok(self.entries.get(key).cloned())
+    }
+
+    fn put(&mut self, key: &str, value: &str) -> Result<(), StorageError> {
+        check_key(key)?;
+        self.entries.insert(key.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), StorageError> {
+        check_key(key)?;
+        self.entries.remove(key);
+        Ok(())
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+        Ok(self
+            .entries
+            .range(prefix.to_string()..)
+            .take_while(|(key, _)| key.starts_with(prefix))
+            .map(|(key, _)| key.clone())
+            .collect())
+    }
+}
+
+fn backend(error: impl fmt::Display) -> StorageError {
+    StorageError::Backend {
+        message: error.to_string(),
+    }
+}
+
+/// Native storage: one `<key>.json` file per key in a directory.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStorage {
+    pub root: std::path::PathBuf,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl FileStorage {
+    /// Storage in `root`, created if missing.
+    pub fn open(root: impl Into<std::path::PathBuf>) -> Result<Self, StorageError> {
+        let root = root.into();
+        std::fs::create_dir_all(&root).map_err(backend)?;
+        Ok(Self { root })
+    }
+
+    fn path(&self, key: &str) -> Result<std::path::PathBuf, StorageError> {
+        check_key(key)?;
+        Ok(self.root.join(format!("{key}.json")))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Storage for FileStorage {
+    fn get(&self, key: &str) -> Result<Option<String>, StorageError> {
+        match std::fs::read_to_string(self.path(key)?) {
+            Ok(value) => Ok(Some(value)),
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(error) => Err(backend(error)),
+        }
+    }
+
+    fn put(&mut self, key: &str, value: &str) -> Result<(), StorageError> {
+        // Write then rename, so a crash never leaves a half-written value.
+        let path = self.path(key)?;
+        let partial = path.with_extension("json.tmp");
+        std::fs::write(&partial, value).map_err(backend)?;
+        std::fs::rename(&partial, &path).map_err(backend)
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), StorageError> {
+        match std::fs::remove_file(self.path(key)?) {
+            Err(error) if error.kind() != std::io::ErrorKind::NotFound => Err(backend(error)),
+            _ => Ok(()),
+        }
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+        let mut keys = Vec::new();
+        for entry in std::fs::read_dir(&self.root).map_err(backend)? {
+            let name = entry.map_err(backend)?.file_name();
+            let key = name.to_str().and_then(|name| name.strip_suffix(".json"));
+            if let Some(key) = key.filter(|key| key.starts_with(prefix)) {
+                keys.push(key.to_string());
+            }
+        }
+        keys.sort();
+        Ok(keys)
+    }
+}
+
+/// Server storage in a sled tree.
+#[cfg(feature = "sled")]
+#[derive(Debug, Clone)]
+pub struct SledStorage {
+    pub tree: sled::Tree,
+}
+
+#[cfg(feature = "sled")]
+impl Storage for SledStorage {
+    fn get(&self, key: &str) -> Result<Option<String>, StorageError> {
+        check_key(key)?;
+        let Some(value) = self.tree.get(key).map_err(backend)? else {
+            return Ok(None);
+        };
+        String::from_utf8(value.to_vec())
+            .map(Some)
+            .map_err(|error| StorageError::Corrupt {
+                key: key.to_string(),
+                message: error.to_string(),
+            })
+    }
+
+    fn put(&mut self, key: &str, value: &str) -> Result<(), StorageError> {
+        check_key(key)?;
+        self.tree.insert(key, value.as_bytes()).map_err(backend)?;
+        Ok(())
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), StorageError> {
+        check_key(key)?;
+        self.tree.remove(key).map_err(backend)?;
+        Ok(())
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+        self.tree
+            .scan_prefix(prefix)
+            .keys()
+            .map(|key| Ok(String::from_utf8_lossy(&key.map_err(backend)?).into_owned()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::profile::PlayerProfile;
+
+    fn round_trip(storage: &mut impl Storage) {
+        let profile = PlayerProfile::default();
+        store(storage, PROFILE_KEY, &profile).unwrap();
+        storage.put("save.b", "[1, 2").unwrap();
+        storage.put("save.a", "{}").unwrap();
+        assert_eq!(load(storage, PROFILE_KEY).unwrap(), Some(profile));
+        assert_eq!(storage.list(SAVE_PREFIX).unwrap(), ["save.a", "save.b"]);
+
+        storage.remove("save.a").unwrap();
+        assert_eq!(storage.get("save.a").unwrap(), None);
+        assert!(matches!(
+            storage.put("../escape", "{}"),
+            Err(StorageError::InvalidKey { .. })
+        ));
+        assert!(matches!(
+            load::<PlayerProfile>(storage, "save.b"),
+            Err(StorageError::Corrupt { .. })
+        ));
+    }
+
+    #[test]
+    fn backends_behave_alike() {
+        round_trip(&mut MemoryStorage::default());
+        let dir = std::env::temp_dir().join(format!("qmf-storage-{}", std::process::id()));
+        round_trip(&mut FileStorage::open(&dir).unwrap());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
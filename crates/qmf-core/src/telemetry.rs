@@ -0,0 +1,30 @@
+//! Telemetry behind the `tracing` feature. Spans are attached with
+//! `cfg_attr(feature = "tracing", tracing::instrument(..))`; events go
+//! through the macros here, which compile to nothing with the feature off,
+//! so the hot paths pay nothing by default.
+
+/// [`tracing::debug!`] with the `tracing` feature, else nothing.
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::debug!($($arg)*);
+    };
+}
+
+/// [`tracing::trace!`] with the `tracing` feature, else nothing.
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::trace!($($arg)*);
+    };
+}
+
+/// [`tracing::warn!`] with the `tracing` feature, else nothing.
+macro_rules! warning {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        ::tracing::warn!($($arg)*);
+    };
+}
+
+pub(crate) use {debug, trace, warning};
@@ -0,0 +1,143 @@
+use alloc::collections::BTreeMap;
+use alloc::rc::Rc;
+
+use crate::action::{Action, ActionResult};
+use crate::grid::QuantumGrid;
+#[cfg(doc)]
+use crate::shared::Shared;
+
+pub type BranchId = u32;
+
+/// "Many-worlds" explorer: hypothetical forks of a game that never touch the
+/// live grid.
+///
+/// Forks share state through `Rc` and are only copied the first time they
+/// are mutated (copy-on-write), so forking for a preview is O(1) and a
+/// fork that is only inspected never allocates. Even that first copy is
+/// shallow: the cells, config, entanglement and mine map are [`Shared`]
+/// and only copied once the fork changes them, so a typical move copies
+/// the cells and leaves the rest with its parent.
+///
+/// Forks see the real mine map and RNG state, so evaluating a move reveals
+/// exactly what would happen — frontends should gate this behind an assist.
+#[derive(Debug, Clone)]
+pub struct Timeline {
+    root: Rc<QuantumGrid>,
+    branches: BTreeMap<BranchId, Rc<QuantumGrid>>,
+    next_id: BranchId,
+}
+
+impl Timeline {
+    /// Root a timeline at the given state (see [`QuantumGrid::branch`]).
+    pub fn new(root: QuantumGrid) -> Self {
+        Self {
+            root: Rc::new(root),
+            branches: BTreeMap::new(),
+            next_id: 0,
+        }
+    }
+
+    pub fn root(&self) -> &QuantumGrid {
+        &self.root
+    }
+
+    /// Fork the root, or an existing branch when `from` is given.
+    pub fn fork(&mut self, from: Option<BranchId>) -> Option<BranchId> {
+        let source = match from {
+            Some(id) => Rc::clone(self.branches.get(&id)?),
+            None => Rc::clone(&self.root),
+        };
+        let id = self.next_id;
+        self.next_id += 1;
+        self.branches.insert(id, source);
+        Some(id)
+    }
+
+    /// Apply `action` inside a branch, copying its state first if shared.
+    pub fn evaluate(&mut self, id: BranchId, action: Action) -> Option<ActionResult> {
+        let branch = self.branches.get_mut(&id)?;
+        Some(Rc::make_mut(branch).apply(action))
+    }
+
+    pub fn get(&self, id: BranchId) -> Option<&QuantumGrid> {
+        self.branches.get(&id).map(|grid| grid.as_ref())
+    }
+
+    pub fn discard(&mut self, id: BranchId) -> bool {
+        self.branches.remove(&id).is_some()
+    }
+
+    pub fn branch_ids(&self) -> impl Iterator<Item = BranchId> + '_ {
+        self.branches.keys().copied()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::grid::{CellState, RevealOutcome};
+    use crate::shared::Shared;
+
+    #[test]
+    fn forks_do_not_touch_the_live_game() {
+        let mut live = QuantumGrid::new(8, 8, 10, 42, "observer");
+        live.reveal_cell(0, 0);
+        let mut timeline = live.branch();
+        let a = timeline.fork(None).unwrap();
+        let b = timeline.fork(Some(a)).unwrap();
+
+        let mine = live.mine_map.iter().position(|&m| m).unwrap();
+        let (x, y) = (mine as u32 % 8, mine as u32 / 8);
+        let result = timeline.evaluate(a, Action::Reveal { x, y }).unwrap();
+        assert!(matches!(
+            result,
+            ActionResult::Outcome {
+                outcome: RevealOutcome::MineDetonated { .. }
+            }
+        ));
+        assert!(timeline.get(a).unwrap().game_over);
+        // Sibling fork and the live game are unaffected.
+        assert!(!timeline.get(b).unwrap().game_over);
+        assert!(!live.game_over);
+        assert!(matches!(
+            live.cells[mine].state,
+            CellState::Superposition { .. }
+        ));
+
+        assert!(timeline.discard(a));
+        assert!(timeline.evaluate(a, Action::Reveal { x, y }).is_none());
+        assert_eq!(timeline.branch_ids().collect::<Vec<_>>(), vec![b]);
+    }
+
+    #[test]
+    fn unmodified_forks_share_state() {
+        let mut timeline = QuantumGrid::new(8, 8, 10, 1, "observer").branch();
+        let a = timeline.fork(None).unwrap();
+        assert!(Rc::ptr_eq(&timeline.root, &timeline.branches[&a]));
+        timeline.evaluate(a, Action::Hadamard { x: 1, y: 1 });
+        assert!(!Rc::ptr_eq(&timeline.root, &timeline.branches[&a]));
+    }
+
+    #[test]
+    fn forks_copy_only_what_they_change() {
+        let mut live = QuantumGrid::new(8, 8, 10, 1, "observer");
+        live.reveal_cell(0, 0);
+        let mut timeline = live.branch();
+        assert!(Shared::ptr_eq(&live.cells, &timeline.root().cells));
+        assert!(Shared::ptr_eq(&live.mine_map, &timeline.root().mine_map));
+
+        let a = timeline.fork(None).unwrap();
+        let (x, y) = live
+            .cells
+            .iter()
+            .find(|c| matches!(c.state, CellState::Superposition { .. }))
+            .map(|c| (c.x, c.y))
+            .unwrap();
+        timeline.evaluate(a, Action::Hadamard { x, y });
+        let (root, fork) = (timeline.root(), timeline.get(a).unwrap());
+        assert!(!Shared::ptr_eq(&root.cells, &fork.cells));
+        assert!(Shared::ptr_eq(&root.config, &fork.config));
+        assert!(Shared::ptr_eq(&root.entanglement, &fork.entanglement));
+        assert!(Shared::ptr_eq(&root.mine_map, &fork.mine_map));
+    }
+}
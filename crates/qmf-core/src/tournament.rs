@@ -0,0 +1,402 @@
+use core::cmp::Ordering;
+use core::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::commitment::SeedCommitment;
+use crate::config::{AssistLevel, GridConfig};
+use crate::grid::QuantumGrid;
+use crate::prelude::*;
+use crate::referee::PlayerId;
+use crate::replay::{Replay, ReplayError};
+
+/// How a finished match is ranked: a win beats a loss, then more resolved
+/// cells, then more points, then less uncertainty left on the board, then less measurement
+/// disturbance, then less elapsed time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct MatchScore {
+    pub won: bool,
+    /// Cells revealed or contained.
+    pub resolved: u32,
+    /// [`QuantumGrid::score`], combo multiplier included.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub points: u64,
+    /// [`QuantumGrid::quantum_entropy`] at the end, in thousandths of a bit.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub entropy_millibits: u32,
+    /// Lifetime decoherence points (see `DecoherenceMeter`).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub decoherence: u32,
+    pub elapsed_ms: u64,
+}
+
+impl MatchScore {
+    pub fn from_grid(grid: &QuantumGrid) -> Self {
+        Self {
+            won: grid.won,
+            resolved: grid.resolved_cells(),
+            points: grid.score,
+            entropy_millibits: (grid.quantum_entropy() * 1000.0).round() as u32,
+            decoherence: grid.decoherence.lifetime,
+            elapsed_ms: grid.timing_stats().elapsed_ms,
+        }
+    }
+
+    /// Ranking order: `Greater` means `self` is the better result.
+    pub fn rank(&self, other: &Self) -> Ordering {
+        self.won
+            .cmp(&other.won)
+            .then(self.resolved.cmp(&other.resolved))
+            .then(self.points.cmp(&other.points))
+            .then(other.entropy_millibits.cmp(&self.entropy_millibits))
+            .then(other.decoherence.cmp(&self.decoherence))
+            .then(other.elapsed_ms.cmp(&self.elapsed_ms))
+    }
+}
+
+/// One pairing in a round. `away` is `None` for a bye.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Match {
+    pub home: PlayerId,
+    pub away: Option<PlayerId>,
+    pub home_score: Option<MatchScore>,
+    pub away_score: Option<MatchScore>,
+}
+
+impl Match {
+    /// The player who advances, once the match can be decided. Byes advance
+    /// `home` unconditionally; exact ties go to `home` (the higher seed).
+    pub fn winner(&self) -> Option<PlayerId> {
+        let Some(away) = self.away else {
+            return Some(self.home);
+        };
+        let (home_score, away_score) = (self.home_score?, self.away_score?);
+        if away_score.rank(&home_score) == Ordering::Greater {
+            Some(away)
+        } else {
+            Some(self.home)
+        }
+    }
+
+    fn score_slot(&mut self, player: PlayerId) -> Option<&mut Option<MatchScore>> {
+        if player == self.home {
+            Some(&mut self.home_score)
+        } else if self.away == Some(player) {
+            Some(&mut self.away_score)
+        } else {
+            None
+        }
+    }
+}
+
+/// A round's seed and salt, published once the round is over.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SeedReveal {
+    pub seed: u64,
+    pub salt: Vec<u8>,
+}
+
+/// Every match of a round is played on the same board. Only the commitment is
+/// public while the round runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Round {
+    pub commitment: SeedCommitment,
+    pub matches: Vec<Match>,
+    pub revealed: Option<SeedReveal>,
+}
+
+/// Why the [`Tournament`] refused a request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum TournamentError {
+    /// A round is already running.
+    RoundInProgress,
+    /// There is no running round.
+    NoRoundInProgress,
+    /// A champion has already been decided.
+    Finished,
+    NotInRound {
+        player: PlayerId,
+    },
+    AlreadySubmitted {
+        player: PlayerId,
+    },
+    /// The replay was not played on this round's board.
+    WrongBoard {
+        player: PlayerId,
+    },
+    InvalidReplay {
+        player: PlayerId,
+        error: ReplayError,
+    },
+    /// Some matches are still waiting for results.
+    MatchesPending,
+}
+
+impl fmt::Display for TournamentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RoundInProgress => write!(f, "a round is already in progress"),
+            Self::NoRoundInProgress => write!(f, "no round is in progress"),
+            Self::Finished => write!(f, "the tournament is over"),
+            Self::NotInRound { player } => write!(f, "player {player} is not in this round"),
+            Self::AlreadySubmitted { player } => {
+                write!(f, "player {player} already submitted a result")
+            }
+            Self::WrongBoard { player } => {
+                write!(f, "player {player} submitted a replay for another board")
+            }
+            Self::InvalidReplay { player, error } => {
+                write!(f, "replay from player {player} is invalid: {error}")
+            }
+            Self::MatchesPending => write!(f, "some matches have no result yet"),
+        }
+    }
+}
+
+impl core::error::Error for TournamentError {}
+
+/// Single-elimination bracket for competitive play.
+///
+/// This is server-side state: it holds the current round's seed and salt,
+/// which must not be sent to players until [`finish_round`](Self::finish_round)
+/// reveals them. Players get the round's [`SeedCommitment`] up front and can
+/// check the reveal afterwards.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Tournament {
+    /// Board settings for every round; the seed is replaced per round.
+    pub base: GridConfig,
+    rounds: Vec<Round>,
+    /// Players still in the bracket, in seeding order.
+    remaining: Vec<PlayerId>,
+    secret: Option<SeedReveal>,
+}
+
+impl Tournament {
+    /// `players` are given in seeding order: 1st plays 2nd, 3rd plays 4th, …
+    /// and an odd player out gets a bye. Tournament boards are always
+    /// unassisted, whatever `base.assist` says.
+    pub fn new(base: GridConfig, players: Vec<PlayerId>) -> Self {
+        Self {
+            base: base.with_assist(AssistLevel::None),
+            rounds: Vec::new(),
+            remaining: players,
+            secret: None,
+        }
+    }
+
+    pub fn rounds(&self) -> &[Round] {
+        &self.rounds
+    }
+
+    /// Players still in contention.
+    pub fn remaining(&self) -> &[PlayerId] {
+        &self.remaining
+    }
+
+    /// The winner, once only one player is left and no round is running.
+    pub fn champion(&self) -> Option<PlayerId> {
+        match self.remaining.as_slice() {
+            [champion] if self.secret.is_none() => Some(*champion),
+            _ => None,
+        }
+    }
+
+    /// Pair the remaining players on a fresh board and return the commitment
+    /// to publish. `salt` must be random and kept secret until the reveal.
+    pub fn start_round(
+        &mut self,
+        seed: u64,
+        salt: Vec<u8>,
+    ) -> Result<SeedCommitment, TournamentError> {
+        if self.secret.is_some() {
+            return Err(TournamentError::RoundInProgress);
+        }
+        if self.remaining.len() < 2 {
+            return Err(TournamentError::Finished);
+        }
+        let commitment = SeedCommitment::commit(seed, &salt);
+        let matches = self
+            .remaining
+            .chunks(2)
+            .filter_map(|pair| match *pair {
+                [home, away] => Some((home, Some(away))),
+                [home] => Some((home, None)),
+                _ => None,
+            })
+            .map(|(home, away)| Match {
+                home,
+                away,
+                home_score: None,
+                away_score: None,
+            })
+            .collect();
+        self.rounds.push(Round {
+            commitment,
+            matches,
+            revealed: None,
+        });
+        self.secret = Some(SeedReveal { seed, salt });
+        Ok(commitment)
+    }
+
+    /// Board for the running round — server-side only, it contains the seed.
+    pub fn round_config(&self) -> Option<GridConfig> {
+        let secret = self.secret.as_ref()?;
+        let mut config = self.base.clone();
+        config.seed = secret.seed;
+        Some(config)
+    }
+
+    /// Verify `replay` by playing it back on this round's board and record
+    /// the resulting score for `player`.
+    pub fn submit(
+        &mut self,
+        player: PlayerId,
+        replay: &Replay,
+    ) -> Result<MatchScore, TournamentError> {
+        let config = self
+            .round_config()
+            .ok_or(TournamentError::NoRoundInProgress)?;
+        let round = self
+            .rounds
+            .last_mut()
+            .ok_or(TournamentError::NoRoundInProgress)?;
+        let slot = round
+            .matches
+            .iter_mut()
+            .find_map(|m| m.score_slot(player))
+            .ok_or(TournamentError::NotInRound { player })?;
+        if slot.is_some() {
+            return Err(TournamentError::AlreadySubmitted { player });
+        }
+        if replay.config != config {
+            return Err(TournamentError::WrongBoard { player });
+        }
+        let grid = replay
+            .play()
+            .map_err(|error| TournamentError::InvalidReplay { player, error })?;
+        let score = MatchScore::from_grid(&grid);
+        *slot = Some(score);
+        Ok(score)
+    }
+
+    /// Close the running round once every match has a result: advance the
+    /// winners and reveal the seed. Returns the players who advanced.
+    pub fn finish_round(&mut self) -> Result<Vec<PlayerId>, TournamentError> {
+        if self.secret.is_none() {
+            return Err(TournamentError::NoRoundInProgress);
+        }
+        let round = self
+            .rounds
+            .last_mut()
+            .ok_or(TournamentError::NoRoundInProgress)?;
+        let winners = round
+            .matches
+            .iter()
+            .map(Match::winner)
+            .collect::<Option<Vec<_>>>()
+            .ok_or(TournamentError::MatchesPending)?;
+        round.revealed = self.secret.take();
+        self.remaining = winners.clone();
+        Ok(winners)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::action::Action;
+    use crate::grid::CellState;
+
+    fn opening(config: &GridConfig) -> Replay {
+        let mut replay = Replay::new(config.clone());
+        replay.record(0, Action::Reveal { x: 0, y: 0 });
+        replay
+    }
+
+    /// The opening plus one more safe reveal, so it outscores [`opening`].
+    fn better(config: &GridConfig) -> Replay {
+        let mut replay = opening(config);
+        let grid = replay.play().unwrap();
+        let index = (0..grid.cells.len())
+            .find(|&i| {
+                matches!(grid.cells[i].state, CellState::Superposition { .. }) && !grid.mine_map[i]
+            })
+            .unwrap();
+        let (x, y) = (index as u32 % grid.width, index as u32 / grid.width);
+        replay.record(500, Action::Reveal { x, y });
+        replay
+    }
+
+    #[test]
+    fn bracket_runs_to_a_champion() {
+        let mut t = Tournament::new(GridConfig::new(8, 8, 10, 0, "observer"), vec![1, 2, 3]);
+        let commitment = t.start_round(11, b"round-one".to_vec()).unwrap();
+        assert_eq!(t.rounds()[0].matches[1].away, None);
+        let config = t.round_config().unwrap();
+        assert_eq!(config.seed, 11);
+        assert_eq!(config.assist, AssistLevel::None);
+
+        t.submit(1, &opening(&config)).unwrap();
+        assert_eq!(t.finish_round(), Err(TournamentError::MatchesPending));
+        t.submit(2, &better(&config)).unwrap();
+        assert_eq!(t.finish_round(), Ok(vec![2, 3]));
+
+        let reveal = t.rounds()[0].revealed.clone().unwrap();
+        assert!(commitment.verify(reveal.seed, &reveal.salt));
+        assert_eq!(t.champion(), None);
+
+        t.start_round(22, b"final".to_vec()).unwrap();
+        let config = t.round_config().unwrap();
+        t.submit(2, &opening(&config)).unwrap();
+        t.submit(3, &opening(&config)).unwrap();
+        // Identical results: the higher seed advances.
+        assert_eq!(t.finish_round(), Ok(vec![2]));
+        assert_eq!(t.champion(), Some(2));
+        assert_eq!(
+            t.start_round(33, Vec::new()),
+            Err(TournamentError::Finished)
+        );
+    }
+
+    #[test]
+    fn submissions_are_checked() {
+        let mut t = Tournament::new(GridConfig::new(8, 8, 10, 0, "observer"), vec![1, 2]);
+        let stale = opening(&GridConfig::new(8, 8, 10, 0, "observer"));
+        assert_eq!(t.submit(1, &stale), Err(TournamentError::NoRoundInProgress));
+        t.start_round(5, b"salt".to_vec()).unwrap();
+        assert_eq!(
+            t.start_round(6, b"salt".to_vec()),
+            Err(TournamentError::RoundInProgress)
+        );
+        assert_eq!(
+            t.submit(1, &stale),
+            Err(TournamentError::WrongBoard { player: 1 })
+        );
+
+        let config = t.round_config().unwrap();
+        assert_eq!(
+            t.submit(9, &opening(&config)),
+            Err(TournamentError::NotInRound { player: 9 })
+        );
+        let mut forged = opening(&config);
+        forged.record(10, Action::Reveal { x: 0, y: 0 });
+        assert!(matches!(
+            t.submit(1, &forged),
+            Err(TournamentError::InvalidReplay { player: 1, .. })
+        ));
+        t.submit(1, &opening(&config)).unwrap();
+        assert_eq!(
+            t.submit(1, &opening(&config)),
+            Err(TournamentError::AlreadySubmitted { player: 1 })
+        );
+    }
+}
@@ -0,0 +1,14 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::circuit::Circuit;
+
+/// Something that happens once, when its cell is revealed.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", rename_all = "snake_case"))]
+pub enum CellTrigger {
+    /// Phase kickback: run `circuit` over every unresolved cell in the
+    /// oracle's row and column.
+    Oracle { circuit: Circuit },
+}
@@ -0,0 +1,165 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::config::GridConfig;
+pub use crate::config::DIFFICULTIES;
+use crate::prelude::*;
+use crate::selfplay::{self, Strategy};
+
+/// Mine densities the tuner searches between.
+pub const DENSITY_RANGE: (f64, f64) = (0.05, 0.35);
+
+/// Parameters for a game, with the win rate simulated for them.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DifficultyParams {
+    pub difficulty: String,
+    /// Mines per cell.
+    pub mine_density: f64,
+    /// Share of simulated games the tuning strategy won.
+    pub win_rate: f64,
+}
+
+impl DifficultyParams {
+    /// A config for a `width × height` board with these parameters.
+    pub fn config(&self, width: u32, height: u32, seed: u64) -> GridConfig {
+        let cells = f64::from(width * height);
+        let mines = (self.mine_density * cells).round().max(1.0) as u32;
+        GridConfig::new(width, height, mines, seed, &self.difficulty)
+    }
+}
+
+/// Searches game parameters for a target win rate by simulating games
+/// with a [`Strategy`]. Every candidate plays the same seeds, so results
+/// are reproducible and comparisons between candidates fair.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Tuner {
+    pub width: u32,
+    pub height: u32,
+    /// Simulated games per candidate.
+    pub games: u32,
+    /// Seed of the first simulated game.
+    pub seed: u64,
+    /// Bisection steps over mine density per difficulty.
+    pub steps: u32,
+}
+
+impl Default for Tuner {
+    fn default() -> Self {
+        Self {
+            width: 9,
+            height: 9,
+            games: 64,
+            seed: 0,
+            steps: 8,
+        }
+    }
+}
+
+impl Tuner {
+    pub fn with_board(mut self, width: u32, height: u32) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    pub fn with_games(mut self, games: u32) -> Self {
+        self.games = games;
+        self
+    }
+
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// The parameters whose simulated win rate lands closest to
+    /// `target_win_rate`. For each difficulty, mine density is bisected
+    /// (more mines, fewer wins); ties go to the easier difficulty.
+    pub fn fit(&self, target_win_rate: f64, strategy: Strategy) -> DifficultyParams {
+        let target = target_win_rate.clamp(0.0, 1.0);
+        let mut best: Option<DifficultyParams> = None;
+        for difficulty in DIFFICULTIES {
+            let (mut low, mut high) = DENSITY_RANGE;
+            for _ in 0..self.steps {
+                let density = (low + high) / 2.0;
+                let candidate = self.simulate(difficulty, density, strategy);
+                if candidate.win_rate > target {
+                    low = density;
+                } else {
+                    high = density;
+                }
+                let closer = best.as_ref().is_none_or(|best| {
+                    (candidate.win_rate - target).abs() < (best.win_rate - target).abs()
+                });
+                if closer {
+                    best = Some(candidate);
+                }
+            }
+        }
+        best.unwrap_or_else(|| self.simulate(DIFFICULTIES[0], DENSITY_RANGE.0, strategy))
+    }
+
+    /// Adaptive difficulty: parameters for the player's next game, given
+    /// the game they just played with `current` and their `recent` results
+    /// (`true` for a win). A player winning more often than
+    /// `target_win_rate` gets a board the strategy wins less often, and
+    /// the other way round.
+    pub fn adapt(
+        &self,
+        current: &DifficultyParams,
+        recent: &[bool],
+        target_win_rate: f64,
+        strategy: Strategy,
+    ) -> DifficultyParams {
+        if recent.is_empty() {
+            return current.clone();
+        }
+        let player = recent.iter().filter(|&&won| won).count() as f64 / recent.len() as f64;
+        self.fit(current.win_rate + target_win_rate - player, strategy)
+    }
+
+    fn simulate(&self, difficulty: &str, density: f64, strategy: Strategy) -> DifficultyParams {
+        let mut params = DifficultyParams {
+            difficulty: difficulty.to_string(),
+            mine_density: density,
+            win_rate: 0.0,
+        };
+        let config = params.config(self.width, self.height, self.seed);
+        let wins = selfplay::batch(&config, strategy, self.games)
+            .iter()
+            .filter(|record| record.step == 0 && record.won)
+            .count();
+        params.win_rate = wins as f64 / f64::from(self.games.max(1));
+        params
+    }
+}
+
+/// [`Tuner::fit`] with the default tuner.
+pub fn fit(target_win_rate: f64, strategy: Strategy) -> DifficultyParams {
+    Tuner::default().fit(target_win_rate, strategy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fit_tracks_the_target_win_rate() {
+        let tuner = Tuner::default().with_board(6, 6).with_games(16);
+        let easy = tuner.fit(0.9, Strategy::LowestHint);
+        let hard = tuner.fit(0.1, Strategy::LowestHint);
+        assert!(easy.win_rate > hard.win_rate);
+        assert!((0.0..=1.0).contains(&easy.win_rate));
+        assert_eq!(tuner.fit(0.9, Strategy::LowestHint), easy);
+
+        let config = easy.config(6, 6, 7);
+        assert_eq!(config.difficulty, easy.difficulty);
+        assert!(config.mine_count >= 1);
+
+        // A player losing every game gets an easier board next.
+        let next = tuner.adapt(&hard, &[false, false, false], 0.5, Strategy::LowestHint);
+        assert!(next.win_rate >= hard.win_rate);
+    }
+}
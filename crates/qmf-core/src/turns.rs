@@ -0,0 +1,94 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::config::TurnRules;
+
+/// Where a turn-based game stands (see
+/// [`GridConfig::turns`](crate::config::GridConfig::turns)). Both counters
+/// stay at 0 in real-time play.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Turns {
+    /// The current turn, from 1.
+    pub turn: u32,
+    /// Action points left this turn.
+    pub action_points: u32,
+}
+
+impl Turns {
+    pub fn new(rules: Option<&TurnRules>) -> Self {
+        rules.map_or_else(Self::default, |rules| Self {
+            turn: 1,
+            action_points: rules.action_points,
+        })
+    }
+
+    /// Spend `cost` points if this turn has them.
+    pub(crate) fn spend(&mut self, cost: u32) -> bool {
+        let Some(left) = self.action_points.checked_sub(cost) else {
+            return false;
+        };
+        self.action_points = left;
+        true
+    }
+
+    /// Start the next turn with a full budget.
+    pub(crate) fn next(&mut self, rules: &TurnRules) {
+        self.turn = self.turn.saturating_add(1);
+        self.action_points = rules.action_points;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::action::{Action, ActionResult, IllegalAction};
+    use crate::config::{GridConfig, TurnRules};
+    use crate::events::GameEvent;
+    use crate::grid::{CellState, QuantumGrid};
+
+    #[test]
+    fn actions_spend_the_turn_budget() {
+        let config = GridConfig::new(8, 8, 10, 42, "observer");
+        let mut grid = QuantumGrid::from_config(config.clone().with_turns(TurnRules::default()));
+        assert_eq!((grid.turns.turn, grid.turns.action_points), (1, 3));
+        grid.apply(Action::Reveal { x: 0, y: 0 });
+        assert_eq!(grid.turns.action_points, 2);
+
+        let (x, y) = (0..64)
+            .find(|&i| matches!(grid.cells[i].state, CellState::Superposition { .. }))
+            .map(|i| (i as u32 % 8, i as u32 / 8))
+            .unwrap();
+        let hadamard = Action::Hadamard { x, y };
+        assert_eq!(
+            grid.validate_action(&hadamard),
+            Err(IllegalAction::NotEnoughActionPoints)
+        );
+        assert!(grid.apply(hadamard).is_rejection());
+        assert_eq!(grid.turns.action_points, 2);
+
+        assert_eq!(
+            grid.apply(Action::EndTurn),
+            ActionResult::NextTurn {
+                turn: 2,
+                action_points: 3
+            }
+        );
+        assert_eq!(grid.decoherence.lifetime, 100);
+        grid.apply(hadamard);
+        assert_eq!(grid.turns.turn, 3);
+        let events = grid.drain_events();
+        for turn in [2, 3] {
+            assert!(events.contains(&GameEvent::TurnStarted {
+                turn,
+                action_points: 3
+            }));
+        }
+
+        let mut realtime = QuantumGrid::from_config(config);
+        assert_eq!(
+            realtime.validate_action(&Action::EndTurn),
+            Err(IllegalAction::NotTurnBased)
+        );
+        assert!(realtime.apply(Action::EndTurn).is_rejection());
+    }
+}
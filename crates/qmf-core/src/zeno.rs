@@ -0,0 +1,97 @@
+use alloc::collections::BTreeMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Consecutive weak measurements of one cell that freeze it.
+pub const STREAK: u32 = 3;
+/// Moves a frozen cell stays frozen for.
+pub const FROZEN_MOVES: u32 = 5;
+
+/// A frozen cell as reported in snapshots.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct FrozenCell {
+    pub x: u32,
+    pub y: u32,
+    pub moves_left: u32,
+}
+
+/// Quantum Zeno effect: a cell watched closely enough stops evolving.
+///
+/// Measuring the same cell weakly [`STREAK`] moves in a row freezes it for
+/// [`FROZEN_MOVES`] moves. A frozen cell takes no measurement drift or
+/// decoherence, is never picked by a decoherence collapse, and ignores
+/// entanglement and interference. The player can still resolve it, which
+/// ends the freeze.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ZenoTracker {
+    /// Cell measured by the current run of back-to-back weak measurements.
+    pub streak_cell: Option<usize>,
+    pub streak: u32,
+    /// Frozen cells and the moves each has left.
+    pub frozen: BTreeMap<usize, u32>,
+}
+
+impl ZenoTracker {
+    pub fn is_frozen(&self, index: usize) -> bool {
+        self.frozen.contains_key(&index)
+    }
+
+    /// Account for one accepted move; `measured` is the cell it weakly
+    /// measured, if any. Returns the cell this move froze.
+    pub(crate) fn advance(&mut self, measured: Option<usize>) -> Option<usize> {
+        self.frozen.retain(|_, left| {
+            *left = left.saturating_sub(1);
+            *left > 0
+        });
+        let Some(index) = measured else {
+            self.streak_cell = None;
+            self.streak = 0;
+            return None;
+        };
+        if self.streak_cell == Some(index) {
+            self.streak = self.streak.saturating_add(1);
+        } else {
+            self.streak_cell = Some(index);
+            self.streak = 1;
+        }
+        if self.streak < STREAK {
+            return None;
+        }
+        self.streak_cell = None;
+        self.streak = 0;
+        self.frozen.insert(index, FROZEN_MOVES);
+        Some(index)
+    }
+
+    /// The cell collapsed; it has nothing left to freeze.
+    pub(crate) fn release(&mut self, index: usize) {
+        self.frozen.remove(&index);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn back_to_back_measurements_freeze_for_a_while() {
+        let mut zeno = ZenoTracker::default();
+        assert_eq!(zeno.advance(Some(4)), None);
+        assert_eq!(zeno.advance(None), None);
+        assert_eq!(zeno.advance(Some(4)), None);
+        assert_eq!(zeno.advance(Some(4)), None);
+        assert_eq!(zeno.advance(Some(4)), Some(4));
+        assert!(zeno.is_frozen(4));
+
+        for _ in 1..FROZEN_MOVES {
+            zeno.advance(Some(7));
+            zeno.advance(None);
+        }
+        // Interleaved moves never let 7 build a streak; 4 has thawed.
+        assert!(!zeno.is_frozen(4));
+        assert!(!zeno.is_frozen(7));
+    }
+}
@@ -0,0 +1,166 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::circuit::Region;
+use crate::prelude::*;
+
+/// A change to the rules inside a [`Zone`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Modifier {
+    /// Entanglement neither reaches nor leaves the zone's cells.
+    NoEntanglement,
+    /// Weak measurements drift the zone's hints twice as far.
+    DoubleDrift,
+    /// The zone's hints carry twice the noise.
+    DoubleHintNoise,
+}
+
+impl Modifier {
+    fn bit(self) -> u8 {
+        match self {
+            Self::NoEntanglement => 1,
+            Self::DoubleDrift => 2,
+            Self::DoubleHintNoise => 4,
+        }
+    }
+}
+
+/// A stretch of board with its own rules, placed by a scenario. Where
+/// zones overlap, their modifiers add up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Zone {
+    pub region: Region,
+    pub modifiers: Vec<Modifier>,
+}
+
+impl Zone {
+    /// Shielded from the rest of the board: no entanglement.
+    pub fn stabilized(region: Region) -> Self {
+        Self {
+            region,
+            modifiers: vec![Modifier::NoEntanglement],
+        }
+    }
+
+    /// Restless: doubled drift and doubled hint noise.
+    pub fn turbulent(region: Region) -> Self {
+        Self {
+            region,
+            modifiers: vec![Modifier::DoubleDrift, Modifier::DoubleHintNoise],
+        }
+    }
+}
+
+/// The modifiers in force on each cell, worked out once from the config's
+/// zones. Empty when the board has none.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct ZoneMap {
+    cells: Vec<u8>,
+}
+
+impl ZoneMap {
+    pub fn new(zones: &[Zone], width: u32, height: u32) -> Self {
+        if zones.is_empty() {
+            return Self::default();
+        }
+        let mut cells = vec![0; (width * height) as usize];
+        for zone in zones {
+            let bits = zone.modifiers.iter().fold(0, |bits, m| bits | m.bit());
+            for index in zone.region.cells(width, height) {
+                if let Some(cell) = cells.get_mut(index) {
+                    *cell |= bits;
+                }
+            }
+        }
+        Self { cells }
+    }
+
+    pub fn has(&self, index: usize, modifier: Modifier) -> bool {
+        self.cells
+            .get(index)
+            .is_some_and(|&bits| bits & modifier.bit() != 0)
+    }
+
+    /// Whether entanglement is cut off at `index`.
+    pub fn isolated(&self, index: usize) -> bool {
+        self.has(index, Modifier::NoEntanglement)
+    }
+
+    /// Multiplier on measurement drift at `index`.
+    pub fn drift_scale(&self, index: usize) -> f64 {
+        if self.has(index, Modifier::DoubleDrift) {
+            2.0
+        } else {
+            1.0
+        }
+    }
+
+    /// Multiplier on hint noise at `index`.
+    pub fn noise_scale(&self, index: usize) -> f64 {
+        if self.has(index, Modifier::DoubleHintNoise) {
+            2.0
+        } else {
+            1.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::GridConfig;
+    use crate::entanglement::LinkType;
+    use crate::grid::{CellState, QuantumGrid};
+
+    #[test]
+    fn zones_cut_entanglement_and_stir_hints() {
+        let left = Region::Rect {
+            x0: 0,
+            y0: 0,
+            x1: 4,
+            y1: 8,
+        };
+        let config = GridConfig::new(8, 8, 10, 42, "theorist");
+        let mut grid = QuantumGrid::from_config(config.clone().with_zone(Zone::stabilized(left)));
+        assert!(grid.zones.isolated(0) && !grid.zones.isolated(4));
+        assert!(grid
+            .entanglement
+            .pairs
+            .iter()
+            .all(|pair| [pair.left, pair.right].iter().all(|&i| i % 8 >= 4)));
+
+        grid.reveal_cell(7, 7);
+        let unresolved =
+            |g: &QuantumGrid, i: usize| matches!(g.cells[i].state, CellState::Superposition { .. });
+        let inside = (0..64)
+            .find(|&i| i % 8 < 4 && unresolved(&grid, i))
+            .unwrap();
+        let outside = (0..64)
+            .find(|&i| i % 8 >= 4 && !grid.mine_map[i] && unresolved(&grid, i))
+            .unwrap();
+        grid.entanglement
+            .add_pair(outside, inside, 1.0, LinkType::BellState);
+        grid.reveal_cell(outside as u32 % 8, outside as u32 / 8);
+        assert!(unresolved(&grid, inside));
+
+        // The same draw drifts a turbulent cell twice as far.
+        let everywhere = Region::Rect {
+            x0: 0,
+            y0: 0,
+            x1: 8,
+            y1: 8,
+        };
+        let mut calm = QuantumGrid::from_config(config.clone());
+        let mut stirred = QuantumGrid::from_config(config.with_zone(Zone::turbulent(everywhere)));
+        let drift = |g: &mut QuantumGrid| {
+            let before = g.measure_weak(3, 3).unwrap();
+            g.get_probability_cloud()[27] - before
+        };
+        let (calm, stirred) = (drift(&mut calm), drift(&mut stirred));
+        assert!((stirred - 2.0 * calm).abs() < 1e-9, "{calm} vs {stirred}");
+    }
+}
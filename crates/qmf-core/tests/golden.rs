@@ -0,0 +1,199 @@
+//! Golden replays: recorded games re-simulated on every `cargo test`, each
+//! checked against the state hash it finished with when recorded. A
+//! refactor that changes behaviour anywhere along one of these games fails
+//! here, at the step where play first diverged when the RNG audit catches
+//! it.
+//!
+//! After an intentional rules change (with its `RULESET_VERSION` bump),
+//! re-record the library with `QMF_BLESS=1 cargo test -p qmf-core --test
+//! golden` and commit the new files.
+
+use std::fs;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use qmf_core::action::{Action, IllegalAction};
+use qmf_core::circuit::Region;
+use qmf_core::commitment::to_hex;
+use qmf_core::config::{DaemonRules, GridConfig, TimedRules, TurnRules};
+use qmf_core::grid::QuantumGrid;
+use qmf_core::objective::Objective;
+use qmf_core::replay::Replay;
+use qmf_core::rng::SplitMix64;
+use qmf_core::selfplay::Strategy;
+use qmf_core::zone::Zone;
+
+/// Longest game recorded, in steps.
+const MAX_STEPS: usize = 300;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Golden {
+    /// Hex [`QuantumGrid::state_hash`] of the final position.
+    state_hash: String,
+    replay: Replay,
+}
+
+/// How a library game is played when it is recorded.
+#[derive(Clone, Copy)]
+struct Script {
+    strategy: Strategy,
+    /// Swap some moves for a Hadamard or weak measurement on the same cell.
+    tools: bool,
+    /// Let time pass every few moves.
+    advance: bool,
+}
+
+const PLAIN: Script = Script {
+    strategy: Strategy::LowestHint,
+    tools: false,
+    advance: false,
+};
+
+fn library() -> Vec<(&'static str, GridConfig, Script)> {
+    let with_tools = Script {
+        tools: true,
+        ..PLAIN
+    };
+    vec![
+        (
+            "observer-9x9",
+            GridConfig::new(9, 9, 10, 1, "observer"),
+            PLAIN,
+        ),
+        (
+            "researcher-16x16",
+            GridConfig::new(16, 16, 40, 2, "researcher"),
+            PLAIN,
+        ),
+        (
+            "theorist-random-shields",
+            GridConfig::new(16, 16, 40, 3, "theorist").with_shields(4),
+            Script {
+                strategy: Strategy::Random,
+                ..PLAIN
+            },
+        ),
+        (
+            "researcher-tools",
+            GridConfig::new(12, 12, 20, 4, "researcher").with_swap_charges(2),
+            with_tools,
+        ),
+        (
+            "timed",
+            GridConfig::new(12, 12, 20, 5, "observer").with_timed(TimedRules {
+                ambient_decoherence: 2,
+                link_half_life_ms: Some(4_000),
+                containment_decay: 0,
+            }),
+            Script {
+                advance: true,
+                ..with_tools
+            },
+        ),
+        (
+            "turns-daemon",
+            GridConfig::new(12, 12, 20, 6, "researcher")
+                .with_turns(TurnRules::default())
+                .with_daemon(DaemonRules::default()),
+            with_tools,
+        ),
+        (
+            "zones-objectives",
+            GridConfig::new(12, 12, 20, 7, "theorist")
+                .with_zone(Zone::turbulent(Region::Rect {
+                    x0: 0,
+                    y0: 0,
+                    x1: 6,
+                    y1: 12,
+                }))
+                .with_objective(Objective::ResolveAll)
+                .with_objective(Objective::DecoherenceUnder { points: 5_000 }),
+            with_tools,
+        ),
+    ]
+}
+
+/// Play `config` by `script` and keep the game as an audited replay.
+fn record(config: &GridConfig, script: Script) -> Replay {
+    let mut grid = QuantumGrid::from_config(config.clone());
+    let mut replay = Replay::new(config.clone());
+    let mut rng = SplitMix64::new(config.seed);
+    let mut t_ms = 0;
+    for step in 1..=MAX_STEPS {
+        if grid.game_over || grid.won {
+            break;
+        }
+        let action = if script.advance && step % 5 == 0 {
+            Action::Advance { dt_ms: 750 }
+        } else {
+            let Some(action) = script.strategy.choose(&grid, &mut rng) else {
+                break;
+            };
+            match action.coords() {
+                Some((x, y)) if script.tools && step % 3 == 0 => {
+                    let tool = if step % 2 == 0 {
+                        Action::Hadamard { x, y }
+                    } else {
+                        Action::MeasureWeak { x, y }
+                    };
+                    if grid.validate_action(&tool).is_ok() {
+                        tool
+                    } else {
+                        action
+                    }
+                }
+                _ => action,
+            }
+        };
+        let action = match grid.validate_action(&action) {
+            Ok(()) => action,
+            Err(IllegalAction::NotEnoughActionPoints) => Action::EndTurn,
+            Err(_) => break,
+        };
+        t_ms += 250;
+        grid.apply_at(action, t_ms).unwrap();
+        replay.record(t_ms, action);
+    }
+    replay.audited().unwrap()
+}
+
+fn path(name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden")
+        .join(format!("{name}.json"))
+}
+
+#[test]
+fn golden_replays_still_play_out_the_same() {
+    let bless = std::env::var_os("QMF_BLESS").is_some();
+    for (name, config, script) in library() {
+        if bless {
+            let replay = record(&config, script);
+            let golden = Golden {
+                state_hash: to_hex(&replay.play().unwrap().state_hash()),
+                replay,
+            };
+            fs::create_dir_all(path(name).parent().unwrap()).unwrap();
+            fs::write(path(name), serde_json::to_string(&golden).unwrap() + "\n").unwrap();
+            continue;
+        }
+        let text = fs::read_to_string(path(name))
+            .unwrap_or_else(|_| panic!("{name}: no golden replay; record it with QMF_BLESS=1"));
+        let golden: Golden = serde_json::from_str(&text).unwrap();
+        assert_eq!(golden.replay.config, config, "{name}: library changed");
+        let grid = golden
+            .replay
+            .play()
+            .unwrap_or_else(|error| panic!("{name}: {error:?}"));
+        assert_eq!(
+            to_hex(&grid.state_hash()),
+            golden.state_hash,
+            "{name}: final state differs"
+        );
+        assert!(
+            golden.replay.steps.len() > 5,
+            "{name}: too short to guard much"
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use qmf_core::action::{Action, IllegalAction};
+use qmf_core::config::GridConfig;
+use qmf_core::grid::{CellState, QuantumGrid};
+use qmf_core::observation::{ObservationSpec, ProbabilityView};
+
+/// Reward for clearing the whole board.
+pub const WIN_REWARD: f32 = 1.0;
+/// Reward for setting off a mine that ends the game.
+pub const DETONATION_REWARD: f32 = -1.0;
+/// Reward for an action the engine refused; the board is left unchanged.
+pub const ILLEGAL_REWARD: f32 = -0.1;
+
+/// What the agent sees after a reset or step: one `width × height` channel
+/// per feature, row-major, as flat `f32` arrays ready to wrap as tensors.
+/// Channels the [`ObservationSpec`] hides are `None`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Observation {
+    pub width: u32,
+    pub height: u32,
+    /// Mine probability of each unresolved cell, as the spec's
+    /// [`ProbabilityView`] shows it; resolved cells read `1.0` (contained
+    /// or detonated) or `0.0` (revealed).
+    pub probabilities: Option<Vec<f32>>,
+    /// Adjacent mine count of revealed cells, `-1.0` everywhere else.
+    pub numbers: Vec<f32>,
+    /// Strength of the strongest entanglement link on each cell, `0.0`
+    /// for unlinked cells.
+    pub entanglement: Option<Vec<f32>>,
+    /// `1.0` on every mine, once mines are placed.
+    pub mines: Option<Vec<f32>>,
+}
+
+impl Observation {
+    /// Observe `grid` through a spec that passed
+    /// [`ObservationSpec::validate`] for its config.
+    fn of(grid: &QuantumGrid, spec: &ObservationSpec) -> Self {
+        let to_f32 = |values: Vec<f64>| values.into_iter().map(|p| p as f32).collect();
+        let probabilities = match spec.probabilities {
+            ProbabilityView::Displayed => Some(to_f32(grid.get_probability_cloud())),
+            ProbabilityView::True => grid.unscrambled_probabilities().ok().map(to_f32),
+            ProbabilityView::Hidden => None,
+        };
+        let entanglement = spec.entanglement.then(|| {
+            let mut strengths = vec![0.0_f32; grid.cells.len()];
+            for pair in &grid.entanglement.pairs {
+                for index in [pair.left, pair.right] {
+                    if let Some(slot) = strengths.get_mut(index) {
+                        *slot = slot.max(pair.strength as f32);
+                    }
+                }
+            }
+            strengths
+        });
+        let mines = spec.ground_truth.then(|| {
+            grid.mine_map
+                .iter()
+                .map(|&mine| if mine { 1.0 } else { 0.0 })
+                .collect()
+        });
+        Self {
+            width: grid.width,
+            height: grid.height,
+            probabilities,
+            numbers: grid
+                .cells
+                .iter()
+                .map(|cell| match cell.state {
+                    CellState::Revealed { adjacent_mines } => f32::from(adjacent_mines),
+                    _ => -1.0,
+                })
+                .collect(),
+            entanglement,
+            mines,
+        }
+    }
+
+    /// The visible channels in a fixed order: probabilities, numbers,
+    /// entanglement, mines.
+    pub fn channels(&self) -> Vec<&[f32]> {
+        [
+            self.probabilities.as_deref(),
+            Some(self.numbers.as_slice()),
+            self.entanglement.as_deref(),
+            self.mines.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
+    }
+
+    /// The visible channels stacked channel-first
+    /// (`[channels, height, width]`).
+    pub fn to_tensor(&self) -> Vec<f32> {
+        self.channels().concat()
+    }
+}
+
+/// Gym-style environment over one board configuration: [`Self::reset`]
+/// deals a new board, [`Self::step`] plays one action against it.
+#[derive(Debug, Clone)]
+pub struct Env {
+    config: GridConfig,
+    spec: ObservationSpec,
+    grid: QuantumGrid,
+}
+
+impl Env {
+    /// An environment dealing boards like `config`; its seed is replaced on
+    /// every [`Self::reset`].
+    pub fn new(config: GridConfig) -> Self {
+        let grid = QuantumGrid::from_config(config.clone());
+        Self {
+            config,
+            spec: ObservationSpec::default(),
+            grid,
+        }
+    }
+
+    /// Observe through `spec` instead of what a player sees. Analysis views
+    /// need a config with full analysis enabled.
+    pub fn with_spec(mut self, spec: ObservationSpec) -> Result<Self, IllegalAction> {
+        spec.validate(&self.config)?;
+        self.spec = spec;
+        Ok(self)
+    }
+
+    pub fn spec(&self) -> &ObservationSpec {
+        &self.spec
+    }
+
+    /// Deal a fresh board from `seed`.
+    pub fn reset(&mut self, seed: u64) -> Observation {
+        let config = GridConfig {
+            seed,
+            ..self.config.clone()
+        };
+        self.grid = QuantumGrid::from_config(config);
+        Observation::of(&self.grid, &self.spec)
+    }
+
+    /// Play `action` and return the new observation, its reward and
+    /// whether the episode is over. Safe progress earns each newly cleared
+    /// cell's share of the board.
+    pub fn step(&mut self, action: Action) -> (Observation, f32, bool) {
+        let resolved = self.grid.resolved_cells();
+        let rejected =
+            self.grid.validate_action(&action).is_err() || self.grid.apply(action).is_rejection();
+        let reward = if rejected {
+            ILLEGAL_REWARD
+        } else if self.grid.won {
+            WIN_REWARD
+        } else if self.grid.game_over {
+            DETONATION_REWARD
+        } else {
+            let cleared = self.grid.resolved_cells().saturating_sub(resolved);
+            cleared as f32 / self.grid.cells.len() as f32
+        };
+        let done = self.grid.game_over || self.grid.won;
+        (Observation::of(&self.grid, &self.spec), reward, done)
+    }
+
+    /// Size of the discrete action space used by [`Self::action`].
+    pub fn action_count(&self) -> usize {
+        2 * self.grid.cells.len()
+    }
+
+    /// Discrete action `index`: reveals for the first `width × height`
+    /// indices, containments for the rest, both row-major.
+    pub fn action(&self, index: usize) -> Option<Action> {
+        let cells = self.grid.cells.len();
+        let cell = index % cells;
+        let (x, y) = (cell as u32 % self.grid.width, cell as u32 / self.grid.width);
+        match index / cells {
+            0 => Some(Action::Reveal { x, y }),
+            1 => Some(Action::Contain { x, y }),
+            _ => None,
+        }
+    }
+
+    /// The board being played, for inspection and rendering.
+    pub fn grid(&self) -> &QuantumGrid {
+        &self.grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qmf_core::config::AssistLevel;
+
+    #[test]
+    fn episodes_are_reproducible_and_rewarded() {
+        let mut env = Env::new(GridConfig::new(8, 8, 10, 0, "observer"));
+        let first = env.reset(42);
+        assert_eq!(first.to_tensor().len(), 3 * 64);
+        assert!(first.numbers.iter().all(|&n| n == -1.0));
+
+        let (observation, reward, done) = env.step(env.action(0).unwrap());
+        assert!(reward > 0.0 && !done);
+        assert!(observation.numbers[0] >= 0.0);
+        let (_, reward, _) = env.step(Action::Reveal { x: 0, y: 0 });
+        assert_eq!(reward, ILLEGAL_REWARD);
+        assert_eq!(env.action(env.action_count()), None);
+
+        let mine = env.grid().mine_map.iter().position(|&m| m).unwrap();
+        let (_, reward, done) = env.step(env.action(mine).unwrap());
+        assert_eq!((reward, done), (DETONATION_REWARD, true));
+
+        assert_eq!(env.reset(42), first);
+    }
+
+    #[test]
+    fn spec_picks_the_channels() {
+        let config = GridConfig::new(8, 8, 10, 0, "observer");
+        let spec = ObservationSpec::default()
+            .with_probabilities(ProbabilityView::Hidden)
+            .with_entanglement(false)
+            .with_ground_truth(true);
+        let mut env = Env::new(config.clone()).with_spec(spec).unwrap();
+        env.reset(42);
+        let (observation, _, _) = env.step(Action::Reveal { x: 0, y: 0 });
+        assert_eq!(observation.channels().len(), 2);
+        let mines = observation.mines.unwrap();
+        assert_eq!(mines.iter().filter(|&&m| m == 1.0).count(), 10);
+
+        let ranked = config.with_assist(AssistLevel::None);
+        assert_eq!(
+            Env::new(ranked).with_spec(spec).unwrap_err(),
+            IllegalAction::AssistDisabled
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use std::io::{self, Read};
+use std::process::ExitCode;
+
+use qmf_core::compatibility::{self, Compatibility};
+use qmf_core::replay::Replay;
+use qmf_core::tournament::MatchScore;
+
+/// Headless replay verifier: reads a JSON `Replay` from stdin, plays it
+/// back and prints the verified `MatchScore` as JSON. Builds for
+/// `wasm32-wasip1` so verification can run on edge platforms.
+fn main() -> ExitCode {
+    let mut input = String::new();
+    if let Err(error) = io::stdin().read_to_string(&mut input) {
+        eprintln!("could not read stdin: {error}");
+        return ExitCode::FAILURE;
+    }
+    match verify(&input) {
+        Ok(score) => {
+            println!("{score}");
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Parse, gate and play a replay, returning its score as JSON. Only
+/// replays stamped with this engine's rules are scored.
+fn verify(input: &str) -> Result<String, String> {
+    let replay: Replay =
+        serde_json::from_str(input).map_err(|error| format!("invalid replay: {error}"))?;
+    match compatibility::check(&replay) {
+        Compatibility::Compatible => {}
+        Compatibility::NeedsRuleset { version } => {
+            return Err(format!("replay needs ruleset version {version}"));
+        }
+        Compatibility::Incompatible { reason } => return Err(format!("rejected: {reason}")),
+    }
+    let grid = replay
+        .play()
+        .map_err(|error| format!("rejected: {error}"))?;
+    serde_json::to_string(&MatchScore::from_grid(&grid)).map_err(|error| error.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use qmf_core::action::Action;
+    use qmf_core::config::GridConfig;
+
+    #[test]
+    fn scores_valid_replays_and_rejects_the_rest() {
+        let mut replay = Replay::new(GridConfig::new(8, 8, 10, 42, "observer"));
+        replay.record(0, Action::Reveal { x: 0, y: 0 });
+        let json = serde_json::to_string(&replay).unwrap();
+        let score: MatchScore = serde_json::from_str(&verify(&json).unwrap()).unwrap();
+        assert!(score.resolved > 0);
+
+        replay.record(10, Action::Reveal { x: 0, y: 0 });
+        let tampered = serde_json::to_string(&replay).unwrap();
+        assert!(verify(&tampered).unwrap_err().starts_with("rejected"));
+
+        replay.stamp = None;
+        let unstamped = serde_json::to_string(&replay).unwrap();
+        assert!(verify(&unstamped).is_err());
+        assert!(verify("{}").unwrap_err().starts_with("invalid replay"));
+    }
+}
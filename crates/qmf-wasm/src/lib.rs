@@ -1,6 +1,33 @@
-use qmf_core::grid::{CellState, QuantumCell as CoreQuantumCell, QuantumGrid};
+use qmf_core::action::Action;
+use qmf_core::analysis;
+use qmf_core::codec;
+use qmf_core::commitment::{self, SeedCommitment};
+use qmf_core::compatibility;
+use qmf_core::config::{AssistLevel, GridConfig};
+use qmf_core::cursor::{Cursor, CursorCommand, Direction, EdgeRule};
+use qmf_core::generator::{self, SeedSearch};
+use qmf_core::grid::{CellState, Mark, QuantumCell as CoreQuantumCell, QuantumGrid};
+use qmf_core::haptics;
+use qmf_core::input::{Bindings, ControllerEvent, InputMapper};
+use qmf_core::manifest::{ManifestError, SignedManifest, MANIFEST_KEY};
+use qmf_core::notes::CellNote;
+use qmf_core::pack::Pack;
+use qmf_core::profile::PlayerProfile;
+use qmf_core::progress::{Phase, ProgressSink};
+use qmf_core::reconstruct::ObservedCell;
+use qmf_core::replay::{Replay, ReplayIndex};
+use qmf_core::selfplay::Strategy;
+use qmf_core::session::{GameStats, SessionManager};
+use qmf_core::solver::SolveBudget;
+use qmf_core::splits;
+use qmf_core::storage::{self, Storage, StorageError, PROFILE_KEY, REPLAY_PREFIX};
+use qmf_core::timeline::Timeline;
+use qmf_core::tuner::{DifficultyParams, Tuner};
 use wasm_bindgen::prelude::*;
 
+#[cfg(feature = "tracing")]
+mod telemetry;
+
 #[wasm_bindgen]
 pub struct QuantumCell {
     x: u32,
@@ -67,6 +94,12 @@ impl From<&CoreQuantumCell> for QuantumCell {
 pub struct QuantumGame {
     grid: QuantumGrid,
     quantum_inspector_enabled: bool,
+    timeline: Option<Timeline>,
+    cursor: Cursor,
+    input: InputMapper,
+    session: SessionManager,
+    /// Whether this game has been fed to `session` yet.
+    session_recorded: bool,
 }
 
 /// Create a new game with a random seed.
@@ -78,6 +111,11 @@ pub fn init_game(width: u32, height: u32, mine_count: u32, difficulty: &str) ->
     QuantumGame {
         grid: QuantumGrid::new(width, height, mine_count, seed, difficulty),
         quantum_inspector_enabled: false,
+        timeline: None,
+        cursor: Cursor::default(),
+        input: InputMapper::default(),
+        session: SessionManager::default(),
+        session_recorded: false,
     }
 }
 
@@ -93,21 +131,631 @@ pub fn init_game_seeded(
     QuantumGame {
         grid: QuantumGrid::new(width, height, mine_count, seed, difficulty),
         quantum_inspector_enabled: false,
+        timeline: None,
+        cursor: Cursor::default(),
+        input: InputMapper::default(),
+        session: SessionManager::default(),
+        session_recorded: false,
+    }
+}
+
+/// Create a new game from a full `GridConfig` object (first-click policy,
+/// safe-zone radius, ...). Missing optional fields fall back to defaults.
+///
+/// Unlike `init_game`, the config is not silently adjusted: an impossible
+/// request throws the serialized `ConfigError` (e.g.
+/// `{ kind: "too_many_mines", requested, max }`) so the UI can warn.
+#[wasm_bindgen]
+pub fn init_game_with_config(config: JsValue) -> Result<QuantumGame, JsValue> {
+    let config: GridConfig = serde_wasm_bindgen::from_value(config)
+        .map_err(|error| JsValue::from_str(&format!("invalid config: {error}")))?;
+    let grid = QuantumGrid::try_from_config(config).map_err(|error| {
+        to_js_value(&error).unwrap_or_else(|_| JsValue::from_str(&error.to_string()))
+    })?;
+    Ok(QuantumGame {
+        grid,
+        quantum_inspector_enabled: false,
+        timeline: None,
+        cursor: Cursor::default(),
+        input: InputMapper::default(),
+        session: SessionManager::default(),
+        session_recorded: false,
+    })
+}
+
+/// Rebuild a board for analysis from an array of observed cells
+/// (`{ x, y, seen }`, e.g. pasted from a screenshot) and the board's
+/// `GridConfig`. Moves on it are refused; an inconsistent paste throws the
+/// serialized `ReconstructError`.
+#[wasm_bindgen]
+pub fn reconstruct_game(cells: JsValue, config: JsValue) -> Result<QuantumGame, JsValue> {
+    let cells: Vec<ObservedCell> = serde_wasm_bindgen::from_value(cells)
+        .map_err(|error| JsValue::from_str(&format!("invalid cells: {error}")))?;
+    let config: GridConfig = serde_wasm_bindgen::from_value(config)
+        .map_err(|error| JsValue::from_str(&format!("invalid config: {error}")))?;
+    let grid = QuantumGrid::reconstruct(&cells, config).map_err(|error| {
+        to_js_value(&error).unwrap_or_else(|_| JsValue::from_str(&error.to_string()))
+    })?;
+    Ok(QuantumGame {
+        grid,
+        quantum_inspector_enabled: false,
+        timeline: None,
+        cursor: Cursor::default(),
+        input: InputMapper::default(),
+        session: SessionManager::default(),
+        session_recorded: false,
+    })
+}
+
+/// Practice mode: restart a serialized `Replay` after its first
+/// `move_index` steps, RNG included. `practice()` on the game then tracks
+/// where the new run leaves the original; a replay that does not play back
+/// throws the serialized `ReplayError`.
+#[wasm_bindgen]
+pub fn practice_replay(replay: JsValue, move_index: u32) -> Result<QuantumGame, JsValue> {
+    let replay: Replay = serde_wasm_bindgen::from_value(replay)
+        .map_err(|error| JsValue::from_str(&format!("invalid replay: {error}")))?;
+    let grid = QuantumGrid::from_replay_at(&replay, move_index as usize).map_err(|error| {
+        to_js_value(&error).unwrap_or_else(|_| JsValue::from_str(&error.to_string()))
+    })?;
+    Ok(QuantumGame {
+        grid,
+        quantum_inspector_enabled: false,
+        timeline: None,
+        cursor: Cursor::default(),
+        input: InputMapper::default(),
+        session: SessionManager::default(),
+        session_recorded: false,
+    })
+}
+
+/// Check a `GridConfig` object without starting a game. Returns `null` when
+/// it is valid, otherwise the serialized `ConfigError`.
+#[wasm_bindgen]
+pub fn validate_config(config: JsValue) -> Result<JsValue, JsValue> {
+    let config: GridConfig = serde_wasm_bindgen::from_value(config)
+        .map_err(|error| JsValue::from_str(&format!("invalid config: {error}")))?;
+    match config.validate() {
+        Ok(()) => Ok(JsValue::NULL),
+        Err(error) => to_js_value(&error),
+    }
+}
+
+/// Check a server's published seed commitment (hex) against the seed and
+/// salt (hex) it revealed after the game.
+#[wasm_bindgen]
+pub fn verify_seed_commitment(commitment: &str, seed: u64, salt: &str) -> Result<bool, JsValue> {
+    let commitment = SeedCommitment::from_hex(commitment).map_err(JsValue::from_str)?;
+    let salt = commitment::from_hex(salt).map_err(JsValue::from_str)?;
+    Ok(commitment.verify(seed, &salt))
+}
+
+/// A previous run (e.g. the daily leader's) to overlay on the live game.
+#[wasm_bindgen]
+pub struct GhostReplay {
+    index: ReplayIndex,
+}
+
+/// Load a serialized `Replay` as a ghost. Throws the serialized
+/// `ReplayError` if the replay does not play back cleanly.
+#[wasm_bindgen]
+pub fn load_ghost(replay: JsValue) -> Result<GhostReplay, JsValue> {
+    let replay: Replay = serde_wasm_bindgen::from_value(replay)
+        .map_err(|error| JsValue::from_str(&format!("invalid replay: {error}")))?;
+    let index = replay.index().map_err(|error| {
+        to_js_value(&error).unwrap_or_else(|_| JsValue::from_str(&error.to_string()))
+    })?;
+    Ok(GhostReplay { index })
+}
+
+/// A scenario pack loaded from its file.
+#[wasm_bindgen]
+pub struct ScenarioPack {
+    pack: Pack,
+}
+
+/// Unpack a scenario pack file and check it. Throws the serialized
+/// `PackError`.
+#[wasm_bindgen]
+pub fn load_pack(bytes: &[u8]) -> Result<ScenarioPack, JsValue> {
+    let pack = Pack::read(bytes).map_err(|error| {
+        to_js_value(&error).unwrap_or_else(|_| JsValue::from_str(&error.to_string()))
+    })?;
+    Ok(ScenarioPack { pack })
+}
+
+/// Check a serialized `Replay`'s ruleset stamp against this engine, e.g.
+/// before submitting it to a leaderboard. Returns the `Compatibility`.
+#[wasm_bindgen]
+pub fn check_replay_compatibility(replay: JsValue) -> Result<JsValue, JsValue> {
+    let replay: Replay = serde_wasm_bindgen::from_value(replay)
+        .map_err(|error| JsValue::from_str(&format!("invalid replay: {error}")))?;
+    to_js_value(&compatibility::check(&replay))
+}
+
+/// Adaptive difficulty: suggest `DifficultyParams` for the next game from
+/// the parameters of the last one and the player's recent results (`true`
+/// for a win), aiming at `target_win_rate`. Simulates games, so call it
+/// between games rather than during one.
+#[wasm_bindgen]
+pub fn suggest_difficulty(
+    current: JsValue,
+    recent: Vec<u8>,
+    target_win_rate: f64,
+) -> Result<JsValue, JsValue> {
+    let current: DifficultyParams = serde_wasm_bindgen::from_value(current)
+        .map_err(|error| JsValue::from_str(&format!("invalid params: {error}")))?;
+    let recent: Vec<bool> = recent.into_iter().map(|won| won != 0).collect();
+    to_js_value(&Tuner::default().adapt(&current, &recent, target_win_rate, Strategy::LowestHint))
+}
+
+#[wasm_bindgen]
+extern "C" {
+    /// The browser's `Storage` interface.
+    type WebStorage;
+
+    #[wasm_bindgen(method, catch, js_name = getItem)]
+    fn get_item(this: &WebStorage, key: &str) -> Result<Option<String>, JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = setItem)]
+    fn set_item(this: &WebStorage, key: &str, value: &str) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(method, catch, js_name = removeItem)]
+    fn remove_item(this: &WebStorage, key: &str) -> Result<(), JsValue>;
+
+    #[wasm_bindgen(method)]
+    fn key(this: &WebStorage, index: u32) -> Option<String>;
+
+    #[wasm_bindgen(method, getter)]
+    fn length(this: &WebStorage) -> u32;
+}
+
+/// [`Storage`] backed by the browser's `localStorage`.
+pub struct LocalStorage {
+    storage: WebStorage,
+}
+
+impl LocalStorage {
+    /// Fails where `localStorage` is missing or blocked, e.g. in a worker
+    /// or with site data disabled.
+    pub fn open() -> Result<Self, StorageError> {
+        js_sys::Reflect::get(&js_sys::global(), &JsValue::from_str("localStorage"))
+            .ok()
+            .filter(|storage| !storage.is_undefined() && !storage.is_null())
+            .map(|storage| Self {
+                storage: storage.unchecked_into(),
+            })
+            .ok_or_else(|| StorageError::Backend {
+                message: "localStorage is unavailable".to_string(),
+            })
+    }
+}
+
+fn backend_error(error: JsValue) -> StorageError {
+    StorageError::Backend {
+        message: format!("{error:?}"),
+    }
+}
+
+impl Storage for LocalStorage {
+    fn get(&self, key: &str) -> Result<Option<String>, StorageError> {
+        storage::check_key(key)?;
+        self.storage.get_item(key).map_err(backend_error)
+    }
+
+    fn put(&mut self, key: &str, value: &str) -> Result<(), StorageError> {
+        storage::check_key(key)?;
+        self.storage.set_item(key, value).map_err(backend_error)
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), StorageError> {
+        storage::check_key(key)?;
+        self.storage.remove_item(key).map_err(backend_error)
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, StorageError> {
+        let mut keys: Vec<String> = (0..self.storage.length())
+            .filter_map(|index| self.storage.key(index))
+            .filter(|key| key.starts_with(prefix))
+            .collect();
+        keys.sort();
+        Ok(keys)
+    }
+}
+
+fn storage_error(error: StorageError) -> JsValue {
+    to_js_value(&error).unwrap_or_else(|_| JsValue::from_str(&error.to_string()))
+}
+
+/// The `PlayerProfile` saved in `localStorage`, or `null` if there is none
+/// yet. Throws the serialized `StorageError`.
+#[wasm_bindgen]
+pub fn load_profile() -> Result<JsValue, JsValue> {
+    let storage = LocalStorage::open().map_err(storage_error)?;
+    let profile: Option<PlayerProfile> =
+        storage::load(&storage, PROFILE_KEY).map_err(storage_error)?;
+    to_js_value(&profile)
+}
+
+#[wasm_bindgen]
+pub fn save_profile(profile: JsValue) -> Result<(), JsValue> {
+    let profile: PlayerProfile = serde_wasm_bindgen::from_value(profile)
+        .map_err(|error| JsValue::from_str(&format!("invalid profile: {error}")))?;
+    let mut storage = LocalStorage::open().map_err(storage_error)?;
+    storage::store(&mut storage, PROFILE_KEY, &profile).map_err(storage_error)
+}
+
+/// Combine two devices' copies of a `PlayerProfile` without losing
+/// progress on either, e.g. after fetching the server's copy.
+#[wasm_bindgen]
+pub fn merge_profiles(local: JsValue, remote: JsValue) -> Result<JsValue, JsValue> {
+    let parse = |profile| {
+        serde_wasm_bindgen::from_value::<PlayerProfile>(profile)
+            .map_err(|error| JsValue::from_str(&format!("invalid profile: {error}")))
+    };
+    to_js_value(&parse(local)?.merge(&parse(remote)?))
+}
+
+/// Keep a serialized `Replay` in `localStorage` under `name`.
+#[wasm_bindgen]
+pub fn store_replay(name: &str, replay: JsValue) -> Result<(), JsValue> {
+    let replay: Replay = serde_wasm_bindgen::from_value(replay)
+        .map_err(|error| JsValue::from_str(&format!("invalid replay: {error}")))?;
+    let mut storage = LocalStorage::open().map_err(storage_error)?;
+    storage::store(&mut storage, &format!("{REPLAY_PREFIX}{name}"), &replay).map_err(storage_error)
+}
+
+/// The replay stored under `name`, or `null`.
+#[wasm_bindgen]
+pub fn load_replay(name: &str) -> Result<JsValue, JsValue> {
+    let storage = LocalStorage::open().map_err(storage_error)?;
+    let replay: Option<Replay> =
+        storage::load(&storage, &format!("{REPLAY_PREFIX}{name}")).map_err(storage_error)?;
+    to_js_value(&replay)
+}
+
+/// Names of the stored replays, sorted.
+#[wasm_bindgen]
+pub fn list_replays() -> Result<Vec<String>, JsValue> {
+    let storage = LocalStorage::open().map_err(storage_error)?;
+    let keys = storage.list(REPLAY_PREFIX).map_err(storage_error)?;
+    Ok(keys
+        .into_iter()
+        .map(|key| key[REPLAY_PREFIX.len()..].to_string())
+        .collect())
+}
+
+/// Hex Ed25519 key daily manifests must be signed with, embedded from
+/// `QMF_MANIFEST_KEY` at build time.
+const MANIFEST_PUBLIC_KEY: Option<&str> = option_env!("QMF_MANIFEST_KEY");
+
+fn manifest_key() -> Result<[u8; 32], JsValue> {
+    MANIFEST_PUBLIC_KEY
+        .and_then(|hex| commitment::from_hex(hex).ok())
+        .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+        .ok_or_else(|| JsValue::from_str("this build embeds no manifest key"))
+}
+
+fn manifest_error(error: ManifestError) -> JsValue {
+    to_js_value(&error).unwrap_or_else(|_| JsValue::from_str(&error.to_string()))
+}
+
+/// Check a `SignedManifest` fetched from the server against the embedded
+/// key and keep it in `localStorage`, so the dailies it lists stay
+/// playable offline. Returns the `Manifest`; throws the serialized
+/// `ManifestError`.
+#[wasm_bindgen]
+pub fn accept_manifest(signed: JsValue) -> Result<JsValue, JsValue> {
+    let signed: SignedManifest = serde_wasm_bindgen::from_value(signed)
+        .map_err(|error| JsValue::from_str(&format!("invalid manifest: {error}")))?;
+    let manifest = signed.verify(&manifest_key()?).map_err(manifest_error)?;
+    let mut storage = LocalStorage::open().map_err(storage_error)?;
+    storage::store(&mut storage, MANIFEST_KEY, &signed).map_err(storage_error)?;
+    to_js_value(&manifest)
+}
+
+/// The last manifest `accept_manifest` kept, checked again, or `null`.
+#[wasm_bindgen]
+pub fn cached_manifest() -> Result<JsValue, JsValue> {
+    let storage = LocalStorage::open().map_err(storage_error)?;
+    let signed: Option<SignedManifest> =
+        storage::load(&storage, MANIFEST_KEY).map_err(storage_error)?;
+    let Some(signed) = signed else {
+        return Ok(JsValue::NULL);
+    };
+    to_js_value(&signed.verify(&manifest_key()?).map_err(manifest_error)?)
+}
+
+/// Strip a serialized `Replay` down to a `SpectatorReplay` (no seed, no
+/// mine map) that can be shared before others have played the board.
+#[wasm_bindgen]
+pub fn export_spectator_replay(replay: JsValue) -> Result<JsValue, JsValue> {
+    let replay: Replay = serde_wasm_bindgen::from_value(replay)
+        .map_err(|error| JsValue::from_str(&format!("invalid replay: {error}")))?;
+    let spectator = replay.spectator().map_err(|error| {
+        to_js_value(&error).unwrap_or_else(|_| JsValue::from_str(&error.to_string()))
+    })?;
+    to_js_value(&spectator)
+}
+
+/// The serialized `Replay` without its player, notes or absolute times,
+/// for the share button. It still verifies.
+#[wasm_bindgen]
+pub fn scrub_replay(replay: JsValue) -> Result<JsValue, JsValue> {
+    let replay: Replay = serde_wasm_bindgen::from_value(replay)
+        .map_err(|error| JsValue::from_str(&format!("invalid replay: {error}")))?;
+    to_js_value(&replay.scrubbed())
+}
+
+/// Per-cell decision times and the hesitation heatmap of a serialized
+/// `Replay`, for the post-game review screen.
+#[wasm_bindgen]
+pub fn timing_heatmap(replay: JsValue) -> Result<JsValue, JsValue> {
+    let replay: Replay = serde_wasm_bindgen::from_value(replay)
+        .map_err(|error| JsValue::from_str(&format!("invalid replay: {error}")))?;
+    to_js_value(&analysis::timing_heatmap(&replay))
+}
+
+/// Every reveal and containment of a serialized `Replay`, classified by
+/// the solver, for the post-game review. A replay that does not play back
+/// throws the serialized `ReplayError`.
+#[wasm_bindgen]
+pub fn post_mortem(replay: JsValue) -> Result<JsValue, JsValue> {
+    let replay: Replay = serde_wasm_bindgen::from_value(replay)
+        .map_err(|error| JsValue::from_str(&format!("invalid replay: {error}")))?;
+    let review = analysis::post_mortem(&replay).map_err(|error| {
+        to_js_value(&error).unwrap_or_else(|_| JsValue::from_str(&error.to_string()))
+    })?;
+    to_js_value(&review)
+}
+
+/// The serialized `Replay` with coaching tags on the moves worth
+/// reviewing, to save or share.
+#[wasm_bindgen]
+pub fn coach_replay(replay: JsValue) -> Result<JsValue, JsValue> {
+    let mut replay: Replay = serde_wasm_bindgen::from_value(replay)
+        .map_err(|error| JsValue::from_str(&format!("invalid replay: {error}")))?;
+    analysis::coach(&mut replay).map_err(|error| {
+        to_js_value(&error).unwrap_or_else(|_| JsValue::from_str(&error.to_string()))
+    })?;
+    to_js_value(&replay)
+}
+
+/// Forwards core progress reports to a JS `(fraction, phase) => void`.
+struct JsProgress(js_sys::Function);
+
+impl ProgressSink for JsProgress {
+    fn on_progress(&mut self, fraction: f64, phase: Phase) {
+        let phase = to_js_value(&phase).unwrap_or(JsValue::UNDEFINED);
+        // A throwing callback must not abort the operation it observes.
+        let _ = self
+            .0
+            .call2(&JsValue::NULL, &JsValue::from_f64(fraction), &phase);
+    }
+}
+
+/// What a seed-search predicate gets to judge each candidate board by.
+#[derive(serde::Serialize)]
+struct BoardMetrics {
+    three_bv: u32,
+    longest_bell_chain: usize,
+}
+
+type JsPredicate = Box<dyn Fn(&QuantumGrid) -> bool>;
+
+/// A seed search the UI drives a slice at a time, so generation never
+/// freezes the tab. Drop it (or just stop calling `step`) to cancel.
+#[wasm_bindgen]
+pub struct SeedSearchJob {
+    search: SeedSearch<JsPredicate>,
+}
+
+/// Start scanning up to `budget` seeds from `config.seed`. `predicate` is
+/// called with `{ three_bv, longest_bell_chain }` for each candidate board
+/// and should return `true` to accept it; a throw counts as `false`.
+#[wasm_bindgen]
+pub fn start_seed_search(
+    config: JsValue,
+    predicate: js_sys::Function,
+    budget: u32,
+) -> Result<SeedSearchJob, JsValue> {
+    let config: GridConfig = serde_wasm_bindgen::from_value(config)
+        .map_err(|error| JsValue::from_str(&format!("invalid config: {error}")))?;
+    let predicate: JsPredicate = Box::new(move |grid: &QuantumGrid| {
+        let metrics = BoardMetrics {
+            three_bv: generator::three_bv(grid),
+            longest_bell_chain: grid.entanglement.longest_bell_chain(),
+        };
+        to_js_value(&metrics)
+            .and_then(|metrics| predicate.call1(&JsValue::NULL, &metrics))
+            .is_ok_and(|accepted| accepted.is_truthy())
+    });
+    Ok(SeedSearchJob {
+        search: SeedSearch::new(config, predicate, budget),
+    })
+}
+
+#[wasm_bindgen]
+impl SeedSearchJob {
+    /// Scan up to `candidates` more seeds and return the `SearchStatus`.
+    /// `on_progress(fraction, phase)` is called after every candidate.
+    pub fn step(
+        &mut self,
+        candidates: u32,
+        on_progress: Option<js_sys::Function>,
+    ) -> Result<JsValue, JsValue> {
+        let status = match on_progress {
+            Some(callback) => self.search.step_with(candidates, &mut JsProgress(callback)),
+            None => self.search.step(candidates),
+        };
+        to_js_value(&status)
+    }
+
+    pub fn progress(&self) -> f64 {
+        self.search.progress()
+    }
+}
+
+#[wasm_bindgen]
+impl GhostReplay {
+    /// The ghost's progress and last action `t_ms` after its first move.
+    pub fn cursor_at(&self, t_ms: u64) -> Result<JsValue, JsValue> {
+        to_js_value(&self.index.cursor_at(t_ms))
+    }
+}
+
+#[wasm_bindgen]
+impl ScenarioPack {
+    /// The `PackIndex`: title, author, and the scenarios in play order.
+    pub fn index(&self) -> Result<JsValue, JsValue> {
+        to_js_value(&self.pack.index)
+    }
+
+    /// The `Scenario` at `position`, or `null`.
+    pub fn scenario(&self, position: usize) -> Result<JsValue, JsValue> {
+        to_js_value(&self.pack.scenarios.get(position))
+    }
+
+    /// Whether the scenario at `position` is open, given the ids of the
+    /// pack's scenarios the player has completed.
+    pub fn is_unlocked(&self, position: usize, completed: Vec<String>) -> bool {
+        self.pack
+            .is_unlocked(position, &completed.into_iter().collect())
+    }
+
+    /// An asset's bytes, or `undefined` if the pack has no such file.
+    pub fn asset(&self, file: &str) -> Option<Vec<u8>> {
+        self.pack.assets.get(file).cloned()
     }
 }
 
 #[wasm_bindgen]
 impl QuantumGame {
     pub fn reveal_cell(&mut self, x: u32, y: u32) -> Result<JsValue, JsValue> {
+        self.timeline = None;
         let outcome = self.grid.reveal_cell(x, y);
         to_js_value(&outcome)
     }
 
     pub fn contain_cell(&mut self, x: u32, y: u32) -> Result<JsValue, JsValue> {
+        self.timeline = None;
         let outcome = self.grid.contain_cell(x, y);
         to_js_value(&outcome)
     }
 
+    /// Apply an action object (e.g. `{ kind: "contain", x, y }`) and return
+    /// its result. Passing an `action_id` makes the call safe to retry: a
+    /// repeated ID returns the original result without applying it again.
+    pub fn apply_action(
+        &mut self,
+        action: JsValue,
+        action_id: Option<u64>,
+    ) -> Result<JsValue, JsValue> {
+        let action: Action = serde_wasm_bindgen::from_value(action)
+            .map_err(|error| JsValue::from_str(&format!("invalid action: {error}")))?;
+        self.timeline = None;
+        to_js_value(&self.grid.apply_with_id(action, action_id))
+    }
+
+    /// Apply an action object stamped `t_ms` on the caller's clock (e.g.
+    /// `performance.now()`), which times the config's speedrun splits.
+    /// Throws a `ClockError` if time went backwards.
+    pub fn apply_action_at(&mut self, action: JsValue, t_ms: u64) -> Result<JsValue, JsValue> {
+        let action: Action = serde_wasm_bindgen::from_value(action)
+            .map_err(|error| JsValue::from_str(&format!("invalid action: {error}")))?;
+        self.timeline = None;
+        let result = self.grid.apply_at(action, t_ms).map_err(|error| {
+            to_js_value(&error).unwrap_or_else(|_| JsValue::from_str(&error.to_string()))
+        })?;
+        to_js_value(&result)
+    }
+
+    /// Keyboard mode: move the cursor (`"up"`, `"down"`, `"left"`,
+    /// `"right"`) and return it.
+    pub fn cursor_move(&mut self, direction: JsValue) -> Result<JsValue, JsValue> {
+        let direction: Direction = serde_wasm_bindgen::from_value(direction)
+            .map_err(|error| JsValue::from_str(&format!("invalid direction: {error}")))?;
+        self.cursor
+            .step(direction, self.grid.width, self.grid.height);
+        to_js_value(&self.cursor)
+    }
+
+    /// Keyboard mode: act at the cursor with a command object (e.g.
+    /// `{ kind: "tool", tool: "teleport" }`). Returns the applied action's
+    /// `ActionResult`, or `null` when the command only changed the
+    /// selection.
+    pub fn cursor_action(&mut self, command: JsValue) -> Result<JsValue, JsValue> {
+        let command: CursorCommand = serde_wasm_bindgen::from_value(command)
+            .map_err(|error| JsValue::from_str(&format!("invalid command: {error}")))?;
+        let Some(action) = self.cursor.command(command) else {
+            return Ok(JsValue::NULL);
+        };
+        self.timeline = None;
+        to_js_value(&self.grid.apply(action))
+    }
+
+    pub fn get_cursor(&self) -> Result<JsValue, JsValue> {
+        to_js_value(&self.cursor)
+    }
+
+    pub fn set_cursor_wrap(&mut self, wrap: bool) {
+        self.cursor.edge = if wrap {
+            EdgeRule::Wrap
+        } else {
+            EdgeRule::Clamp
+        };
+    }
+
+    /// Gamepad mode: feed a controller event (e.g.
+    /// `{ kind: "pressed", button: "south" }`) through the bindings.
+    /// Returns the applied action's `ActionResult`, or `null`.
+    pub fn controller_event(&mut self, event: JsValue) -> Result<JsValue, JsValue> {
+        let event: ControllerEvent = serde_wasm_bindgen::from_value(event)
+            .map_err(|error| JsValue::from_str(&format!("invalid event: {error}")))?;
+        let (width, height) = (self.grid.width, self.grid.height);
+        let Some(action) = self.input.handle(event, &mut self.cursor, width, height) else {
+            return Ok(JsValue::NULL);
+        };
+        self.timeline = None;
+        to_js_value(&self.grid.apply(action))
+    }
+
+    /// Replace the controller bindings, e.g. from the player profile.
+    pub fn set_bindings(&mut self, bindings: JsValue) -> Result<(), JsValue> {
+        let bindings: Bindings = serde_wasm_bindgen::from_value(bindings)
+            .map_err(|error| JsValue::from_str(&format!("invalid bindings: {error}")))?;
+        self.input = InputMapper::new(bindings);
+        Ok(())
+    }
+
+    pub fn get_bindings(&self) -> Result<JsValue, JsValue> {
+        to_js_value(&self.input.bindings)
+    }
+
+    /// Load the adaptive-difficulty state, e.g. from the player profile.
+    pub fn set_session(&mut self, session: JsValue) -> Result<(), JsValue> {
+        self.session = serde_wasm_bindgen::from_value(session)
+            .map_err(|error| JsValue::from_str(&format!("invalid session: {error}")))?;
+        Ok(())
+    }
+
+    /// The adaptive-difficulty state, to save back into the profile.
+    pub fn get_session(&self) -> Result<JsValue, JsValue> {
+        to_js_value(&self.session)
+    }
+
+    /// Config for the player's next game. Once this game is over it is
+    /// counted toward the session first (only once, however often this is
+    /// called).
+    pub fn next_recommended_config(&mut self) -> Result<JsValue, JsValue> {
+        if (self.grid.game_over || self.grid.won) && !self.session_recorded {
+            self.session.ingest(&GameStats::from_grid(&self.grid));
+            self.session_recorded = true;
+        }
+        let seed = (js_sys::Math::random() * u64::MAX as f64) as u64;
+        to_js_value(&self.session.recommend(seed))
+    }
+
     pub fn get_probability_cloud(&self) -> Result<JsValue, JsValue> {
         let cloud = self.grid.get_probability_cloud();
         to_js_value(&cloud)
@@ -118,42 +766,362 @@ impl QuantumGame {
         to_js_value(&snapshot)
     }
 
+    /// Cells in `[x0, x1) × [y0, y1)`, clipped to the board — the visible
+    /// window when panning a board too large to fetch whole.
+    pub fn get_viewport(&self, x0: u32, y0: u32, x1: u32, y1: u32) -> Result<JsValue, JsValue> {
+        let cells: Vec<_> = self.grid.cells_in_rect(x0, y0, x1, y1).collect();
+        to_js_value(&cells)
+    }
+
+    /// Hex SHA-256 of the play-relevant state; compare with the server's
+    /// every few moves to detect a desync.
+    pub fn state_hash(&self) -> String {
+        commitment::to_hex(&self.grid.state_hash())
+    }
+
+    /// Live Hadamard waves as `{ x, y, age }`, for drawing ripples.
+    pub fn get_interference(&self) -> Result<JsValue, JsValue> {
+        to_js_value(&self.grid.interference.waves)
+    }
+
+    /// Revision of the latest cell mutation; pass it back to
+    /// `get_dirty_chunks` after uploading tiles.
+    pub fn get_revision(&self) -> u64 {
+        self.grid.chunks.revision()
+    }
+
+    /// 16×16 chunks changed after `since`, as `{ cx, cy }` objects.
+    pub fn get_dirty_chunks(&self, since: u64) -> Result<JsValue, JsValue> {
+        to_js_value(&self.grid.chunks.changed_since(since))
+    }
+
+    /// The snapshot in the compact binary wire format (see
+    /// `qmf_core::codec`), for large boards where the JSON shape is costly.
+    pub fn get_compact_snapshot(&self) -> Vec<u8> {
+        codec::encode_snapshot(&self.grid.snapshot())
+    }
+
     pub fn get_cell(&self, x: u32, y: u32) -> Result<QuantumCell, JsValue> {
-        let index = if x < self.grid.width && y < self.grid.height {
-            (y * self.grid.width + x) as usize
-        } else {
-            return Err(JsValue::from_str("coordinates out of bounds"));
-        };
+        self.grid
+            .cell(x, y)
+            .map(QuantumCell::from)
+            .ok_or_else(|| JsValue::from_str("coordinates out of bounds"))
+    }
+
+    /// Let `dt_ms` of game time pass for timed modes (call from the frame
+    /// loop). Returns the `ActionResult` with the new game time.
+    pub fn advance(&mut self, dt_ms: u32) -> Result<JsValue, JsValue> {
+        self.timeline = None;
+        to_js_value(&self.grid.apply(Action::Advance { dt_ms }))
+    }
+
+    /// Timing derived from actions stamped via the core `*_at` API.
+    pub fn get_timing_stats(&self) -> Result<JsValue, JsValue> {
+        to_js_value(&self.grid.timing_stats())
+    }
+
+    /// Take all engine events queued since the last call (oldest first),
+    /// each with a `haptic` intensity for the vibration API.
+    pub fn drain_events(&mut self) -> Result<JsValue, JsValue> {
+        let events = self.grid.drain_events();
+        to_js_object(&haptics::annotate(events))
+    }
 
-        Ok(QuantumCell::from(&self.grid.cells[index]))
+    /// Take all sound cues queued since the last call (oldest first), each
+    /// with a `haptic` intensity.
+    pub fn drain_cues(&mut self) -> Result<JsValue, JsValue> {
+        to_js_object(&haptics::annotate(self.grid.drain_cues()))
     }
 
     pub fn get_seed(&self) -> u64 {
         self.grid.seed
     }
 
-    pub fn set_quantum_inspector(&mut self, enabled: bool) {
+    pub fn mines_remaining(&self) -> u32 {
+        self.grid.mines_remaining()
+    }
+
+    /// Lives left when the config plays with a lives rule.
+    pub fn lives(&self) -> Option<u32> {
+        self.grid.rules.lives()
+    }
+
+    /// Scenario objectives with their status and partial credit, and the
+    /// moves made; the list is empty in the classic game.
+    pub fn objectives(&self) -> Result<JsValue, JsValue> {
+        to_js_value(&self.grid.objectives)
+    }
+
+    /// Where a practice run started and whether it still follows the
+    /// original game; `null` outside practice.
+    pub fn practice(&self) -> Result<JsValue, JsValue> {
+        to_js_value(&self.grid.practice)
+    }
+
+    /// The speedrun splits and the times reached so far.
+    pub fn splits(&self) -> Result<JsValue, JsValue> {
+        to_js_value(&self.grid.splits)
+    }
+
+    /// Each split against `best`, a personal best's `splits`, as deltas
+    /// for a livesplit-style display.
+    pub fn split_deltas(&self, best: JsValue) -> Result<JsValue, JsValue> {
+        let best: Vec<Option<u64>> = serde_wasm_bindgen::from_value(best)
+            .map_err(|error| JsValue::from_str(&format!("invalid splits: {error}")))?;
+        to_js_value(&splits::compare(&self.grid.splits, &best))
+    }
+
+    /// The current turn and action points left, in turn-based play.
+    pub fn turns(&self) -> Result<JsValue, JsValue> {
+        to_js_value(&self.grid.turns)
+    }
+
+    /// Turn the probability overlay on or off. Turning it on needs the
+    /// config's `assist` to be at least `"hints"`.
+    pub fn set_quantum_inspector(&mut self, enabled: bool) -> Result<(), JsValue> {
+        if enabled && !self.grid.config.assist.allows(AssistLevel::Hints) {
+            return Err(JsValue::from_str(
+                "the quantum inspector is disabled for unassisted games",
+            ));
+        }
         self.quantum_inspector_enabled = enabled;
+        Ok(())
     }
 
     pub fn is_quantum_inspector_enabled(&self) -> bool {
         self.quantum_inspector_enabled
     }
 
+    /// Fork a hypothetical branch of the current game (or of `from`, an
+    /// existing branch id) for the "what happens if I click here?" preview.
+    /// Branches see ground truth, so this requires the quantum inspector
+    /// and the `"full_analysis"` assist level.
+    pub fn fork(&mut self, from: Option<u32>) -> Result<u32, JsValue> {
+        if !self.quantum_inspector_enabled {
+            return Err(JsValue::from_str(
+                "what-if branches require the quantum inspector",
+            ));
+        }
+        if !self.grid.config.assist.allows(AssistLevel::FullAnalysis) {
+            return Err(JsValue::from_str(
+                "what-if branches require full analysis assists",
+            ));
+        }
+        // Branches are rooted at the live state when the first one is forked;
+        // any live move drops them all.
+        let grid = &self.grid;
+        self.timeline
+            .get_or_insert_with(|| grid.branch())
+            .fork(from)
+            .ok_or_else(|| JsValue::from_str("unknown branch"))
+    }
+
+    /// Apply an action object (e.g. `{ kind: "reveal", x, y }`) inside a
+    /// branch and return its result. The live game is untouched.
+    pub fn evaluate(&mut self, branch: u32, action: JsValue) -> Result<JsValue, JsValue> {
+        let action: Action = serde_wasm_bindgen::from_value(action)
+            .map_err(|error| JsValue::from_str(&format!("invalid action: {error}")))?;
+        let result = self
+            .timeline
+            .as_mut()
+            .and_then(|timeline| timeline.evaluate(branch, action))
+            .ok_or_else(|| JsValue::from_str("unknown branch"))?;
+        to_js_value(&result)
+    }
+
+    /// Map a displayed hint back to `{ mean, spread }`, the probability
+    /// that went into this board's circuit. Requires the quantum inspector.
+    pub fn calibrate(&self, displayed: f64) -> Result<JsValue, JsValue> {
+        if !self.quantum_inspector_enabled {
+            return Err(JsValue::from_str(
+                "hint calibration requires the quantum inspector",
+            ));
+        }
+        to_js_value(&self.grid.circuit.calibrate(displayed))
+    }
+
+    /// Risk meter: detonation risk, information gain (bits) and cascade size
+    /// of an action object, worked out against ground truth. Requires the
+    /// quantum inspector; an illegal action throws the serialized
+    /// `IllegalAction`.
+    pub fn evaluate_move(&self, action: JsValue) -> Result<JsValue, JsValue> {
+        if !self.quantum_inspector_enabled {
+            return Err(JsValue::from_str(
+                "the risk meter requires the quantum inspector",
+            ));
+        }
+        let action: Action = serde_wasm_bindgen::from_value(action)
+            .map_err(|error| JsValue::from_str(&format!("invalid action: {error}")))?;
+        let evaluation = self.grid.evaluate_move(action).map_err(|error| {
+            to_js_value(&error).unwrap_or_else(|_| JsValue::from_str("illegal action"))
+        })?;
+        to_js_value(&evaluation)
+    }
+
+    /// Why the cell at `(x, y)` must be safe or a mine: `{ x, y, mine,
+    /// steps }`, each step naming the numbers to highlight (`premises`) and
+    /// the cells it proves, or `null` when the numbers do not decide it.
+    pub fn explain(&self, x: u32, y: u32) -> Result<JsValue, JsValue> {
+        let explanation = self.grid.explain(x, y).map_err(|error| {
+            to_js_value(&error).unwrap_or_else(|_| JsValue::from_str("illegal action"))
+        })?;
+        to_js_value(&explanation)
+    }
+
+    /// Whether only a coin flip or worse is left, e.g. an endgame 50/50.
+    pub fn is_forced_guess(&self) -> bool {
+        self.grid.is_forced_guess()
+    }
+
+    /// Each cell's chance of being a mine from the revealed numbers,
+    /// `{ cells, components, sampled }`, within the default work budget.
+    pub fn mine_probabilities(&self) -> Result<JsValue, JsValue> {
+        let solved = self
+            .grid
+            .mine_probabilities(SolveBudget::default())
+            .map_err(|error| {
+                to_js_value(&error).unwrap_or_else(|_| JsValue::from_str("illegal action"))
+            })?;
+        to_js_value(&solved)
+    }
+
+    /// Hints without circuit scrambling or noise (full analysis only).
+    pub fn get_unscrambled_probabilities(&self) -> Result<JsValue, JsValue> {
+        let probabilities = self.grid.unscrambled_probabilities().map_err(|error| {
+            to_js_value(&error).unwrap_or_else(|_| JsValue::from_str("assist disabled"))
+        })?;
+        to_js_value(&probabilities)
+    }
+
+    pub fn get_branch_snapshot(&self, branch: u32) -> Result<JsValue, JsValue> {
+        let grid = self
+            .timeline
+            .as_ref()
+            .and_then(|timeline| timeline.get(branch))
+            .ok_or_else(|| JsValue::from_str("unknown branch"))?;
+        to_js_value(&grid.snapshot())
+    }
+
+    pub fn discard_branch(&mut self, branch: u32) -> bool {
+        self.timeline
+            .as_mut()
+            .is_some_and(|timeline| timeline.discard(branch))
+    }
+
+    pub fn discard_all_branches(&mut self) {
+        self.timeline = None;
+    }
+
     /// Apply the Hadamard (interference) tool to a cell in Superposition.
     pub fn apply_hadamard(&mut self, x: u32, y: u32) -> Result<JsValue, JsValue> {
+        self.timeline = None;
         self.grid
             .apply_hadamard(x, y)
-            .map(|p| JsValue::from_f64(p))
-            .map_err(|e| JsValue::from_str(e))
+            .map(JsValue::from_f64)
+            .map_err(JsValue::from_str)
+    }
+
+    /// Set a player annotation (`"flag"`, `"question"`, or `"none"`).
+    /// Returns the previous mark so the UI can undo.
+    pub fn set_mark(&mut self, x: u32, y: u32, mark: &str) -> Result<String, JsValue> {
+        let mark = match mark {
+            "flag" => Mark::Flag,
+            "question" => Mark::Question,
+            "none" => Mark::None,
+            other => return Err(JsValue::from_str(&format!("unknown mark: {other}"))),
+        };
+        let previous = self.grid.set_mark(x, y, mark).map_err(JsValue::from_str)?;
+        Ok(match previous {
+            Mark::Flag => "flag",
+            Mark::Question => "question",
+            Mark::None => "none",
+        }
+        .to_string())
+    }
+
+    /// Attach an analysis note: a string becomes a text note, a number a
+    /// numeric note, and `null`/`undefined` clears it.
+    pub fn set_note(&mut self, x: u32, y: u32, note: JsValue) -> Result<(), JsValue> {
+        let note = if note.is_null() || note.is_undefined() {
+            None
+        } else if let Some(text) = note.as_string() {
+            Some(CellNote::Text(text))
+        } else if let Some(value) = note.as_f64() {
+            Some(CellNote::Number(value))
+        } else {
+            return Err(JsValue::from_str("note must be a string, number, or null"));
+        };
+        self.grid
+            .set_note(x, y, note)
+            .map(|_| ())
+            .map_err(JsValue::from_str)
+    }
+
+    /// The note on a cell as a string or number, or `null` if there is none.
+    pub fn get_note(&self, x: u32, y: u32) -> JsValue {
+        match self.grid.note(x, y) {
+            Some(CellNote::Text(text)) => JsValue::from_str(text),
+            Some(CellNote::Number(value)) => JsValue::from_f64(*value),
+            None => JsValue::NULL,
+        }
     }
 
     /// Weak measurement — returns the probability but introduces observer drift.
     pub fn measure_weak(&mut self, x: u32, y: u32) -> Result<JsValue, JsValue> {
+        self.timeline = None;
         self.grid
             .measure_weak(x, y)
-            .map(|p| JsValue::from_f64(p))
-            .map_err(|e| JsValue::from_str(e))
+            .map(JsValue::from_f64)
+            .map_err(JsValue::from_str)
+    }
+
+    /// Weak measurement at `precision` in `[0, 1]`: sharper readings cost
+    /// more drift and decoherence.
+    pub fn measure_weak_with_precision(
+        &mut self,
+        x: u32,
+        y: u32,
+        precision: f64,
+    ) -> Result<JsValue, JsValue> {
+        self.timeline = None;
+        self.grid
+            .measure_weak_with_precision(x, y, precision)
+            .map(JsValue::from_f64)
+            .map_err(JsValue::from_str)
+    }
+
+    /// Entanglement swapping: spend a swap charge to re-link the Bell
+    /// partners of two cells with each other. Returns the newly linked
+    /// cells as `[[x, y], [x, y]]`.
+    pub fn swap_entanglement(
+        &mut self,
+        x: u32,
+        y: u32,
+        other_x: u32,
+        other_y: u32,
+    ) -> Result<JsValue, JsValue> {
+        self.timeline = None;
+        let linked = self
+            .grid
+            .swap_entanglement(x, y, other_x, other_y)
+            .map_err(JsValue::from_str)?;
+        to_js_value(&linked)
+    }
+
+    /// Cells holding an oracle that has not fired yet, as `[x, y]` pairs.
+    pub fn get_oracles(&self) -> Result<JsValue, JsValue> {
+        to_js_value(&self.grid.oracles())
+    }
+
+    /// Quantum teleportation: move the state of `(x, y)` onto
+    /// `(to_x, to_y)`, consuming one of the source's Bell links. Returns
+    /// the destination's new probability.
+    pub fn teleport(&mut self, x: u32, y: u32, to_x: u32, to_y: u32) -> Result<JsValue, JsValue> {
+        self.timeline = None;
+        self.grid
+            .teleport(x, y, to_x, to_y)
+            .map(JsValue::from_f64)
+            .map_err(JsValue::from_str)
     }
 }
 
@@ -167,3 +1135,17 @@ where
         .serialize(&serializer)
         .map_err(|error| JsValue::from_str(&format!("serialization failure: {error}")))
 }
+
+/// Like [`to_js_value`], but for payloads built with `#[serde(flatten)]`,
+/// which serde emits as maps: these must arrive as plain objects.
+fn to_js_object<T>(value: &T) -> Result<JsValue, JsValue>
+where
+    T: serde::Serialize,
+{
+    let serializer = serde_wasm_bindgen::Serializer::new()
+        .serialize_large_number_types_as_bigints(true)
+        .serialize_maps_as_objects(true);
+    value
+        .serialize(&serializer)
+        .map_err(|error| JsValue::from_str(&format!("serialization failure: {error}")))
+}
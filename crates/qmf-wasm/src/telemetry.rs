@@ -0,0 +1,86 @@
+use std::io;
+
+use tracing::{Level, Metadata};
+use tracing_subscriber::fmt::MakeWriter;
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = console, js_name = log)]
+    fn console_log(line: &str);
+
+    #[wasm_bindgen(js_namespace = console, js_name = warn)]
+    fn console_warn(line: &str);
+
+    #[wasm_bindgen(js_namespace = console, js_name = error)]
+    fn console_error(line: &str);
+}
+
+/// One formatted event, written to the console when dropped.
+struct ConsoleLine {
+    level: Level,
+    text: Vec<u8>,
+}
+
+impl io::Write for ConsoleLine {
+    fn write(&mut self, bytes: &[u8]) -> io::Result<usize> {
+        self.text.extend_from_slice(bytes);
+        Ok(bytes.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl Drop for ConsoleLine {
+    fn drop(&mut self) {
+        let text = String::from_utf8_lossy(&self.text);
+        let line = text.trim_end();
+        if line.is_empty() {
+            return;
+        }
+        match self.level {
+            Level::ERROR => console_error(line),
+            Level::WARN => console_warn(line),
+            _ => console_log(line),
+        }
+    }
+}
+
+/// Routes each event to the console method for its level.
+struct Console;
+
+impl<'a> MakeWriter<'a> for Console {
+    type Writer = ConsoleLine;
+
+    fn make_writer(&'a self) -> ConsoleLine {
+        ConsoleLine {
+            level: Level::INFO,
+            text: Vec::new(),
+        }
+    }
+
+    fn make_writer_for(&'a self, meta: &Metadata<'_>) -> ConsoleLine {
+        ConsoleLine {
+            level: *meta.level(),
+            text: Vec::new(),
+        }
+    }
+}
+
+/// Forward the engine's spans and events at `level` ("error" to "trace")
+/// and above to the browser console. Only the first call installs the
+/// subscriber.
+#[wasm_bindgen]
+pub fn init_tracing(level: &str) -> Result<(), JsValue> {
+    let level: Level = level
+        .parse()
+        .map_err(|_| JsValue::from_str(&format!("unknown level: {level}")))?;
+    tracing_subscriber::fmt()
+        .with_max_level(level)
+        .without_time()
+        .with_writer(Console)
+        .try_init()
+        .map_err(|error| JsValue::from_str(&error.to_string()))
+}
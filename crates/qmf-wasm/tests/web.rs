@@ -0,0 +1,142 @@
+//! Contract tests for the JS-facing API, run in a headless browser with
+//! `pnpm wasm:test`. The frontend reads these objects by field name, so a
+//! serde rename in `qmf-core` has to fail here before it breaks the UI.
+#![cfg(target_arch = "wasm32")]
+
+use js_sys::{Array, Object, Reflect};
+use qmf_core::action::Action;
+use qmf_core::config::GridConfig;
+use qmf_core::profile::PlayerProfile;
+use qmf_core::replay::Replay;
+use qmf_wasm::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen_test::*;
+
+wasm_bindgen_test_configure!(run_in_browser);
+
+/// Own field names of a JS object, sorted.
+fn keys(value: &JsValue) -> Vec<String> {
+    let mut keys: Vec<String> = Object::keys(value.unchecked_ref())
+        .iter()
+        .filter_map(|key| key.as_string())
+        .collect();
+    keys.sort();
+    keys
+}
+
+fn sorted(names: &[&str]) -> Vec<String> {
+    let mut names: Vec<String> = names.iter().map(|name| name.to_string()).collect();
+    names.sort();
+    names
+}
+
+fn field(value: &JsValue, name: &str) -> JsValue {
+    Reflect::get(value, &JsValue::from_str(name)).unwrap()
+}
+
+fn kind(value: &JsValue) -> String {
+    field(value, "kind").as_string().unwrap()
+}
+
+fn game() -> QuantumGame {
+    init_game_seeded(8, 8, 10, 42, "researcher")
+}
+
+#[wasm_bindgen_test]
+fn snapshots_keep_their_field_names() {
+    let mut game = game();
+    let outcome = game.reveal_cell(4, 4).unwrap();
+    assert_eq!(kind(&outcome), "revealed");
+    assert_eq!(
+        keys(&field(&outcome, "cell")),
+        sorted(&["x", "y", "state", "mark"])
+    );
+
+    let snapshot = game.get_grid_snapshot().unwrap();
+    assert_eq!(
+        keys(&snapshot),
+        sorted(&[
+            "width",
+            "height",
+            "game_over",
+            "won",
+            "seed",
+            "containment_charges",
+            "swap_charges",
+            "mines_remaining",
+            "entropy",
+            "quantum_entropy",
+            "cells",
+            "decoherence",
+            "frozen",
+            "theme",
+            "score",
+            "combo",
+            "isotopes",
+            "inventory",
+            "integrity",
+            "cosmetics",
+        ])
+    );
+    assert!(field(&snapshot, "seed").is_bigint());
+
+    let cells = Array::from(&field(&snapshot, "cells"));
+    assert_eq!(cells.length(), 64);
+    let states: Vec<String> = cells
+        .iter()
+        .map(|cell| field(&field(&cell, "state"), "state").as_string().unwrap())
+        .collect();
+    assert!(states.iter().any(|state| state == "revealed"));
+    assert!(states.iter().any(|state| state == "superposition"));
+    let hidden = cells
+        .iter()
+        .map(|cell| field(&cell, "state"))
+        .find(|state| field(state, "state").as_string().as_deref() == Some("superposition"))
+        .unwrap();
+    assert_eq!(keys(&hidden), sorted(&["state", "probability"]));
+}
+
+#[wasm_bindgen_test]
+fn actions_results_and_events_are_kind_tagged() {
+    let mut game = game();
+    game.reveal_cell(4, 4).unwrap();
+    let hadamard = serde_wasm_bindgen::to_value(&Action::Hadamard { x: 0, y: 0 }).unwrap();
+    assert_eq!(kind(&hadamard), "hadamard");
+    let result = game.apply_action(hadamard, Some(1)).unwrap();
+    assert!(!kind(&result).is_empty());
+
+    let events = Array::from(&game.drain_events().unwrap());
+    assert!(events.length() > 0);
+    for event in events.iter() {
+        assert!(!kind(&event).is_empty());
+        assert!(field(&event, "haptic").as_f64().is_some());
+    }
+    assert_eq!(Array::from(&game.drain_events().unwrap()).length(), 0);
+}
+
+#[wasm_bindgen_test]
+fn config_errors_arrive_as_tagged_objects() {
+    let config = serde_wasm_bindgen::to_value(&GridConfig::new(8, 8, 60, 0, "observer")).unwrap();
+    let error = init_game_with_config(config).err().unwrap();
+    assert_eq!(kind(&error), "too_many_mines");
+    assert_eq!(keys(&error), sorted(&["kind", "requested", "max"]));
+}
+
+#[wasm_bindgen_test]
+fn replays_and_profiles_survive_save_and_load() {
+    let mut replay = Replay::new(GridConfig::new(8, 8, 10, 42, "observer"));
+    replay.record(0, Action::Reveal { x: 4, y: 4 });
+    store_replay("contract", serde_wasm_bindgen::to_value(&replay).unwrap()).unwrap();
+    assert!(list_replays().unwrap().contains(&"contract".to_string()));
+
+    let loaded = load_replay("contract").unwrap();
+    assert_eq!(keys(&loaded), sorted(&["config", "steps", "stamp"]));
+    let step = Array::from(&field(&loaded, "steps")).get(0);
+    assert_eq!(keys(&step), sorted(&["t_ms", "action"]));
+    assert_eq!(kind(&field(&step, "action")), "reveal");
+    load_ghost(loaded).unwrap();
+    assert!(load_replay("missing").unwrap().is_null());
+
+    save_profile(serde_wasm_bindgen::to_value(&PlayerProfile::default()).unwrap()).unwrap();
+    assert!(!load_profile().unwrap().is_null());
+}